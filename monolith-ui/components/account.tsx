@@ -13,9 +13,9 @@ import {
 import { Button } from "@/components/ui/button";
 import AddAccountDialog from "./dialogs/add-account-dialog";
 import { useLauncher } from "./launcher-provider";
-import { invoke } from "@/lib/tauri";
+import { getListen, invoke } from "@/lib/tauri";
 import { slugify } from "@/lib/launcher-utils";
-import type { Account } from "@/lib/launcher-types";
+import type { Account, AccountStatus, AccountUsage } from "@/lib/launcher-types";
 
 const formatTimestamp = (value?: string | null) => {
   if (!value) return "No recent activity";
@@ -33,6 +33,55 @@ export default function Account() {
   const [showDeleteModal, setShowDeleteModal] = useState(false);
   const [deleteConfirmName, setDeleteConfirmName] = useState("");
   const [deleteTarget, setDeleteTarget] = useState<Account | null>(null);
+  const [sortByLastUsed, setSortByLastUsed] = useState(false);
+  const [usage, setUsage] = useState<AccountUsage[]>([]);
+  const [statuses, setStatuses] = useState<AccountStatus[]>([]);
+  const [microsoftLoginPending, setMicrosoftLoginPending] = useState(false);
+
+  useEffect(() => {
+    const listen = getListen();
+    if (!listen) return;
+    const stops: Array<() => void> = [];
+    const clearPending = () => setMicrosoftLoginPending(false);
+    ["microsoft:code", "microsoft:error", "microsoft:cancelled"].forEach((name) => {
+      listen(name, clearPending).then((stop: () => void) => {
+        stops.push(stop);
+      });
+    });
+    return () => {
+      stops.forEach((stop) => stop());
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<AccountUsage[]>("get_account_usage", {})
+      .then((data) => setUsage(data || []))
+      .catch(() => setUsage([]));
+    invoke<AccountStatus[]>("get_account_status", {})
+      .then((data) => setStatuses(data || []))
+      .catch(() => setStatuses([]));
+  }, [accounts]);
+
+  const statusById = useMemo(
+    () => new Map(statuses.map((entry) => [entry.account_id, entry])),
+    [statuses],
+  );
+
+  const staleAccountIds = useMemo(
+    () =>
+      new Set(usage.filter((entry) => entry.stale).map((entry) => entry.account_id)),
+    [usage],
+  );
+
+  const sortedAccounts = useMemo(() => {
+    if (!sortByLastUsed) return accounts;
+    const order = new Map(usage.map((entry, index) => [entry.account_id, index]));
+    return [...accounts].sort(
+      (a, b) =>
+        (order.get(a.id) ?? Number.MAX_SAFE_INTEGER) -
+        (order.get(b.id) ?? Number.MAX_SAFE_INTEGER),
+    );
+  }, [accounts, sortByLastUsed, usage]);
 
   useEffect(() => {
     if (!accounts.length) {
@@ -127,6 +176,7 @@ export default function Account() {
     }
     try {
       const authorizeUrl = await invoke<string>("start_microsoft_login", { clientId });
+      setMicrosoftLoginPending(true);
       await invoke("open_external", { url: authorizeUrl });
       setStatus("Opening Microsoft sign-in in your browser.");
     } catch (err: any) {
@@ -135,6 +185,17 @@ export default function Account() {
     }
   };
 
+  const handleCancelMicrosoftLogin = async () => {
+    try {
+      await invoke<boolean>("cancel_microsoft_login", {});
+      setMicrosoftLoginPending(false);
+      setStatus("Microsoft sign-in cancelled.");
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to cancel Microsoft sign-in.";
+      setStatus(message, "error");
+    }
+  };
+
   const hasMicrosoftAccount = accounts.some((account) => account.kind === "microsoft");
 
   const MicrosoftIcon = ({
@@ -183,6 +244,25 @@ export default function Account() {
     );
   };
 
+  const renderSessionBadge = (account: Account) => {
+    const status = statusById.get(account.id);
+    if (account.kind !== "microsoft" || !status) return null;
+    if (status.token_valid && !status.expiring_soon) return null;
+    let label = "EXPIRING";
+    let className = "bg-amber-500/15 text-amber-300";
+    if (!status.token_valid) {
+      label = status.can_refresh ? "REFRESH NEEDED" : "RE-LOGIN";
+      className = status.can_refresh
+        ? "bg-amber-500/15 text-amber-300"
+        : "bg-destructive/15 text-destructive";
+    }
+    return (
+      <span className={`rounded px-2 py-0.5 text-[10px] font-bold uppercase ${className}`}>
+        {label}
+      </span>
+    );
+  };
+
   const accountStateLabel = (account: Account) => {
     if (account.kind === "offline") return "Offline profile";
     if (account.owns_minecraft === true) return "Licensed profile";
@@ -220,13 +300,24 @@ export default function Account() {
               Manage Minecraft identities, active profile selection, and account health.
             </p>
           </div>
-          <Button
-            onClick={() => setShowDialog(true)}
-            data-tip-id="account-add-account"
-            className="h-11 bg-primary text-primary-foreground hover:bg-primary/90"
-          >
-            Add Account
-          </Button>
+          <div className="flex items-center gap-2">
+            {microsoftLoginPending ? (
+              <Button
+                variant="outline"
+                onClick={handleCancelMicrosoftLogin}
+                className="h-11 bg-transparent"
+              >
+                Cancel Sign-in
+              </Button>
+            ) : null}
+            <Button
+              onClick={() => setShowDialog(true)}
+              data-tip-id="account-add-account"
+              className="h-11 bg-primary text-primary-foreground hover:bg-primary/90"
+            >
+              Add Account
+            </Button>
+          </div>
         </div>
       </div>
 
@@ -246,8 +337,18 @@ export default function Account() {
             </div>
           ) : (
             <div className="max-h-[260px] overflow-y-auto p-3 sm:p-4 xl:max-h-none xl:min-h-0">
+              <div className="mb-3 flex justify-end">
+                <button
+                  type="button"
+                  onClick={() => setSortByLastUsed((prev) => !prev)}
+                  className="inline-flex items-center gap-1.5 text-xs text-foreground/60 hover:text-foreground"
+                >
+                  <Clock3 size={12} />
+                  {sortByLastUsed ? "Sorted by last used" : "Sort by last used"}
+                </button>
+              </div>
               <div className="grid gap-2 sm:grid-cols-2 xl:grid-cols-1">
-                {accounts.map((account) => (
+                {sortedAccounts.map((account) => (
                   <button
                     key={account.id}
                     onClick={() => setSelectedAccountId(account.id)}
@@ -273,10 +374,18 @@ export default function Account() {
                               Active
                             </span>
                           ) : null}
+                          {staleAccountIds.has(account.id) ? (
+                            <span className="rounded bg-secondary/50 px-2 py-0.5 text-[10px] font-bold uppercase text-foreground/60">
+                              Stale
+                            </span>
+                          ) : null}
                           {renderOwnershipBadge(account, "badge")}
+                          {renderSessionBadge(account)}
                         </div>
                         <p className="mt-1 text-xs text-foreground/60">
-                          {accountStateLabel(account)}
+                          {account.gamertag
+                            ? `${account.gamertag} · ${accountStateLabel(account)}`
+                            : accountStateLabel(account)}
                         </p>
                         <p className="mt-2 text-[11px] text-foreground/45">
                           {formatTimestamp(account.last_used)}
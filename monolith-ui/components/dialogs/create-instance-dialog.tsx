@@ -12,13 +12,15 @@ import {
 } from "@/components/ui/select";
 import { invoke } from "@/lib/tauri";
 import type {
+  CreatedInstance,
   ForgeVersionSummary,
-  Instance,
+  LoaderAvailability,
   LoaderKind,
   LoaderVersionSummary,
   VersionSummary,
 } from "@/lib/launcher-types";
 import { useLauncher } from "@/components/launcher-provider";
+import { resolveNewInstanceForm } from "@/lib/launcher-utils";
 
 interface CreateInstanceDialogProps {
   open: boolean;
@@ -33,7 +35,7 @@ const pickRecommendedLoaderVersion = (
   versions: LoaderVersion[],
 ) => {
   if (versions.length === 0) return "";
-  if (loader === "fabric") {
+  if (loader === "fabric" || loader === "quilt") {
     const stable = versions.find(
       (entry): entry is LoaderVersionSummary =>
         "stable" in entry && entry.stable,
@@ -55,10 +57,14 @@ export default function CreateInstanceDialog({
   const [displayName, setDisplayName] = useState("");
   const [loader, setLoader] = useState<LoaderKind>("vanilla");
   const [includeSnapshots, setIncludeSnapshots] = useState(false);
+  const [includeLegacy, setIncludeLegacy] = useState(false);
   const [gameVersions, setGameVersions] = useState<VersionSummary[]>([]);
   const [gameVersion, setGameVersion] = useState("");
   const [loaderVersions, setLoaderVersions] = useState<LoaderVersion[]>([]);
   const [loaderVersion, setLoaderVersion] = useState("");
+  const [loaderSupport, setLoaderSupport] = useState<Record<string, boolean>>(
+    {},
+  );
   const [minRamMb, setMinRamMb] = useState(512);
   const [maxRamMb, setMaxRamMb] = useState(1024);
   const [ramUnit, setRamUnit] = useState<"mb" | "gb">("mb");
@@ -92,10 +98,11 @@ export default function CreateInstanceDialog({
 
   useEffect(() => {
     if (!open) return;
-    const defaults = config?.settings?.java;
-    setMinRamMb(defaults?.min_ram_mb ?? 512);
-    setMaxRamMb(defaults?.max_ram_mb ?? 1024);
-    setJvmArgs(defaults?.jvm_args ?? "");
+    const defaults = resolveNewInstanceForm(config?.settings);
+    setMinRamMb(defaults.minRamMb);
+    setMaxRamMb(defaults.maxRamMb);
+    setJvmArgs(defaults.jvmArgs);
+    setIncludeSnapshots(defaults.showSnapshots);
   }, [config, open]);
 
   useEffect(() => {
@@ -111,6 +118,7 @@ export default function CreateInstanceDialog({
               })
             : await invoke<VersionSummary[]>("list_vanilla_versions", {
                 includeSnapshots,
+                includeLegacy: loader === "vanilla" && includeLegacy,
               });
         if (cancelled) return;
         setGameVersions(versions);
@@ -129,7 +137,29 @@ export default function CreateInstanceDialog({
     return () => {
       cancelled = true;
     };
-  }, [open, loader, includeSnapshots, gameVersion, setStatus]);
+  }, [open, loader, includeSnapshots, includeLegacy, gameVersion, setStatus]);
+
+  useEffect(() => {
+    if (!open || !gameVersion) return;
+    let cancelled = false;
+    setLoaderSupport({});
+    invoke<LoaderAvailability[]>("list_supported_loaders", { gameVersion })
+      .then((entries) => {
+        if (cancelled) return;
+        const support: Record<string, boolean> = {};
+        for (const entry of entries || []) {
+          // Only gray out loaders we know have no builds, not ones we failed to check.
+          if (!entry.error) support[entry.loader] = entry.available;
+        }
+        setLoaderSupport(support);
+      })
+      .catch(() => {
+        if (!cancelled) setLoaderSupport({});
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, gameVersion]);
 
   useEffect(() => {
     if (!open) return;
@@ -157,6 +187,14 @@ export default function CreateInstanceDialog({
                   includeSnapshots: true,
                 },
               )
+            : loader === "quilt"
+              ? await invoke<LoaderVersionSummary[]>(
+                  "list_quilt_loader_versions",
+                  {
+                    gameVersion,
+                    includeSnapshots: true,
+                  },
+                )
             : loader === "neoforge"
               ? await invoke<ForgeVersionSummary[]>("list_neoforge_versions", {
                   gameVersion,
@@ -193,14 +231,16 @@ export default function CreateInstanceDialog({
   }, [open, loader, gameVersion, includeSnapshots, loaderVersion, setStatus]);
 
   const resetForm = () => {
+    const defaults = resolveNewInstanceForm(config?.settings);
     setDisplayName("");
     setLoader("vanilla");
-    setIncludeSnapshots(false);
+    setIncludeSnapshots(defaults.showSnapshots);
+    setIncludeLegacy(false);
     setGameVersion("");
     setLoaderVersion("");
-    setMinRamMb(config?.settings?.java?.min_ram_mb ?? 512);
-    setMaxRamMb(config?.settings?.java?.max_ram_mb ?? 1024);
-    setJvmArgs(config?.settings?.java?.jvm_args ?? "");
+    setMinRamMb(defaults.minRamMb);
+    setMaxRamMb(defaults.maxRamMb);
+    setJvmArgs(defaults.jvmArgs);
   };
 
   const handleClose = () => {
@@ -237,7 +277,9 @@ export default function CreateInstanceDialog({
 
     try {
       setSubmitting(true);
-      const created = await invoke<Instance>("create_instance", { request });
+      const { instance: created } = await invoke<CreatedInstance>("create_instance", {
+        request,
+      });
       if (created?.id) {
         await invoke("update_instance_settings", {
           instanceId: created.id,
@@ -305,9 +347,32 @@ export default function CreateInstanceDialog({
               </SelectTrigger>
               <SelectContent className="bg-card border-border text-foreground">
                 <SelectItem value="vanilla">Vanilla</SelectItem>
-                <SelectItem value="fabric">Fabric</SelectItem>
-                <SelectItem value="forge">Forge</SelectItem>
-                <SelectItem value="neoforge">NeoForge</SelectItem>
+                <SelectItem
+                  value="fabric"
+                  disabled={loader !== "fabric" && loaderSupport.fabric === false}
+                >
+                  Fabric
+                </SelectItem>
+                <SelectItem
+                  value="quilt"
+                  disabled={loader !== "quilt" && loaderSupport.quilt === false}
+                >
+                  Quilt
+                </SelectItem>
+                <SelectItem
+                  value="forge"
+                  disabled={loader !== "forge" && loaderSupport.forge === false}
+                >
+                  Forge
+                </SelectItem>
+                <SelectItem
+                  value="neoforge"
+                  disabled={
+                    loader !== "neoforge" && loaderSupport.neoforge === false
+                  }
+                >
+                  NeoForge
+                </SelectItem>
               </SelectContent>
             </Select>
           </div>
@@ -339,6 +404,35 @@ export default function CreateInstanceDialog({
             </p>
           </div>
 
+          {loader === "vanilla" && (
+            <div>
+              <div className="flex items-center justify-between mb-3">
+                <label className="block text-sm font-medium">
+                  Include Alpha &amp; Beta
+                </label>
+                <button
+                  onClick={() => setIncludeLegacy(!includeLegacy)}
+                  className={`relative h-6 w-10 rounded-lg border transition-all ${
+                    includeLegacy
+                      ? "border-emerald-500/35 bg-emerald-500/18 shadow-[inset_0_0_0_1px_rgba(16,185,129,0.08)]"
+                      : "border-red-500/28 bg-red-500/14 shadow-[inset_0_0_0_1px_rgba(239,68,68,0.06)]"
+                  }`}
+                >
+                  <div
+                    className={`absolute left-0.5 top-0.5 h-5 w-5 rounded-md border border-border/70 bg-foreground/95 shadow-sm transition-transform ${
+                      includeLegacy ? "translate-x-4" : "translate-x-0"
+                    }`}
+                  />
+                </button>
+              </div>
+              <p className="text-xs text-foreground/60">
+                {includeLegacy
+                  ? "Classic alpha and beta releases will be available in the list."
+                  : "Classic alpha and beta releases are hidden."}
+              </p>
+            </div>
+          )}
+
           <div>
             <label className="block text-sm font-medium mb-2">Version</label>
             <Select
@@ -389,7 +483,9 @@ export default function CreateInstanceDialog({
               <SelectContent className="bg-card border-border text-foreground">
                 {loaderVersions.map((entry) => {
                   const label =
-                    loader === "fabric" && "stable" in entry && !entry.stable
+                    (loader === "fabric" || loader === "quilt") &&
+                    "stable" in entry &&
+                    !entry.stable
                       ? `${entry.version} (unstable)`
                       : entry.version;
                   const recommended = entry.version === recommendedLoaderVersion;
@@ -15,6 +15,14 @@ const formatProgress = (
   return `${message} (${current}/${total})`;
 };
 
+const formatElapsed = (ms: number) => {
+  const secs = Math.floor(ms / 1000);
+  if (secs < 60) {
+    return `${secs}s`;
+  }
+  return `${Math.floor(secs / 60)}m${String(secs % 60).padStart(2, "0")}s`;
+};
+
 export default function InstallOverlay() {
   const { installing, installProgress, installDetails } = useLauncher();
   const [minimized, setMinimized] = useState(false);
@@ -33,10 +41,16 @@ export default function InstallOverlay() {
   const message = installProgress?.message || "Preparing launch";
   const current = installProgress?.current ?? 0;
   const total = installProgress?.total ?? null;
+  const overall = installProgress?.overall ?? null;
+  const stage = installProgress?.stage ?? null;
+  const stageElapsed = installProgress?.stage_elapsed_ms ?? null;
+  const totalElapsed = installProgress?.total_elapsed_ms ?? null;
   const percent =
-    total && total > 0
-      ? Math.min(100, Math.round((current / total) * 100))
-      : null;
+    overall !== null
+      ? Math.min(100, Math.round(overall))
+      : total && total > 0
+        ? Math.min(100, Math.round((current / total) * 100))
+        : null;
 
   if (minimized) {
     return (
@@ -68,6 +82,12 @@ export default function InstallOverlay() {
         <p className="text-sm text-foreground/70 mb-4">
           {formatProgress(message, current, total)}
         </p>
+        {stage && stageElapsed !== null && (
+          <p className="-mt-3 mb-4 text-xs text-foreground/50">
+            {stage}: {formatElapsed(stageElapsed)}
+            {totalElapsed !== null && ` · total ${formatElapsed(totalElapsed)}`}
+          </p>
+        )}
         <div className="relative h-2 w-full overflow-hidden rounded-full bg-primary/20">
           {percent === null ? (
             <div className="progress-indeterminate h-full w-1/3 bg-primary" />
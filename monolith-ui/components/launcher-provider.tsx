@@ -10,8 +10,15 @@ import React, {
   useState,
 } from "react";
 import { getInvoke, getListen, waitForTauri } from "@/lib/tauri";
-import type { AppConfig, ProgressEvent } from "@/lib/launcher-types";
+import type {
+  AppConfig,
+  GameReadyEvent,
+  ProgressEvent,
+  ScreenshotEvent,
+  TempFileCleanup,
+} from "@/lib/launcher-types";
 import { toast } from "@/hooks/use-toast";
+import { ToastAction } from "@/components/ui/toast";
 import { getStackGroupsByAxisId } from "recharts/types/util/ChartUtils";
 
 type UnlistenFn = () => void;
@@ -274,9 +281,15 @@ export const LauncherProvider = ({
     let unlistenInstallDone: (() => void) | null = null;
     let unlistenInstallError: (() => void) | null = null;
     let unlistenLaunchStarted: (() => void) | null = null;
+    let unlistenGameReady: (() => void) | null = null;
     let unlistenLaunchError: (() => void) | null = null;
     let unlistenMicrosoftCode: (() => void) | null = null;
     let unlistenMicrosoftError: (() => void) | null = null;
+    let unlistenConfigChanged: (() => void) | null = null;
+    let unlistenInstancesChanged: (() => void) | null = null;
+    let unlistenInstanceUpdated: (() => void) | null = null;
+    let unlistenTempFiles: (() => void) | null = null;
+    let unlistenScreenshot: (() => void) | null = null;
 
     listen("instance:log", (event: any) => {
       const payload = event?.payload || {};
@@ -303,6 +316,9 @@ export const LauncherProvider = ({
         current: payload.current || 0,
         total: payload.total ?? null,
         detail: payload.detail ?? null,
+        overall: payload.overall ?? null,
+        stage_elapsed_ms: payload.stage_elapsed_ms ?? null,
+        total_elapsed_ms: payload.total_elapsed_ms ?? null,
       });
       setInstalling(true);
       if (payload.stage === "prepare" && payload.current === 0) {
@@ -348,6 +364,14 @@ export const LauncherProvider = ({
       unlistenLaunchStarted = unlisten;
     });
 
+    listen("game:ready", (event: any) => {
+      const payload = (event?.payload || {}) as GameReadyEvent;
+      const seconds = Math.max(0, Math.round((payload.ready_after_ms || 0) / 1000));
+      setStatus(`Game ready in ${seconds}s.`);
+    }).then((unlisten: UnlistenFn) => {
+      unlistenGameReady = unlisten;
+    });
+
     listen("launch:error", (event: any) => {
       setInstalling(false);
       setInstallProgress(null);
@@ -386,15 +410,103 @@ export const LauncherProvider = ({
       unlistenMicrosoftError = unlisten;
     });
 
+    listen("config:changed", () => {
+      refreshConfig({ silent: true });
+    }).then((unlisten: UnlistenFn) => {
+      unlistenConfigChanged = unlisten;
+    });
+
+    listen("instances:changed", () => {
+      refreshConfig({ silent: true });
+    }).then((unlisten: UnlistenFn) => {
+      unlistenInstancesChanged = unlisten;
+    });
+
+    listen("instance:updated", () => {
+      refreshConfig({ silent: true });
+    }).then((unlisten: UnlistenFn) => {
+      unlistenInstanceUpdated = unlisten;
+    });
+
+    listen("maintenance:temp-files", (event: any) => {
+      const cleaned = (event?.payload || []) as TempFileCleanup[];
+      const files = cleaned.reduce((sum, entry) => sum + entry.files, 0);
+      if (files > 0) {
+        setStatus(
+          `Removed ${files} leftover partial download${files === 1 ? "" : "s"}.`,
+        );
+      }
+    }).then((unlisten: UnlistenFn) => {
+      unlistenTempFiles = unlisten;
+    });
+
+    listen("screenshot:taken", (event: any) => {
+      const shot = (event?.payload || {}) as ScreenshotEvent;
+      if (!shot.instance_id || !shot.file_name) return;
+      const copyScreenshot = async () => {
+        const invoker = getInvoke();
+        if (!invoker) return;
+        try {
+          const dataUrl: string = await invoker("read_instance_screenshot", {
+            instanceId: shot.instance_id,
+            fileName: shot.file_name,
+          });
+          const blob = await (await fetch(dataUrl)).blob();
+          await navigator.clipboard.write([
+            new ClipboardItem({ "image/png": blob }),
+          ]);
+          setStatus("Screenshot copied to clipboard.");
+        } catch (err: any) {
+          const message = err?.toString?.() || "Failed to copy screenshot.";
+          setStatus(message, "error");
+        }
+      };
+      const openFolder = async () => {
+        const invoker = getInvoke();
+        if (!invoker) return;
+        try {
+          await invoker("open_instance_path", {
+            instanceId: shot.instance_id,
+            kind: "screenshots",
+          });
+        } catch (err: any) {
+          const message = err?.toString?.() || "Failed to open screenshots.";
+          setStatus(message, "error");
+        }
+      };
+      toast({
+        title: "Screenshot saved",
+        description: shot.file_name,
+        action: (
+          <div className="flex gap-2">
+            <ToastAction altText="Copy screenshot" onClick={copyScreenshot}>
+              Copy
+            </ToastAction>
+            <ToastAction altText="Open screenshots folder" onClick={openFolder}>
+              Share
+            </ToastAction>
+          </div>
+        ),
+      });
+    }).then((unlisten: UnlistenFn) => {
+      unlistenScreenshot = unlisten;
+    });
+
     return () => {
       unlistenInstance?.();
       unlistenInstallProgress?.();
       unlistenInstallDone?.();
       unlistenInstallError?.();
       unlistenLaunchStarted?.();
+      unlistenGameReady?.();
       unlistenLaunchError?.();
       unlistenMicrosoftCode?.();
       unlistenMicrosoftError?.();
+      unlistenConfigChanged?.();
+      unlistenInstancesChanged?.();
+      unlistenInstanceUpdated?.();
+      unlistenTempFiles?.();
+      unlistenScreenshot?.();
     };
   }, [appendGameLog, appendInstanceLog, refreshConfig, setStatus]);
 
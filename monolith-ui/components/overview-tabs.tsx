@@ -24,27 +24,39 @@ import { Button } from "@/components/ui/button";
 import { getListen, invoke } from "@/lib/tauri";
 import type {
   ForgeVersionSummary,
+  GcLogSummary,
   Instance,
   InstancePreflightReport,
+  InstanceChangeEvent,
+  InstanceBranding,
   InstanceSnapshot,
   InstanceMetrics,
+  InstanceSearchDefaults,
   JavaRuntimeEntry,
   LoaderVersionSummary,
   ModEntry,
+  ModToggleResult,
+  ModrinthCompatibilityReport,
   ModrinthDependencyPlan,
   ModrinthDependencyPlanItem,
   ModrinthProjectHit,
+  OptiFineInstall,
   PackEntry,
+  ReleaseChannel,
   RepairResult,
   ServerLatencyReport,
+  LanGame,
   ServerEntry,
   WorldEntry,
+  WorldSizeEvent,
 } from "@/lib/launcher-types";
 import { useLauncher } from "@/components/launcher-provider";
-import { resolveLoaderLabel } from "@/lib/launcher-utils";
+import { formatJvmArgs, imageFileToPngDataUrl, resolveLoaderLabel } from "@/lib/launcher-utils";
 import ConsolePanel from "@/components/overview/ConsolePanel";
 import ContentDeleteDialog from "@/components/overview/dialogs/ContentDeleteDialog";
+import ModDependentsDialog from "@/components/overview/dialogs/ModDependentsDialog";
 import PackTable from "@/components/overview/PackTable";
+import ResourcePackOrder from "@/components/overview/ResourcePackOrder";
 import DatapackTable from "@/components/overview/DatapackTable";
 import ModDependencyInstallDialog from "@/components/overview/dialogs/ModDependencyInstallDialog";
 import ServerTable from "@/components/overview/ServerTable";
@@ -101,6 +113,8 @@ export default function OverviewTabs({
   const modrinthLoader =
     instance.loader === "fabric"
       ? "fabric"
+      : instance.loader === "quilt"
+        ? "quilt"
       : instance.loader === "forge"
         ? "forge"
         : instance.loader === "neoforge"
@@ -120,6 +134,8 @@ export default function OverviewTabs({
   const [activeTab, setActiveTab] = useState<TabType>("console");
   const [mods, setMods] = useState<ModEntry[]>([]);
   const [resourcepacks, setResourcepacks] = useState<PackEntry[]>([]);
+  const [resourcePackOrder, setResourcePackOrder] = useState<string[]>([]);
+  const [savingPackOrder, setSavingPackOrder] = useState(false);
   const [texturepacks, setTexturepacks] = useState<PackEntry[]>([]);
   const [shaders, setShaders] = useState<PackEntry[]>([]);
   const [datapacks, setDatapacks] = useState<PackEntry[]>([]);
@@ -166,6 +182,9 @@ export default function OverviewTabs({
   });
   const [modrinthDialogKind, setModrinthDialogKind] =
     useState<ModrinthKind | null>(null);
+  const [searchDefaultsLoaded, setSearchDefaultsLoaded] = useState<
+    ModrinthKind[]
+  >([]);
   const [modrinthInstalled, setModrinthInstalled] = useState<
     Record<ModrinthKind, string[]>
   >({
@@ -192,6 +211,7 @@ export default function OverviewTabs({
     dependencies: ModrinthDependencyPlanItem[];
   } | null>(null);
   const [servers, setServers] = useState<ServerEntry[]>([]);
+  const [lanGames, setLanGames] = useState<LanGame[]>([]);
   const [serverLatencyByAddress, setServerLatencyByAddress] = useState<
     Record<string, ServerLatencyReport>
   >({});
@@ -232,11 +252,24 @@ export default function OverviewTabs({
     instance.loader_version ?? "",
   );
   const [savingLoaderVersion, setSavingLoaderVersion] = useState(false);
+  const [branding, setBranding] = useState<InstanceBranding>({
+    windowTitle: instance.window_title ?? "",
+    brandName: instance.brand_name ?? "",
+    brandVersion: instance.brand_version ?? "",
+  });
+  const [savingBranding, setSavingBranding] = useState(false);
+  const [installingOptifine, setInstallingOptifine] = useState(false);
+  const [installingShaderPreset, setInstallingShaderPreset] = useState(false);
+  const [gcSummary, setGcSummary] = useState<GcLogSummary | null>(null);
+  const [loadingGcSummary, setLoadingGcSummary] = useState(false);
   const [showDeleteModal, setShowDeleteModal] = useState(false);
   const [deleteConfirmName, setDeleteConfirmName] = useState("");
   const [contentDeleteTarget, setContentDeleteTarget] =
     useState<ContentDeleteTarget | null>(null);
   const [deletingContent, setDeletingContent] = useState(false);
+  const [brokenDependents, setBrokenDependents] =
+    useState<ModToggleResult | null>(null);
+  const [disablingDependents, setDisablingDependents] = useState(false);
   const [tableFilters, setTableFilters] = useState<Record<FilterKey, string>>({
     mods: "",
     resources: "",
@@ -294,7 +327,7 @@ export default function OverviewTabs({
   }, [datapackWorldId, worlds]);
   const recommendedLoaderVersion = useMemo(() => {
     if (loaderVersions.length === 0) return "";
-    if (instance.loader === "fabric") {
+    if (instance.loader === "fabric" || instance.loader === "quilt") {
       const stable = loaderVersions.find(
         (entry): entry is LoaderVersionSummary =>
           "stable" in entry && entry.stable,
@@ -341,9 +374,21 @@ export default function OverviewTabs({
     setInstanceNameState(instance.name);
     setMinRamMb(instance.java_min_ram_mb ?? defaults.minRam);
     setMaxRamMb(instance.java_max_ram_mb ?? defaults.maxRam);
-    setJvmArgs(instance.jvm_args ?? defaults.jvmArgs);
+    setJvmArgs(
+      instance.jvm_args ? formatJvmArgs(instance.jvm_args) : defaults.jvmArgs,
+    );
     setLoaderVersionState(instance.loader_version ?? "");
     setSavingLoaderVersion(false);
+    setBranding({
+      windowTitle: instance.window_title ?? "",
+      brandName: instance.brand_name ?? "",
+      brandVersion: instance.brand_version ?? "",
+    });
+    setSavingBranding(false);
+    setInstallingOptifine(false);
+    setInstallingShaderPreset(false);
+    setGcSummary(null);
+    setLoadingGcSummary(false);
     setIsRunning(false);
     setMemoryUsageMb(null);
     setCpuLoadPct(null);
@@ -379,6 +424,7 @@ export default function OverviewTabs({
       icon: null,
     });
     setModrinthDialogKind(null);
+    setSearchDefaultsLoaded([]);
     setModrinthBusy({});
     setModrinthInstalled({
       mods: [],
@@ -509,6 +555,106 @@ export default function OverviewTabs({
     };
   }, [appendInstanceLog, instanceId, setStatus]);
 
+  useEffect(() => {
+    const listen = getListen();
+    if (!listen) return;
+    let unlisten: (() => void) | null = null;
+    listen("world:size", (event: any) => {
+      const payload = (event?.payload || {}) as WorldSizeEvent;
+      if (payload.instance_id !== instanceId) return;
+      setWorlds((prev) =>
+        prev.map((world) =>
+          world.id === payload.world_id
+            ? { ...world, size_bytes: payload.size_bytes }
+            : world,
+        ),
+      );
+    }).then((stop: () => void) => {
+      unlisten = stop;
+    });
+    return () => {
+      unlisten?.();
+    };
+  }, [instanceId]);
+
+  useEffect(() => {
+    const listen = getListen();
+    if (!listen) return;
+    let unlisten: (() => void) | null = null;
+    listen("instance:updated", async (event: any) => {
+      const payload = (event?.payload || {}) as InstanceChangeEvent;
+      if (payload.instance_id !== instanceId || payload.change !== "worlds") return;
+      try {
+        const data = await invoke<WorldEntry[]>("list_instance_worlds", {
+          instanceId,
+        });
+        setWorlds(data || []);
+      } catch {
+        // The next tab switch reloads the list anyway.
+      }
+    }).then((stop: () => void) => {
+      unlisten = stop;
+    });
+    return () => {
+      unlisten?.();
+    };
+  }, [instanceId]);
+
+  useEffect(() => {
+    const listen = getListen();
+    if (!listen) return;
+    let unlisten: (() => void) | null = null;
+    listen("launch:stopped", (event: any) => {
+      const payload = event?.payload || {};
+      const stoppedId = payload.instance_id || payload.instanceId;
+      if (stoppedId !== instanceId || !payload.forced) return;
+      const message = "Instance ignored the stop request and was killed.";
+      appendInstanceLog(instanceId, message);
+      setStatus(message);
+    }).then((stop: () => void) => {
+      unlisten = stop;
+    });
+    return () => {
+      unlisten?.();
+    };
+  }, [appendInstanceLog, instanceId, setStatus]);
+
+  useEffect(() => {
+    const listen = getListen();
+    if (!listen) return;
+    let unlisten: (() => void) | null = null;
+    listen("servers:status", (event: any) => {
+      const payload = event?.payload || {};
+      if (payload.instance_id !== instanceId) return;
+      setServers((prev) =>
+        prev.map((server) =>
+          server.ip === payload.address
+            ? { ...server, status: payload.status }
+            : server,
+        ),
+      );
+    }).then((stop: () => void) => {
+      unlisten = stop;
+    });
+    return () => {
+      unlisten?.();
+    };
+  }, [instanceId]);
+
+  useEffect(() => {
+    const listen = getListen();
+    if (!listen) return;
+    let unlisten: (() => void) | null = null;
+    listen("lan:games", (event: any) => {
+      setLanGames(Array.isArray(event?.payload) ? event.payload : []);
+    }).then((stop: () => void) => {
+      unlisten = stop;
+    });
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
   useEffect(() => {
     if (!instanceId) return;
     const loadTab = async () => {
@@ -527,6 +673,7 @@ export default function OverviewTabs({
               kind: "resourcepacks",
             });
             setResourcepacks(data || []);
+            await loadResourcePackOrder();
             break;
           }
           case "textures": {
@@ -551,6 +698,8 @@ export default function OverviewTabs({
             });
             setServers(data || []);
             setEditingServerIndex(null);
+            const lan = await invoke<LanGame[]>("list_lan_games");
+            setLanGames(lan || []);
             break;
           }
           case "worlds": {
@@ -623,6 +772,11 @@ export default function OverviewTabs({
                 gameVersion: instance.version,
                 includeSnapshots: true,
               })
+            : instance.loader === "quilt"
+              ? await invoke<LoaderVersionSummary[]>("list_quilt_loader_versions", {
+                  gameVersion: instance.version,
+                  includeSnapshots: true,
+                })
             : instance.loader === "neoforge"
               ? await invoke<ForgeVersionSummary[]>("list_neoforge_versions", {
                   gameVersion: instance.version,
@@ -708,6 +862,26 @@ export default function OverviewTabs({
     }
   };
 
+  const handleDeployStagedDatapacks = async () => {
+    if (!datapackWorldId) return;
+    try {
+      const deployed = await invoke<number>("deploy_staged_datapacks", {
+        instanceId,
+        worldIds: [datapackWorldId],
+      });
+      await Promise.all([
+        loadDatapacks(datapackWorldId),
+        loadModrinthInstalls("datapacks", datapackWorldId),
+      ]);
+      setStatus(
+        `Deployed ${deployed} staged datapack${deployed === 1 ? "" : "s"}.`,
+      );
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to deploy staged datapacks.";
+      setStatus(message, "error");
+    }
+  };
+
   const loadDatapacks = async (worldId: string) => {
     if (!worldId) {
       setDatapacks([]);
@@ -833,13 +1007,10 @@ export default function OverviewTabs({
     const filters = options?.filters ?? modrinthState[kind].filters;
     const limit = query.length === 0 ? 10 : 16;
     const loaderOverride = kind === "mods" ? filters.loaders : [];
+    // Game version and loader fall back to the instance on the backend.
     const loader =
-      kind === "mods" && loaderOverride.length === 1
-        ? loaderOverride[0]
-        : kind === "mods"
-          ? modrinthLoader
-          : null;
-    const gameVersion = filters.showAllVersions ? "" : instance.version;
+      kind === "mods" && loaderOverride.length === 1 ? loaderOverride[0] : null;
+    const gameVersion = filters.showAllVersions ? "" : null;
     const extraFacets = buildModrinthFacets(kind, filters);
     updateModrinthState(kind, { loading: true, error: null, query });
     try {
@@ -853,6 +1024,7 @@ export default function OverviewTabs({
           limit,
           sort,
           extraFacets,
+          instanceId,
         },
       );
       updateModrinthState(kind, {
@@ -876,7 +1048,9 @@ export default function OverviewTabs({
   ) => {
     try {
       const worldId =
-        kind === "datapacks" ? worldIdOverride ?? datapackWorldId : undefined;
+        kind === "datapacks"
+          ? worldIdOverride ?? (datapackWorldId || undefined)
+          : undefined;
       const installed = await invoke<string[]>("list_modrinth_installs", {
         instanceId,
         projectType: resolveModrinthProjectType(kind),
@@ -898,7 +1072,9 @@ export default function OverviewTabs({
   ) => {
     try {
       const worldId =
-        kind === "datapacks" ? worldIdOverride ?? datapackWorldId : undefined;
+        kind === "datapacks"
+          ? worldIdOverride ?? (datapackWorldId || undefined)
+          : undefined;
       const loaderOverride =
         kind === "mods" ? modrinthState[kind].filters.loaders : [];
       const loader =
@@ -924,31 +1100,71 @@ export default function OverviewTabs({
     }
   };
 
-  const openModrinthDialog = async (kind: ModrinthKind) => {
-    if (kind === "datapacks" && !datapackWorldId) {
-      setStatus("Select a world before browsing datapacks.", "error");
-      return;
+  const loadModrinthSearchDefaults = async (kind: ModrinthKind) => {
+    let sort = modrinthState[kind].sort;
+    let filters = modrinthState[kind].filters;
+    if (searchDefaultsLoaded.includes(kind)) {
+      return { sort, filters };
     }
+    try {
+      const saved = await invoke<InstanceSearchDefaults>(
+        "get_instance_search_defaults",
+        { instanceId },
+      );
+      const entry = saved?.defaults?.[resolveModrinthProjectType(kind)];
+      if (entry) {
+        sort = (entry.sort as ModrinthSort | null) || sort;
+        filters = { ...filters, categories: entry.categories || [] };
+        updateModrinthState(kind, { sort, filters });
+      }
+    } catch {
+      // Searching with the built-in defaults is fine when the manifest can't be read.
+    }
+    setSearchDefaultsLoaded((prev) => [...prev, kind]);
+    return { sort, filters };
+  };
+
+  const saveModrinthSearchDefaults = async (
+    kind: ModrinthKind,
+    sort: ModrinthSort,
+    filters: ModrinthFilters,
+  ) => {
+    try {
+      await invoke("set_instance_search_defaults", {
+        instanceId,
+        projectType: resolveModrinthProjectType(kind),
+        categories: filters.categories,
+        sort,
+      });
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to save search defaults.";
+      setStatus(message, "error");
+    }
+  };
+
+  const openModrinthDialog = async (kind: ModrinthKind) => {
     updateModrinthState(kind, {
       loading: true,
       error: null,
       hasLoaded: false,
     });
     setModrinthDialogKind(kind);
-    await Promise.all([
+    const [{ sort, filters }] = await Promise.all([
+      loadModrinthSearchDefaults(kind),
       loadModrinthInstalls(kind),
       loadModrinthUpdates(kind),
     ]);
     await handleModrinthSearch(kind, {
       query: modrinthState[kind].query,
-      sort: modrinthState[kind].sort,
-      filters: modrinthState[kind].filters,
+      sort,
+      filters,
     });
   };
 
   const handleModrinthSortChange = (kind: ModrinthKind, value: ModrinthSort) => {
     updateModrinthState(kind, { sort: value });
     handleModrinthSearch(kind, { sort: value });
+    saveModrinthSearchDefaults(kind, value, modrinthState[kind].filters);
   };
 
   const handleModrinthCategoryToggle = (kind: ModrinthKind, value: string) => {
@@ -958,6 +1174,7 @@ export default function OverviewTabs({
     };
     updateModrinthState(kind, { filters: nextFilters });
     handleModrinthSearch(kind, { filters: nextFilters });
+    saveModrinthSearchDefaults(kind, modrinthState[kind].sort, nextFilters);
   };
 
   const handleModrinthLoaderToggle = (kind: ModrinthKind, value: string) => {
@@ -1027,14 +1244,41 @@ export default function OverviewTabs({
         kind === "mods" && loaderOverride.length === 1
           ? loaderOverride[0]
           : modrinthLoader;
+      let versionId: string | null = null;
+      let compatibilityWarning: string | null = null;
+      if (kind === "resources") {
+        const compatibility = await invoke<ModrinthCompatibilityReport>(
+          "check_modrinth_compatibility",
+          {
+            projectId: project.project_id,
+            projectType: resolveModrinthProjectType(kind),
+            gameVersion: instance.version,
+            loader: null,
+            versionId: null,
+            instanceId,
+          },
+        );
+        if (!compatibility.compatible) {
+          if (!compatibility.nearest_version_id) {
+            setStatus(
+              compatibility.warning || "No compatible version found.",
+              "error",
+            );
+            return;
+          }
+          versionId = compatibility.nearest_version_id;
+          compatibilityWarning = compatibility.warning || null;
+        }
+      }
       await invoke("install_modrinth_project", {
         instanceId,
         projectId: project.project_id,
         projectType: resolveModrinthProjectType(kind),
         gameVersion: instance.version,
         loader: kind === "mods" ? installLoader : null,
-        worldId: kind === "datapacks" ? datapackWorldId : null,
+        worldId: kind === "datapacks" ? datapackWorldId || null : null,
         installDependencies,
+        versionId,
       });
       if (kind === "mods") {
         const data = await invoke<ModEntry[]>("list_instance_mods", {
@@ -1061,7 +1305,11 @@ export default function OverviewTabs({
         loadModrinthInstalls(kind),
         loadModrinthUpdates(kind),
       ]);
-      setStatus(`${project.title} installed.`);
+      setStatus(
+        compatibilityWarning
+          ? `${project.title} installed. ${compatibilityWarning}`
+          : `${project.title} installed.`,
+      );
     } catch (err: any) {
       const message = err?.toString?.() || "Failed to install from Modrinth.";
       setStatus(message, "error");
@@ -1078,7 +1326,7 @@ export default function OverviewTabs({
     kind: ModrinthKind,
     project: ModrinthProjectHit,
   ) => {
-    if (kind !== "mods") {
+    if (kind !== "mods" && kind !== "shaders") {
       await handleModrinthInstall(kind, project, true);
       return;
     }
@@ -1094,7 +1342,7 @@ export default function OverviewTabs({
         projectId: project.project_id,
         projectType: resolveModrinthProjectType(kind),
         gameVersion: instance.version,
-        loader: installLoader,
+        loader: kind === "mods" ? installLoader : null,
         worldId: null,
       });
       if ((plan.dependencies || []).length > 0) {
@@ -1126,7 +1374,7 @@ export default function OverviewTabs({
         instanceId,
         projectId: project.project_id,
         projectType: resolveModrinthProjectType(kind),
-        worldId: kind === "datapacks" ? datapackWorldId : null,
+        worldId: kind === "datapacks" ? datapackWorldId || null : null,
       });
       if (kind === "mods") {
         const data = await invoke<ModEntry[]>("list_instance_mods", {
@@ -1187,7 +1435,7 @@ export default function OverviewTabs({
         projectType: resolveModrinthProjectType(kind),
         gameVersion: instance.version,
         loader: kind === "mods" ? updateLoader : null,
-        worldId: kind === "datapacks" ? datapackWorldId : null,
+        worldId: kind === "datapacks" ? datapackWorldId || null : null,
       });
       if (kind === "mods") {
         const data = await invoke<ModEntry[]>("list_instance_mods", {
@@ -1212,7 +1460,7 @@ export default function OverviewTabs({
     }
   };
 
-  const handleStart = async () => {
+  const handleStart = async (joinServer?: string) => {
     try {
       const report = await loadPreflight();
       if (report && !report.ready) {
@@ -1223,10 +1471,14 @@ export default function OverviewTabs({
       await invoke("launch_instance", {
         instanceId,
         playerName: resolvePlayerName(),
+        joinServer: joinServer ?? null,
       });
       setIsRunning(true);
-      appendInstanceLog(instanceId, "Launching instance.");
-      setStatus("Launching instance.");
+      const message = joinServer
+        ? `Launching instance and joining ${joinServer}.`
+        : "Launching instance.";
+      appendInstanceLog(instanceId, message);
+      setStatus(message);
     } catch (err: any) {
       const message = err?.toString?.() || "Launch failed.";
       setStatus(message, "error");
@@ -1237,11 +1489,6 @@ export default function OverviewTabs({
   const handleStop = async () => {
     try {
       await invoke("stop_instance", { instanceId });
-      setIsRunning(false);
-      setMemoryUsageMb(null);
-      setCpuLoadPct(null);
-      setGpuLoadPct(null);
-      setMetricHistory([]);
       appendInstanceLog(instanceId, "Stop signal sent.");
       setStatus("Stop signal sent.");
     } catch (err: any) {
@@ -1303,18 +1550,15 @@ export default function OverviewTabs({
     const file = event.target.files?.[0] ?? null;
     event.target.value = "";
     if (!file) return;
-    if (file.type !== "image/png") {
-      setStatus("World icon must be a PNG file.", "error");
+    if (!file.type.startsWith("image/")) {
+      setStatus("World icon must be an image file.", "error");
       return;
     }
-    const reader = new FileReader();
-    reader.onload = () => {
-      const result = reader.result;
-      if (typeof result === "string") {
-        setWorldDraft((prev) => ({ ...prev, icon: result }));
-      }
-    };
-    reader.readAsDataURL(file);
+    imageFileToPngDataUrl(file)
+      .then((icon) => setWorldDraft((prev) => ({ ...prev, icon })))
+      .catch((err: any) => {
+        setStatus(err?.message || "Failed to read the icon image.", "error");
+      });
   };
 
   const persistServers = async (next: ServerEntry[]) => {
@@ -1528,7 +1772,7 @@ export default function OverviewTabs({
 
   const handleToggleMod = async (entry: ModEntry) => {
     try {
-      await invoke("toggle_mod", {
+      const result = await invoke<ModToggleResult>("toggle_mod", {
         instanceId,
         filename: entry.filename,
         enabled: !entry.enabled,
@@ -1537,12 +1781,65 @@ export default function OverviewTabs({
         instanceId,
       });
       setMods(data || []);
+      if (result?.broken_dependents?.length) {
+        setBrokenDependents(result);
+      }
     } catch (err: any) {
       const message = err?.toString?.() || "Failed to update mod.";
       setStatus(message, "error");
     }
   };
 
+  const resolveBrokenDependents = async (disableDependents: boolean) => {
+    if (!brokenDependents) return;
+    setDisablingDependents(true);
+    try {
+      await invoke<ModToggleResult>("toggle_mod", {
+        instanceId,
+        filename: brokenDependents.filename,
+        enabled: !disableDependents,
+        disableDependents,
+      });
+      const data = await invoke<ModEntry[]>("list_instance_mods", {
+        instanceId,
+      });
+      setMods(data || []);
+      setBrokenDependents(null);
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to update mods.";
+      setStatus(message, "error");
+    } finally {
+      setDisablingDependents(false);
+    }
+  };
+
+  const loadResourcePackOrder = async () => {
+    try {
+      const order = await invoke<string[]>("get_resource_pack_order", {
+        instanceId,
+      });
+      setResourcePackOrder(order || []);
+    } catch {
+      setResourcePackOrder([]);
+    }
+  };
+
+  const handleResourcePackOrderChange = async (next: string[]) => {
+    try {
+      setSavingPackOrder(true);
+      const order = await invoke<string[]>("set_resource_pack_order", {
+        instanceId,
+        filenames: next,
+      });
+      setResourcePackOrder(order || []);
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to update resource pack order.";
+      setStatus(message, "error");
+    } finally {
+      setSavingPackOrder(false);
+    }
+  };
+
   const handleTogglePack = async (entry: PackEntry, kind: TableKind) => {
     try {
       await invoke("toggle_instance_pack", {
@@ -1555,7 +1852,10 @@ export default function OverviewTabs({
         instanceId,
         kind,
       });
-      if (kind === "resourcepacks") setResourcepacks(data || []);
+      if (kind === "resourcepacks") {
+        setResourcepacks(data || []);
+        await loadResourcePackOrder();
+      }
       if (kind === "texturepacks") setTexturepacks(data || []);
       if (kind === "shaderpacks") setShaders(data || []);
     } catch (err: any) {
@@ -1644,6 +1944,7 @@ export default function OverviewTabs({
           await Promise.all([
             loadModrinthInstalls("resources"),
             loadModrinthUpdates("resources"),
+            loadResourcePackOrder(),
           ]);
         }
         if (contentDeleteTarget.kind === "shaderpacks") {
@@ -1709,6 +2010,32 @@ export default function OverviewTabs({
     }
   };
 
+  const handleAutoUpdateModsChange = async (enabled: boolean) => {
+    try {
+      await invoke("set_instance_auto_update_mods", { instanceId, enabled });
+      await refreshConfig();
+      setStatus(
+        enabled
+          ? "Mods will be updated before each launch."
+          : "Automatic mod updates disabled.",
+      );
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to update setting.";
+      setStatus(message, "error");
+    }
+  };
+
+  const handleReleaseChannelChange = async (channel: ReleaseChannel | null) => {
+    try {
+      await invoke("set_instance_release_channel", { instanceId, channel });
+      await refreshConfig();
+      setStatus("Release channel updated.");
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to update setting.";
+      setStatus(message, "error");
+    }
+  };
+
   const handleSaveLoaderVersion = async () => {
     if (instance.loader === "vanilla") return;
     const nextVersion = loaderVersionState.trim();
@@ -1736,6 +2063,88 @@ export default function OverviewTabs({
     }
   };
 
+  const handleSaveBranding = async () => {
+    try {
+      setSavingBranding(true);
+      const warning = await invoke<string | null>("set_instance_branding", {
+        instanceId,
+        windowTitle: branding.windowTitle,
+        brandName: branding.brandName,
+        brandVersion: branding.brandVersion,
+      });
+      await refreshConfig();
+      if (warning) {
+        setStatus(`Branding updated. ${warning}`, "error");
+      } else {
+        setStatus("Branding updated. Changes apply on the next launch.");
+      }
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to update branding.";
+      setStatus(message, "error");
+    } finally {
+      setSavingBranding(false);
+    }
+  };
+
+  const handleInstallOptifine = (event: ChangeEvent<HTMLInputElement>) => {
+    const file = event.target.files?.[0] ?? null;
+    event.target.value = "";
+    if (!file) return;
+    const reader = new FileReader();
+    reader.onload = async () => {
+      const result = reader.result;
+      if (typeof result !== "string") return;
+      try {
+        setInstallingOptifine(true);
+        const installed = (await invoke("install_optifine", {
+          instanceId,
+          fileName: file.name,
+          data: result.slice(result.indexOf(",") + 1),
+        })) as OptiFineInstall;
+        await refreshConfig();
+        setStatus(`OptiFine ${installed.edition} ${installed.patch} installed.`);
+      } catch (err: any) {
+        const message = err?.toString?.() || "Failed to install OptiFine.";
+        setStatus(message, "error");
+      } finally {
+        setInstallingOptifine(false);
+      }
+    };
+    reader.readAsDataURL(file);
+  };
+
+  const handleInstallShaderPreset = async () => {
+    try {
+      setInstallingShaderPreset(true);
+      await invoke("install_shader_preset", { instanceId });
+      const data = await invoke<ModEntry[]>("list_instance_mods", {
+        instanceId,
+      });
+      setMods(data || []);
+      await loadModrinthInstalls("mods");
+      setStatus("Iris and Sodium installed. Drop shader packs into the shaderpacks folder.");
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to install the shader preset.";
+      setStatus(message, "error");
+    } finally {
+      setInstallingShaderPreset(false);
+    }
+  };
+
+  const handleAnalyzeGcLog = async () => {
+    try {
+      setLoadingGcSummary(true);
+      const summary = (await invoke("summarize_gc_log", { instanceId })) as GcLogSummary;
+      setGcSummary(summary);
+    } catch (err: any) {
+      const message = err?.toString?.() || "Failed to read GC log.";
+      setGcSummary(null);
+      setStatus(message, "error");
+    } finally {
+      setLoadingGcSummary(false);
+    }
+  };
+
   const handleResetSettings = async () => {
     try {
       await invoke("update_instance_settings", {
@@ -1946,7 +2355,7 @@ export default function OverviewTabs({
               breadcrumbs={renderBreadcrumbs()}
               isRunning={isRunning}
               hasAccounts={hasAccounts}
-              onStart={handleStart}
+              onStart={() => handleStart()}
               onStop={handleStop}
               onKill={handleKill}
               onCopyLogs={handleCopyLogs}
@@ -1985,6 +2394,13 @@ export default function OverviewTabs({
               onToggle={(entry) => handleTogglePack(entry, "resourcepacks")}
               onDelete={(entry) => handleRequestDeletePack(entry, "resourcepacks")}
               onOpen={() => openPath("resourcepacks")}
+              orderPanel={
+                <ResourcePackOrder
+                  order={resourcePackOrder}
+                  saving={savingPackOrder}
+                  onChange={handleResourcePackOrderChange}
+                />
+              }
               query={getFilter("resources")}
               onQueryChange={(value) => setFilter("resources", value)}
               showVersion
@@ -2038,13 +2454,14 @@ export default function OverviewTabs({
                 "Datapacks",
                 datapackWorldName
                   ? `Discover datapacks for ${datapackWorldName}.`
-                  : "Discover datapacks for this world.",
-                !datapackWorldId,
+                  : "Stage datapacks now and deploy them to a world later.",
+                false,
               )}
               worlds={worlds}
               worldId={datapackWorldId}
               onWorldChange={setDatapackWorldId}
               onOpen={() => openDatapacksPath(datapackWorldId)}
+              onDeployStaged={handleDeployStagedDatapacks}
               items={datapacks}
               onToggle={handleToggleDatapack}
               onDelete={handleRequestDeleteDatapack}
@@ -2076,6 +2493,9 @@ export default function OverviewTabs({
               probingAddress={probingServerAddress}
               latencyByAddress={serverLatencyByAddress}
               onProbe={handleProbeServer}
+              lanGames={lanGames}
+              joinDisabled={isRunning}
+              onJoinLan={(game) => handleStart(game.address)}
             />
           )}
           {activeTab === "worlds" && (
@@ -2142,6 +2562,9 @@ export default function OverviewTabs({
                     : Math.round(value);
                 setMaxRamMb(Number.isFinite(next) ? next : 0);
               }}
+              gcSummary={gcSummary}
+              loadingGcSummary={loadingGcSummary}
+              onAnalyzeGcLog={handleAnalyzeGcLog}
               jvmArgs={jvmArgs}
               onJvmArgsChange={setJvmArgs}
               onSaveSettings={handleSaveSettings}
@@ -2152,6 +2575,27 @@ export default function OverviewTabs({
               onLoaderVersionChange={setLoaderVersionState}
               onSaveLoaderVersion={handleSaveLoaderVersion}
               savingLoaderVersion={savingLoaderVersion}
+              autoUpdateMods={Boolean(instance.auto_update_mods)}
+              onAutoUpdateModsChange={handleAutoUpdateModsChange}
+              releaseChannel={instance.release_channel ?? null}
+              onReleaseChannelChange={handleReleaseChannelChange}
+              branding={branding}
+              onBrandingChange={setBranding}
+              onSaveBranding={handleSaveBranding}
+              savingBranding={savingBranding}
+              canInstallOptifine={
+                instance.kind !== "server" &&
+                ["vanilla", "forge", "fabric", "quilt"].includes(instance.loader)
+              }
+              installedOptifine={instance.optifine ?? null}
+              installingOptifine={installingOptifine}
+              onInstallOptifine={handleInstallOptifine}
+              canInstallShaderPreset={
+                instance.kind !== "server" &&
+                ["fabric", "quilt"].includes(instance.loader)
+              }
+              installingShaderPreset={installingShaderPreset}
+              onInstallShaderPreset={handleInstallShaderPreset}
               onRepair={handleRepairInstance}
               repairing={repairing}
               onDelete={() => setShowDeleteModal(true)}
@@ -2354,6 +2798,15 @@ export default function OverviewTabs({
           onClose={() => setContentDeleteTarget(null)}
           onConfirm={handleConfirmDeleteContent}
         />
+        <ModDependentsDialog
+          open={!!brokenDependents}
+          filename={brokenDependents?.filename ?? ""}
+          dependents={brokenDependents?.broken_dependents ?? []}
+          busy={disablingDependents}
+          onClose={() => setBrokenDependents(null)}
+          onUndo={() => resolveBrokenDependents(false)}
+          onDisableDependents={() => resolveBrokenDependents(true)}
+        />
       </div>
     </div>
   );
@@ -1,5 +1,5 @@
 import type { ReactNode } from "react";
-import { FolderOpen, Trash2 } from "lucide-react";
+import { FolderOpen, PackagePlus, Trash2 } from "lucide-react";
 import { useMemo } from "react";
 import { Button } from "@/components/ui/button";
 import {
@@ -18,6 +18,7 @@ interface DatapackTableProps {
   worldId: string;
   onWorldChange: (value: string) => void;
   onOpen: () => void;
+  onDeployStaged: () => void;
   items: PackEntry[];
   onToggle: (entry: PackEntry) => void;
   onDelete: (entry: PackEntry) => void;
@@ -32,6 +33,7 @@ export default function DatapackTable({
   worldId,
   onWorldChange,
   onOpen,
+  onDeployStaged,
   items,
   onToggle,
   onDelete,
@@ -86,6 +88,15 @@ export default function DatapackTable({
           >
             <FolderOpen size={18} />
           </Button>
+          <Button
+            variant="outline"
+            className="gap-2"
+            onClick={onDeployStaged}
+            disabled={!worldId}
+          >
+            <PackagePlus size={16} />
+            Deploy staged
+          </Button>
         </div>
       )}
 
@@ -1,4 +1,4 @@
-import type { ReactNode } from "react";
+import type { ChangeEvent, ReactNode } from "react";
 import { AlertTriangle, Copy, RefreshCw, RotateCcw, ShieldAlert, Star } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import {
@@ -13,6 +13,10 @@ import type {
   InstancePreflightReport,
   InstanceSnapshot,
   JavaRuntimeEntry,
+  GcLogSummary,
+  InstanceBranding,
+  OptiFineInstall,
+  ReleaseChannel,
 } from "@/lib/launcher-types";
 
 interface InstanceSettingsPanelProps {
@@ -48,6 +52,9 @@ interface InstanceSettingsPanelProps {
   ramDisplayUnit: string;
   onMinRamChange: (value: number) => void;
   onMaxRamChange: (value: number) => void;
+  gcSummary: GcLogSummary | null;
+  loadingGcSummary: boolean;
+  onAnalyzeGcLog: () => void;
   jvmArgs: string;
   onJvmArgsChange: (value: string) => void;
   onSaveSettings: () => void;
@@ -58,6 +65,21 @@ interface InstanceSettingsPanelProps {
   onLoaderVersionChange: (value: string) => void;
   onSaveLoaderVersion: () => void;
   savingLoaderVersion: boolean;
+  autoUpdateMods: boolean;
+  onAutoUpdateModsChange: (enabled: boolean) => void;
+  releaseChannel: ReleaseChannel | null;
+  onReleaseChannelChange: (channel: ReleaseChannel | null) => void;
+  branding: InstanceBranding;
+  onBrandingChange: (branding: InstanceBranding) => void;
+  onSaveBranding: () => void;
+  savingBranding: boolean;
+  canInstallOptifine: boolean;
+  installedOptifine: OptiFineInstall | null;
+  installingOptifine: boolean;
+  onInstallOptifine: (event: ChangeEvent<HTMLInputElement>) => void;
+  canInstallShaderPreset: boolean;
+  installingShaderPreset: boolean;
+  onInstallShaderPreset: () => void;
   onRepair: () => void;
   repairing: boolean;
   onDelete: () => void;
@@ -126,6 +148,9 @@ export default function InstanceSettingsPanel({
   ramDisplayUnit,
   onMinRamChange,
   onMaxRamChange,
+  gcSummary,
+  loadingGcSummary,
+  onAnalyzeGcLog,
   jvmArgs,
   onJvmArgsChange,
   onSaveSettings,
@@ -136,6 +161,21 @@ export default function InstanceSettingsPanel({
   onLoaderVersionChange,
   onSaveLoaderVersion,
   savingLoaderVersion,
+  autoUpdateMods,
+  onAutoUpdateModsChange,
+  releaseChannel,
+  onReleaseChannelChange,
+  branding,
+  onBrandingChange,
+  onSaveBranding,
+  savingBranding,
+  canInstallOptifine,
+  installedOptifine,
+  installingOptifine,
+  onInstallOptifine,
+  canInstallShaderPreset,
+  installingShaderPreset,
+  onInstallShaderPreset,
   onRepair,
   repairing,
   onDelete,
@@ -490,6 +530,54 @@ export default function InstanceSettingsPanel({
         <p className="mt-4 text-xs text-foreground/50">
           Keep maximum RAM high enough for modded instances, but leave headroom for the system.
         </p>
+        <div className="mt-4 rounded-xl border border-border bg-secondary/15 p-4">
+          <div className="flex items-center justify-between gap-3">
+            <div>
+              <p className="text-sm font-semibold">GC Pauses</p>
+              <p className="text-xs text-foreground/60">
+                Reads logs/gc.log from the last session with GC logging enabled.
+              </p>
+            </div>
+            <Button
+              variant="outline"
+              onClick={onAnalyzeGcLog}
+              disabled={loadingGcSummary}
+              className="bg-transparent"
+            >
+              {loadingGcSummary ? "Analyzing..." : "Analyze GC Log"}
+            </Button>
+          </div>
+          {gcSummary ? (
+            <div className="mt-4 space-y-3">
+              <div className="grid gap-3 text-sm md:grid-cols-4">
+                <div>
+                  <p className="text-[10px] uppercase tracking-[0.22em] text-foreground/45">Pauses</p>
+                  <p className="font-mono font-bold">{gcSummary.pause_count}</p>
+                </div>
+                <div>
+                  <p className="text-[10px] uppercase tracking-[0.22em] text-foreground/45">Average</p>
+                  <p className="font-mono font-bold">{gcSummary.average_pause_ms.toFixed(1)} ms</p>
+                </div>
+                <div>
+                  <p className="text-[10px] uppercase tracking-[0.22em] text-foreground/45">95th pct</p>
+                  <p className="font-mono font-bold">{gcSummary.p95_pause_ms.toFixed(1)} ms</p>
+                </div>
+                <div>
+                  <p className="text-[10px] uppercase tracking-[0.22em] text-foreground/45">Longest</p>
+                  <p className="font-mono font-bold">{gcSummary.max_pause_ms.toFixed(1)} ms</p>
+                </div>
+              </div>
+              <p className="text-xs text-foreground/60">
+                {gcSummary.full_gc_count} full collections, {gcSummary.long_pause_count} pauses over 50 ms
+                {gcSummary.heap_capacity_mb
+                  ? `, peak ${gcSummary.peak_heap_after_gc_mb ?? 0} MB of ${gcSummary.heap_capacity_mb} MB kept after GC`
+                  : ""}
+                .
+              </p>
+              <p className="text-sm text-foreground/80">{gcSummary.recommendation}</p>
+            </div>
+          ) : null}
+        </div>
       </div>
 
       {canEditLoaderVersion ? (
@@ -540,6 +628,50 @@ export default function InstanceSettingsPanel({
         </div>
       ) : null}
 
+      {canEditLoaderVersion ? (
+        <div className="bg-card border border-border rounded-xl p-4 md:p-6">
+          <div className="mb-4">
+            <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
+              Content
+            </p>
+            <h3 className="mt-2 text-lg font-bold">Mod Updates</h3>
+          </div>
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={autoUpdateMods}
+              onChange={(event) => onAutoUpdateModsChange(event.target.checked)}
+            />
+            Update Modrinth mods before each launch
+          </label>
+          <p className="mt-2 text-xs text-foreground/60">
+            Pinned and blacklisted mods are skipped. The mods folder is snapshotted before any update is applied.
+          </p>
+          <div className="mt-4 space-y-2">
+            <label className="text-sm font-semibold">Release Channel</label>
+            <Select
+              value={releaseChannel ?? "default"}
+              onValueChange={(value) =>
+                onReleaseChannelChange(value === "default" ? null : (value as ReleaseChannel))
+              }
+            >
+              <SelectTrigger className="w-full bg-input border border-border rounded-lg px-4 py-2 text-foreground focus:ring-2 focus:ring-ring">
+                <SelectValue placeholder="Select release channel" />
+              </SelectTrigger>
+              <SelectContent className="bg-card border-border text-foreground">
+                <SelectItem value="default">Prefer releases, fall back to pre-releases</SelectItem>
+                <SelectItem value="release">Releases only</SelectItem>
+                <SelectItem value="beta">Releases and betas</SelectItem>
+                <SelectItem value="alpha">Everything, including alphas</SelectItem>
+              </SelectContent>
+            </Select>
+            <p className="text-xs text-foreground/60">
+              Applies to Modrinth installs, dependency resolution and update checks.
+            </p>
+          </div>
+        </div>
+      ) : null}
+
       <div className="bg-card border border-border rounded-xl p-4 md:p-6">
         <div className="mb-4">
           <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
@@ -570,6 +702,113 @@ export default function InstanceSettingsPanel({
         </div>
       </div>
 
+      <div className="bg-card border border-border rounded-xl p-4 md:p-6">
+        <div className="mb-4">
+          <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
+            Presentation
+          </p>
+          <h3 className="mt-2 text-lg font-bold">Window & Branding</h3>
+        </div>
+        <div className="grid gap-3 md:grid-cols-3">
+          <div className="space-y-2">
+            <label className="text-sm font-semibold">Window Title</label>
+            <input
+              value={branding.windowTitle}
+              onChange={(e) => onBrandingChange({ ...branding, windowTitle: e.target.value })}
+              placeholder="Minecraft default"
+              className="w-full bg-input border border-border rounded-lg px-4 py-2 text-foreground focus:outline-none focus:ring-2 focus:ring-accent"
+            />
+          </div>
+          <div className="space-y-2">
+            <label className="text-sm font-semibold">Launcher Name</label>
+            <input
+              value={branding.brandName}
+              onChange={(e) => onBrandingChange({ ...branding, brandName: e.target.value })}
+              placeholder="monolith"
+              className="w-full bg-input border border-border rounded-lg px-4 py-2 text-foreground focus:outline-none focus:ring-2 focus:ring-accent"
+            />
+          </div>
+          <div className="space-y-2">
+            <label className="text-sm font-semibold">Launcher Version</label>
+            <input
+              value={branding.brandVersion}
+              onChange={(e) => onBrandingChange({ ...branding, brandVersion: e.target.value })}
+              placeholder="Current launcher version"
+              className="w-full bg-input border border-border rounded-lg px-4 py-2 text-foreground focus:outline-none focus:ring-2 focus:ring-accent"
+            />
+          </div>
+        </div>
+        <div className="mt-4 flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
+          <p className="text-xs text-foreground/50">
+            Leave fields empty to use the defaults. The window title requires the Custom Window Title mod.
+          </p>
+          <Button
+            onClick={onSaveBranding}
+            disabled={savingBranding}
+            className="bg-accent text-accent-foreground hover:bg-accent/90"
+          >
+            {savingBranding ? "Saving..." : "Save Branding"}
+          </Button>
+        </div>
+      </div>
+
+      {canInstallOptifine ? (
+        <div className="bg-card border border-border rounded-xl p-4 md:p-6">
+          <div className="mb-4">
+            <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
+              Performance
+            </p>
+            <h3 className="mt-2 text-lg font-bold">OptiFine</h3>
+          </div>
+          <div className="flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
+            <p className="text-xs text-foreground/60">
+              {installedOptifine
+                ? `Installed: ${installedOptifine.edition} ${installedOptifine.patch}. Select a newer jar to replace it.`
+                : `Select an OptiFine jar for ${version} downloaded from optifine.net. Fabric and Quilt instances also get OptiFabric.`}
+            </p>
+            <Button
+              asChild
+              disabled={installingOptifine}
+              className="bg-accent text-accent-foreground hover:bg-accent/90"
+            >
+              <label className="cursor-pointer h-9 inline-flex items-center">
+                {installingOptifine ? "Installing..." : "Install OptiFine"}
+                <input
+                  type="file"
+                  accept=".jar"
+                  className="hidden"
+                  disabled={installingOptifine}
+                  onChange={onInstallOptifine}
+                />
+              </label>
+            </Button>
+          </div>
+        </div>
+      ) : null}
+
+      {canInstallShaderPreset ? (
+        <div className="bg-card border border-border rounded-xl p-4 md:p-6">
+          <div className="mb-4">
+            <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
+              Shaders
+            </p>
+            <h3 className="mt-2 text-lg font-bold">Iris + Sodium</h3>
+          </div>
+          <div className="flex flex-col gap-3 md:flex-row md:items-center md:justify-between">
+            <p className="text-xs text-foreground/60">
+              Installs compatible Iris and Sodium builds for {version} with their dependencies and creates the shaderpacks folder.
+            </p>
+            <Button
+              onClick={onInstallShaderPreset}
+              disabled={installingShaderPreset}
+              className="bg-accent text-accent-foreground hover:bg-accent/90"
+            >
+              {installingShaderPreset ? "Installing..." : "Make Shader-Ready"}
+            </Button>
+          </div>
+        </div>
+      ) : null}
+
       <div className="bg-card border border-border rounded-xl p-4 md:p-6">
         <div className="mb-4">
           <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
@@ -13,6 +13,7 @@ interface PackTableProps {
   query: string;
   onQueryChange: (value: string) => void;
   modrinthPanel?: ReactNode;
+  orderPanel?: ReactNode;
   emptyLabel?: string;
   showVersion?: boolean;
   versionLabel?: string;
@@ -27,6 +28,7 @@ export default function PackTable({
   query,
   onQueryChange,
   modrinthPanel,
+  orderPanel,
   emptyLabel = "No items",
   showVersion = true,
   versionLabel = "Version",
@@ -50,6 +52,7 @@ export default function PackTable({
     <div className="space-y-4">
       {breadcrumbs}
       {modrinthPanel}
+      {orderPanel}
       <div className="flex flex-wrap items-center gap-3">
         <input
           value={query}
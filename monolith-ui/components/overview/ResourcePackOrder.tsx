@@ -0,0 +1,66 @@
+import { ChevronDown, ChevronUp } from "lucide-react";
+
+interface ResourcePackOrderProps {
+  order: string[];
+  saving: boolean;
+  onChange: (next: string[]) => void;
+}
+
+export default function ResourcePackOrder({
+  order,
+  saving,
+  onChange,
+}: ResourcePackOrderProps) {
+  if (order.length === 0) return null;
+
+  const move = (index: number, offset: number) => {
+    const target = index + offset;
+    if (target < 0 || target >= order.length) return;
+    const next = [...order];
+    [next[index], next[target]] = [next[target], next[index]];
+    onChange(next);
+  };
+
+  return (
+    <div className="bg-card border border-border rounded-lg p-4">
+      <div className="mb-3">
+        <p className="text-sm font-semibold">Load Order</p>
+        <p className="mt-1 text-xs text-foreground/60">
+          Active packs as written to options.txt. Packs at the top override the ones below.
+        </p>
+      </div>
+      <ol className="space-y-2">
+        {order.map((filename, index) => (
+          <li
+            key={filename}
+            className="flex items-center justify-between gap-3 rounded-md border border-border bg-secondary/15 px-3 py-2"
+          >
+            <span className="truncate font-mono text-xs text-foreground/80">
+              {index + 1}. {filename}
+            </span>
+            <div className="flex items-center gap-1">
+              <button
+                type="button"
+                onClick={() => move(index, -1)}
+                disabled={saving || index === 0}
+                className="inline-flex h-7 w-7 items-center justify-center rounded-md border border-border hover:bg-muted disabled:opacity-40"
+                aria-label={`Raise ${filename}`}
+              >
+                <ChevronUp size={14} />
+              </button>
+              <button
+                type="button"
+                onClick={() => move(index, 1)}
+                disabled={saving || index === order.length - 1}
+                className="inline-flex h-7 w-7 items-center justify-center rounded-md border border-border hover:bg-muted disabled:opacity-40"
+                aria-label={`Lower ${filename}`}
+              >
+                <ChevronDown size={14} />
+              </button>
+            </div>
+          </li>
+        ))}
+      </ol>
+    </div>
+  );
+}
@@ -1,7 +1,7 @@
 import type { ReactNode } from "react";
-import { Check, Globe, Plus, X, FolderOpen, Activity } from "lucide-react";
+import { Check, Globe, Plus, X, FolderOpen, Activity, Wifi } from "lucide-react";
 import { Button } from "@/components/ui/button";
-import type { ServerEntry, ServerLatencyReport } from "@/lib/launcher-types";
+import type { LanGame, ServerEntry, ServerLatencyReport } from "@/lib/launcher-types";
 
 interface ServerTableProps {
   breadcrumbs: ReactNode;
@@ -24,6 +24,9 @@ interface ServerTableProps {
   probingAddress: string | null;
   latencyByAddress: Record<string, ServerLatencyReport>;
   onProbe: (server: ServerEntry) => void;
+  lanGames: LanGame[];
+  joinDisabled: boolean;
+  onJoinLan: (game: LanGame) => void;
 }
 
 export default function ServerTable({
@@ -47,6 +50,9 @@ export default function ServerTable({
   probingAddress,
   latencyByAddress,
   onProbe,
+  lanGames,
+  joinDisabled,
+  onJoinLan,
 }: ServerTableProps) {
   const filteredServers = query.trim()
     ? servers.filter((server) => {
@@ -224,6 +230,23 @@ export default function ServerTable({
                     <td className="p-4 text-sm font-mono text-foreground/70">
                       <div className="space-y-1">
                         <p>{server.ip}</p>
+                        {server.status ? (
+                          <p className="text-[11px] text-foreground/55">
+                            {server.status.online
+                              ? `Online${
+                                  server.status.players_online !== null &&
+                                  server.status.players_online !== undefined
+                                    ? ` • ${server.status.players_online}/${server.status.players_max ?? "?"} players`
+                                    : ""
+                                }${
+                                  server.status.latency_ms !== null &&
+                                  server.status.latency_ms !== undefined
+                                    ? ` • ${server.status.latency_ms}ms`
+                                    : ""
+                                }`
+                              : "Offline"}
+                          </p>
+                        ) : null}
                         {latencyByAddress[server.ip.toLowerCase().trim()] ? (
                           <p className="text-[11px] text-foreground/55">
                             {latencyByAddress[server.ip.toLowerCase().trim()].median_ms !== null &&
@@ -282,6 +305,42 @@ export default function ServerTable({
           </tbody>
         </table>
       </div>
+
+      {lanGames.length > 0 ? (
+        <div className="bg-card border border-border rounded-lg overflow-hidden">
+          <div className="flex items-center gap-2 p-4 border-b border-border bg-secondary/20">
+            <Wifi size={16} className="text-foreground/60" />
+            <span className="font-semibold text-sm">LAN Games</span>
+          </div>
+          <table className="w-full">
+            <tbody>
+              {lanGames.map((game) => (
+                <tr
+                  key={game.address}
+                  className="border-b border-border last:border-b-0 hover:bg-secondary/10"
+                >
+                  <td className="p-4 text-sm">{game.motd}</td>
+                  <td className="p-4 text-sm font-mono text-foreground/70">
+                    {game.address}
+                  </td>
+                  <td className="p-4">
+                    <div className="flex items-center justify-end">
+                      <Button
+                        size="sm"
+                        className="bg-primary text-primary-foreground hover:bg-primary/90"
+                        onClick={() => onJoinLan(game)}
+                        disabled={joinDisabled}
+                      >
+                        Join
+                      </Button>
+                    </div>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      ) : null}
     </div>
   );
 }
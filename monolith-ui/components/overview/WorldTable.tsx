@@ -125,7 +125,7 @@ export default function WorldTable({
                               Upload Icon
                               <input
                                 type="file"
-                                accept="image/png"
+                                accept="image/*"
                                 className="hidden"
                                 onChange={onIconSelect}
                               />
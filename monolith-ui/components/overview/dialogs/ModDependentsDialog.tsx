@@ -0,0 +1,105 @@
+"use client";
+
+import { AlertTriangle, X } from "lucide-react";
+import { Button } from "@/components/ui/button";
+
+interface ModDependentsDialogProps {
+  open: boolean;
+  filename: string;
+  dependents: string[];
+  busy: boolean;
+  onClose: () => void;
+  onUndo: () => void;
+  onDisableDependents: () => void;
+}
+
+export default function ModDependentsDialog({
+  open,
+  filename,
+  dependents,
+  busy,
+  onClose,
+  onUndo,
+  onDisableDependents,
+}: ModDependentsDialogProps) {
+  if (!open) return null;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/70 px-4 py-6 backdrop-blur-sm">
+      <div className="w-full max-w-lg overflow-hidden rounded-2xl border border-border bg-card shadow-2xl">
+        <div className="border-b border-border px-5 py-4 md:px-6">
+          <div className="flex items-start justify-between gap-4">
+            <div className="flex items-start gap-3">
+              <div className="rounded-xl border border-border bg-secondary/30 p-2.5 text-foreground/70">
+                <AlertTriangle size={16} />
+              </div>
+              <div>
+                <p className="text-[10px] uppercase tracking-[0.28em] text-foreground/45">
+                  Broken Dependencies
+                </p>
+                <h2 className="mt-1 text-xl font-bold text-foreground">
+                  Other mods need this one
+                </h2>
+                <p className="mt-2 text-sm text-foreground/65">
+                  {dependents.length === 1
+                    ? "1 enabled mod requires"
+                    : `${dependents.length} enabled mods require`}{" "}
+                  the mod you just disabled and will fail to load.
+                </p>
+              </div>
+            </div>
+            <Button
+              variant="ghost"
+              size="icon-sm"
+              onClick={onClose}
+              className="text-foreground/60 hover:bg-secondary/50 hover:text-foreground"
+            >
+              <X size={16} />
+            </Button>
+          </div>
+        </div>
+        <div className="space-y-5 px-5 py-5 md:px-6 md:py-6">
+          <div className="rounded-xl border border-border bg-secondary/15 p-4">
+            <p className="text-[10px] uppercase tracking-[0.24em] text-foreground/45">
+              Disabled
+            </p>
+            <p className="mt-2 break-all font-mono text-sm text-foreground/75">
+              {filename}
+            </p>
+            <p className="mt-4 text-[10px] uppercase tracking-[0.24em] text-foreground/45">
+              Dependents
+            </p>
+            <ul className="mt-2 max-h-48 space-y-1 overflow-y-auto">
+              {dependents.map((dependent) => (
+                <li key={dependent} className="break-all font-mono text-sm text-foreground/75">
+                  {dependent}
+                </li>
+              ))}
+            </ul>
+          </div>
+          <div className="flex flex-col gap-3 border-t border-border pt-5 sm:flex-row sm:justify-end">
+            <Button
+              variant="outline"
+              onClick={onUndo}
+              className="border-border bg-transparent text-foreground hover:bg-secondary/50 hover:text-foreground"
+              disabled={busy}
+            >
+              Re-enable
+            </Button>
+            <Button
+              variant="outline"
+              onClick={onClose}
+              className="border-border bg-transparent text-foreground hover:bg-secondary/50 hover:text-foreground"
+              disabled={busy}
+            >
+              Keep as is
+            </Button>
+            <Button variant="destructive" onClick={onDisableDependents} disabled={busy}>
+              {busy ? "Disabling..." : "Disable dependents"}
+            </Button>
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
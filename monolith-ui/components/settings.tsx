@@ -15,11 +15,12 @@ import {
 import AddDirectoryDialog from "./dialogs/add-directory-dialog";
 import AddJavaDialog from "./dialogs/add-java-dialog";
 import { useLauncher } from "./launcher-provider";
-import { slugify } from "@/lib/launcher-utils";
+import { formatJvmArgs, slugify } from "@/lib/launcher-utils";
 import type {
   DiscordPresenceMode,
   InstanceRoot,
   JavaRuntimeEntry,
+  NewInstanceDefaults,
 } from "@/lib/launcher-types";
 
 export default function Settings() {
@@ -40,11 +41,18 @@ export default function Settings() {
   const [selectedRuntimeId, setSelectedRuntimeId] = useState("auto");
   const [syncEnabled, setSyncEnabled] = useState(true);
   const [applyToNew, setApplyToNew] = useState(true);
+  const [newDefaults, setNewDefaults] = useState<NewInstanceDefaults>({
+    show_snapshots: false,
+    install_fabric_api: false,
+  });
+  const [newDefaultsJvmArgs, setNewDefaultsJvmArgs] = useState("");
   const [discordPresence, setDiscordPresence] = useState(true);
   const [discordPresenceMode, setDiscordPresenceMode] =
     useState<DiscordPresenceMode>("dynamic_minecraft");
   const [networkDiagnostics, setNetworkDiagnostics] = useState(true);
   const [smartNetworkOptimization, setSmartNetworkOptimization] = useState(true);
+  const [politeDownloads, setPoliteDownloads] = useState(false);
+  const [gcLogging, setGcLogging] = useState(false);
   const [performanceGamemode, setPerformanceGamemode] = useState(false);
   const [performanceMangohud, setPerformanceMangohud] = useState(false);
   const [performanceZink, setPerformanceZink] = useState(false);
@@ -69,12 +77,24 @@ export default function Settings() {
     setSelectedSecondary(secondary);
     setSyncEnabled(config.settings.pack_sync.enabled);
     setApplyToNew(config.settings.apply_to_new_instances);
+    const template = config.settings.new_instance_defaults ?? {
+      show_snapshots: false,
+      install_fabric_api: false,
+    };
+    setNewDefaults(template);
+    setNewDefaultsJvmArgs(
+      Array.isArray(template.jvm_args)
+        ? formatJvmArgs(template.jvm_args)
+        : template.jvm_args ?? "",
+    );
     setDiscordPresence(config.settings.discord_presence ?? true);
     setDiscordPresenceMode(
       config.settings.discord_presence_mode ?? "dynamic_minecraft",
     );
     setNetworkDiagnostics(config.settings.network_diagnostics ?? true);
     setSmartNetworkOptimization(config.settings.smart_network_optimization ?? true);
+    setPoliteDownloads(config.settings.polite_downloads ?? false);
+    setGcLogging(config.settings.gc_logging ?? false);
     setPerformanceGamemode(config.settings.performance_gamemode ?? false);
     setPerformanceMangohud(config.settings.performance_mangohud ?? false);
     setPerformanceZink(config.settings.performance_zink ?? false);
@@ -363,6 +383,36 @@ export default function Settings() {
     setStatus("Reference instance updated.");
   };
 
+  const saveNewInstanceDefaults = async (
+    updates: Partial<NewInstanceDefaults>,
+  ) => {
+    if (!config) return;
+    const merged = {
+      ...newDefaults,
+      jvm_args: newDefaultsJvmArgs.trim() || null,
+      ...updates,
+    };
+    setNewDefaults(merged);
+    const next = {
+      ...config,
+      settings: {
+        ...config.settings,
+        new_instance_defaults: merged,
+      },
+    };
+    await saveConfig(next);
+  };
+
+  const parseOptionalMb = (value: string) => {
+    const parsed = Math.round(Number(value));
+    return value.trim() && Number.isFinite(parsed) && parsed > 0 ? parsed : null;
+  };
+
+  const parseOptionalCount = (value: string) => {
+    const parsed = Math.round(Number(value));
+    return value.trim() && Number.isFinite(parsed) && parsed >= 0 ? parsed : null;
+  };
+
   const saveJavaSettings = async () => {
     if (!config) return;
     const next = {
@@ -435,6 +485,46 @@ export default function Settings() {
     await saveConfig(nextConfig);
   };
 
+  const handlePoliteDownloadsToggle = async (next: boolean) => {
+    if (!config) return;
+    setPoliteDownloads(next);
+    const nextConfig = {
+      ...config,
+      settings: {
+        ...config.settings,
+        polite_downloads: next,
+      },
+    };
+    await saveConfig(nextConfig);
+  };
+
+  const handleGcLoggingToggle = async (next: boolean) => {
+    if (!config) return;
+    setGcLogging(next);
+    const nextConfig = {
+      ...config,
+      settings: {
+        ...config.settings,
+        gc_logging: next,
+      },
+    };
+    await saveConfig(nextConfig);
+  };
+
+  const handleLegacyResourceProxyChange = async (value: string) => {
+    if (!config) return;
+    const next = value.trim();
+    if (next === (config.settings.legacy_resource_proxy ?? "")) return;
+    const nextConfig = {
+      ...config,
+      settings: {
+        ...config.settings,
+        legacy_resource_proxy: next,
+      },
+    };
+    await saveConfig(nextConfig);
+  };
+
   const handlePerformanceGamemodeToggle = async (next: boolean) => {
     if (!config) return;
     setPerformanceGamemode(next);
@@ -828,6 +918,53 @@ export default function Settings() {
               </div>
             </div>
 
+            <div className="mt-4 rounded-xl border border-border bg-secondary/15 p-4">
+              <div className="flex items-center justify-between gap-4">
+                <div>
+                  <p className="text-sm font-medium">Polite Downloads</p>
+                  <p className="mt-1 text-sm text-foreground/60">
+                    Installs with 2 connections and short random pauses. Slower, but avoids CDN throttling on flaky connections.
+                  </p>
+                </div>
+                <Switch
+                  checked={politeDownloads}
+                  onCheckedChange={handlePoliteDownloadsToggle}
+                  className="flex-shrink-0"
+                />
+              </div>
+            </div>
+
+            <div className="mt-4 rounded-xl border border-border bg-secondary/15 p-4">
+              <div className="flex items-center justify-between gap-4">
+                <div>
+                  <p className="text-sm font-medium">GC Logging</p>
+                  <p className="mt-1 text-sm text-foreground/60">
+                    Records Java garbage collection pauses to logs/gc.log so stutter can be traced back to RAM settings.
+                  </p>
+                </div>
+                <Switch
+                  checked={gcLogging}
+                  onCheckedChange={handleGcLoggingToggle}
+                  className="flex-shrink-0"
+                />
+              </div>
+            </div>
+
+            <div className="mt-4 rounded-xl border border-border bg-secondary/15 p-4">
+              <p className="text-sm font-medium">Legacy Resource Proxy</p>
+              <p className="mt-1 text-sm text-foreground/60">
+                HTTP proxy used by alpha, beta and pre-1.6 clients to fetch sounds and skins from retired endpoints.
+              </p>
+              <input
+                type="text"
+                defaultValue={config?.settings.legacy_resource_proxy ?? ""}
+                key={`legacy-proxy-${config?.settings.legacy_resource_proxy ?? ""}`}
+                onBlur={(e) => handleLegacyResourceProxyChange(e.target.value)}
+                placeholder="host:port"
+                className="mt-3 w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+              />
+            </div>
+
             <div className="mt-6 rounded-xl border border-border bg-secondary/15 p-4">
               <div className="flex items-center justify-between gap-4">
                 <div>
@@ -986,6 +1123,144 @@ export default function Settings() {
                       />
                     </div>
 
+                    {applyToNew && (
+                      <div className="rounded-xl border border-border bg-secondary/15 p-4 space-y-4">
+                        <p className="text-[10px] uppercase tracking-[0.24em] text-foreground/45">
+                          New Instance Defaults
+                        </p>
+                        <div className="flex items-center justify-between">
+                          <label className="text-sm font-medium">
+                            Show snapshots
+                          </label>
+                          <Switch
+                            checked={newDefaults.show_snapshots}
+                            onCheckedChange={(checked) =>
+                              saveNewInstanceDefaults({ show_snapshots: checked })
+                            }
+                          />
+                        </div>
+                        <div className="flex items-center justify-between">
+                          <label className="text-sm font-medium">
+                            Install Fabric API on Fabric instances
+                          </label>
+                          <Switch
+                            checked={newDefaults.install_fabric_api}
+                            onCheckedChange={(checked) =>
+                              saveNewInstanceDefaults({
+                                install_fabric_api: checked,
+                              })
+                            }
+                          />
+                        </div>
+                        <div>
+                          <label className="mb-2 block text-xs text-foreground/60">
+                            Root
+                          </label>
+                          <Select
+                            value={newDefaults.root_id || "default"}
+                            onValueChange={(value) =>
+                              saveNewInstanceDefaults({
+                                root_id: value === "default" ? null : value,
+                              })
+                            }
+                          >
+                            <SelectTrigger className="w-full">
+                              <SelectValue />
+                            </SelectTrigger>
+                            <SelectContent>
+                              <SelectItem value="default">
+                                Primary directory
+                              </SelectItem>
+                              {roots.map((root) => (
+                                <SelectItem key={root.id} value={root.id}>
+                                  {root.label || root.id}
+                                </SelectItem>
+                              ))}
+                            </SelectContent>
+                          </Select>
+                        </div>
+                        <div className="grid gap-3 md:grid-cols-2">
+                          <input
+                            type="number"
+                            min={128}
+                            defaultValue={newDefaults.java_min_ram_mb ?? ""}
+                            key={`min-${newDefaults.java_min_ram_mb ?? ""}`}
+                            onBlur={(e) =>
+                              saveNewInstanceDefaults({
+                                java_min_ram_mb: parseOptionalMb(e.target.value),
+                              })
+                            }
+                            placeholder="Min RAM (MB)"
+                            className="w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+                          />
+                          <input
+                            type="number"
+                            min={128}
+                            defaultValue={newDefaults.java_max_ram_mb ?? ""}
+                            key={`max-${newDefaults.java_max_ram_mb ?? ""}`}
+                            onBlur={(e) =>
+                              saveNewInstanceDefaults({
+                                java_max_ram_mb: parseOptionalMb(e.target.value),
+                              })
+                            }
+                            placeholder="Max RAM (MB)"
+                            className="w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+                          />
+                        </div>
+                        <input
+                          type="text"
+                          value={newDefaultsJvmArgs}
+                          onChange={(e) => setNewDefaultsJvmArgs(e.target.value)}
+                          onBlur={() => saveNewInstanceDefaults({})}
+                          placeholder="JVM arguments (blank uses the global value)"
+                          className="w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+                        />
+                        <div className="grid gap-3 md:grid-cols-3">
+                          <input
+                            type="text"
+                            defaultValue={newDefaults.language ?? ""}
+                            key={`lang-${newDefaults.language ?? ""}`}
+                            onBlur={(e) =>
+                              saveNewInstanceDefaults({
+                                language: e.target.value.trim() || null,
+                              })
+                            }
+                            placeholder="Language (e.g. de_de)"
+                            className="w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+                          />
+                          <input
+                            type="number"
+                            min={0}
+                            defaultValue={newDefaults.gui_scale ?? ""}
+                            key={`gui-${newDefaults.gui_scale ?? ""}`}
+                            onBlur={(e) =>
+                              saveNewInstanceDefaults({
+                                gui_scale: parseOptionalCount(e.target.value),
+                              })
+                            }
+                            placeholder="GUI scale (0 = auto)"
+                            className="w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+                          />
+                          <input
+                            type="number"
+                            min={10}
+                            defaultValue={newDefaults.max_framerate ?? ""}
+                            key={`fps-${newDefaults.max_framerate ?? ""}`}
+                            onBlur={(e) =>
+                              saveNewInstanceDefaults({
+                                max_framerate: parseOptionalMb(e.target.value),
+                              })
+                            }
+                            placeholder="Max framerate"
+                            className="w-full bg-input border border-border rounded-lg px-4 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-accent"
+                          />
+                        </div>
+                        <p className="text-xs text-foreground/50">
+                          Language, GUI scale and framerate are written to options.txt when a client instance is created.
+                        </p>
+                      </div>
+                    )}
+
                     <div>
                       <label className="mb-3 block text-[10px] uppercase tracking-[0.24em] text-foreground/45">
                         Reference Instance
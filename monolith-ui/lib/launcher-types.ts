@@ -1,4 +1,12 @@
-export type LoaderKind = "vanilla" | "fabric" | "forge" | "neoforge";
+export type LoaderKind =
+  | "vanilla"
+  | "fabric"
+  | "quilt"
+  | "forge"
+  | "neoforge"
+  | "paper"
+  | "folia"
+  | "purpur";
 
 export interface Account {
   id: string;
@@ -10,6 +18,27 @@ export interface Account {
   expires_at?: number | null;
   uuid?: string | null;
   owns_minecraft?: boolean | null;
+  gamertag?: string | null;
+}
+
+export interface AccountUsage {
+  account_id: string;
+  display_name: string;
+  kind: Account["kind"];
+  last_used?: string | null;
+  days_since_used?: number | null;
+  stale: boolean;
+}
+
+export interface AccountStatus {
+  account_id: string;
+  display_name: string;
+  kind: Account["kind"];
+  token_valid: boolean;
+  expires_at?: number | null;
+  expiring_soon: boolean;
+  can_refresh: boolean;
+  owns_minecraft?: boolean | null;
 }
 
 export interface InstanceRoot {
@@ -18,9 +47,14 @@ export interface InstanceRoot {
   path: string;
 }
 
+export type InstanceKind = "client" | "server";
+
+export type ReleaseChannel = "release" | "beta" | "alpha";
+
 export interface Instance {
   id: string;
   name: string;
+  kind?: InstanceKind;
   version: string;
   loader: LoaderKind;
   loader_version?: string | null;
@@ -30,7 +64,45 @@ export interface Instance {
   directory: string;
   java_min_ram_mb?: number | null;
   java_max_ram_mb?: number | null;
-  jvm_args?: string | null;
+  jvm_args?: string[] | null;
+  auto_update_mods?: boolean;
+  custom_version?: string | null;
+  main_class_override?: string | null;
+  client_jar_override?: string | null;
+  release_channel?: ReleaseChannel | null;
+  window_title?: string | null;
+  brand_name?: string | null;
+  brand_version?: string | null;
+  created_at_unix?: number | null;
+  optifine?: OptiFineInstall | null;
+}
+
+export interface OptiFineInstall {
+  game_version: string;
+  edition: string;
+  patch: string;
+  file_name: string;
+  sha256?: string | null;
+  version_id?: string | null;
+}
+
+export interface GcLogSummary {
+  pause_count: number;
+  full_gc_count: number;
+  total_pause_ms: number;
+  average_pause_ms: number;
+  p95_pause_ms: number;
+  max_pause_ms: number;
+  long_pause_count: number;
+  heap_capacity_mb?: number | null;
+  peak_heap_after_gc_mb?: number | null;
+  recommendation: string;
+}
+
+export interface InstanceBranding {
+  windowTitle: string;
+  brandName: string;
+  brandVersion: string;
 }
 
 export interface PackSync {
@@ -84,8 +156,35 @@ export interface Settings {
   performance_gamemode: boolean;
   performance_mangohud: boolean;
   performance_zink: boolean;
+  performance_discrete_gpu?: boolean;
+  network_retry_delays_ms?: number[];
+  network_connect_timeout_secs?: number;
+  network_request_timeout_secs?: number;
+  polite_downloads?: boolean;
+  gc_logging?: boolean;
+  legacy_resource_proxy?: string;
+  progress_events_per_second?: number;
+  stop_grace_period_secs?: number;
   microsoft_client_id: string;
   skipped_release_tag?: string | null;
+  content_blacklist?: string[];
+  sync_directory?: string | null;
+  scheduled_tasks?: ScheduledTaskSettings;
+  new_instance_defaults?: NewInstanceDefaults;
+}
+
+export interface NewInstanceDefaults {
+  java_min_ram_mb?: number | null;
+  java_max_ram_mb?: number | null;
+  /** A plain string is split into arguments by the backend. */
+  jvm_args?: string[] | string | null;
+  show_snapshots: boolean;
+  root_id?: string | null;
+  install_fabric_api: boolean;
+  /** Seeded into options.txt of new client instances. */
+  language?: string | null;
+  gui_scale?: number | null;
+  max_framerate?: number | null;
 }
 
 export interface AppConfig {
@@ -105,11 +204,51 @@ export interface VersionSummary {
   released?: string | null;
 }
 
+export interface VersionPatchNotes {
+  version: string;
+  title: string;
+  kind: string;
+  date: string | null;
+  short_text: string | null;
+  image_url: string | null;
+  body: string;
+}
+
+export interface OperationEvent<T = unknown> {
+  instance_id: string | null;
+  operation_id: string;
+  kind: string;
+  payload: T;
+}
+
+export interface ImportPreview {
+  instance_id: string;
+  directory: string;
+  created: string[];
+  overwritten: string[];
+  download_count: number;
+  download_bytes: number;
+  unresolved: string[];
+}
+
+export interface RecipeImportResult {
+  instance: Instance;
+  failed_projects: string[];
+}
+
 export interface LoaderVersionSummary {
   version: string;
   stable: boolean;
 }
 
+export interface LoaderAvailability {
+  loader: string;
+  available: boolean;
+  build_count: number;
+  latest?: string | null;
+  error?: string | null;
+}
+
 export interface ForgeVersionSummary {
   version: string;
   installer_url: string;
@@ -120,6 +259,13 @@ export interface ModEntry {
   filename: string;
   version?: string | null;
   enabled: boolean;
+  blacklisted?: boolean;
+}
+
+export interface ModToggleResult {
+  filename: string;
+  broken_dependents: string[];
+  disabled_dependents: string[];
 }
 
 export interface PackEntry {
@@ -135,11 +281,125 @@ export interface SimpleEntry {
   info?: string | null;
 }
 
+export interface ServerStatus {
+  online: boolean;
+  players_online?: number | null;
+  players_max?: number | null;
+  latency_ms?: number | null;
+  checked_at_unix: number;
+}
+
 export interface ServerEntry {
   name: string;
   ip: string;
   accept_textures?: boolean | null;
   icon?: string | null;
+  status?: ServerStatus | null;
+}
+
+export type ScheduledTask =
+  | "check_mod_updates"
+  | "prune_logs"
+  | "backup_worlds"
+  | "refresh_tokens";
+
+export interface ScheduledTaskSettings {
+  check_mod_updates: boolean;
+  prune_logs: boolean;
+  backup_worlds: boolean;
+  refresh_tokens: boolean;
+  log_retention_days: number;
+}
+
+export interface ScheduledTaskResult {
+  task: ScheduledTask;
+  instance_id?: string | null;
+  success: boolean;
+  summary: string;
+  finished_at_unix: number;
+}
+
+export interface SyncConflict {
+  item: string;
+  detail: string;
+}
+
+export interface SyncReport {
+  directory: string;
+  pushed: string[];
+  pulled: string[];
+  merged: string[];
+  conflicts: SyncConflict[];
+  available_recipes: string[];
+  synced_at_unix: number;
+}
+
+export interface LaunchStoppedEvent {
+  instance_id: string;
+  pid: number;
+  forced: boolean;
+}
+
+export interface GameReadyEvent {
+  instance_id: string;
+  ready_after_ms: number;
+}
+
+export interface PlaySession {
+  instance_id: string;
+  started_at_unix: number;
+  ended_at_unix: number;
+  duration_secs: number;
+  exit_code?: number | null;
+  exit_status: "clean" | "error" | "terminated";
+}
+
+export interface PlaytimeBucket {
+  period_start_unix: number;
+  total_secs: number;
+  session_count: number;
+}
+
+export interface ModrinthChangelogEntry {
+  version_number: string;
+  version_type: string;
+  date_published: string;
+  changelog: string;
+}
+
+export interface ModrinthUpdateChangelog {
+  project_id: string;
+  title: string;
+  installed_version?: string | null;
+  latest_version: string;
+  entries: ModrinthChangelogEntry[];
+}
+
+export interface ModGraphNode {
+  id: string;
+  name?: string | null;
+  file_name?: string | null;
+  enabled: boolean;
+  missing: boolean;
+  modrinth_project_id?: string | null;
+}
+
+export interface ModGraphEdge {
+  from: string;
+  to: string;
+}
+
+export interface ModDependencyGraph {
+  nodes: ModGraphNode[];
+  edges: ModGraphEdge[];
+}
+
+export interface LanGame {
+  address: string;
+  host: string;
+  port: number;
+  motd: string;
+  last_seen_unix: number;
 }
 
 export interface ServerLatencyReport {
@@ -163,6 +423,12 @@ export interface WorldEntry {
   size_bytes?: number | null;
 }
 
+export interface WorldSizeEvent {
+  instance_id: string;
+  world_id: string;
+  size_bytes: number;
+}
+
 export interface ModrinthProjectHit {
   project_id: string;
   title: string;
@@ -183,10 +449,21 @@ export interface ModrinthDependencyPlan {
   dependencies: ModrinthDependencyPlanItem[];
 }
 
+export interface ModrinthCompatibilityReport {
+  compatible: boolean;
+  version?: string | null;
+  game_versions: string[];
+  nearest_version_id?: string | null;
+  nearest_version?: string | null;
+  nearest_game_versions: string[];
+  warning?: string | null;
+}
+
 export interface InstanceMetrics {
   rss_mb: number;
   cpu_load_pct: number;
   gpu_load_pct?: number | null;
+  process_count?: number;
 }
 
 export interface ProgressEvent {
@@ -195,6 +472,9 @@ export interface ProgressEvent {
   current: number;
   total?: number | null;
   detail?: string | null;
+  overall?: number | null;
+  stage_elapsed_ms?: number | null;
+  total_elapsed_ms?: number | null;
 }
 
 export interface ResolvedJavaRuntime {
@@ -228,18 +508,115 @@ export interface InstanceSnapshot {
   file_count: number;
 }
 
+export interface ModsSnapshotFile {
+  name: string;
+  size: number;
+  sha256: string;
+}
+
+export interface ModsSnapshot {
+  id: string;
+  created_at_unix: number;
+  reason?: string | null;
+  archived: boolean;
+  files: ModsSnapshotFile[];
+}
+
+export interface ModsRestoreResult {
+  restored: string[];
+  stashed: string[];
+  stash_dir?: string | null;
+  missing: string[];
+}
+
+export interface ModrinthBulkUpdateResult {
+  snapshot_id?: string | null;
+  updated: { filename: string; version: string; project_id: string }[];
+  failures: string[];
+}
+
 export interface RepairResult {
   snapshot?: InstanceSnapshot | null;
   cleared_targets: string[];
   summary: string;
 }
 
+export interface InstallSummary {
+  finished_at_unix: number;
+  duration_ms: number;
+  files_downloaded: number;
+  bytes_downloaded: number;
+  warnings: string[];
+}
+
+export interface InstanceChangeEvent {
+  instance_id: string;
+  change: string;
+  previous_id?: string | null;
+}
+
+export interface ScreenshotEvent {
+  instance_id: string;
+  path: string;
+  file_name: string;
+}
+
+export interface TempFileCleanup {
+  instance_id: string;
+  files: number;
+  bytes: number;
+}
+
+export interface InstanceDescription {
+  description?: string | null;
+  homepage?: string | null;
+  source?: "manifest" | "readme" | null;
+}
+
+export interface ModrinthSearchDefaults {
+  categories: string[];
+  sort?: string | null;
+  channel?: ReleaseChannel | null;
+}
+
+export interface InstanceSearchDefaults {
+  defaults: Record<string, ModrinthSearchDefaults>;
+}
+
+export interface CreatedInstance {
+  instance: Instance;
+  summary?: InstallSummary | null;
+}
+
+export interface InstallRetryResult {
+  success: boolean;
+  failure_kind?: "network" | "version" | "other" | null;
+  retryable: boolean;
+  error?: string | null;
+  summary?: InstallSummary | null;
+}
+
 export interface JavaCompatibility {
   recommended_major: number;
   selected?: ResolvedJavaRuntime | null;
   compatible: boolean;
 }
 
+export interface ValidationItem {
+  id: string;
+  label: string;
+  status: "ok" | "warn" | "error";
+  summary: string;
+  details: string[];
+  suggested_fix?: string | null;
+}
+
+export interface InstanceValidationReport {
+  instance_id: string;
+  ready: boolean;
+  items: ValidationItem[];
+}
+
 export interface InstancePreflightReport {
   ready: boolean;
   checks: InstanceCheck[];
@@ -1,4 +1,4 @@
-import type { LoaderKind } from "./launcher-types";
+import type { LoaderKind, Settings } from "./launcher-types";
 
 export const slugify = (value: string) =>
   value
@@ -12,6 +12,8 @@ export const resolveLoaderLabel = (loader: LoaderKind | string) => {
       return "Vanilla";
     case "fabric":
       return "Fabric";
+    case "quilt":
+      return "Quilt";
     case "forge":
       return "Forge";
     case "neoforge":
@@ -20,3 +22,54 @@ export const resolveLoaderLabel = (loader: LoaderKind | string) => {
       return loader || "Unknown";
   }
 };
+
+/** Joins stored JVM args back into an editable string, quoting where needed. */
+export const formatJvmArgs = (args: string[]) =>
+  args
+    .map((arg) =>
+      arg === "" || /[\s"']/.test(arg)
+        ? `"${arg.replace(/\\/g, "\\\\").replace(/"/g, '\\"')}"`
+        : arg,
+    )
+    .join(" ");
+
+/** Initial create-dialog values: the new-instance template when enabled, else the global Java settings. */
+export const resolveNewInstanceForm = (settings?: Settings | null) => {
+  const template = settings?.apply_to_new_instances
+    ? settings.new_instance_defaults
+    : null;
+  const templateArgs = template?.jvm_args;
+  return {
+    minRamMb: template?.java_min_ram_mb ?? settings?.java?.min_ram_mb ?? 512,
+    maxRamMb: template?.java_max_ram_mb ?? settings?.java?.max_ram_mb ?? 1024,
+    jvmArgs: Array.isArray(templateArgs)
+      ? formatJvmArgs(templateArgs)
+      : templateArgs || settings?.java?.jvm_args || "",
+    showSnapshots: template?.show_snapshots ?? false,
+  };
+};
+
+/** Re-encodes any image the webview can decode (JPEG, WebP, GIF, ...) as a PNG data URL. */
+export const imageFileToPngDataUrl = (file: File) =>
+  new Promise<string>((resolve, reject) => {
+    const url = URL.createObjectURL(file);
+    const image = new Image();
+    image.onload = () => {
+      const canvas = document.createElement("canvas");
+      canvas.width = image.naturalWidth;
+      canvas.height = image.naturalHeight;
+      const context = canvas.getContext("2d");
+      URL.revokeObjectURL(url);
+      if (!context) {
+        reject(new Error("Canvas is not available."));
+        return;
+      }
+      context.drawImage(image, 0, 0);
+      resolve(canvas.toDataURL("image/png"));
+    };
+    image.onerror = () => {
+      URL.revokeObjectURL(url);
+      reject(new Error("Unsupported image file."));
+    };
+    image.src = url;
+  });
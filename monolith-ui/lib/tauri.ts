@@ -1,3 +1,5 @@
+import type { OperationEvent } from "@/lib/launcher-types";
+
 export const getTauri = () =>
   typeof window !== "undefined" ? (window as any).__TAURI__ : null;
 
@@ -8,7 +10,21 @@ export const getInvoke = () => {
 
 export const getListen = () => {
   const tauri = getTauri();
-  return tauri?.event?.listen || tauri?.listen || null;
+  const listen = tauri?.event?.listen || tauri?.listen || null;
+  if (!listen) return null;
+  // Operation events arrive once, wrapped in the `operation` envelope; direct events keep
+  // their own name. Subscribing to both lets callers listen by kind either way.
+  return async (name: string, handler: (event: any) => void) => {
+    const stops = await Promise.all([
+      listen(name, handler),
+      listen("operation", (event: any) => {
+        const envelope = event?.payload as OperationEvent | undefined;
+        if (envelope?.kind !== name) return;
+        handler({ ...event, event: name, payload: envelope.payload });
+      }),
+    ]);
+    return () => stops.forEach((stop: () => void) => stop());
+  };
 };
 
 export const waitForTauri = async (retries = 60, delay = 100) => {
@@ -0,0 +1,8 @@
+public class MonolithJavaTest {
+  public static void main(String[] args) {
+    System.out.println("java.version=" + System.getProperty("java.version"));
+    System.out.println("java.vendor=" + System.getProperty("java.vendor"));
+    System.out.println("os.arch=" + System.getProperty("os.arch"));
+    System.out.println("max.memory=" + Runtime.getRuntime().maxMemory());
+  }
+}
@@ -31,6 +31,10 @@ pub(crate) fn save_config(
   let discord_mode = config.settings.discord_presence_mode.clone();
   let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
   store.set(config).map_err(|err| err.to_string())?;
+  let settings = store.get().settings;
+  crate::minecraft::apply_network_settings(&settings);
+  crate::events::apply_progress_settings(&settings);
+  crate::modrinth::apply_content_blacklist(&settings);
   let mut rpc = discord.lock().map_err(|_| "discord rpc lock poisoned".to_string())?;
   rpc.set_config(discord_enabled, discord_mode);
   Ok(())
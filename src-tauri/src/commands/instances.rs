@@ -1,16 +1,56 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::thread;
+
+use base64::Engine;
+use tauri::Manager;
 
 use crate::commands::system::open_target;
-use crate::config::{self, ConfigStore, Instance, InstanceManifest, INSTANCE_CONFIG_FILE};
+use crate::config::{
+  self, ConfigStore, InstallSummary, Instance, InstanceManifest, ModrinthSearchDefaults,
+  OptiFineInstall, ReleaseChannel, split_jvm_args, INSTANCE_CONFIG_FILE,
+};
 use crate::diagnostics::{
-  build_instance_preflight, create_snapshot, delete_snapshot, list_instance_snapshots as load_snapshots,
-  repair_instance as repair_instance_files, restore_snapshot,
+  build_instance_preflight, build_mod_dependency_graph, classify_install_failure,
+  create_mods_snapshot as capture_mods_snapshot, create_snapshot, delete_snapshot,
+  list_instance_snapshots as load_snapshots,
+  find_orphaned_temp_files, list_mods_snapshots as load_mods_snapshots, recommended_java_major,
+  remove_orphaned_temp_files, repair_instance as repair_instance_files,
+  restore_mods_snapshot as apply_mods_snapshot,
+  restore_snapshot, summarize_gc_log as summarize_instance_gc_log,
+  validate_instance as build_validation_report, validate_ram_settings, GcLogSummary,
+  InstallRetryResult, InstanceDiagnostic, InstanceValidationReport, ModDependencyGraph,
+  ModsRestoreResult, ModsSnapshot,
+};
+use crate::events::{
+  emit_instance_renamed, emit_instance_updated, emit_instances_changed, InstanceOperationGuard,
+  OperationEmitter,
 };
-use crate::java::detect_java_version;
+use crate::java::{
+  detect_java_arch, detect_java_version, discover_java_runtimes, parse_java_major,
+  resolve_java_command,
+};
+use crate::minecraft::{
+  allocate_instance_id, ensure_instance_ready, export_launch_script as write_launch_script,
+  install_custom_version, install_optifine as install_optifine_build, window_title_mod_installed,
+  ProgressEvent,
+};
+use crate::modrinth::{install_optifabric, modrinth_mod_projects};
+use crate::watcher::SCREENSHOTS_DIR;
 use crate::resolve_instance_dir;
 
+#[derive(serde::Serialize, Default)]
+pub(crate) struct ImportPreview {
+  pub(crate) instance_id: String,
+  pub(crate) directory: String,
+  pub(crate) created: Vec<String>,
+  pub(crate) overwritten: Vec<String>,
+  pub(crate) download_count: u64,
+  pub(crate) download_bytes: u64,
+  pub(crate) unresolved: Vec<String>,
+}
+
 fn load_manifest(path: &PathBuf) -> Result<InstanceManifest, String> {
   let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
   serde_json::from_str(&data).map_err(|err| err.to_string())
@@ -49,6 +89,7 @@ pub(crate) fn open_instance_path(
     "mods" => instance_dir.join("mods"),
     "worlds" | "saves" => instance_dir.join("saves"),
     "servers" => instance_dir.join("servers.dat"),
+    "screenshots" => instance_dir.join(SCREENSHOTS_DIR),
     _ => return Err("unsupported path kind".to_string()),
   };
   if !target.exists() {
@@ -57,35 +98,106 @@ pub(crate) fn open_instance_path(
   open_target(&target.to_string_lossy())
 }
 
+/// Screenshot as a PNG data URL, for copying to the clipboard from the webview.
+#[tauri::command]
+pub(crate) fn read_instance_screenshot(
+  instance_id: String,
+  file_name: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<String, String> {
+  if file_name.contains(['/', '\\']) || file_name == ".." || file_name.is_empty() {
+    return Err("invalid screenshot name".to_string());
+  }
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let path = instance_dir.join(SCREENSHOTS_DIR).join(&file_name);
+  let bytes = fs::read(&path).map_err(|err| err.to_string())?;
+  let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+  Ok(format!("data:image/png;base64,{}", encoded))
+}
+
 #[tauri::command]
 pub(crate) fn rename_instance(
   instance_id: String,
   new_name: String,
+  relocate: Option<bool>,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
-) -> Result<(), String> {
-  if new_name.trim().is_empty() {
+) -> Result<Instance, String> {
+  let trimmed = new_name.trim().to_string();
+  if trimmed.is_empty() {
     return Err("instance name cannot be empty".to_string());
   }
-  let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
-  let config = store.get();
+  let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+  let mut config = store.get();
   let instance = config
     .instances
     .iter()
     .find(|item| item.id == instance_id)
+    .cloned()
     .ok_or_else(|| "instance not found".to_string())?;
-  let manifest_path = PathBuf::from(&instance.directory).join(INSTANCE_CONFIG_FILE);
+  if config
+    .instances
+    .iter()
+    .any(|item| item.id != instance.id && item.name.eq_ignore_ascii_case(&trimmed))
+  {
+    return Err("instance name already exists".to_string());
+  }
+  let source_dir = PathBuf::from(&instance.directory);
+  let mut guard = InstanceOperationGuard::acquire(&instance.id, &source_dir, "rename")?;
+  let manifest_path = source_dir.join(INSTANCE_CONFIG_FILE);
   if !manifest_path.exists() {
     return Err("instance manifest missing".to_string());
   }
   let mut manifest = load_manifest(&manifest_path)?;
-  manifest.name = new_name.trim().to_string();
-  save_manifest(&manifest_path, &manifest)
+  manifest.name = trimmed;
+
+  let mut target_id = instance.id.clone();
+  if relocate.unwrap_or(false) {
+    let root_path = source_dir
+      .parent()
+      .ok_or_else(|| "instance root missing".to_string())?
+      .to_path_buf();
+    config.instances.retain(|item| item.id != instance.id);
+    let candidate = allocate_instance_id(&manifest.name, &config, &root_path)?;
+    if candidate != instance.id {
+      let target_dir = root_path.join(&candidate);
+      fs::rename(&source_dir, &target_dir).map_err(|err| err.to_string())?;
+      guard.relocate(&target_dir);
+      manifest.id = candidate.clone();
+      manifest.directory = Some(target_dir.to_string_lossy().to_string());
+      save_manifest(&target_dir.join(INSTANCE_CONFIG_FILE), &manifest)?;
+      if config.settings.reference_instance_id.as_deref() == Some(instance.id.as_str()) {
+        config.settings.reference_instance_id = Some(candidate.clone());
+      }
+      for entry in config.settings.java.overrides.iter_mut() {
+        if entry.instance_id == instance.id {
+          entry.instance_id = candidate.clone();
+        }
+      }
+      target_id = candidate;
+    } else {
+      save_manifest(&manifest_path, &manifest)?;
+    }
+  } else {
+    save_manifest(&manifest_path, &manifest)?;
+  }
+
+  store.set(config).map_err(|err| err.to_string())?;
+  let renamed = store
+    .get()
+    .instances
+    .into_iter()
+    .find(|item| item.id == target_id)
+    .ok_or_else(|| "instance not found".to_string())?;
+  emit_instance_renamed(&app, &renamed.id, &instance.id);
+  Ok(renamed)
 }
 
 #[tauri::command]
 pub(crate) fn set_instance_pinned(
   instance_id: String,
   pinned: bool,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
@@ -95,12 +207,389 @@ pub(crate) fn set_instance_pinned(
   }
   let mut manifest = load_manifest(&manifest_path)?;
   manifest.pinned = pinned;
-  save_manifest(&manifest_path, &manifest)
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instances_changed(&app, &instance_id, "pinned");
+  Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn get_instance_install_summary(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Option<InstallSummary>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  Ok(load_manifest(&manifest_path)?.install_summary)
+}
+
+const README_FILES: [&str; 3] = ["README.md", "README.txt", "README"];
+
+#[derive(serde::Serialize)]
+pub(crate) struct InstanceDescription {
+  description: Option<String>,
+  homepage: Option<String>,
+  /// "manifest" when the pack supplied a description, "readme" for a README file.
+  source: Option<String>,
+}
+
+/// Pack description for the instance page, falling back to a README shipped in the instance.
+#[tauri::command]
+pub(crate) fn get_instance_description(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<InstanceDescription, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let manifest = load_manifest(&manifest_path)?;
+  let homepage = manifest.homepage.filter(|value| !value.trim().is_empty());
+  if let Some(description) = manifest.description.filter(|value| !value.trim().is_empty()) {
+    return Ok(InstanceDescription {
+      description: Some(description),
+      homepage,
+      source: Some("manifest".to_string()),
+    });
+  }
+  let readme = README_FILES
+    .iter()
+    .map(|name| instance_dir.join(name))
+    .filter(|path| path.is_file())
+    .find_map(|path| fs::read_to_string(path).ok())
+    .filter(|value| !value.trim().is_empty());
+  Ok(InstanceDescription {
+    source: readme.as_ref().map(|_| "readme".to_string()),
+    description: readme,
+    homepage,
+  })
+}
+
+#[tauri::command]
+pub(crate) fn set_instance_release_channel(
+  instance_id: String,
+  channel: Option<ReleaseChannel>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<(), String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let mut manifest = load_manifest(&manifest_path)?;
+  manifest.release_channel = channel;
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "release_channel");
+  Ok(())
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct InstanceSearchDefaults {
+  defaults: std::collections::HashMap<String, ModrinthSearchDefaults>,
+}
+
+#[tauri::command]
+pub(crate) fn get_instance_search_defaults(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<InstanceSearchDefaults, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let manifest = load_manifest(&manifest_path)?;
+  Ok(InstanceSearchDefaults {
+    defaults: manifest.modrinth_search_defaults,
+  })
+}
+
+/// Saves the search filters for one project type; empty filters drop the entry.
+#[tauri::command]
+pub(crate) fn set_instance_search_defaults(
+  instance_id: String,
+  project_type: String,
+  categories: Vec<String>,
+  sort: Option<String>,
+  channel: Option<ReleaseChannel>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<(), String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let mut manifest = load_manifest(&manifest_path)?;
+  let mut categories: Vec<String> = categories
+    .into_iter()
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+    .collect();
+  categories.sort();
+  categories.dedup();
+  let sort = sort.filter(|value| !value.trim().is_empty());
+  let channel = channel.or_else(|| {
+    manifest
+      .modrinth_search_defaults
+      .get(&project_type)
+      .and_then(|defaults| defaults.channel)
+  });
+  if categories.is_empty() && sort.is_none() && channel.is_none() {
+    manifest.modrinth_search_defaults.remove(&project_type);
+  } else {
+    manifest.modrinth_search_defaults.insert(
+      project_type,
+      ModrinthSearchDefaults {
+        categories,
+        sort,
+        channel,
+      },
+    );
+  }
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "search_defaults");
+  Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn set_instance_auto_update_mods(
+  instance_id: String,
+  enabled: bool,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<(), String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let mut manifest = load_manifest(&manifest_path)?;
+  manifest.auto_update_mods = enabled;
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "auto_update_mods");
+  Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn add_custom_version(
+  instance_id: String,
+  json_path: String,
+  jar_path: Option<String>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<String, String> {
+  let instance = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?
+  };
+  if instance.kind == config::InstanceKind::Server {
+    return Err("custom versions are only supported for client instances".to_string());
+  }
+  let instance_dir = PathBuf::from(&instance.directory);
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let json_source = PathBuf::from(json_path.trim());
+  let jar_source = jar_path
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+    .map(PathBuf::from);
+  let guard =
+    InstanceOperationGuard::acquire(&instance.id, &instance_dir, "custom version install")?;
+  let ops = OperationEmitter::new(&app, Some(&instance.id));
+  let progress_ops = ops.clone();
+  let version_id = tauri::async_runtime::spawn_blocking(move || {
+    let _guard = guard;
+    let emitter = |event: ProgressEvent| {
+      progress_ops.emit_progress(event);
+    };
+    install_custom_version(&instance_dir, &json_source, jar_source.as_deref(), &emitter)
+  })
+  .await
+  .map_err(|_| "custom version task cancelled".to_string())?;
+  let version_id = match version_id {
+    Ok(version_id) => version_id,
+    Err(err) => {
+      ops.emit("install:error", err.clone());
+      return Err(err);
+    }
+  };
+  let mut manifest = load_manifest(&manifest_path)?;
+  manifest.custom_version = Some(version_id.clone());
+  save_manifest(&manifest_path, &manifest)?;
+  ops.emit("install:done", &instance);
+  emit_instance_updated(&app, &instance.id, "custom_version");
+  Ok(version_id)
+}
+
+#[tauri::command]
+pub(crate) fn set_instance_custom_version(
+  instance_id: String,
+  version_id: Option<String>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<(), String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let version_id = version_id
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  if let Some(id) = &version_id {
+    let version_json = instance_dir.join("versions").join(id).join(format!("{}.json", id));
+    if !version_json.is_file() {
+      return Err(format!("version '{}' is not installed in this instance", id));
+    }
+  }
+  let mut manifest = load_manifest(&manifest_path)?;
+  manifest.custom_version = version_id;
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "custom_version");
+  Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn set_instance_launch_overrides(
+  instance_id: String,
+  main_class: Option<String>,
+  client_jar: Option<String>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<(), String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let main_class = main_class
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  let client_jar = client_jar
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  if let Some(path) = &client_jar {
+    if !instance_dir.join(path).is_file() {
+      return Err(format!("client jar '{}' not found", path));
+    }
+  }
+  let mut manifest = load_manifest(&manifest_path)?;
+  manifest.main_class_override = main_class;
+  manifest.client_jar_override = client_jar;
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "launch_overrides");
+  Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn set_instance_branding(
+  instance_id: String,
+  window_title: Option<String>,
+  brand_name: Option<String>,
+  brand_version: Option<String>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Option<String>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+  if !manifest_path.exists() {
+    return Err("instance manifest missing".to_string());
+  }
+  let clean = |value: Option<String>| {
+    value
+      .map(|value| value.trim().to_string())
+      .filter(|value| !value.is_empty())
+  };
+  let mut manifest = load_manifest(&manifest_path)?;
+  manifest.window_title = clean(window_title);
+  manifest.brand_name = clean(brand_name);
+  manifest.brand_version = clean(brand_version);
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "branding");
+  // Saved either way, but the title only shows up once the mod is installed.
+  let title_inactive =
+    manifest.window_title.is_some() && !window_title_mod_installed(&instance_dir);
+  Ok(title_inactive.then(|| {
+    "The window title requires the Custom Window Title mod and has no effect until it is installed."
+      .to_string()
+  }))
+}
+
+#[tauri::command]
+pub(crate) async fn install_optifine(
+  instance_id: String,
+  file_name: String,
+  data: String,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<OptiFineInstall, String> {
+  let instance = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?
+  };
+  let data = base64::engine::general_purpose::STANDARD
+    .decode(data.trim())
+    .map_err(|_| "invalid OptiFine jar data".to_string())?;
+  let instance_dir = Path::new(&instance.directory);
+  let guard = InstanceOperationGuard::acquire(&instance.id, instance_dir, "optifine")?;
+  let ops = OperationEmitter::new(&app, Some(&instance.id));
+  let progress_ops = ops.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let _guard = guard;
+    let emit = |event: ProgressEvent| progress_ops.emit_progress(event);
+    let record = install_optifine_build(&instance, &file_name, &data, &emit)?;
+    if matches!(instance.loader, config::Loader::Fabric | config::Loader::Quilt) {
+      install_optifabric(Path::new(&instance.directory), &instance.version)
+        .map_err(|err| {
+          format!("OptiFine was added but OptiFabric could not be installed: {}", err)
+        })?;
+    }
+    Ok::<_, String>(record)
+  })
+  .await
+  .map_err(|_| "OptiFine task failed".to_string())?;
+  match &result {
+    Ok(record) => ops.emit("optifine:done", record.clone()),
+    Err(err) => ops.emit("optifine:error", err.clone()),
+  }
+  if result.is_ok() {
+    emit_instance_updated(&app, &instance_id, "optifine");
+  }
+  result
+}
+
+#[tauri::command]
+pub(crate) async fn summarize_gc_log(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<GcLogSummary, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  tauri::async_runtime::spawn_blocking(move || summarize_instance_gc_log(&instance_dir))
+    .await
+    .map_err(|_| "GC log task failed".to_string())?
 }
 
 #[tauri::command]
 pub(crate) fn remove_instance(
   instance_id: String,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
   let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
@@ -125,22 +614,167 @@ pub(crate) fn remove_instance(
     .overrides
     .retain(|item| item.instance_id != instance.id);
   store.set(config).map_err(|err| err.to_string())?;
+  emit_instances_changed(&app, &instance.id, "removed");
   Ok(())
 }
 
 #[tauri::command]
-pub(crate) fn repair_instance(
+pub(crate) async fn repair_instance(
   instance_id: String,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<crate::diagnostics::RepairResult, String> {
-  let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
-  let config = store.get();
-  let instance = config
-    .instances
-    .iter()
-    .find(|item| item.id == instance_id)
-    .ok_or_else(|| "instance not found".to_string())?;
-  repair_instance_files(instance)
+  let instance = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?
+  };
+  let instance_dir = Path::new(&instance.directory);
+  let guard = InstanceOperationGuard::acquire(&instance.id, instance_dir, "repair")?;
+  let ops = OperationEmitter::new(&app, Some(&instance.id));
+  ops.emit("repair:started", instance.id.clone());
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let _guard = guard;
+    repair_instance_files(&instance)
+  })
+  .await
+  .map_err(|_| "repair task failed".to_string())?;
+  match &result {
+    Ok(repaired) => ops.emit("repair:done", repaired.clone()),
+    Err(err) => ops.emit("repair:error", err.clone()),
+  }
+  result
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct TempFileCleanup {
+  instance_id: String,
+  files: usize,
+  bytes: u64,
+}
+
+/// Removes orphaned partial downloads, skipping instances that are busy with another operation.
+fn clean_temp_files(instances: &[Instance]) -> Vec<TempFileCleanup> {
+  let mut cleaned = Vec::new();
+  for instance in instances {
+    let instance_dir = Path::new(&instance.directory);
+    if find_orphaned_temp_files(instance_dir).is_empty() {
+      continue;
+    }
+    let _guard = match InstanceOperationGuard::acquire(&instance.id, instance_dir, "cleanup") {
+      Ok(guard) => guard,
+      Err(err) => {
+        log::info!("skipping temp file cleanup for {}: {}", instance.id, err);
+        continue;
+      }
+    };
+    let (files, bytes) = remove_orphaned_temp_files(instance_dir);
+    if files > 0 {
+      log::info!(
+        "removed {} leftover temp files ({} bytes) from {}",
+        files,
+        bytes,
+        instance.id
+      );
+      cleaned.push(TempFileCleanup {
+        instance_id: instance.id.clone(),
+        files,
+        bytes,
+      });
+    }
+  }
+  cleaned
+}
+
+pub(crate) fn spawn_startup_temp_scan(app: tauri::AppHandle) {
+  thread::spawn(move || {
+    let instances = {
+      let store_state = app.state::<Mutex<ConfigStore>>();
+      let store = match store_state.lock() {
+        Ok(store) => store,
+        Err(_) => return,
+      };
+      store.get().instances
+    };
+    let cleaned = clean_temp_files(&instances);
+    if !cleaned.is_empty() {
+      OperationEmitter::new(&app, None).emit("maintenance:temp-files", cleaned);
+    }
+  });
+}
+
+#[tauri::command]
+pub(crate) async fn clean_orphaned_temp_files(
+  instance_id: Option<String>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<TempFileCleanup>, String> {
+  let instances = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .filter(|item| instance_id.as_deref().map_or(true, |id| item.id == id))
+      .collect::<Vec<_>>()
+  };
+  if instances.is_empty() && instance_id.is_some() {
+    return Err("instance not found".to_string());
+  }
+  tauri::async_runtime::spawn_blocking(move || clean_temp_files(&instances))
+    .await
+    .map_err(|_| "cleanup task failed".to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn retry_instance_install(
+  instance_id: String,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<InstallRetryResult, String> {
+  let instance = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?
+  };
+  let instance_dir = Path::new(&instance.directory);
+  let guard = InstanceOperationGuard::acquire(&instance.id, instance_dir, "install")?;
+  let ops = OperationEmitter::new(&app, Some(&instance.id));
+  let progress_ops = ops.clone();
+  let retry_instance = instance.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let _guard = guard;
+    let emitter = |event: ProgressEvent| {
+      progress_ops.emit_progress(event);
+    };
+    ensure_instance_ready(&retry_instance, &emitter)
+  })
+  .await
+  .map_err(|_| "install task cancelled".to_string())?;
+  match result {
+    Ok(summary) => {
+      ops.emit("install:done", &instance);
+      Ok(InstallRetryResult {
+        success: true,
+        failure_kind: None,
+        retryable: false,
+        error: None,
+        summary,
+      })
+    }
+    Err(err) => {
+      let outcome = classify_install_failure(&err);
+      ops.emit("install:error", err);
+      Ok(outcome)
+    }
+  }
 }
 
 #[tauri::command]
@@ -158,6 +792,63 @@ pub(crate) fn get_instance_preflight(
   Ok(build_instance_preflight(&config, instance))
 }
 
+#[tauri::command]
+pub(crate) async fn validate_instance(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<InstanceValidationReport, String> {
+  let config = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store.get()
+  };
+  tauri::async_runtime::spawn_blocking(move || {
+    let instance = config
+      .instances
+      .iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?;
+    Ok(build_validation_report(&config, instance))
+  })
+  .await
+  .map_err(|_| "validation task failed".to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn export_launch_script(
+  instance_id: String,
+  destination: Option<String>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<String, String> {
+  let config = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store.get()
+  };
+  let destination = destination
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+    .map(PathBuf::from);
+  let path = tauri::async_runtime::spawn_blocking(move || {
+    write_launch_script(&instance_id, &config, destination)
+  })
+  .await
+  .map_err(|_| "launch script export cancelled".to_string())??;
+  Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn get_mod_dependency_graph(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<ModDependencyGraph, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  tauri::async_runtime::spawn_blocking(move || {
+    let projects = modrinth_mod_projects(&instance_dir)?;
+    Ok(build_mod_dependency_graph(&instance_dir, &projects))
+  })
+  .await
+  .map_err(|_| "dependency graph task failed".to_string())?
+}
+
 #[tauri::command]
 pub(crate) fn list_instance_snapshots(
   instance_id: String,
@@ -171,6 +862,7 @@ pub(crate) fn list_instance_snapshots(
 pub(crate) fn create_instance_snapshot(
   instance_id: String,
   reason: Option<String>,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<crate::diagnostics::InstanceSnapshot, String> {
   let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
@@ -180,13 +872,23 @@ pub(crate) fn create_instance_snapshot(
     .iter()
     .find(|item| item.id == instance_id)
     .ok_or_else(|| "instance not found".to_string())?;
-  create_snapshot(instance, reason)
+  let _guard =
+    InstanceOperationGuard::acquire(&instance.id, Path::new(&instance.directory), "backup")?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  ops.emit("backup:started", "create");
+  let result = create_snapshot(instance, reason);
+  match &result {
+    Ok(snapshot) => ops.emit("backup:done", snapshot.clone()),
+    Err(err) => ops.emit("backup:error", err.clone()),
+  }
+  result
 }
 
 #[tauri::command]
 pub(crate) fn restore_instance_snapshot(
   instance_id: String,
   snapshot_id: String,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
   let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
@@ -196,7 +898,16 @@ pub(crate) fn restore_instance_snapshot(
     .iter()
     .find(|item| item.id == instance_id)
     .ok_or_else(|| "instance not found".to_string())?;
-  restore_snapshot(instance, &snapshot_id)
+  let _guard =
+    InstanceOperationGuard::acquire(&instance.id, Path::new(&instance.directory), "restore")?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  ops.emit("backup:started", "restore");
+  let result = restore_snapshot(instance, &snapshot_id);
+  match &result {
+    Ok(()) => ops.emit("backup:done", snapshot_id),
+    Err(err) => ops.emit("backup:error", err.clone()),
+  }
+  result
 }
 
 #[tauri::command]
@@ -215,10 +926,61 @@ pub(crate) fn delete_instance_snapshot(
   delete_snapshot(instance, &snapshot_id)
 }
 
+#[tauri::command]
+pub(crate) fn list_mods_snapshots(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ModsSnapshot>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  load_mods_snapshots(&instance_dir)
+}
+
+#[tauri::command]
+pub(crate) async fn create_mods_snapshot(
+  instance_id: String,
+  reason: Option<String>,
+  archive: Option<bool>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<ModsSnapshot, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let guard = InstanceOperationGuard::acquire(&instance_id, &instance_dir, "mods backup")?;
+  tauri::async_runtime::spawn_blocking(move || {
+    let _guard = guard;
+    capture_mods_snapshot(&instance_dir, reason, archive.unwrap_or(true))
+  })
+  .await
+  .map_err(|_| "mods snapshot task failed".to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn restore_mods_snapshot(
+  instance_id: String,
+  snapshot_id: String,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<ModsRestoreResult, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let guard = InstanceOperationGuard::acquire(&instance_id, &instance_dir, "mods restore")?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  ops.emit("backup:started", "restore-mods");
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let _guard = guard;
+    apply_mods_snapshot(&instance_dir, &snapshot_id)
+  })
+  .await
+  .map_err(|_| "mods restore task failed".to_string())?;
+  match &result {
+    Ok(restored) => ops.emit("backup:done", restored.clone()),
+    Err(err) => ops.emit("backup:error", err.clone()),
+  }
+  result
+}
+
 #[tauri::command]
 pub(crate) fn set_instance_java_override(
   instance_id: String,
   java_path: Option<String>,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
   let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
@@ -244,16 +1006,71 @@ pub(crate) fn set_instance_java_override(
   if let Some(path) = next_path {
     let version = detect_java_version(&path);
     config.settings.java.overrides.push(config::JavaOverride {
-      instance_id,
+      instance_id: instance_id.clone(),
       version,
       path: Some(path),
     });
   }
 
-  store.set(config).map_err(|err| err.to_string())
+  store.set(config).map_err(|err| err.to_string())?;
+  emit_instance_updated(&app, &instance_id, "java");
+  Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn switch_instance_java_to_arm64(
+  instance_id: String,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<String, String> {
+  let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+  let mut config = store.get();
+  let instance = config
+    .instances
+    .iter()
+    .find(|item| item.id == instance_id)
+    .cloned()
+    .ok_or_else(|| "instance not found".to_string())?;
+  let required_major = recommended_java_major(&instance.version);
+
+  let runtime = discover_java_runtimes(Some(&config))
+    .into_iter()
+    .filter(|entry| {
+      matches!(
+        detect_java_arch(&entry.path).as_deref(),
+        Some("aarch64") | Some("arm64")
+      )
+    })
+    .filter_map(|entry| {
+      let major = entry.version.as_deref().and_then(parse_java_major)?;
+      (major >= required_major).then_some((major, entry))
+    })
+    .min_by_key(|(major, _)| *major)
+    .map(|(_, entry)| entry)
+    .ok_or_else(|| {
+      format!(
+        "No ARM64 Java {} or newer was found. Install an aarch64 JDK and rescan Java runtimes.",
+        required_major
+      )
+    })?;
+
+  config
+    .settings
+    .java
+    .overrides
+    .retain(|item| item.instance_id != instance_id);
+  config.settings.java.overrides.push(config::JavaOverride {
+    instance_id: instance_id.clone(),
+    version: runtime.version.clone(),
+    path: Some(runtime.path.clone()),
+  });
+  store.set(config).map_err(|err| err.to_string())?;
+  emit_instance_updated(&app, &instance_id, "java");
+  Ok(runtime.path)
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn import_instance(
   path: String,
   name: String,
@@ -262,10 +1079,14 @@ pub(crate) fn import_instance(
   loader_version: Option<String>,
   show_snapshots: bool,
   root_id: Option<String>,
+  app: tauri::AppHandle,
 ) -> Result<(), String> {
   if matches!(
     loader,
-    config::Loader::Fabric | config::Loader::Forge | config::Loader::NeoForge
+    config::Loader::Fabric
+      | config::Loader::Quilt
+      | config::Loader::Forge
+      | config::Loader::NeoForge
   )
     && loader_version.is_none()
   {
@@ -280,9 +1101,14 @@ pub(crate) fn import_instance(
     .and_then(|name| name.to_str())
     .ok_or_else(|| "invalid instance folder name".to_string())?
     .to_string();
+  let created_at = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
   let instance = Instance {
     id,
     name: name.trim().to_string(),
+    kind: config::InstanceKind::Client,
     version: version.trim().to_string(),
     loader,
     loader_version,
@@ -295,46 +1121,100 @@ pub(crate) fn import_instance(
     java_max_ram_mb: None,
     java_max_ram_gb: None,
     jvm_args: None,
+    auto_update_mods: false,
+    custom_version: None,
+    main_class_override: None,
+    client_jar_override: None,
+    release_channel: None,
+    window_title: None,
+    brand_name: None,
+    brand_version: None,
+    created_at_unix: Some(created_at),
+    optifine: None,
   };
-  let created_at = std::time::SystemTime::now()
-    .duration_since(std::time::UNIX_EPOCH)
-    .unwrap_or_default()
-    .as_secs();
   let manifest = InstanceManifest::from_instance(&instance, Some(created_at));
   let manifest_path = directory.join(INSTANCE_CONFIG_FILE);
   save_manifest(&manifest_path, &manifest)?;
+  emit_instances_changed(&app, &instance.id, "imported");
   Ok(())
 }
 
+#[tauri::command]
+pub(crate) fn preview_import_instance(path: String) -> Result<ImportPreview, String> {
+  let directory = PathBuf::from(&path);
+  if !directory.is_dir() {
+    return Err("instance directory does not exist".to_string());
+  }
+  let id = directory
+    .file_name()
+    .and_then(|name| name.to_str())
+    .ok_or_else(|| "invalid instance folder name".to_string())?
+    .to_string();
+  let manifest_path = directory.join(INSTANCE_CONFIG_FILE).to_string_lossy().to_string();
+  let mut preview = ImportPreview {
+    instance_id: id,
+    directory: directory.to_string_lossy().to_string(),
+    ..ImportPreview::default()
+  };
+  if directory.join(INSTANCE_CONFIG_FILE).exists() {
+    preview.overwritten.push(manifest_path);
+  } else {
+    preview.created.push(manifest_path);
+  }
+  Ok(preview)
+}
+
 #[tauri::command]
 pub(crate) fn update_instance_settings(
   instance_id: String,
   min_ram_mb: Option<u32>,
   max_ram_mb: Option<u32>,
   jvm_args: Option<String>,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
-) -> Result<(), String> {
-  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
-  let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+) -> Result<Vec<InstanceDiagnostic>, String> {
+  let (instance, config) = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    let config = store.get();
+    let instance = config
+      .instances
+      .iter()
+      .find(|item| item.id == instance_id)
+      .cloned()
+      .ok_or_else(|| "instance not found".to_string())?;
+    (instance, config)
+  };
+  let manifest_path = PathBuf::from(&instance.directory).join(INSTANCE_CONFIG_FILE);
   if !manifest_path.exists() {
     return Err("instance manifest missing".to_string());
   }
+
+  let effective_min = min_ram_mb.unwrap_or(config.settings.java.min_ram_mb);
+  let effective_max = max_ram_mb.unwrap_or(config.settings.java.max_ram_mb);
+  let java_cmd = resolve_java_command(&config, &instance).ok();
+  let findings = validate_ram_settings(effective_min, effective_max, java_cmd.as_deref());
+  if let Some(error) = findings.iter().find(|finding| finding.severity == "error") {
+    return Err(error.summary.clone());
+  }
+
   let mut manifest = load_manifest(&manifest_path)?;
   manifest.java_min_ram_mb = min_ram_mb;
   manifest.java_min_ram_gb = None;
   manifest.java_max_ram_mb = max_ram_mb;
   manifest.java_max_ram_gb = None;
-  manifest.jvm_args = jvm_args.and_then(|value| {
-    let trimmed = value.trim().to_string();
-    if trimmed.is_empty() { None } else { Some(trimmed) }
-  });
-  save_manifest(&manifest_path, &manifest)
+  manifest.jvm_args = jvm_args
+    .map(|value| split_jvm_args(&value))
+    .filter(|args| !args.is_empty());
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "settings");
+  Ok(findings)
 }
 
 #[tauri::command]
 pub(crate) fn update_instance_loader_version(
   instance_id: String,
   loader_version: Option<String>,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
@@ -361,8 +1241,10 @@ pub(crate) fn update_instance_loader_version(
     return Err("loader version is required".to_string());
   }
 
+  // The installed_* fields stay as they are: the mismatch triggers the reinstall, and
+  // the next launch needs them to prune the previous loader profile.
   manifest.loader_version = normalized;
-  manifest.installed_loader = None;
-  manifest.installed_loader_version = None;
-  save_manifest(&manifest_path, &manifest)
+  save_manifest(&manifest_path, &manifest)?;
+  emit_instance_updated(&app, &instance_id, "loader_version");
+  Ok(())
 }
@@ -1,6 +1,8 @@
 pub mod config;
 pub mod instances;
 pub mod packs;
+pub mod recipes;
+pub mod server_instances;
 pub mod servers;
 pub mod system;
 pub mod worlds;
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -5,11 +6,21 @@ use std::sync::Mutex;
 
 use crate::commands::system::open_target;
 use crate::config::ConfigStore;
+use crate::diagnostics::{build_mod_dependency_graph, enabled_mod_dependents};
+use crate::minecraft::long_path;
+use crate::modrinth::{blacklisted_mod_files, is_content_blacklisted};
 use crate::resolve_instance_dir;
 use zip::ZipArchive;
 
+const OPTIONS_FILE: &str = "options.txt";
+const RESOURCE_PACKS_KEY: &str = "resourcePacks";
+const FILE_PACK_PREFIX: &str = "file/";
+/// Data version of 18w43a (1.13), which started prefixing pack ids with their source.
+const PREFIXED_PACK_IDS_DATA_VERSION: u32 = 1519;
+
 #[derive(Default)]
 struct ModMetadata {
+  id: Option<String>,
   name: Option<String>,
   version: Option<String>,
 }
@@ -20,6 +31,14 @@ pub(crate) struct ModEntry {
   filename: String,
   version: Option<String>,
   enabled: bool,
+  blacklisted: bool,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct ModToggleResult {
+  filename: String,
+  broken_dependents: Vec<String>,
+  disabled_dependents: Vec<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -84,18 +103,44 @@ fn load_zip_entry(zip: &mut ZipArchive<fs::File>, name: &str) -> Option<String>
 
 fn read_pack_format(path: &Path) -> Option<String> {
   if path.is_dir() {
-    let mcmeta = fs::read_to_string(path.join("pack.mcmeta")).ok()?;
+    let mcmeta = fs::read_to_string(long_path(&path.join("pack.mcmeta"))).ok()?;
     return parse_pack_format(&mcmeta);
   }
-  let file = fs::File::open(path).ok()?;
+  let file = fs::File::open(long_path(path)).ok()?;
   let mut zip = ZipArchive::new(file).ok()?;
   let contents = load_zip_entry(&mut zip, "pack.mcmeta")?;
   parse_pack_format(&contents)
 }
 
+fn read_plugin_version(path: &Path) -> Option<String> {
+  let file = fs::File::open(long_path(path)).ok()?;
+  let mut zip = ZipArchive::new(file).ok()?;
+  let contents = load_zip_entry(&mut zip, "paper-plugin.yml")
+    .or_else(|| load_zip_entry(&mut zip, "plugin.yml"))?;
+  contents.lines().find_map(|line| {
+    let value = line.strip_prefix("version:")?.trim();
+    let value = value.trim_matches(|ch| ch == '\'' || ch == '"');
+    if value.is_empty() {
+      None
+    } else {
+      Some(value.to_string())
+    }
+  })
+}
+
 fn parse_mod_json(contents: &str) -> ModMetadata {
   let mut meta = ModMetadata::default();
   if let Ok(value) = serde_json::from_str::<serde_json::Value>(contents) {
+    if let Some(id) = value
+      .get("id")
+      .or_else(|| value.get("quilt_loader").and_then(|loader| loader.get("id")))
+      .and_then(|v| v.as_str())
+    {
+      let trimmed = id.trim();
+      if !trimmed.is_empty() {
+        meta.id = Some(trimmed.to_string());
+      }
+    }
     if let Some(name) = value.get("name").and_then(|v| v.as_str()) {
       let trimmed = name.trim();
       if !trimmed.is_empty() {
@@ -121,6 +166,12 @@ fn parse_mods_toml(contents: &str) -> ModMetadata {
   let mods = value.get("mods").and_then(|value| value.as_array());
   if let Some(first) = mods.and_then(|mods| mods.first()) {
     if let Some(table) = first.as_table() {
+      if let Some(id) = table.get("modId").and_then(|value| value.as_str()) {
+        let trimmed = id.trim();
+        if !trimmed.is_empty() {
+          meta.id = Some(trimmed.to_string());
+        }
+      }
       if let Some(name) = table
         .get("displayName")
         .and_then(|value| value.as_str())
@@ -210,6 +261,9 @@ fn find_pom_properties(zip: &mut ZipArchive<fs::File>) -> Option<String> {
 }
 
 fn merge_metadata(base: &mut ModMetadata, incoming: ModMetadata) {
+  if base.id.is_none() {
+    base.id = incoming.id;
+  }
   if base.name.is_none() {
     base.name = incoming.name;
   }
@@ -219,7 +273,7 @@ fn merge_metadata(base: &mut ModMetadata, incoming: ModMetadata) {
 }
 
 fn read_mod_metadata(path: &Path) -> ModMetadata {
-  let file = match fs::File::open(path) {
+  let file = match fs::File::open(long_path(path)) {
     Ok(file) => file,
     Err(_) => return ModMetadata::default(),
   };
@@ -262,6 +316,7 @@ fn resolve_pack_dir(instance_dir: &Path, kind: &str) -> Option<PathBuf> {
     "shaderpacks" => "shaderpacks",
     "texturepacks" => "texturepacks",
     "mods" => "mods",
+    "plugins" => "plugins",
     _ => return None,
   };
   Some(instance_dir.join(folder))
@@ -273,9 +328,9 @@ fn resolve_datapack_dir(instance_dir: &Path, world_id: &str) -> PathBuf {
 
 fn remove_path(path: &Path) -> Result<(), String> {
   if path.is_dir() {
-    fs::remove_dir_all(path).map_err(|err| err.to_string())
+    fs::remove_dir_all(long_path(path)).map_err(|err| err.to_string())
   } else {
-    fs::remove_file(path).map_err(|err| err.to_string())
+    fs::remove_file(long_path(path)).map_err(|err| err.to_string())
   }
 }
 
@@ -289,7 +344,8 @@ pub(crate) fn list_instance_mods(
   if !mods_dir.exists() {
     return Ok(Vec::new());
   }
-  let entries = fs::read_dir(&mods_dir).map_err(|err| err.to_string())?;
+  let entries = fs::read_dir(long_path(&mods_dir)).map_err(|err| err.to_string())?;
+  let blacklisted_files = blacklisted_mod_files(&instance_dir);
   let mut results = Vec::new();
   for entry in entries.flatten() {
     let path = entry.path();
@@ -305,6 +361,8 @@ pub(crate) fn list_instance_mods(
     }
     let enabled = filename.ends_with(".jar");
     let metadata = read_mod_metadata(&path);
+    let blacklisted = blacklisted_files.contains(filename.trim_end_matches(".disabled"))
+      || metadata.id.as_deref().map(is_content_blacklisted).unwrap_or(false);
     let name = metadata
       .name
       .unwrap_or_else(|| strip_known_suffixes(&filename));
@@ -314,6 +372,7 @@ pub(crate) fn list_instance_mods(
       filename,
       version,
       enabled,
+      blacklisted,
     });
   }
   results.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
@@ -325,27 +384,49 @@ pub(crate) fn toggle_mod(
   instance_id: String,
   filename: String,
   enabled: bool,
+  disable_dependents: Option<bool>,
   state: tauri::State<'_, Mutex<ConfigStore>>,
-) -> Result<(), String> {
+) -> Result<ModToggleResult, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
   let mods_dir = instance_dir.join("mods");
   let source = mods_dir.join(&filename);
   if !source.exists() {
     return Err("mod file not found".to_string());
   }
-  let target = if enabled {
-    if filename.ends_with(".disabled") {
-      mods_dir.join(filename.trim_end_matches(".disabled"))
-    } else {
-      return Ok(());
-    }
+  let target_name = if enabled {
+    filename.trim_end_matches(".disabled").to_string()
   } else if filename.ends_with(".disabled") {
-    return Ok(());
+    filename.clone()
   } else {
-    mods_dir.join(format!("{}.disabled", filename))
+    format!("{}.disabled", filename)
   };
-  fs::rename(source, target).map_err(|err| err.to_string())?;
-  Ok(())
+  if target_name != filename {
+    fs::rename(long_path(&source), long_path(&mods_dir.join(&target_name)))
+      .map_err(|err| err.to_string())?;
+  }
+  let mut result = ModToggleResult {
+    filename: target_name.clone(),
+    broken_dependents: Vec::new(),
+    disabled_dependents: Vec::new(),
+  };
+  if enabled {
+    return Ok(result);
+  }
+
+  // Disabling a library leaves every enabled mod that needs it unable to load.
+  let graph = build_mod_dependency_graph(&instance_dir, &HashMap::new());
+  let dependents = enabled_mod_dependents(&graph, &target_name);
+  if disable_dependents.unwrap_or(false) {
+    for dependent in dependents {
+      let disabled = format!("{}.disabled", dependent);
+      fs::rename(long_path(&mods_dir.join(&dependent)), long_path(&mods_dir.join(&disabled)))
+        .map_err(|err| err.to_string())?;
+      result.disabled_dependents.push(dependent);
+    }
+  } else {
+    result.broken_dependents = dependents;
+  }
+  Ok(result)
 }
 
 #[tauri::command]
@@ -362,6 +443,195 @@ pub(crate) fn delete_mod(
   remove_path(&path)
 }
 
+fn resolve_instance_version(
+  instance_id: &str,
+  state: &tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<String, String> {
+  let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+  store
+    .get()
+    .instances
+    .into_iter()
+    .find(|item| item.id == instance_id)
+    .map(|instance| instance.version)
+    .ok_or_else(|| "instance not found".to_string())
+}
+
+/// The `resourcePacks` list in `options.txt`, lowest priority first.
+struct ResourcePackOptions {
+  lines: Vec<String>,
+  entries: Vec<String>,
+  prefixed: bool,
+}
+
+impl ResourcePackOptions {
+  fn load(instance_dir: &Path, game_version: &str) -> Self {
+    let lines: Vec<String> = fs::read_to_string(instance_dir.join(OPTIONS_FILE))
+      .unwrap_or_default()
+      .lines()
+      .map(str::to_string)
+      .collect();
+    let option = |key: &str| {
+      lines.iter().find_map(|line| {
+        line
+          .split_once(':')
+          .filter(|(name, _)| *name == key)
+          .map(|(_, value)| value.trim().to_string())
+      })
+    };
+    let entries: Vec<String> = option(RESOURCE_PACKS_KEY)
+      .and_then(|value| serde_json::from_str(&value).ok())
+      .unwrap_or_default();
+    let prefixed = if entries
+      .iter()
+      .any(|entry| entry == "vanilla" || entry.starts_with(FILE_PACK_PREFIX))
+    {
+      true
+    } else {
+      match option("version").and_then(|value| value.parse::<u32>().ok()) {
+        Some(data_version) => data_version >= PREFIXED_PACK_IDS_DATA_VERSION,
+        None => uses_prefixed_pack_ids(game_version),
+      }
+    };
+    Self {
+      lines,
+      entries,
+      prefixed,
+    }
+  }
+
+  /// Folder pack filenames, highest priority first as the in-game screen shows them.
+  fn enabled_packs(&self) -> Vec<String> {
+    self
+      .entries
+      .iter()
+      .rev()
+      .filter_map(|entry| {
+        if self.prefixed {
+          entry.strip_prefix(FILE_PACK_PREFIX).map(str::to_string)
+        } else {
+          Some(entry.clone())
+        }
+      })
+      .collect()
+  }
+
+  /// Refills the folder pack slots with `order`, highest priority first.
+  fn save(mut self, instance_dir: &Path, order: &[String]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    let mut folder_packs = order
+      .iter()
+      .rev()
+      .filter(|filename| seen.insert(filename.as_str()))
+      .map(|filename| {
+        if self.prefixed {
+          format!("{}{}", FILE_PACK_PREFIX, filename)
+        } else {
+          filename.clone()
+        }
+      });
+    let mut entries = Vec::new();
+    for entry in &self.entries {
+      if self.prefixed && !entry.starts_with(FILE_PACK_PREFIX) {
+        entries.push(entry.clone());
+      } else if let Some(folder_pack) = folder_packs.next() {
+        entries.push(folder_pack);
+      }
+    }
+    if self.prefixed && !entries.iter().any(|entry| !entry.starts_with(FILE_PACK_PREFIX)) {
+      entries.insert(0, "vanilla".to_string());
+    }
+    entries.extend(folder_packs);
+    let value = serde_json::to_string(&entries).map_err(|err| err.to_string())?;
+    let line = format!("{}:{}", RESOURCE_PACKS_KEY, value);
+    match self
+      .lines
+      .iter_mut()
+      .find(|existing| existing.split(':').next() == Some(RESOURCE_PACKS_KEY))
+    {
+      Some(existing) => *existing = line,
+      None => self.lines.push(line),
+    }
+    fs::write(instance_dir.join(OPTIONS_FILE), format!("{}\n", self.lines.join("\n")))
+      .map_err(|err| err.to_string())
+  }
+}
+
+/// Whether a version writes `file/`-prefixed pack ids; snapshots are assumed to be recent.
+fn uses_prefixed_pack_ids(game_version: &str) -> bool {
+  match game_version.trim().strip_prefix("1.") {
+    Some(rest) => rest
+      .split(|ch: char| !ch.is_ascii_digit())
+      .next()
+      .and_then(|minor| minor.parse::<u32>().ok())
+      .map_or(true, |minor| minor >= 13),
+    None => true,
+  }
+}
+
+/// Keeps `options.txt` in step when a resource pack is toggled or removed.
+fn sync_resource_pack_option(
+  instance_dir: &Path,
+  game_version: &str,
+  filename: &str,
+  enabled: bool,
+) -> Result<(), String> {
+  let options = ResourcePackOptions::load(instance_dir, game_version);
+  let mut order = options.enabled_packs();
+  let present = order.iter().any(|entry| entry == filename);
+  if enabled == present {
+    return Ok(());
+  }
+  if enabled {
+    order.insert(0, filename.to_string());
+  } else {
+    order.retain(|entry| entry != filename);
+  }
+  options.save(instance_dir, &order)
+}
+
+/// Enabled resource packs in `options.txt`, highest priority first.
+#[tauri::command]
+pub(crate) fn get_resource_pack_order(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<String>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let game_version = resolve_instance_version(&instance_id, &state)?;
+  Ok(ResourcePackOptions::load(&instance_dir, &game_version).enabled_packs())
+}
+
+/// Writes the enabled resource packs, highest priority first, to `options.txt`.
+#[tauri::command]
+pub(crate) fn set_resource_pack_order(
+  instance_id: String,
+  filenames: Vec<String>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+  running: tauri::State<'_, Mutex<HashMap<String, u32>>>,
+) -> Result<Vec<String>, String> {
+  let is_running = running
+    .lock()
+    .map_err(|_| "process map lock poisoned".to_string())?
+    .contains_key(&instance_id);
+  if is_running {
+    return Err("stop the instance before reordering resource packs".to_string());
+  }
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let game_version = resolve_instance_version(&instance_id, &state)?;
+  let pack_dir = instance_dir.join("resourcepacks");
+  for filename in &filenames {
+    if filename.contains(['/', '\\']) || filename == ".." || filename.is_empty() {
+      return Err(format!("invalid resource pack name '{}'", filename));
+    }
+    if filename.ends_with(".disabled") || !pack_dir.join(filename).exists() {
+      return Err(format!("resource pack '{}' is not enabled", filename));
+    }
+  }
+  let options = ResourcePackOptions::load(&instance_dir, &game_version);
+  options.save(&instance_dir, &filenames)?;
+  Ok(ResourcePackOptions::load(&instance_dir, &game_version).enabled_packs())
+}
+
 #[tauri::command]
 pub(crate) fn list_instance_packs(
   instance_id: String,
@@ -374,7 +644,7 @@ pub(crate) fn list_instance_packs(
   if !pack_dir.exists() {
     return Ok(Vec::new());
   }
-  let entries = fs::read_dir(&pack_dir).map_err(|err| err.to_string())?;
+  let entries = fs::read_dir(long_path(&pack_dir)).map_err(|err| err.to_string())?;
   let mut results = Vec::new();
   for entry in entries.flatten() {
     let path = entry.path();
@@ -385,10 +655,15 @@ pub(crate) fn list_instance_packs(
     if should_skip_pack_filename(&filename) {
       continue;
     }
+    if kind == "plugins" && !path.is_file() {
+      continue;
+    }
     let enabled = !filename.ends_with(".disabled");
     let name = strip_known_suffixes(&filename);
     let version = if kind == "shaderpacks" {
       None
+    } else if kind == "plugins" {
+      read_plugin_version(&path).or_else(|| parse_version_from_name(&filename))
     } else if kind == "resourcepacks" || kind == "texturepacks" {
       read_pack_format(&path).or_else(|| parse_version_from_name(&filename))
     } else {
@@ -431,7 +706,12 @@ pub(crate) fn toggle_instance_pack(
   } else {
     pack_dir.join(format!("{}.disabled", filename))
   };
-  fs::rename(source, target).map_err(|err| err.to_string())?;
+  fs::rename(long_path(&source), long_path(&target)).map_err(|err| err.to_string())?;
+  if kind == "resourcepacks" {
+    let game_version = resolve_instance_version(&instance_id, &state)?;
+    let name = filename.trim_end_matches(".disabled");
+    sync_resource_pack_option(&instance_dir, &game_version, name, enabled)?;
+  }
   Ok(())
 }
 
@@ -449,7 +729,13 @@ pub(crate) fn delete_instance_pack(
   if !path.exists() {
     return Err("pack file not found".to_string());
   }
-  remove_path(&path)
+  remove_path(&path)?;
+  if kind == "resourcepacks" {
+    let game_version = resolve_instance_version(&instance_id, &state)?;
+    let name = filename.trim_end_matches(".disabled");
+    sync_resource_pack_option(&instance_dir, &game_version, name, false)?;
+  }
+  Ok(())
 }
 
 #[tauri::command]
@@ -463,7 +749,7 @@ pub(crate) fn list_instance_datapacks(
   if !datapack_dir.exists() {
     return Ok(Vec::new());
   }
-  let entries = fs::read_dir(&datapack_dir).map_err(|err| err.to_string())?;
+  let entries = fs::read_dir(long_path(&datapack_dir)).map_err(|err| err.to_string())?;
   let mut results = Vec::new();
   for entry in entries.flatten() {
     let path = entry.path();
@@ -513,7 +799,7 @@ pub(crate) fn toggle_instance_datapack(
   } else {
     datapack_dir.join(format!("{}.disabled", filename))
   };
-  fs::rename(source, target).map_err(|err| err.to_string())?;
+  fs::rename(long_path(&source), long_path(&target)).map_err(|err| err.to_string())?;
   Ok(())
 }
 
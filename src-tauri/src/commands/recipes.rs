@@ -0,0 +1,273 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::Manager;
+
+use crate::commands::instances::ImportPreview;
+use crate::events::{InstanceOperationGuard, OperationEmitter};
+use crate::config::{
+  resolve_home_dir, ConfigStore, Instance, InstanceKind, InstanceManifest, Loader,
+  INSTANCE_CONFIG_FILE,
+};
+use crate::minecraft::{
+  create_instance, plan_instance_directory, NewInstanceRequest, ProgressEvent,
+};
+use crate::modrinth::{
+  collect_recipe_projects, install_recipe_projects, plan_recipe_downloads, ModrinthRecipeProject,
+};
+
+const INSTANCE_RECIPE_FORMAT: u32 = 1;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct InstanceRecipe {
+  format: u32,
+  name: String,
+  #[serde(default)]
+  kind: InstanceKind,
+  game_version: String,
+  loader: Loader,
+  #[serde(default)]
+  loader_version: Option<String>,
+  #[serde(default)]
+  show_snapshots: bool,
+  #[serde(default)]
+  java_min_ram_mb: Option<u32>,
+  #[serde(default)]
+  java_max_ram_mb: Option<u32>,
+  #[serde(default, deserialize_with = "crate::config::deserialize_jvm_args")]
+  jvm_args: Option<Vec<String>>,
+  #[serde(default)]
+  projects: Vec<ModrinthRecipeProject>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  homepage: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct RecipeImportResult {
+  instance: Instance,
+  failed_projects: Vec<String>,
+}
+
+pub(crate) fn modrinth_loader(loader: &Loader) -> Option<String> {
+  if *loader == Loader::Vanilla {
+    return None;
+  }
+  serde_json::to_value(loader)
+    .ok()
+    .and_then(|value| value.as_str().map(|value| value.to_string()))
+}
+
+fn load_recipe(path: &str) -> Result<InstanceRecipe, String> {
+  let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
+  let recipe: InstanceRecipe = serde_json::from_str(&data).map_err(|err| err.to_string())?;
+  if recipe.format > INSTANCE_RECIPE_FORMAT {
+    return Err(format!("unsupported recipe format {}", recipe.format));
+  }
+  Ok(recipe)
+}
+
+fn recipe_request(
+  recipe: &InstanceRecipe,
+  name: Option<String>,
+  root_id: Option<String>,
+) -> NewInstanceRequest {
+  NewInstanceRequest {
+    name: name
+      .filter(|value| !value.trim().is_empty())
+      .unwrap_or_else(|| recipe.name.clone()),
+    game_version: recipe.game_version.clone(),
+    loader: recipe.loader.clone(),
+    loader_version: recipe.loader_version.clone(),
+    show_snapshots: Some(recipe.show_snapshots),
+    root_id,
+    kind: recipe.kind.clone(),
+  }
+}
+
+pub(crate) fn build_instance_recipe(instance: &Instance) -> Result<InstanceRecipe, String> {
+  let instance_dir = PathBuf::from(&instance.directory);
+  let projects = collect_recipe_projects(&instance_dir)?;
+  let manifest = fs::read_to_string(instance_dir.join(INSTANCE_CONFIG_FILE))
+    .ok()
+    .and_then(|data| serde_json::from_str::<InstanceManifest>(&data).ok());
+  let (description, homepage) = manifest
+    .map(|manifest| (manifest.description, manifest.homepage))
+    .unwrap_or_default();
+  Ok(InstanceRecipe {
+    format: INSTANCE_RECIPE_FORMAT,
+    name: instance.name.clone(),
+    kind: instance.kind.clone(),
+    game_version: instance.version.clone(),
+    loader: instance.loader.clone(),
+    loader_version: instance.loader_version.clone(),
+    show_snapshots: instance.show_snapshots,
+    java_min_ram_mb: instance.java_min_ram_mb,
+    java_max_ram_mb: instance.java_max_ram_mb,
+    jvm_args: instance.jvm_args.clone(),
+    projects,
+    description,
+    homepage,
+  })
+}
+
+#[tauri::command]
+pub(crate) fn export_instance_recipe(
+  instance_id: String,
+  destination: Option<String>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<String, String> {
+  let instance = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?
+  };
+  let recipe = build_instance_recipe(&instance)?;
+
+  let export_path = match destination {
+    Some(path) if !path.trim().is_empty() => PathBuf::from(path.trim()),
+    _ => resolve_home_dir()
+      .unwrap_or_else(|| PathBuf::from("."))
+      .join(format!("{}.monolith-recipe.json", instance.id)),
+  };
+  let payload = serde_json::to_vec_pretty(&recipe).map_err(|err| err.to_string())?;
+  fs::write(&export_path, payload).map_err(|err| err.to_string())?;
+  Ok(export_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn preview_instance_recipe_import(
+  path: String,
+  name: Option<String>,
+  root_id: Option<String>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<ImportPreview, String> {
+  let recipe = load_recipe(&path)?;
+  let config = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store.get()
+  };
+  let request = recipe_request(&recipe, name, root_id);
+  let (_, instance_id, directory) = plan_instance_directory(&request, &config)?;
+
+  tauri::async_runtime::spawn_blocking(move || {
+    let loader = modrinth_loader(&recipe.loader);
+    let plan =
+      plan_recipe_downloads(&directory, &recipe.game_version, loader.as_deref(), &recipe.projects);
+    let mut preview = ImportPreview {
+      instance_id,
+      directory: directory.to_string_lossy().to_string(),
+      created: vec![
+        directory.join(INSTANCE_CONFIG_FILE).to_string_lossy().to_string(),
+        directory.join("modrinth.json").to_string_lossy().to_string(),
+      ],
+      download_count: plan.files.len() as u64,
+      unresolved: plan.failures,
+      ..ImportPreview::default()
+    };
+    for (file, size) in plan.files {
+      preview.download_bytes += size;
+      let display = file.to_string_lossy().to_string();
+      if file.exists() {
+        preview.overwritten.push(display);
+      } else {
+        preview.created.push(display);
+      }
+    }
+    preview
+  })
+  .await
+  .map_err(|_| "recipe preview task failed".to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn import_instance_recipe(
+  window: tauri::Window,
+  path: String,
+  name: Option<String>,
+  root_id: Option<String>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<RecipeImportResult, String> {
+  let recipe = load_recipe(&path)?;
+  let config = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store.get()
+  };
+
+  let ops = OperationEmitter::new(window.app_handle(), None);
+  let progress_ops = ops.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let mut config = config;
+    let emitter = |event: ProgressEvent| {
+      progress_ops.emit_progress(event);
+    };
+    let request = recipe_request(&recipe, name, root_id);
+    let guard = RefCell::new(None);
+    let reserved = |instance_id: &str, instance_dir: &Path| {
+      progress_ops.bind_instance(instance_id);
+      let acquired = InstanceOperationGuard::acquire(instance_id, instance_dir, "import")?;
+      *guard.borrow_mut() = Some(acquired);
+      Ok(())
+    };
+    let mut instance = create_instance(request, &mut config, &reserved, &emitter)?;
+
+    instance.java_min_ram_mb = recipe.java_min_ram_mb;
+    instance.java_max_ram_mb = recipe.java_max_ram_mb;
+    instance.jvm_args = recipe.jvm_args.clone();
+    let instance_dir = PathBuf::from(&instance.directory);
+    let manifest_path = instance_dir.join(INSTANCE_CONFIG_FILE);
+    let mut manifest: InstanceManifest = fs::read_to_string(&manifest_path)
+      .map_err(|err| err.to_string())
+      .and_then(|data| serde_json::from_str(&data).map_err(|err| err.to_string()))?;
+    manifest.java_min_ram_mb = instance.java_min_ram_mb;
+    manifest.java_max_ram_mb = instance.java_max_ram_mb;
+    manifest.jvm_args = instance.jvm_args.clone();
+    manifest.description = recipe.description.clone();
+    manifest.homepage = recipe.homepage.clone();
+    let payload = serde_json::to_vec_pretty(&manifest).map_err(|err| err.to_string())?;
+    fs::write(&manifest_path, payload).map_err(|err| err.to_string())?;
+
+    let total = recipe.projects.len() as u64;
+    let loader = modrinth_loader(&recipe.loader);
+    let failed_projects = install_recipe_projects(
+      &instance_dir,
+      &recipe.game_version,
+      loader.as_deref(),
+      &recipe.projects,
+      &|index, project_id| {
+        emitter(ProgressEvent {
+          stage: "recipe".to_string(),
+          message: "Downloading recipe content".to_string(),
+          current: index as u64 + 1,
+          total: Some(total),
+          detail: Some(project_id.to_string()),
+          ..Default::default()
+        });
+      },
+    )?;
+    Ok::<_, String>((instance, failed_projects))
+  })
+  .await
+  .map_err(|_| "recipe import task failed".to_string())?;
+
+  match result {
+    Ok((instance, failed_projects)) => {
+      ops.emit("install:done", &instance);
+      Ok(RecipeImportResult {
+        instance,
+        failed_projects,
+      })
+    }
+    Err(err) => {
+      ops.emit("install:error", err.clone());
+      Err(err)
+    }
+  }
+}
@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ChildStdin;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::config::{ConfigStore, InstanceKind};
+use crate::minecraft::{server_eula_accepted, SERVER_EULA_FILE, SERVER_EULA_URL};
+
+const SERVER_PROPERTIES_FILE: &str = "server.properties";
+
+pub(crate) struct ServerConsole {
+  pub pid: u32,
+  pub stdin: ChildStdin,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct ServerProperty {
+  key: String,
+  value: Value,
+}
+
+pub(crate) fn resolve_server_instance_dir(
+  instance_id: &str,
+  state: &tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<PathBuf, String> {
+  let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+  let config = store.get();
+  let instance = config
+    .instances
+    .iter()
+    .find(|item| item.id == instance_id)
+    .ok_or_else(|| "instance not found".to_string())?;
+  if instance.kind != InstanceKind::Server {
+    return Err("instance is not a server instance".to_string());
+  }
+  Ok(PathBuf::from(&instance.directory))
+}
+
+fn unescape_property(raw: &str) -> String {
+  let mut result = String::new();
+  let mut chars = raw.chars();
+  while let Some(ch) = chars.next() {
+    if ch != '\\' {
+      result.push(ch);
+      continue;
+    }
+    match chars.next() {
+      Some('n') => result.push('\n'),
+      Some('t') => result.push('\t'),
+      Some('r') => result.push('\r'),
+      Some('u') => {
+        let code: String = chars.by_ref().take(4).collect();
+        match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+          Some(decoded) => result.push(decoded),
+          None => {
+            result.push_str("\\u");
+            result.push_str(&code);
+          }
+        }
+      }
+      Some(other) => result.push(other),
+      None => {}
+    }
+  }
+  result
+}
+
+fn escape_property(value: &str) -> String {
+  let mut result = String::new();
+  for (idx, ch) in value.chars().enumerate() {
+    match ch {
+      '\\' => result.push_str("\\\\"),
+      '\n' => result.push_str("\\n"),
+      '\t' => result.push_str("\\t"),
+      '\r' => result.push_str("\\r"),
+      ':' | '=' | '#' | '!' => {
+        result.push('\\');
+        result.push(ch);
+      }
+      ' ' if idx == 0 => result.push_str("\\ "),
+      _ => result.push(ch),
+    }
+  }
+  result
+}
+
+fn split_property_line(line: &str) -> Option<(String, String)> {
+  let trimmed = line.trim_start();
+  if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+    return None;
+  }
+  let mut escaped = false;
+  for (idx, ch) in trimmed.char_indices() {
+    if escaped {
+      escaped = false;
+      continue;
+    }
+    if ch == '\\' {
+      escaped = true;
+      continue;
+    }
+    if ch == '=' || ch == ':' {
+      let key = unescape_property(trimmed[..idx].trim_end());
+      let value = unescape_property(trimmed[idx + 1..].trim_start());
+      return Some((key, value));
+    }
+  }
+  Some((unescape_property(trimmed.trim_end()), String::new()))
+}
+
+fn typed_property_value(raw: &str) -> Value {
+  match raw {
+    "true" => return Value::Bool(true),
+    "false" => return Value::Bool(false),
+    _ => {}
+  }
+  if let Ok(number) = raw.parse::<i64>() {
+    if number.to_string() == raw {
+      return Value::from(number);
+    }
+  }
+  Value::String(raw.to_string())
+}
+
+fn format_property_value(value: &Value) -> Result<String, String> {
+  match value {
+    Value::Null => Ok(String::new()),
+    Value::Bool(flag) => Ok(flag.to_string()),
+    Value::Number(number) => Ok(number.to_string()),
+    Value::String(text) => Ok(escape_property(text)),
+    Value::Array(_) | Value::Object(_) => {
+      Err("server properties only support text, number and boolean values".to_string())
+    }
+  }
+}
+
+fn read_server_properties(path: &Path) -> Result<Vec<ServerProperty>, String> {
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+  let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
+  Ok(
+    data
+      .lines()
+      .filter_map(split_property_line)
+      .map(|(key, value)| ServerProperty {
+        value: typed_property_value(&value),
+        key,
+      })
+      .collect(),
+  )
+}
+
+#[tauri::command]
+pub(crate) fn get_server_properties(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ServerProperty>, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  read_server_properties(&instance_dir.join(SERVER_PROPERTIES_FILE))
+}
+
+#[tauri::command]
+pub(crate) fn set_server_properties(
+  instance_id: String,
+  values: HashMap<String, Value>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ServerProperty>, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  let path = instance_dir.join(SERVER_PROPERTIES_FILE);
+
+  let mut pending = HashMap::new();
+  for (key, value) in values {
+    let key = key.trim().to_string();
+    if key.is_empty() {
+      return Err("property key cannot be empty".to_string());
+    }
+    let formatted = format_property_value(&value)?;
+    pending.insert(key, formatted);
+  }
+
+  let existing = if path.exists() {
+    fs::read_to_string(&path).map_err(|err| err.to_string())?
+  } else {
+    String::new()
+  };
+
+  let mut lines = Vec::new();
+  for line in existing.lines() {
+    match split_property_line(line) {
+      Some((key, _)) => match pending.remove(&key) {
+        Some(value) => lines.push(format!("{}={}", escape_property(&key), value)),
+        None => lines.push(line.to_string()),
+      },
+      None => lines.push(line.to_string()),
+    }
+  }
+
+  let mut remaining = pending.into_iter().collect::<Vec<_>>();
+  remaining.sort_by(|a, b| a.0.cmp(&b.0));
+  for (key, value) in remaining {
+    lines.push(format!("{}={}", escape_property(&key), value));
+  }
+
+  let mut payload = lines.join("\n");
+  payload.push('\n');
+  fs::write(&path, payload).map_err(|err| err.to_string())?;
+  read_server_properties(&path)
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ServerPlayerEntry {
+  uuid: String,
+  name: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  level: Option<u8>,
+  #[serde(
+    rename = "bypassesPlayerLimit",
+    default,
+    skip_serializing_if = "Option::is_none"
+  )]
+  bypasses_player_limit: Option<bool>,
+  #[serde(flatten)]
+  extra: HashMap<String, Value>,
+}
+
+#[derive(serde::Deserialize)]
+struct MojangProfileLookup {
+  id: String,
+  name: String,
+}
+
+fn server_player_list_file(list: &str) -> Result<&'static str, String> {
+  match list {
+    "whitelist" => Ok("whitelist.json"),
+    "ops" => Ok("ops.json"),
+    _ => Err("unsupported player list".to_string()),
+  }
+}
+
+fn load_server_player_list(path: &Path) -> Result<Vec<ServerPlayerEntry>, String> {
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+  let data = fs::read_to_string(path).map_err(|err| err.to_string())?;
+  if data.trim().is_empty() {
+    return Ok(Vec::new());
+  }
+  serde_json::from_str(&data).map_err(|err| err.to_string())
+}
+
+fn save_server_player_list(path: &Path, entries: &[ServerPlayerEntry]) -> Result<(), String> {
+  let payload = serde_json::to_vec_pretty(entries).map_err(|err| err.to_string())?;
+  fs::write(path, payload).map_err(|err| err.to_string())
+}
+
+fn resolve_player_profile(name: &str) -> Result<(String, String), String> {
+  let url = format!(
+    "https://api.mojang.com/users/profiles/minecraft/{}",
+    urlencoding::encode(name)
+  );
+  let response = match ureq::get(&url).call() {
+    Ok(response) => response,
+    Err(ureq::Error::Status(404, _)) => return Err(format!("player '{}' not found", name)),
+    Err(err) => return Err(err.to_string()),
+  };
+  if response.status() == 204 {
+    return Err(format!("player '{}' not found", name));
+  }
+  let profile: MojangProfileLookup = response.into_json().map_err(|err| err.to_string())?;
+  let uuid = uuid::Uuid::parse_str(&profile.id)
+    .map_err(|err| err.to_string())?
+    .hyphenated()
+    .to_string();
+  Ok((uuid, profile.name))
+}
+
+#[tauri::command]
+pub(crate) fn list_server_players(
+  instance_id: String,
+  list: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ServerPlayerEntry>, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  load_server_player_list(&instance_dir.join(server_player_list_file(&list)?))
+}
+
+#[tauri::command]
+pub(crate) async fn add_server_player(
+  instance_id: String,
+  list: String,
+  name: String,
+  level: Option<u8>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ServerPlayerEntry>, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  let path = instance_dir.join(server_player_list_file(&list)?);
+  let name = name.trim().to_string();
+  if name.is_empty() {
+    return Err("player name cannot be empty".to_string());
+  }
+  let is_ops = list == "ops";
+  let (uuid, resolved_name) =
+    tauri::async_runtime::spawn_blocking(move || resolve_player_profile(&name))
+      .await
+      .map_err(|_| "player lookup task failed".to_string())??;
+
+  let mut entries = load_server_player_list(&path)?;
+  entries.retain(|entry| !entry.uuid.eq_ignore_ascii_case(&uuid));
+  entries.push(ServerPlayerEntry {
+    uuid,
+    name: resolved_name,
+    level: if is_ops { Some(level.unwrap_or(4).clamp(1, 4)) } else { None },
+    bypasses_player_limit: if is_ops { Some(false) } else { None },
+    extra: HashMap::new(),
+  });
+  save_server_player_list(&path, &entries)?;
+  Ok(entries)
+}
+
+#[tauri::command]
+pub(crate) fn remove_server_player(
+  instance_id: String,
+  list: String,
+  name: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ServerPlayerEntry>, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  let path = instance_dir.join(server_player_list_file(&list)?);
+  let mut entries = load_server_player_list(&path)?;
+  let before = entries.len();
+  entries.retain(|entry| !entry.name.eq_ignore_ascii_case(name.trim()));
+  if entries.len() == before {
+    return Err(format!("player '{}' is not on the {}", name.trim(), list));
+  }
+  save_server_player_list(&path, &entries)?;
+  Ok(entries)
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct ServerEulaStatus {
+  accepted: bool,
+  url: String,
+}
+
+#[tauri::command]
+pub(crate) fn get_server_eula_status(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<ServerEulaStatus, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  Ok(ServerEulaStatus {
+    accepted: server_eula_accepted(&instance_dir),
+    url: SERVER_EULA_URL.to_string(),
+  })
+}
+
+#[tauri::command]
+pub(crate) fn accept_server_eula(
+  instance_id: String,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<ServerEulaStatus, String> {
+  let instance_dir = resolve_server_instance_dir(&instance_id, &state)?;
+  let accepted_at = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
+  let payload = format!(
+    "#By changing the setting below to TRUE you are indicating your agreement to our EULA ({}).\n#Accepted via Monolith Launcher at {}\neula=true\n",
+    SERVER_EULA_URL, accepted_at
+  );
+  fs::write(instance_dir.join(SERVER_EULA_FILE), payload).map_err(|err| err.to_string())?;
+  Ok(ServerEulaStatus {
+    accepted: true,
+    url: SERVER_EULA_URL.to_string(),
+  })
+}
+
+#[tauri::command]
+pub(crate) fn send_server_command(
+  instance_id: String,
+  line: String,
+  consoles: tauri::State<'_, Mutex<HashMap<String, ServerConsole>>>,
+) -> Result<(), String> {
+  let command = line.trim();
+  if command.is_empty() {
+    return Err("command cannot be empty".to_string());
+  }
+  let command = command.strip_prefix('/').unwrap_or(command);
+  let mut map = consoles
+    .lock()
+    .map_err(|_| "server console lock poisoned".to_string())?;
+  let console = map
+    .get_mut(&instance_id)
+    .ok_or_else(|| "server is not running".to_string())?;
+  let result = writeln!(console.stdin, "{}", command).and_then(|_| console.stdin.flush());
+  if let Err(err) = result {
+    map.remove(&instance_id);
+    return Err(format!("failed to send server command: {}", err));
+  }
+  Ok(())
+}
@@ -1,17 +1,27 @@
 use std::collections::HashMap;
 use std::fs;
+use std::io::{Read, Write};
 use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use fastnbt::Value as NbtValue;
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
 
 use crate::config::ConfigStore;
+use crate::events::OperationEmitter;
+use crate::minecraft::rotate_file_backups;
 use crate::resolve_instance_dir;
 
+const SERVER_STATUS_FILE: &str = "servers-status.json";
+const SERVER_STATUS_FRESH_SECS: u64 = 30;
+const SERVER_STATUS_TIMEOUT_MS: u64 = 3000;
+const SERVER_STATUS_MAX_RESPONSE: usize = 1 << 20;
+const NBT_BACKUP_GENERATIONS: usize = 3;
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub(crate) struct ServerEntry {
   name: String,
@@ -20,6 +30,36 @@ pub(crate) struct ServerEntry {
   accept_textures: Option<bool>,
   #[serde(default)]
   icon: Option<String>,
+  #[serde(default)]
+  status: Option<ServerStatus>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ServerStatus {
+  online: bool,
+  players_online: Option<u32>,
+  players_max: Option<u32>,
+  latency_ms: Option<u32>,
+  checked_at_unix: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ServerStatusEvent {
+  instance_id: String,
+  address: String,
+  status: ServerStatus,
+}
+
+#[derive(serde::Deserialize)]
+struct StatusResponse {
+  #[serde(default)]
+  players: Option<StatusPlayers>,
+}
+
+#[derive(serde::Deserialize)]
+struct StatusPlayers {
+  online: u32,
+  max: u32,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Default)]
@@ -82,6 +122,7 @@ fn save_servers_dat(path: &Path, servers: Vec<ServerEntry>) -> Result<(), String
       extra: HashMap::new(),
     })
     .collect();
+  rotate_file_backups(path, NBT_BACKUP_GENERATIONS)?;
   let file = fs::File::create(path).map_err(|err| err.to_string())?;
   let mut encoder = GzEncoder::new(file, Compression::default());
   fastnbt::to_writer(&mut encoder, &payload).map_err(|err| err.to_string())?;
@@ -89,9 +130,261 @@ fn save_servers_dat(path: &Path, servers: Vec<ServerEntry>) -> Result<(), String
   Ok(())
 }
 
+fn server_status_path(instance_dir: &Path) -> PathBuf {
+  instance_dir.join(SERVER_STATUS_FILE)
+}
+
+fn load_server_statuses(instance_dir: &Path) -> HashMap<String, ServerStatus> {
+  fs::read_to_string(server_status_path(instance_dir))
+    .ok()
+    .and_then(|data| serde_json::from_str(&data).ok())
+    .unwrap_or_default()
+}
+
+fn save_server_statuses(
+  instance_dir: &Path,
+  statuses: &HashMap<String, ServerStatus>,
+) -> Result<(), String> {
+  let payload = serde_json::to_vec_pretty(statuses).map_err(|err| err.to_string())?;
+  fs::write(server_status_path(instance_dir), payload).map_err(|err| err.to_string())
+}
+
+fn now_unix() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs()
+}
+
+fn write_varint(buffer: &mut Vec<u8>, value: i32) {
+  let mut value = value as u32;
+  loop {
+    if value & !0x7f == 0 {
+      buffer.push(value as u8);
+      return;
+    }
+    buffer.push(((value & 0x7f) | 0x80) as u8);
+    value >>= 7;
+  }
+}
+
+fn read_varint(stream: &mut impl Read) -> Result<i32, String> {
+  let mut result = 0u32;
+  for shift in 0..5 {
+    let mut byte = [0u8; 1];
+    stream.read_exact(&mut byte).map_err(|err| err.to_string())?;
+    result |= ((byte[0] & 0x7f) as u32) << (shift * 7);
+    if byte[0] & 0x80 == 0 {
+      return Ok(result as i32);
+    }
+  }
+  Err("invalid varint in server response".to_string())
+}
+
+fn write_packet(stream: &mut TcpStream, payload: &[u8]) -> Result<(), String> {
+  let mut packet = Vec::with_capacity(payload.len() + 5);
+  write_varint(&mut packet, payload.len() as i32);
+  packet.extend_from_slice(payload);
+  stream.write_all(&packet).map_err(|err| err.to_string())
+}
+
+fn query_server_status(address: &str) -> ServerStatus {
+  let offline = ServerStatus {
+    online: false,
+    players_online: None,
+    players_max: None,
+    latency_ms: None,
+    checked_at_unix: now_unix(),
+  };
+  let Ok((host, port)) = parse_server_target(address) else {
+    return offline;
+  };
+  let Ok(target) = resolve_socket_addr(&host, port) else {
+    return offline;
+  };
+  let timeout = Duration::from_millis(SERVER_STATUS_TIMEOUT_MS);
+  let start = Instant::now();
+  let Ok(mut stream) = TcpStream::connect_timeout(&target, timeout) else {
+    return offline;
+  };
+  let latency_ms = start.elapsed().as_millis() as u32;
+  let _ = stream.set_read_timeout(Some(timeout));
+  let _ = stream.set_write_timeout(Some(timeout));
+
+  let mut handshake = Vec::new();
+  write_varint(&mut handshake, 0x00);
+  write_varint(&mut handshake, -1);
+  write_varint(&mut handshake, host.len() as i32);
+  handshake.extend_from_slice(host.as_bytes());
+  handshake.extend_from_slice(&port.to_be_bytes());
+  write_varint(&mut handshake, 1);
+  let response = write_packet(&mut stream, &handshake)
+    .and_then(|_| write_packet(&mut stream, &[0x00]))
+    .and_then(|_| {
+      let _length = read_varint(&mut stream)?;
+      if read_varint(&mut stream)? != 0x00 {
+        return Err("unexpected packet in server status response".to_string());
+      }
+      let json_length = read_varint(&mut stream)?;
+      if json_length < 0 || json_length as usize > SERVER_STATUS_MAX_RESPONSE {
+        return Err("invalid server status response length".to_string());
+      }
+      let mut json = vec![0u8; json_length as usize];
+      stream.read_exact(&mut json).map_err(|err| err.to_string())?;
+      serde_json::from_slice::<StatusResponse>(&json).map_err(|err| err.to_string())
+    });
+  let _ = stream.shutdown(std::net::Shutdown::Both);
+  // Something that accepts the connection but does not answer the status ping is not a server.
+  let Ok(response) = response else {
+    return offline;
+  };
+  let players = response.players;
+
+  ServerStatus {
+    online: true,
+    players_online: players.as_ref().map(|players| players.online),
+    players_max: players.as_ref().map(|players| players.max),
+    latency_ms: Some(latency_ms),
+    checked_at_unix: offline.checked_at_unix,
+  }
+}
+
+fn refresh_server_statuses(
+  app: tauri::AppHandle,
+  instance_id: String,
+  instance_dir: PathBuf,
+  addresses: Vec<String>,
+) {
+  thread::spawn(move || {
+    let ops = OperationEmitter::new(&app, Some(&instance_id));
+    let cutoff = now_unix().saturating_sub(SERVER_STATUS_FRESH_SECS);
+    let cached = load_server_statuses(&instance_dir);
+    let stale = addresses
+      .into_iter()
+      .filter(|address| {
+        cached
+          .get(address)
+          .map(|status| status.checked_at_unix < cutoff)
+          .unwrap_or(true)
+      })
+      .collect::<Vec<_>>();
+    if stale.is_empty() {
+      return;
+    }
+    let handles = stale
+      .into_iter()
+      .map(|address| {
+        thread::spawn(move || {
+          let status = query_server_status(&address);
+          (address, status)
+        })
+      })
+      .collect::<Vec<_>>();
+    let mut statuses = load_server_statuses(&instance_dir);
+    for handle in handles {
+      let Ok((address, status)) = handle.join() else {
+        continue;
+      };
+      statuses.insert(address.clone(), status.clone());
+      ops.emit(
+        "servers:status",
+        ServerStatusEvent {
+          instance_id: instance_id.clone(),
+          address,
+          status,
+        },
+      );
+    }
+    let _ = save_server_statuses(&instance_dir, &statuses);
+  });
+}
+
+pub(crate) fn load_server_list(instance_dir: &Path) -> Result<Vec<ServerEntry>, String> {
+  let servers_file = instance_dir.join("servers.dat");
+  if !servers_file.exists() {
+    return Ok(Vec::new());
+  }
+  let payload = load_servers_dat(&servers_file)?;
+  Ok(
+    payload
+      .servers
+      .into_iter()
+      .map(|entry| ServerEntry {
+        name: entry.name,
+        ip: entry.ip,
+        accept_textures: entry.accept_textures,
+        icon: entry.icon,
+        status: None,
+      })
+      .collect(),
+  )
+}
+
+pub(crate) fn store_server_list(
+  instance_dir: &Path,
+  servers: Vec<ServerEntry>,
+) -> Result<(), String> {
+  save_servers_dat(&instance_dir.join("servers.dat"), servers)
+}
+
+/// `address#hash` of an entry, so renames and icon changes count as edits.
+pub(crate) fn server_entry_fingerprint(entry: &ServerEntry) -> String {
+  let mut hasher = Sha256::new();
+  hasher.update(entry.name.as_bytes());
+  hasher.update([0]);
+  hasher.update(entry.icon.as_deref().unwrap_or_default().as_bytes());
+  hasher.update([0]);
+  hasher.update(format!("{:?}", entry.accept_textures).as_bytes());
+  format!("{}#{:x}", entry.ip, hasher.finalize())
+}
+
+/// Three-way merge keyed by address; deletions stick and local edits win on overlap.
+pub(crate) fn merge_server_lists(
+  base: &[String],
+  local: Vec<ServerEntry>,
+  remote: Vec<ServerEntry>,
+) -> Vec<ServerEntry> {
+  // Older sync states stored bare addresses; those entries keep the local copy.
+  let base = base
+    .iter()
+    .map(|fingerprint| match fingerprint.rsplit_once('#') {
+      Some((address, _)) => (address.to_string(), fingerprint.clone()),
+      None => (fingerprint.clone(), String::new()),
+    })
+    .collect::<HashMap<_, _>>();
+  let local_addresses = local.iter().map(|entry| entry.ip.clone()).collect::<Vec<_>>();
+  let remote_entries = remote
+    .iter()
+    .map(|entry| (entry.ip.as_str(), entry))
+    .collect::<HashMap<_, _>>();
+  let removed = |address: &String| {
+    base.contains_key(address)
+      && (!local_addresses.contains(address) || !remote_entries.contains_key(address.as_str()))
+  };
+  let mut merged = Vec::new();
+  for entry in local {
+    if removed(&entry.ip) {
+      continue;
+    }
+    let local_unchanged = base.get(&entry.ip) == Some(&server_entry_fingerprint(&entry));
+    match remote_entries.get(entry.ip.as_str()) {
+      Some(remote_entry) if local_unchanged => merged.push((*remote_entry).clone()),
+      _ => merged.push(entry),
+    }
+  }
+  for entry in remote.iter().cloned() {
+    if removed(&entry.ip) || local_addresses.contains(&entry.ip) {
+      continue;
+    }
+    merged.push(entry);
+  }
+  merged
+}
+
 #[tauri::command]
 pub(crate) fn list_instance_servers(
   instance_id: String,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<Vec<ServerEntry>, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
@@ -100,16 +393,20 @@ pub(crate) fn list_instance_servers(
     return Ok(Vec::new());
   }
   let payload = load_servers_dat(&servers_file)?;
+  let statuses = load_server_statuses(&instance_dir);
   let entries = payload
     .servers
     .into_iter()
     .map(|entry| ServerEntry {
+      status: statuses.get(&entry.ip).cloned(),
       name: entry.name,
       ip: entry.ip,
       accept_textures: entry.accept_textures,
       icon: entry.icon,
     })
-    .collect();
+    .collect::<Vec<_>>();
+  let addresses = entries.iter().map(|entry| entry.ip.clone()).collect();
+  refresh_server_statuses(app, instance_id, instance_dir, addresses);
   Ok(entries)
 }
 
@@ -3,7 +3,10 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::ConfigStore;
 use crate::diagnostics::refresh_saved_java_runtimes;
-use crate::java::{detect_java_version, discover_java_runtimes};
+use crate::java::{
+  detect_java_details, detect_java_version, discover_java_runtimes, run_java_smoke_test,
+  JavaInstallDetails, JavaSmokeTestResult,
+};
 
 const DEFAULT_UPDATE_REPO: &str = "dvnxvll/MonolithLauncher";
 
@@ -52,6 +55,10 @@ pub(crate) fn open_target(target: &str) -> Result<(), String> {
 pub(crate) struct JavaDetection {
   path: Option<String>,
   version: Option<String>,
+  vendor: Option<String>,
+  full_version: Option<String>,
+  arch: Option<String>,
+  is_jdk: Option<bool>,
 }
 
 #[derive(serde::Serialize)]
@@ -77,12 +84,39 @@ pub(crate) fn detect_java() -> Result<JavaDetection, String> {
     .as_deref()
     .and_then(detect_java_version)
     .or_else(|| detect_java_version("java"));
+  let details = detect_java_details(java_path.as_deref().unwrap_or("java"));
   Ok(JavaDetection {
     path: java_path,
     version,
+    vendor: details.as_ref().and_then(|details| details.vendor.clone()),
+    full_version: details.as_ref().and_then(|details| details.full_version.clone()),
+    arch: details.as_ref().and_then(|details| details.arch.clone()),
+    is_jdk: details.map(|details| details.is_jdk),
   })
 }
 
+#[tauri::command]
+pub(crate) async fn inspect_java_runtime(path: String) -> Result<JavaInstallDetails, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    detect_java_details(&path)
+      .ok_or_else(|| format!("could not read Java properties from {}", path))
+  })
+  .await
+  .map_err(|_| "java inspection task failed".to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn test_java(
+  path: String,
+  jvm_args: Option<String>,
+) -> Result<JavaSmokeTestResult, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    run_java_smoke_test(&path, jvm_args.as_deref().unwrap_or(""))
+  })
+  .await
+  .map_err(|_| "java test task failed".to_string())?
+}
+
 #[tauri::command]
 pub(crate) fn scan_java_runtimes(
   state: tauri::State<'_, std::sync::Mutex<ConfigStore>>,
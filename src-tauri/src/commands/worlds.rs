@@ -1,17 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use base64::Engine;
 use fastnbt::Value as NbtValue;
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 
-use crate::config::ConfigStore;
+use crate::config::{resolve_home_dir, ConfigStore};
+use crate::events::{emit_instance_updated, OperationEmitter};
+use crate::minecraft::{rotate_file_backups, sanitize_path_component};
 use crate::resolve_instance_dir;
 
 const WORLD_SIZE_CACHE_TTL_SECS: u64 = 300;
+const NBT_BACKUP_GENERATIONS: usize = 3;
+const WORLD_ICON_SIZE: u32 = 64;
+
+static WORLD_SIZE_JOBS: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
 
 #[derive(serde::Serialize)]
 pub(crate) struct WorldEntry {
@@ -38,6 +45,13 @@ struct LevelData {
   extra: HashMap<String, NbtValue>,
 }
 
+#[derive(Clone, serde::Serialize)]
+struct WorldSizeEvent {
+  instance_id: String,
+  world_id: String,
+  size_bytes: u64,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct WorldSizeCache {
   size_bytes: u64,
@@ -105,16 +119,59 @@ fn save_world_size_cache(world_dir: &Path, size_bytes: u64) {
   }
 }
 
-fn resolve_world_size(world_dir: &Path) -> u64 {
-  if let Some(cache) = load_world_size_cache(world_dir) {
-    let age = now_epoch_secs().saturating_sub(cache.computed_at);
-    if age <= WORLD_SIZE_CACHE_TTL_SECS {
-      return cache.size_bytes;
+/// Returns the last known size and whether it is stale enough to recompute.
+fn cached_world_size(world_dir: &Path) -> (Option<u64>, bool) {
+  match load_world_size_cache(world_dir) {
+    Some(cache) => {
+      let age = now_epoch_secs().saturating_sub(cache.computed_at);
+      (Some(cache.size_bytes), age > WORLD_SIZE_CACHE_TTL_SECS)
     }
+    None => (None, true),
   }
-  let size_bytes = directory_size(world_dir);
-  save_world_size_cache(world_dir, size_bytes);
-  size_bytes
+}
+
+/// Walks stale worlds on a background thread, emitting `world:size` as each finishes.
+fn spawn_world_size_refresh(
+  app: tauri::AppHandle,
+  instance_id: String,
+  worlds: Vec<(String, PathBuf)>,
+) {
+  let worlds = {
+    let mut jobs = WORLD_SIZE_JOBS
+      .get_or_init(|| Mutex::new(HashSet::new()))
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    worlds
+      .into_iter()
+      .filter(|(_, path)| jobs.insert(path.clone()))
+      .collect::<Vec<_>>()
+  };
+  if worlds.is_empty() {
+    return;
+  }
+  thread::spawn(move || {
+    let ops = OperationEmitter::new(&app, Some(&instance_id));
+    for (world_id, path) in worlds {
+      let size_bytes = directory_size(&path);
+      if path.exists() {
+        save_world_size_cache(&path, size_bytes);
+        ops.emit(
+          "world:size",
+          WorldSizeEvent {
+            instance_id: instance_id.clone(),
+            world_id,
+            size_bytes,
+          },
+        );
+      }
+      if let Some(jobs) = WORLD_SIZE_JOBS.get() {
+        jobs
+          .lock()
+          .unwrap_or_else(|poisoned| poisoned.into_inner())
+          .remove(&path);
+      }
+    }
+  });
 }
 
 fn load_level_dat(path: &Path) -> Result<LevelDat, String> {
@@ -133,6 +190,7 @@ fn load_level_dat(path: &Path) -> Result<LevelDat, String> {
 }
 
 fn save_level_dat(path: &Path, payload: &LevelDat) -> Result<(), String> {
+  rotate_file_backups(path, NBT_BACKUP_GENERATIONS)?;
   let file = fs::File::create(path).map_err(|err| err.to_string())?;
   let mut encoder = GzEncoder::new(file, Compression::default());
   fastnbt::to_writer(&mut encoder, payload).map_err(|err| err.to_string())?;
@@ -150,9 +208,190 @@ fn load_world_icon(world_dir: &Path) -> Result<Option<String>, String> {
   Ok(Some(format!("data:image/png;base64,{}", encoded)))
 }
 
+fn decode_rgba(bytes: &[u8]) -> Result<(Vec<u8>, u32, u32), String> {
+  let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+  decoder.set_transformations(png::Transformations::normalize_to_color8());
+  let mut reader = decoder.read_info().map_err(|err| format!("invalid icon image: {}", err))?;
+  let mut buffer = vec![0; reader.output_buffer_size()];
+  let frame = reader
+    .next_frame(&mut buffer)
+    .map_err(|err| format!("invalid icon image: {}", err))?;
+  let pixels = &buffer[..frame.buffer_size()];
+  let rgba = match frame.color_type {
+    png::ColorType::Rgba => pixels.to_vec(),
+    png::ColorType::Rgb => pixels
+      .chunks_exact(3)
+      .flat_map(|px| [px[0], px[1], px[2], 255])
+      .collect(),
+    png::ColorType::GrayscaleAlpha => pixels
+      .chunks_exact(2)
+      .flat_map(|px| [px[0], px[0], px[0], px[1]])
+      .collect(),
+    png::ColorType::Grayscale => pixels.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+    png::ColorType::Indexed => return Err("unsupported icon color type".to_string()),
+  };
+  Ok((rgba, frame.width, frame.height))
+}
+
+/// Center-crops to a square and box-filters down (or samples up) to `size` x `size`.
+fn resize_square(rgba: &[u8], width: u32, height: u32, size: u32) -> Vec<u8> {
+  let side = width.min(height) as u64;
+  let offset_x = (width as u64 - side) / 2;
+  let offset_y = (height as u64 - side) / 2;
+  let size64 = size as u64;
+  let mut output = Vec::with_capacity((size * size * 4) as usize);
+  for dy in 0..size64 {
+    let y0 = dy * side / size64;
+    let y1 = ((dy + 1) * side / size64).max(y0 + 1);
+    for dx in 0..size64 {
+      let x0 = dx * side / size64;
+      let x1 = ((dx + 1) * side / size64).max(x0 + 1);
+      // Weight colour by alpha so transparent edges do not darken the result.
+      let mut sums = [0u64; 4];
+      let mut count = 0u64;
+      for y in y0..y1 {
+        for x in x0..x1 {
+          let idx = (((offset_y + y) * width as u64 + offset_x + x) * 4) as usize;
+          let alpha = rgba[idx + 3] as u64;
+          sums[0] += rgba[idx] as u64 * alpha;
+          sums[1] += rgba[idx + 1] as u64 * alpha;
+          sums[2] += rgba[idx + 2] as u64 * alpha;
+          sums[3] += alpha;
+          count += 1;
+        }
+      }
+      let alpha_total = sums[3].max(1);
+      output.extend_from_slice(&[
+        (sums[0] / alpha_total) as u8,
+        (sums[1] / alpha_total) as u8,
+        (sums[2] / alpha_total) as u8,
+        (sums[3] / count) as u8,
+      ]);
+    }
+  }
+  output
+}
+
+/// Minecraft only shows 64x64 PNG world icons, so every upload is normalized to that.
+fn normalize_world_icon(bytes: &[u8]) -> Result<Vec<u8>, String> {
+  let (rgba, width, height) = decode_rgba(bytes)?;
+  if width == 0 || height == 0 {
+    return Err("icon image is empty".to_string());
+  }
+  let resized = resize_square(&rgba, width, height, WORLD_ICON_SIZE);
+  let mut output = Vec::new();
+  {
+    let mut encoder = png::Encoder::new(&mut output, WORLD_ICON_SIZE, WORLD_ICON_SIZE);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer.write_image_data(&resized).map_err(|err| err.to_string())?;
+    writer.finish().map_err(|err| err.to_string())?;
+  }
+  Ok(output)
+}
+
+fn read_world_entry(path: &Path) -> WorldEntry {
+  let id = path
+    .file_name()
+    .and_then(|name| name.to_str())
+    .unwrap_or("world")
+    .to_string();
+  let level_dat = path.join("level.dat");
+  let payload = load_level_dat(&level_dat).ok();
+  let display_name = payload
+    .as_ref()
+    .and_then(|payload| {
+      let name = payload.data.level_name.trim().to_string();
+      if name.is_empty() { None } else { Some(name) }
+    })
+    .unwrap_or_else(|| id.clone());
+  let game_mode = payload.as_ref().and_then(|payload| resolve_game_mode(&payload.data));
+  let icon = load_world_icon(path).ok().flatten();
+  let (size_bytes, _) = cached_world_size(path);
+  WorldEntry {
+    id,
+    name: display_name,
+    icon,
+    game_mode,
+    size_bytes,
+  }
+}
+
+/// Default game directory used by the official launcher on this platform.
+fn vanilla_minecraft_dir() -> Option<PathBuf> {
+  #[cfg(target_os = "windows")]
+  {
+    std::env::var("APPDATA")
+      .ok()
+      .map(|appdata| PathBuf::from(appdata).join(".minecraft"))
+  }
+  #[cfg(target_os = "macos")]
+  {
+    resolve_home_dir().map(|home| {
+      home
+        .join("Library")
+        .join("Application Support")
+        .join("minecraft")
+    })
+  }
+  #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+  {
+    resolve_home_dir().map(|home| home.join(".minecraft"))
+  }
+}
+
+fn resolve_vanilla_saves_dir(minecraft_dir: Option<String>) -> Result<PathBuf, String> {
+  let base = match minecraft_dir
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+  {
+    Some(value) => PathBuf::from(value),
+    None => vanilla_minecraft_dir().ok_or_else(|| "could not locate .minecraft".to_string())?,
+  };
+  // Accept either the game directory or its saves folder.
+  let saves = if base.file_name().map(|name| name == "saves").unwrap_or(false) {
+    base
+  } else {
+    base.join("saves")
+  };
+  if !saves.is_dir() {
+    return Err(format!("no saves folder found at {}", saves.display()));
+  }
+  Ok(saves)
+}
+
+/// Picks a folder name under `saves_dir` that does not exist yet: `World`, `World (2)`, ...
+fn unique_world_dir(saves_dir: &Path, world_id: &str) -> PathBuf {
+  let base = sanitize_path_component(world_id, "world");
+  let mut candidate = saves_dir.join(&base);
+  let mut suffix = 2;
+  while candidate.exists() {
+    candidate = saves_dir.join(format!("{} ({})", base, suffix));
+    suffix += 1;
+  }
+  candidate
+}
+
+fn copy_world_tree(source: &Path, target: &Path) -> Result<(), String> {
+  fs::create_dir_all(target).map_err(|err| err.to_string())?;
+  for entry in fs::read_dir(source).map_err(|err| err.to_string())? {
+    let entry = entry.map_err(|err| err.to_string())?;
+    let path = entry.path();
+    let dest = target.join(entry.file_name());
+    if path.is_dir() {
+      copy_world_tree(&path, &dest)?;
+    } else if entry.file_name() != "session.lock" {
+      fs::copy(&path, &dest).map_err(|err| err.to_string())?;
+    }
+  }
+  Ok(())
+}
+
 #[tauri::command]
 pub(crate) fn list_instance_worlds(
   instance_id: String,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<Vec<WorldEntry>, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
@@ -162,37 +401,20 @@ pub(crate) fn list_instance_worlds(
   }
   let entries = fs::read_dir(&worlds_dir).map_err(|err| err.to_string())?;
   let mut results = Vec::new();
+  let mut stale = Vec::new();
   for entry in entries.flatten() {
     let path = entry.path();
     if !path.is_dir() {
       continue;
     }
-    let id = path
-      .file_name()
-      .and_then(|name| name.to_str())
-      .unwrap_or("world")
-      .to_string();
-    let level_dat = path.join("level.dat");
-    let payload = load_level_dat(&level_dat).ok();
-    let display_name = payload
-      .as_ref()
-      .and_then(|payload| {
-        let name = payload.data.level_name.trim().to_string();
-        if name.is_empty() { None } else { Some(name) }
-      })
-      .unwrap_or_else(|| id.clone());
-    let game_mode = payload.as_ref().and_then(|payload| resolve_game_mode(&payload.data));
-    let icon = load_world_icon(&path).ok().flatten();
-    let size_bytes = Some(resolve_world_size(&path));
-    results.push(WorldEntry {
-      id,
-      name: display_name,
-      icon,
-      game_mode,
-      size_bytes,
-    });
+    let world = read_world_entry(&path);
+    if cached_world_size(&path).1 {
+      stale.push((world.id.clone(), path));
+    }
+    results.push(world);
   }
   results.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+  spawn_world_size_refresh(app, instance_id, stale);
   Ok(results)
 }
 
@@ -202,6 +424,7 @@ pub(crate) fn update_instance_world(
   world_id: String,
   name: String,
   icon: Option<String>,
+  app: tauri::AppHandle,
   state: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
@@ -227,12 +450,71 @@ pub(crate) fn update_instance_world(
       let bytes = base64::engine::general_purpose::STANDARD
         .decode(raw)
         .map_err(|_| "invalid icon data".to_string())?;
-      fs::write(&icon_path, bytes).map_err(|err| err.to_string())?;
+      let normalized = normalize_world_icon(&bytes)?;
+      fs::write(&icon_path, normalized).map_err(|err| err.to_string())?;
     }
     None => {
       let _ = fs::remove_file(&icon_path);
     }
   }
 
+  emit_instance_updated(&app, &instance_id, "worlds");
   Ok(())
 }
+
+#[tauri::command]
+pub(crate) fn list_vanilla_worlds(
+  minecraft_dir: Option<String>,
+) -> Result<Vec<WorldEntry>, String> {
+  let saves_dir = resolve_vanilla_saves_dir(minecraft_dir)?;
+  let entries = fs::read_dir(&saves_dir).map_err(|err| err.to_string())?;
+  let mut results = entries
+    .flatten()
+    .map(|entry| entry.path())
+    .filter(|path| path.join("level.dat").is_file())
+    .map(|path| read_world_entry(&path))
+    .collect::<Vec<_>>();
+  results.sort_by_key(|world| world.name.to_lowercase());
+  Ok(results)
+}
+
+/// Copies worlds from the official launcher's saves folder into an instance.
+/// Returns the folder names the worlds received inside the instance.
+#[tauri::command]
+pub(crate) async fn import_vanilla_worlds(
+  instance_id: String,
+  world_ids: Vec<String>,
+  minecraft_dir: Option<String>,
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<String>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let source_dir = resolve_vanilla_saves_dir(minecraft_dir)?;
+  let imported = tauri::async_runtime::spawn_blocking(move || {
+    let saves_dir = instance_dir.join("saves");
+    fs::create_dir_all(&saves_dir).map_err(|err| err.to_string())?;
+    let mut imported = Vec::new();
+    for world_id in world_ids {
+      let source = source_dir.join(&world_id);
+      if world_id.contains(['/', '\\']) || world_id == ".." || !source.join("level.dat").is_file() {
+        return Err(format!("world '{}' not found in vanilla saves", world_id));
+      }
+      let target = unique_world_dir(&saves_dir, &world_id);
+      if let Err(err) = copy_world_tree(&source, &target) {
+        let _ = fs::remove_dir_all(&target);
+        return Err(err);
+      }
+      imported.push(
+        target
+          .file_name()
+          .map(|name| name.to_string_lossy().to_string())
+          .unwrap_or(world_id),
+      );
+    }
+    Ok(imported)
+  })
+  .await
+  .map_err(|_| "world import task failed".to_string())??;
+  emit_instance_updated(&app, &instance_id, "worlds");
+  Ok(imported)
+}
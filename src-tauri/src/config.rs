@@ -1,6 +1,6 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{
-  collections::HashSet,
+  collections::{HashMap, HashSet},
   fs,
   io,
   path::{Path, PathBuf},
@@ -34,6 +34,78 @@ pub struct Account {
   pub uuid: Option<String>,
   #[serde(default)]
   pub owns_minecraft: Option<bool>,
+  #[serde(default)]
+  pub gamertag: Option<String>,
+}
+
+/// How long before expiry a Microsoft session is refreshed, covering a slow install or launch.
+pub(crate) const SESSION_REFRESH_MARGIN_SECS: u64 = 300;
+
+impl Account {
+  pub(crate) fn session_expiring(&self, now: u64) -> bool {
+    self.access_token.is_none()
+      || self
+        .expires_at
+        .map(|expires_at| expires_at <= now.saturating_add(SESSION_REFRESH_MARGIN_SECS))
+        .unwrap_or(true)
+  }
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DDTHH:MM:SSZ`, the form stored in `Account::last_used`.
+pub(crate) fn format_utc_timestamp(unix: u64) -> String {
+  let days = (unix / 86_400) as i64;
+  let secs = unix % 86_400;
+  // Civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z - era * 146_097;
+  let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  format!(
+    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+    year,
+    month,
+    day,
+    secs / 3_600,
+    secs / 60 % 60,
+    secs % 60
+  )
+}
+
+/// Inverse of `format_utc_timestamp`; other formats yield `None`.
+pub(crate) fn parse_utc_timestamp(value: &str) -> Option<u64> {
+  let value = value.trim().strip_suffix('Z')?;
+  let (date, time) = value.split_once('T')?;
+  let mut date_parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+  let (year, month, day) = (date_parts.next()??, date_parts.next()??, date_parts.next()??);
+  let mut time_parts = time.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+  let (hour, minute, second) = (time_parts.next()??, time_parts.next()??, time_parts.next()??);
+  let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+  let month_days = match month {
+    2 if leap => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    1..=12 => 31,
+    _ => return None,
+  };
+  if !(1..=month_days).contains(&day) {
+    return None;
+  }
+  if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..60).contains(&second) {
+    return None;
+  }
+  let y = if month <= 2 { year - 1 } else { year };
+  let era = y.div_euclid(400);
+  let yoe = y - era * 400;
+  let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  let days = era * 146_097 + doe - 719_468;
+  let unix = days * 86_400 + hour * 3_600 + minute * 60 + second;
+  u64::try_from(unix).ok()
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq)]
@@ -54,6 +126,8 @@ pub struct InstanceRoot {
 pub struct Instance {
   pub id: String,
   pub name: String,
+  #[serde(default)]
+  pub kind: InstanceKind,
   pub version: String,
   pub loader: Loader,
   #[serde(default)]
@@ -71,8 +145,30 @@ pub struct Instance {
   pub java_max_ram_mb: Option<u32>,
   #[serde(default, skip_serializing_if = "Option::is_none")]
   pub java_max_ram_gb: Option<u8>,
+  #[serde(default, deserialize_with = "deserialize_jvm_args")]
+  pub jvm_args: Option<Vec<String>>,
+  #[serde(default)]
+  pub auto_update_mods: bool,
+  #[serde(default)]
+  pub custom_version: Option<String>,
+  #[serde(default)]
+  pub main_class_override: Option<String>,
+  #[serde(default)]
+  pub client_jar_override: Option<String>,
+  #[serde(default)]
+  pub release_channel: Option<ReleaseChannel>,
+  #[serde(default)]
+  pub window_title: Option<String>,
+  #[serde(default)]
+  pub brand_name: Option<String>,
+  #[serde(default)]
+  pub brand_version: Option<String>,
+  /// Read back from the manifest; setting it here does not change the instance.
+  #[serde(default)]
+  pub created_at_unix: Option<u64>,
+  /// Read back from the manifest; written only by `install_optifine`.
   #[serde(default)]
-  pub jvm_args: Option<String>,
+  pub optifine: Option<OptiFineInstall>,
 }
 
 pub const INSTANCE_CONFIG_FILE: &str = "instance.json";
@@ -81,6 +177,8 @@ pub const INSTANCE_CONFIG_FILE: &str = "instance.json";
 pub struct InstanceManifest {
   pub id: String,
   pub name: String,
+  #[serde(default)]
+  pub kind: InstanceKind,
   pub version: String,
   pub loader: Loader,
   #[serde(default)]
@@ -107,8 +205,74 @@ pub struct InstanceManifest {
   pub java_max_ram_mb: Option<u32>,
   #[serde(default, skip_serializing_if = "Option::is_none")]
   pub java_max_ram_gb: Option<u8>,
+  #[serde(default, deserialize_with = "deserialize_jvm_args")]
+  pub jvm_args: Option<Vec<String>>,
+  #[serde(default)]
+  pub auto_update_mods: bool,
+  #[serde(default)]
+  pub custom_version: Option<String>,
   #[serde(default)]
-  pub jvm_args: Option<String>,
+  pub main_class_override: Option<String>,
+  #[serde(default)]
+  pub client_jar_override: Option<String>,
+  #[serde(default)]
+  pub release_channel: Option<ReleaseChannel>,
+  /// Branding overrides passed to the game at launch.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub window_title: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub brand_name: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub brand_version: Option<String>,
+  #[serde(default)]
+  pub install_summary: Option<InstallSummary>,
+  /// Pack description and homepage carried over from the import source.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub homepage: Option<String>,
+  /// Remembered Modrinth search filters, keyed by project type.
+  #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+  pub modrinth_search_defaults: HashMap<String, ModrinthSearchDefaults>,
+  /// OptiFine build added on top of the loader, restored after core reinstalls.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub optifine: Option<OptiFineInstall>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OptiFineInstall {
+  pub game_version: String,
+  pub edition: String,
+  pub patch: String,
+  pub file_name: String,
+  /// Checksum of the user-supplied jar, verified before it is run again.
+  #[serde(default)]
+  pub sha256: Option<String>,
+  /// Generated version id for vanilla instances; `None` when installed as a mod.
+  #[serde(default)]
+  pub version_id: Option<String>,
+}
+
+/// Filters applied to an instance's Modrinth searches when the caller leaves them unset.
+#[derive(Clone, Serialize, Deserialize, Default)]
+pub struct ModrinthSearchDefaults {
+  #[serde(default)]
+  pub categories: Vec<String>,
+  #[serde(default)]
+  pub sort: Option<String>,
+  #[serde(default)]
+  pub channel: Option<ReleaseChannel>,
+}
+
+/// What the most recent install run did, kept in the manifest for support requests.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InstallSummary {
+  pub finished_at_unix: u64,
+  pub duration_ms: u64,
+  pub files_downloaded: u64,
+  pub bytes_downloaded: u64,
+  #[serde(default)]
+  pub warnings: Vec<String>,
 }
 
 impl InstanceManifest {
@@ -116,12 +280,13 @@ impl InstanceManifest {
     Self {
       id: instance.id.clone(),
       name: instance.name.clone(),
+      kind: instance.kind.clone(),
       version: instance.version.clone(),
       loader: instance.loader.clone(),
       loader_version: instance.loader_version.clone(),
       show_snapshots: instance.show_snapshots,
       pinned: instance.pinned,
-      created_at_unix,
+      created_at_unix: created_at_unix.or(instance.created_at_unix),
       directory: Some(instance.directory.clone()),
       installed_version: None,
       installed_loader: None,
@@ -131,11 +296,26 @@ impl InstanceManifest {
       java_max_ram_mb: instance.java_max_ram_mb,
       java_max_ram_gb: None,
       jvm_args: instance.jvm_args.clone(),
+      auto_update_mods: instance.auto_update_mods,
+      custom_version: instance.custom_version.clone(),
+      main_class_override: instance.main_class_override.clone(),
+      client_jar_override: instance.client_jar_override.clone(),
+      release_channel: instance.release_channel,
+      window_title: instance.window_title.clone(),
+      brand_name: instance.brand_name.clone(),
+      brand_version: instance.brand_version.clone(),
+      install_summary: None,
+      description: None,
+      homepage: None,
+      modrinth_search_defaults: HashMap::new(),
+      optifine: instance.optifine.clone(),
     }
   }
 
+  /// The install summary, description/homepage and Modrinth search defaults stay in the
+  /// manifest: they are read on demand by their own commands, and `Instance` is re-sent
+  /// with every config change.
   pub fn into_instance(self, root_id: Option<String>, directory: String) -> Instance {
-    let _ = self.directory;
     let min_mb = self
       .java_min_ram_mb
       .or(self.java_min_ram_gb.map(|gb| gb as u32 * 1024));
@@ -145,6 +325,7 @@ impl InstanceManifest {
     Instance {
       id: self.id,
       name: self.name,
+      kind: self.kind,
       version: self.version,
       loader: self.loader,
       loader_version: self.loader_version,
@@ -157,18 +338,65 @@ impl InstanceManifest {
       java_max_ram_mb: max_mb,
       java_max_ram_gb: None,
       jvm_args: self.jvm_args,
+      auto_update_mods: self.auto_update_mods,
+      custom_version: self.custom_version,
+      main_class_override: self.main_class_override,
+      client_jar_override: self.client_jar_override,
+      release_channel: self.release_channel,
+      window_title: self.window_title,
+      brand_name: self.brand_name,
+      brand_version: self.brand_version,
+      created_at_unix: self.created_at_unix,
+      optifine: self.optifine,
     }
   }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceKind {
+  #[default]
+  Client,
+  Server,
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Loader {
   Vanilla,
   Fabric,
+  Quilt,
   Forge,
   #[serde(rename = "neoforge", alias = "neo_forge")]
   NeoForge,
+  Paper,
+  Folia,
+  Purpur,
+}
+
+impl Loader {
+  pub fn server_only(&self) -> bool {
+    matches!(self, Loader::Paper | Loader::Folia | Loader::Purpur)
+  }
+}
+
+/// Least stable Modrinth version type an instance accepts when picking content versions.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseChannel {
+  Release,
+  Beta,
+  Alpha,
+}
+
+impl ReleaseChannel {
+  pub fn allows(&self, version_type: &str) -> bool {
+    match self {
+      ReleaseChannel::Release => version_type == "release",
+      ReleaseChannel::Beta => version_type == "release" || version_type == "beta",
+      ReleaseChannel::Alpha => true,
+    }
+  }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -193,10 +421,89 @@ pub struct Settings {
   pub performance_mangohud: bool,
   #[serde(default = "default_performance_zink")]
   pub performance_zink: bool,
+  #[serde(default = "default_performance_discrete_gpu")]
+  pub performance_discrete_gpu: bool,
+  #[serde(default = "default_network_retry_delays_ms")]
+  pub network_retry_delays_ms: Vec<u64>,
+  #[serde(default = "default_network_connect_timeout_secs")]
+  pub network_connect_timeout_secs: u64,
+  #[serde(default = "default_network_request_timeout_secs")]
+  pub network_request_timeout_secs: u64,
+  #[serde(default)]
+  pub polite_downloads: bool,
+  /// Write JVM garbage collection logs to `logs/gc.log` for stutter investigations.
+  #[serde(default)]
+  pub gc_logging: bool,
+  /// `host:port` HTTP proxy for pre-1.6 clients, whose sound and skin endpoints are gone.
+  #[serde(default)]
+  pub legacy_resource_proxy: String,
+  #[serde(default = "default_progress_events_per_second")]
+  pub progress_events_per_second: u32,
+  #[serde(default = "default_stop_grace_period_secs")]
+  pub stop_grace_period_secs: u64,
   #[serde(default = "default_microsoft_client_id")]
   pub microsoft_client_id: String,
   #[serde(default)]
   pub skipped_release_tag: Option<String>,
+  #[serde(default)]
+  pub content_blacklist: Vec<String>,
+  #[serde(default)]
+  pub sync_directory: Option<String>,
+  #[serde(default)]
+  pub scheduled_tasks: ScheduledTaskSettings,
+  #[serde(default)]
+  pub new_instance_defaults: NewInstanceDefaults,
+}
+
+/// Values `create_instance` gives new instances while `apply_to_new_instances` is on.
+#[derive(Clone, Serialize, Deserialize, Default)]
+pub struct NewInstanceDefaults {
+  #[serde(default)]
+  pub java_min_ram_mb: Option<u32>,
+  #[serde(default)]
+  pub java_max_ram_mb: Option<u32>,
+  #[serde(default, deserialize_with = "deserialize_jvm_args")]
+  pub jvm_args: Option<Vec<String>>,
+  #[serde(default)]
+  pub show_snapshots: bool,
+  #[serde(default)]
+  pub root_id: Option<String>,
+  /// Install Fabric API into new Fabric client instances.
+  #[serde(default)]
+  pub install_fabric_api: bool,
+  /// Seeded into `options.txt` of new client instances; unset values keep the game default.
+  #[serde(default)]
+  pub language: Option<String>,
+  #[serde(default)]
+  pub gui_scale: Option<u32>,
+  #[serde(default)]
+  pub max_framerate: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScheduledTaskSettings {
+  #[serde(default = "default_scheduled_task_enabled")]
+  pub check_mod_updates: bool,
+  #[serde(default)]
+  pub prune_logs: bool,
+  #[serde(default)]
+  pub backup_worlds: bool,
+  #[serde(default = "default_scheduled_task_enabled")]
+  pub refresh_tokens: bool,
+  #[serde(default = "default_log_retention_days")]
+  pub log_retention_days: u32,
+}
+
+impl Default for ScheduledTaskSettings {
+  fn default() -> Self {
+    Self {
+      check_mod_updates: default_scheduled_task_enabled(),
+      prune_logs: false,
+      backup_worlds: false,
+      refresh_tokens: default_scheduled_task_enabled(),
+      log_retention_days: default_log_retention_days(),
+    }
+  }
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq)]
@@ -282,48 +589,40 @@ impl ConfigStore {
       store.config
     };
 
-    ensure_instance_roots(&config);
-    normalize_microsoft_client_id(&mut config);
-    apply_env_overrides(&mut config);
-    migrate_instance_manifests(&config);
-    config.instances = load_instances_from_roots(&config);
-    normalize_default_accounts(&mut config);
-    normalize_reference_instance(&mut config);
-    normalize_ram_settings(&mut config);
-    normalize_java_runtimes(&mut config);
+    normalize_config(&mut config);
 
     Ok(Self { path, config })
   }
 
   pub fn get(&self) -> AppConfig {
     let mut config = self.config.clone();
-    ensure_instance_roots(&config);
-    normalize_microsoft_client_id(&mut config);
-    apply_env_overrides(&mut config);
-    migrate_instance_manifests(&config);
-    config.instances = load_instances_from_roots(&config);
-    normalize_default_accounts(&mut config);
-    normalize_reference_instance(&mut config);
-    normalize_ram_settings(&mut config);
-    normalize_java_runtimes(&mut config);
+    normalize_config(&mut config);
     config
   }
 
-  pub fn set(&mut self, config: AppConfig) -> io::Result<()> {
-    let mut config = config;
-    ensure_instance_roots(&config);
-    normalize_microsoft_client_id(&mut config);
-    apply_env_overrides(&mut config);
-    migrate_instance_manifests(&config);
-    config.instances = load_instances_from_roots(&config);
-    normalize_default_accounts(&mut config);
-    normalize_reference_instance(&mut config);
-    normalize_ram_settings(&mut config);
-    normalize_java_runtimes(&mut config);
+  pub fn set(&mut self, mut config: AppConfig) -> io::Result<()> {
+    normalize_config(&mut config);
     self.config = config;
     self.persist()
   }
 
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn reload_from_disk(&mut self) -> io::Result<bool> {
+    let data = fs::read_to_string(&self.path)?;
+    let disk: serde_json::Value = serde_json::from_str(&data).map_err(map_json_error)?;
+    let current = serde_json::to_value(&self.config).map_err(map_json_error)?;
+    if disk == current {
+      return Ok(false);
+    }
+    let mut config: AppConfig = serde_json::from_value(disk).map_err(map_json_error)?;
+    normalize_config(&mut config);
+    self.config = config;
+    Ok(true)
+  }
+
   fn persist(&self) -> io::Result<()> {
     let payload = serde_json::to_vec_pretty(&self.config).map_err(map_json_error)?;
     fs::write(&self.path, payload)
@@ -397,13 +696,87 @@ impl AppConfig {
         performance_gamemode: default_performance_gamemode(),
         performance_mangohud: default_performance_mangohud(),
         performance_zink: default_performance_zink(),
+        performance_discrete_gpu: default_performance_discrete_gpu(),
+        network_retry_delays_ms: default_network_retry_delays_ms(),
+        network_connect_timeout_secs: default_network_connect_timeout_secs(),
+        network_request_timeout_secs: default_network_request_timeout_secs(),
+        polite_downloads: false,
+        gc_logging: false,
+        legacy_resource_proxy: String::new(),
+        progress_events_per_second: default_progress_events_per_second(),
+        stop_grace_period_secs: default_stop_grace_period_secs(),
         microsoft_client_id: default_microsoft_client_id(),
         skipped_release_tag: None,
+        content_blacklist: Vec::new(),
+        sync_directory: None,
+        scheduled_tasks: ScheduledTaskSettings::default(),
+        new_instance_defaults: NewInstanceDefaults::default(),
       },
     }
   }
 }
 
+/// Splits a JVM argument string on whitespace, keeping quoted runs together.
+/// Backslashes only escape inside double quotes so Windows paths stay intact.
+pub(crate) fn split_jvm_args(input: &str) -> Vec<String> {
+  let mut args = Vec::new();
+  let mut current = String::new();
+  let mut in_arg = false;
+  let mut quote: Option<char> = None;
+  let mut chars = input.chars();
+  while let Some(ch) = chars.next() {
+    match quote {
+      Some(open) if ch == open => quote = None,
+      Some('"') if ch == '\\' => match chars.next() {
+        Some(next @ ('"' | '\\')) => current.push(next),
+        Some(next) => {
+          current.push(ch);
+          current.push(next);
+        }
+        None => current.push(ch),
+      },
+      Some(_) => current.push(ch),
+      None if ch == '"' || ch == '\'' => {
+        quote = Some(ch);
+        in_arg = true;
+      }
+      None if ch.is_whitespace() => {
+        if in_arg {
+          args.push(std::mem::take(&mut current));
+          in_arg = false;
+        }
+      }
+      None => {
+        current.push(ch);
+        in_arg = true;
+      }
+    }
+  }
+  if in_arg {
+    args.push(current);
+  }
+  args
+}
+
+/// Accepts both the list form and the legacy whitespace-separated string.
+pub(crate) fn deserialize_jvm_args<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum JvmArgs {
+    List(Vec<String>),
+    Legacy(String),
+  }
+  let args = match Option::<JvmArgs>::deserialize(deserializer)? {
+    Some(JvmArgs::List(args)) => args,
+    Some(JvmArgs::Legacy(value)) => split_jvm_args(&value),
+    None => return Ok(None),
+  };
+  Ok(Some(args).filter(|args| !args.is_empty()))
+}
+
 fn default_theme() -> String {
   "dark".to_string()
 }
@@ -436,6 +809,30 @@ fn default_performance_zink() -> bool {
   false
 }
 
+fn default_performance_discrete_gpu() -> bool {
+  false
+}
+
+fn default_network_retry_delays_ms() -> Vec<u64> {
+  vec![200, 500, 1000, 2000, 4000]
+}
+
+fn default_network_connect_timeout_secs() -> u64 {
+  10
+}
+
+fn default_network_request_timeout_secs() -> u64 {
+  120
+}
+
+fn default_progress_events_per_second() -> u32 {
+  20
+}
+
+fn default_stop_grace_period_secs() -> u64 {
+  15
+}
+
 fn default_min_ram_mb() -> u32 {
   512
 }
@@ -444,6 +841,14 @@ fn default_max_ram_mb() -> u32 {
   1024
 }
 
+fn default_scheduled_task_enabled() -> bool {
+  true
+}
+
+fn default_log_retention_days() -> u32 {
+  14
+}
+
 fn default_microsoft_client_id() -> String {
   "f6a09c4f-4f6d-4aad-972e-e770de1ef9c8".to_string()
 }
@@ -452,6 +857,24 @@ fn default_pack_sync_options_txt() -> bool {
   true
 }
 
+/// Shared by load, get, set and reload so every path sees the same normalized config.
+fn normalize_config(config: &mut AppConfig) {
+  ensure_instance_roots(config);
+  normalize_microsoft_client_id(config);
+  apply_env_overrides(config);
+  migrate_instance_manifests(config);
+  config.instances = load_instances_from_roots(config);
+  normalize_default_accounts(config);
+  normalize_reference_instance(config);
+  normalize_ram_settings(config);
+  normalize_java_runtimes(config);
+  normalize_network_settings(config);
+  normalize_progress_settings(config);
+  normalize_stop_settings(config);
+  normalize_content_blacklist(config);
+  normalize_sync_directory(config);
+}
+
 fn normalize_microsoft_client_id(config: &mut AppConfig) {
   let trimmed = config.settings.microsoft_client_id.trim();
   if trimmed.is_empty() || trimmed == "496760c7-41f3-40b4-9cdc-c553219b3fbc" {
@@ -493,7 +916,7 @@ fn ensure_instance_roots(config: &AppConfig) {
   }
 }
 
-fn load_instances_from_roots(config: &AppConfig) -> Vec<Instance> {
+pub(crate) fn load_instances_from_roots(config: &AppConfig) -> Vec<Instance> {
   let mut instances = Vec::new();
 
   for root in &config.instance_roots {
@@ -596,6 +1019,48 @@ fn normalize_ram_settings(config: &mut AppConfig) {
   }
 }
 
+fn normalize_network_settings(config: &mut AppConfig) {
+  let settings = &mut config.settings;
+  settings.network_retry_delays_ms.truncate(10);
+  for delay in settings.network_retry_delays_ms.iter_mut() {
+    *delay = (*delay).min(60_000);
+  }
+  if settings.network_retry_delays_ms.is_empty() {
+    settings.network_retry_delays_ms = default_network_retry_delays_ms();
+  }
+  settings.network_connect_timeout_secs = settings.network_connect_timeout_secs.clamp(1, 300);
+  settings.network_request_timeout_secs = settings.network_request_timeout_secs.clamp(10, 3600);
+}
+
+fn normalize_progress_settings(config: &mut AppConfig) {
+  let settings = &mut config.settings;
+  settings.progress_events_per_second = settings.progress_events_per_second.clamp(1, 240);
+}
+
+fn normalize_stop_settings(config: &mut AppConfig) {
+  let settings = &mut config.settings;
+  settings.stop_grace_period_secs = settings.stop_grace_period_secs.clamp(1, 600);
+}
+
+fn normalize_content_blacklist(config: &mut AppConfig) {
+  let mut seen = HashSet::new();
+  let entries = std::mem::take(&mut config.settings.content_blacklist);
+  config.settings.content_blacklist = entries
+    .into_iter()
+    .map(|entry| entry.trim().to_string())
+    .filter(|entry| !entry.is_empty() && seen.insert(entry.to_ascii_lowercase()))
+    .collect();
+}
+
+fn normalize_sync_directory(config: &mut AppConfig) {
+  config.settings.sync_directory = config
+    .settings
+    .sync_directory
+    .take()
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+}
+
 fn normalize_java_runtimes(config: &mut AppConfig) {
   let mut seen = HashSet::new();
   config.settings.java.runtimes.retain(|runtime| {
@@ -655,7 +1120,7 @@ fn slugify_id(label: &str) -> String {
   result.trim_matches('-').to_string()
 }
 
-fn resolve_home_dir() -> Option<PathBuf> {
+pub(crate) fn resolve_home_dir() -> Option<PathBuf> {
   if let Ok(home) = std::env::var("HOME") {
     return Some(PathBuf::from(home));
   }
@@ -672,3 +1137,93 @@ fn path_to_string(path: &PathBuf) -> String {
 fn map_json_error(error: serde_json::Error) -> io::Error {
   io::Error::new(io::ErrorKind::Other, error)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{deserialize_jvm_args, format_utc_timestamp, parse_utc_timestamp, split_jvm_args};
+  use serde::Deserialize;
+
+  #[derive(Deserialize)]
+  struct Args {
+    #[serde(default, deserialize_with = "deserialize_jvm_args")]
+    jvm_args: Option<Vec<String>>,
+  }
+
+  fn parse(json: &str) -> Option<Vec<String>> {
+    serde_json::from_str::<Args>(json).unwrap().jvm_args
+  }
+
+  #[test]
+  fn splits_on_unquoted_whitespace() {
+    assert_eq!(split_jvm_args("  -Xmx4G\t-XX:+UseG1GC \n"), ["-Xmx4G", "-XX:+UseG1GC"]);
+    assert!(split_jvm_args("   ").is_empty());
+  }
+
+  #[test]
+  fn quotes_keep_spaces_together() {
+    assert_eq!(
+      split_jvm_args(r#"-Dpath="C:\Program Files\Java" -Dname='My Pack'"#),
+      [r"-Dpath=C:\Program Files\Java", "-Dname=My Pack"]
+    );
+    assert_eq!(split_jvm_args(r#""" -Xss2M"#), ["", "-Xss2M"]);
+  }
+
+  #[test]
+  fn escaped_quotes_stay_literal() {
+    assert_eq!(split_jvm_args(r#""-Dmotd=say \"hi\"""#), [r#"-Dmotd=say "hi""#]);
+    assert_eq!(split_jvm_args(r#"'-Dtext=a\"b'"#), [r#"-Dtext=a\"b"#]);
+  }
+
+  #[test]
+  fn accepts_list_and_legacy_string() {
+    assert_eq!(parse(r#"{"jvm_args": ["-Xmx4G", "-Da=b c"]}"#).unwrap(), ["-Xmx4G", "-Da=b c"]);
+    assert_eq!(parse(r#"{"jvm_args": "-Xmx4G \"-Da=b c\""}"#).unwrap(), ["-Xmx4G", "-Da=b c"]);
+    assert_eq!(parse(r#"{"jvm_args": "   "}"#), None);
+    assert_eq!(parse(r#"{"jvm_args": null}"#), None);
+    assert_eq!(parse("{}"), None);
+  }
+
+  #[test]
+  fn utc_timestamps_format_known_dates() {
+    assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_utc_timestamp(1_700_000_000), "2023-11-14T22:13:20Z");
+    assert_eq!(format_utc_timestamp(1_709_251_199), "2024-02-29T23:59:59Z");
+    assert_eq!(format_utc_timestamp(4_107_542_400), "2100-03-01T00:00:00Z");
+  }
+
+  #[test]
+  fn utc_timestamps_round_trip() {
+    for unix in [0, 59, 86_399, 86_400, 951_782_400, 1_709_251_199, 1_709_251_200, 4_102_444_800] {
+      assert_eq!(parse_utc_timestamp(&format_utc_timestamp(unix)), Some(unix));
+    }
+    for unix in (0..5_000_000_000u64).step_by(7_919_993) {
+      assert_eq!(parse_utc_timestamp(&format_utc_timestamp(unix)), Some(unix));
+    }
+  }
+
+  #[test]
+  fn utc_timestamps_reject_other_formats() {
+    assert_eq!(parse_utc_timestamp(" 2023-11-14T22:13:20Z "), Some(1_700_000_000));
+    assert_eq!(parse_utc_timestamp("2023-11-14T22:13:20"), None);
+    assert_eq!(parse_utc_timestamp("2023-11-14 22:13:20Z"), None);
+    assert_eq!(parse_utc_timestamp("2023-13-01T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2023-11-14T24:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2023-11-14T22:60:00Z"), None);
+    assert_eq!(parse_utc_timestamp("1969-12-31T23:59:59Z"), None);
+    assert_eq!(parse_utc_timestamp("soon"), None);
+  }
+
+  #[test]
+  fn utc_timestamps_reject_impossible_days() {
+    assert_eq!(parse_utc_timestamp("2024-02-29T00:00:00Z"), Some(1_709_164_800));
+    assert_eq!(parse_utc_timestamp("2000-02-29T00:00:00Z"), Some(951_782_400));
+    assert_eq!(parse_utc_timestamp("2024-02-30T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2024-02-31T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2023-02-29T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2100-02-29T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2024-04-31T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2024-11-31T00:00:00Z"), None);
+    assert_eq!(parse_utc_timestamp("2024-12-31T00:00:00Z"), Some(1_735_603_200));
+    assert_eq!(parse_utc_timestamp("2024-01-00T00:00:00Z"), None);
+  }
+}
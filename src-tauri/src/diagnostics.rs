@@ -1,15 +1,31 @@
-use crate::config::{AppConfig, Instance, InstanceManifest, Loader, INSTANCE_CONFIG_FILE};
+use crate::config::{
+  AccountKind, AppConfig, InstallSummary, Instance, InstanceKind, InstanceManifest, Loader,
+  INSTANCE_CONFIG_FILE,
+};
 use crate::java::{
-  detect_java_version, discover_java_runtimes, resolve_java_runtime, ResolvedJavaRuntime,
+  detect_java_is_32bit, detect_java_version, discover_java_runtimes, java_runs_under_rosetta,
+  resolve_java_runtime, ResolvedJavaRuntime,
 };
+use sha2::{Digest, Sha256};
 use std::{
   collections::{BTreeMap, BTreeSet, HashMap},
   fs,
-  io::Read,
+  io::{self, Read},
   path::{Path, PathBuf},
   time::{SystemTime, UNIX_EPOCH},
 };
-use zip::ZipArchive;
+use sysinfo::System;
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+
+const JVM_32BIT_HEAP_LIMIT_MB: u32 = 1536;
+const MODS_SNAPSHOT_MANIFEST: &str = "manifest.json";
+const MODS_SNAPSHOT_ARCHIVE: &str = "mods.zip";
+const MODS_SNAPSHOT_INDEX: &str = "modrinth.json";
+const WORLD_BACKUPS_KEEP: usize = 5;
+const LOG_DIRS: &[&str] = &["logs", "crash-reports"];
+const DOWNLOAD_DIRS: &[&str] = &["libraries", "assets", "versions"];
+pub(crate) const GC_LOG_FILE: &str = "gc.log";
+const GC_LONG_PAUSE_MS: f64 = 50.0;
 
 #[derive(Clone, serde::Serialize)]
 pub(crate) struct InstanceCheck {
@@ -51,6 +67,39 @@ pub(crate) struct RepairResult {
   pub summary: String,
 }
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ModsSnapshotFile {
+  pub name: String,
+  pub size: u64,
+  pub sha256: String,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ModsSnapshot {
+  pub id: String,
+  pub created_at_unix: u64,
+  pub reason: Option<String>,
+  pub archived: bool,
+  pub files: Vec<ModsSnapshotFile>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct ModsRestoreResult {
+  pub restored: Vec<String>,
+  pub stashed: Vec<String>,
+  pub stash_dir: Option<String>,
+  pub missing: Vec<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct InstallRetryResult {
+  pub success: bool,
+  pub failure_kind: Option<String>,
+  pub retryable: bool,
+  pub error: Option<String>,
+  pub summary: Option<InstallSummary>,
+}
+
 #[derive(Clone, serde::Serialize)]
 pub(crate) struct InstancePreflightReport {
   pub ready: bool,
@@ -62,6 +111,23 @@ pub(crate) struct InstancePreflightReport {
   pub latest_log_excerpt: Option<String>,
 }
 
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct ValidationItem {
+  pub id: String,
+  pub label: String,
+  pub status: String,
+  pub summary: String,
+  pub details: Vec<String>,
+  pub suggested_fix: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct InstanceValidationReport {
+  pub instance_id: String,
+  pub ready: bool,
+  pub items: Vec<ValidationItem>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct SnapshotMetadata {
   id: String,
@@ -76,9 +142,120 @@ struct ModInspection {
   name: Option<String>,
   version: Option<String>,
   dependencies: Vec<String>,
+  version_ranges: Vec<(String, String)>,
   ecosystems: BTreeSet<String>,
 }
 
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct GcLogSummary {
+  pub pause_count: u32,
+  pub full_gc_count: u32,
+  pub total_pause_ms: f64,
+  pub average_pause_ms: f64,
+  pub p95_pause_ms: f64,
+  pub max_pause_ms: f64,
+  pub long_pause_count: u32,
+  pub heap_capacity_mb: Option<u64>,
+  pub peak_heap_after_gc_mb: Option<u64>,
+  pub recommendation: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct ModGraphNode {
+  pub id: String,
+  pub name: Option<String>,
+  pub file_name: Option<String>,
+  pub enabled: bool,
+  pub missing: bool,
+  pub modrinth_project_id: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct ModGraphEdge {
+  pub from: String,
+  pub to: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct ModDependencyGraph {
+  pub nodes: Vec<ModGraphNode>,
+  pub edges: Vec<ModGraphEdge>,
+}
+
+pub(crate) fn validate_ram_settings(
+  min_ram_mb: u32,
+  max_ram_mb: u32,
+  java_cmd: Option<&str>,
+) -> Vec<InstanceDiagnostic> {
+  let mut findings = Vec::new();
+  if min_ram_mb > max_ram_mb {
+    findings.push(InstanceDiagnostic {
+      code: "ram_min_above_max".to_string(),
+      severity: "error".to_string(),
+      title: "Minimum memory exceeds maximum".to_string(),
+      summary: format!(
+        "Minimum memory ({} MB) is larger than maximum memory ({} MB); the JVM will refuse to start.",
+        min_ram_mb, max_ram_mb
+      ),
+      suggested_fix: Some("Lower the minimum memory or raise the maximum memory.".to_string()),
+    });
+  }
+
+  let mut system = System::new();
+  system.refresh_memory();
+  let physical_mb = (system.total_memory() / 1024 / 1024) as u32;
+  if physical_mb > 0 {
+    if max_ram_mb > physical_mb {
+      findings.push(InstanceDiagnostic {
+        code: "ram_exceeds_physical".to_string(),
+        severity: "warning".to_string(),
+        title: "Maximum memory exceeds installed RAM".to_string(),
+        summary: format!(
+          "Maximum memory ({} MB) is larger than this machine's physical memory ({} MB).",
+          max_ram_mb, physical_mb
+        ),
+        suggested_fix: Some(format!(
+          "Set maximum memory to at most {} MB.",
+          physical_mb * 3 / 4
+        )),
+      });
+    } else if max_ram_mb > physical_mb * 3 / 4 {
+      findings.push(InstanceDiagnostic {
+        code: "ram_near_physical".to_string(),
+        severity: "warning".to_string(),
+        title: "Maximum memory leaves little for the system".to_string(),
+        summary: format!(
+          "Maximum memory ({} MB) uses more than 75% of physical memory ({} MB), which can cause heavy swapping.",
+          max_ram_mb, physical_mb
+        ),
+        suggested_fix: Some(format!(
+          "Consider lowering maximum memory to {} MB or less.",
+          physical_mb * 3 / 4
+        )),
+      });
+    }
+  }
+
+  if max_ram_mb > JVM_32BIT_HEAP_LIMIT_MB {
+    if let Some(java_cmd) = java_cmd {
+      if detect_java_is_32bit(java_cmd) {
+        findings.push(InstanceDiagnostic {
+          code: "ram_32bit_jvm".to_string(),
+          severity: "warning".to_string(),
+          title: "32-bit Java cannot use this much memory".to_string(),
+          summary: format!(
+            "The selected Java runtime is 32-bit and usually cannot reserve more than {} MB of heap.",
+            JVM_32BIT_HEAP_LIMIT_MB
+          ),
+          suggested_fix: Some("Select a 64-bit Java runtime or lower maximum memory.".to_string()),
+        });
+      }
+    }
+  }
+
+  findings
+}
+
 pub(crate) fn build_instance_preflight(
   config: &AppConfig,
   instance: &Instance,
@@ -207,6 +384,25 @@ pub(crate) fn build_instance_preflight(
     }
   }
 
+  if let Some(runtime) = selected_java.as_ref() {
+    if java_runs_under_rosetta(&runtime.path) {
+      checks.push(InstanceCheck {
+        id: "java_arch".to_string(),
+        label: "Java Architecture".to_string(),
+        status: "warn".to_string(),
+        summary: "Selected Java is an x86_64 build running under Rosetta.".to_string(),
+        detail: Some(runtime.path.clone()),
+      });
+      diagnostics.push(InstanceDiagnostic {
+        code: "java_rosetta".to_string(),
+        severity: "warning".to_string(),
+        title: "Java is running under Rosetta".to_string(),
+        summary: "This Mac has Apple Silicon, but the selected Java runtime is built for Intel. Rosetta translation roughly halves game performance.".to_string(),
+        suggested_fix: Some("Switch this instance to an ARM64 (aarch64) Java runtime.".to_string()),
+      });
+    }
+  }
+
   let version_json = expected_version_json_path(instance, &instance_dir);
   if version_json.is_file() {
     checks.push(InstanceCheck {
@@ -287,6 +483,234 @@ pub(crate) fn build_instance_preflight(
   }
 }
 
+/// Launch-readiness checklist. Unlike the preflight report this groups findings
+/// into fixed checklist rows so every row is present even when it passes.
+pub(crate) fn validate_instance(
+  config: &AppConfig,
+  instance: &Instance,
+) -> InstanceValidationReport {
+  let instance_dir = PathBuf::from(&instance.directory);
+  let mut items = Vec::new();
+
+  let mod_findings = inspect_mods(instance);
+  let missing_dependencies = mod_findings
+    .iter()
+    .filter(|finding| finding.code.starts_with("missing_dependency_"))
+    .map(|finding| finding.summary.clone())
+    .collect::<Vec<_>>();
+  items.push(validation_item(
+    "dependencies",
+    "Mod Dependencies",
+    if missing_dependencies.is_empty() { "ok" } else { "error" },
+    if missing_dependencies.is_empty() {
+      "All declared mod dependencies are installed.".to_string()
+    } else {
+      format!("{} mods are missing required dependencies.", missing_dependencies.len())
+    },
+    missing_dependencies,
+    Some("Install the missing dependencies or disable the dependent mod."),
+  ));
+
+  let incompatible = mod_findings
+    .iter()
+    .filter(|finding| {
+      finding.code.starts_with("loader_mismatch_") || finding.code.starts_with("duplicate_mod_")
+    })
+    .map(|finding| finding.summary.clone())
+    .collect::<Vec<_>>();
+  items.push(validation_item(
+    "mod_compatibility",
+    "Mod Compatibility",
+    if incompatible.is_empty() { "ok" } else { "error" },
+    if incompatible.is_empty() {
+      "No duplicate or mismatched mods found.".to_string()
+    } else {
+      format!("{} mods conflict with this instance.", incompatible.len())
+    },
+    incompatible,
+    Some("Remove duplicates and mods built for a different loader."),
+  ));
+
+  let recommended_major = recommended_java_major(&instance.version);
+  let java_item = match resolve_java_runtime(config, instance).ok() {
+    Some(runtime) if runtime.major.map(|major| major >= recommended_major).unwrap_or(false) => {
+      validation_item(
+        "java",
+        "Java Runtime",
+        "ok",
+        format!("{} satisfies Java {}.", runtime.label, recommended_major),
+        vec![runtime.path],
+        None,
+      )
+    }
+    Some(runtime) => validation_item(
+      "java",
+      "Java Runtime",
+      "error",
+      format!(
+        "Selected Java {} is below the required Java {}.",
+        runtime.major.unwrap_or(0),
+        recommended_major
+      ),
+      vec![runtime.path],
+      Some("Choose a newer Java runtime in Settings for this instance."),
+    ),
+    None => validation_item(
+      "java",
+      "Java Runtime",
+      "error",
+      "No Java runtime could be resolved.".to_string(),
+      Vec::new(),
+      Some("Install Java and select it in Settings or configure JAVA_HOME."),
+    ),
+  };
+  items.push(java_item);
+
+  let min_ram_mb = instance.java_min_ram_mb.unwrap_or(config.settings.java.min_ram_mb);
+  let max_ram_mb = instance.java_max_ram_mb.unwrap_or(config.settings.java.max_ram_mb);
+  let ram_findings = validate_ram_settings(min_ram_mb, max_ram_mb, None);
+  let ram_status = if ram_findings.iter().any(|finding| finding.severity == "error") {
+    "error"
+  } else if ram_findings.is_empty() {
+    "ok"
+  } else {
+    "warn"
+  };
+  items.push(validation_item(
+    "memory",
+    "Memory",
+    ram_status,
+    ram_findings
+      .first()
+      .map(|finding| finding.summary.clone())
+      .unwrap_or_else(|| {
+        format!("{} MB to {} MB fits in physical memory.", min_ram_mb, max_ram_mb)
+      }),
+    ram_findings.iter().skip(1).map(|finding| finding.summary.clone()).collect(),
+    ram_findings.first().and_then(|finding| finding.suggested_fix.as_deref()),
+  ));
+
+  if instance.kind == InstanceKind::Client {
+    // The version-specific folder the launcher extracts to; other versions' natives don't count.
+    let natives_dir = crate::minecraft::resolve_natives_dir(instance)
+      .unwrap_or_else(|_| Some(instance_dir.join("natives")));
+    let natives_present = natives_dir.as_ref().map_or(true, |dir| {
+      fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+    });
+    items.push(validation_item(
+      "natives",
+      "Native Libraries",
+      if natives_present { "ok" } else { "warn" },
+      match (&natives_dir, natives_present) {
+        (None, _) => "This version has no native libraries to extract.".to_string(),
+        (Some(_), true) => "Native libraries are extracted.".to_string(),
+        (Some(_), false) => {
+          "Native libraries are missing and will be restored before the next launch.".to_string()
+        }
+      },
+      natives_dir.iter().map(|dir| dir.display().to_string()).collect(),
+      (!natives_present).then_some("Repair the instance to restore native libraries now."),
+    ));
+    items.push(validate_account(config));
+  }
+
+  let ready = items.iter().all(|item| item.status != "error");
+  InstanceValidationReport {
+    instance_id: instance.id.clone(),
+    ready,
+    items,
+  }
+}
+
+fn validate_account(config: &AppConfig) -> ValidationItem {
+  let account = config
+    .active_account_id
+    .as_ref()
+    .and_then(|id| config.accounts.iter().find(|account| &account.id == id));
+  let Some(account) = account else {
+    return validation_item(
+      "account",
+      "Account",
+      "error",
+      "No account is selected.".to_string(),
+      Vec::new(),
+      Some("Add or select an account before launching."),
+    );
+  };
+  if account.kind == AccountKind::Offline {
+    return validation_item(
+      "account",
+      "Account",
+      "ok",
+      format!("Offline account {}.", account.display_name),
+      Vec::new(),
+      None,
+    );
+  }
+  if account.owns_minecraft == Some(false) {
+    return validation_item(
+      "account",
+      "Account",
+      "error",
+      format!("{} does not own Minecraft: Java Edition.", account.display_name),
+      Vec::new(),
+      Some("Sign in with an account that owns the game."),
+    );
+  }
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
+  let expired =
+    account.access_token.is_none() || account.expires_at.map(|ts| ts <= now).unwrap_or(true);
+  match (expired, account.refresh_token.is_some()) {
+    (false, _) => validation_item(
+      "account",
+      "Account",
+      "ok",
+      format!("Signed in as {}.", account.display_name),
+      Vec::new(),
+      None,
+    ),
+    (true, true) => validation_item(
+      "account",
+      "Account",
+      "warn",
+      format!("Session for {} expired and will be refreshed at launch.", account.display_name),
+      Vec::new(),
+      None,
+    ),
+    (true, false) => validation_item(
+      "account",
+      "Account",
+      "error",
+      format!("Session for {} expired.", account.display_name),
+      Vec::new(),
+      Some("Sign in again from the account menu."),
+    ),
+  }
+}
+
+fn validation_item(
+  id: &str,
+  label: &str,
+  status: &str,
+  summary: String,
+  details: Vec<String>,
+  suggested_fix: Option<&str>,
+) -> ValidationItem {
+  ValidationItem {
+    id: id.to_string(),
+    label: label.to_string(),
+    status: status.to_string(),
+    summary,
+    details,
+    suggested_fix: if status == "ok" { None } else { suggested_fix.map(|fix| fix.to_string()) },
+  }
+}
+
 pub(crate) fn create_snapshot(
   instance: &Instance,
   reason: Option<String>,
@@ -387,6 +811,184 @@ pub(crate) fn delete_snapshot(instance: &Instance, snapshot_id: &str) -> Result<
   Ok(())
 }
 
+pub(crate) fn create_mods_snapshot(
+  instance_dir: &Path,
+  reason: Option<String>,
+  archive: bool,
+) -> Result<ModsSnapshot, String> {
+  let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+  let snapshot_id = format!("mods-{}", now.as_millis());
+  let snapshot_dir = mods_snapshots_root(instance_dir).join(&snapshot_id);
+  fs::create_dir_all(&snapshot_dir).map_err(|err| err.to_string())?;
+
+  let mods_dir = instance_dir.join("mods");
+  let mut files = Vec::new();
+  for path in list_mod_files(&mods_dir)? {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let size = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+    files.push(ModsSnapshotFile {
+      name,
+      size,
+      sha256: hash_file(&path)?,
+    });
+  }
+  files.sort_by(|a, b| a.name.cmp(&b.name));
+
+  if archive {
+    let archive_file =
+      fs::File::create(snapshot_dir.join(MODS_SNAPSHOT_ARCHIVE)).map_err(|err| err.to_string())?;
+    let mut writer = ZipWriter::new(archive_file);
+    for file in &files {
+      writer
+        .start_file(file.name.as_str(), FileOptions::default())
+        .map_err(|err| err.to_string())?;
+      let mut source = fs::File::open(mods_dir.join(&file.name)).map_err(|err| err.to_string())?;
+      io::copy(&mut source, &mut writer).map_err(|err| err.to_string())?;
+    }
+    writer.finish().map_err(|err| err.to_string())?;
+  }
+
+  let index_path = instance_dir.join(MODS_SNAPSHOT_INDEX);
+  if index_path.is_file() {
+    fs::copy(&index_path, snapshot_dir.join(MODS_SNAPSHOT_INDEX)).map_err(|err| err.to_string())?;
+  }
+
+  let snapshot = ModsSnapshot {
+    id: snapshot_id,
+    created_at_unix: now.as_secs(),
+    reason: sanitize_reason(reason),
+    archived: archive,
+    files,
+  };
+  let payload = serde_json::to_vec_pretty(&snapshot).map_err(|err| err.to_string())?;
+  fs::write(snapshot_dir.join(MODS_SNAPSHOT_MANIFEST), payload).map_err(|err| err.to_string())?;
+  Ok(snapshot)
+}
+
+pub(crate) fn list_mods_snapshots(instance_dir: &Path) -> Result<Vec<ModsSnapshot>, String> {
+  let root = mods_snapshots_root(instance_dir);
+  if !root.exists() {
+    return Ok(Vec::new());
+  }
+  let mut snapshots = Vec::new();
+  for entry in fs::read_dir(root).map_err(|err| err.to_string())? {
+    let entry = entry.map_err(|err| err.to_string())?;
+    let data = match fs::read_to_string(entry.path().join(MODS_SNAPSHOT_MANIFEST)) {
+      Ok(data) => data,
+      Err(_) => continue,
+    };
+    if let Ok(snapshot) = serde_json::from_str::<ModsSnapshot>(&data) {
+      snapshots.push(snapshot);
+    }
+  }
+  snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+  Ok(snapshots)
+}
+
+pub(crate) fn restore_mods_snapshot(
+  instance_dir: &Path,
+  snapshot_id: &str,
+) -> Result<ModsRestoreResult, String> {
+  let snapshot_dir = mods_snapshots_root(instance_dir).join(snapshot_id);
+  let data = fs::read_to_string(snapshot_dir.join(MODS_SNAPSHOT_MANIFEST))
+    .map_err(|_| "mods snapshot not found".to_string())?;
+  let snapshot: ModsSnapshot = serde_json::from_str(&data).map_err(|err| err.to_string())?;
+  let mods_dir = instance_dir.join("mods");
+  fs::create_dir_all(&mods_dir).map_err(|err| err.to_string())?;
+
+  let expected = snapshot
+    .files
+    .iter()
+    .map(|file| (file.name.as_str(), file.sha256.as_str()))
+    .collect::<HashMap<_, _>>();
+  let mut unknown = Vec::new();
+  let mut current = HashMap::new();
+  for path in list_mod_files(&mods_dir)? {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    if expected.contains_key(name.as_str()) {
+      current.insert(name, hash_file(&path)?);
+    } else {
+      unknown.push(name);
+    }
+  }
+  let changed = snapshot
+    .files
+    .iter()
+    .filter(|file| current.get(&file.name).is_some_and(|hash| hash != &file.sha256))
+    .map(|file| file.name.clone())
+    .collect::<Vec<_>>();
+  let outdated = snapshot
+    .files
+    .iter()
+    .filter(|file| current.get(&file.name) != Some(&file.sha256))
+    .count();
+  if !snapshot.archived && (outdated > 0 || !unknown.is_empty()) {
+    return Err(format!(
+      "snapshot has no archived copy and {} mod files differ from it",
+      outdated + unknown.len()
+    ));
+  }
+
+  // Anything the restore would overwrite or drop is moved aside, never deleted.
+  let mut stashed = Vec::new();
+  let mut stash_dir = None;
+  if !unknown.is_empty() || !changed.is_empty() {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let dir = mods_snapshots_root(instance_dir).join(format!("stash-{}", now.as_millis()));
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    for name in unknown.iter().chain(changed.iter()) {
+      fs::rename(mods_dir.join(name), dir.join(name)).map_err(|err| err.to_string())?;
+      stashed.push(name.clone());
+    }
+    let index_path = instance_dir.join(MODS_SNAPSHOT_INDEX);
+    if index_path.is_file() {
+      fs::copy(&index_path, dir.join(MODS_SNAPSHOT_INDEX)).map_err(|err| err.to_string())?;
+    }
+    stash_dir = Some(dir.to_string_lossy().to_string());
+  }
+
+  let mut archive = if snapshot.archived {
+    let file =
+      fs::File::open(snapshot_dir.join(MODS_SNAPSHOT_ARCHIVE)).map_err(|err| err.to_string())?;
+    Some(ZipArchive::new(file).map_err(|err| err.to_string())?)
+  } else {
+    None
+  };
+  let mut restored = Vec::new();
+  let mut missing = Vec::new();
+  for file in &snapshot.files {
+    if current.get(&file.name) == Some(&file.sha256) {
+      continue;
+    }
+    let Some(archive) = archive.as_mut() else {
+      missing.push(file.name.clone());
+      continue;
+    };
+    let mut entry = match archive.by_name(&file.name) {
+      Ok(entry) => entry,
+      Err(_) => {
+        missing.push(file.name.clone());
+        continue;
+      }
+    };
+    let mut target = fs::File::create(mods_dir.join(&file.name)).map_err(|err| err.to_string())?;
+    io::copy(&mut entry, &mut target).map_err(|err| err.to_string())?;
+    restored.push(file.name.clone());
+  }
+
+  let index_backup = snapshot_dir.join(MODS_SNAPSHOT_INDEX);
+  if index_backup.is_file() {
+    fs::copy(&index_backup, instance_dir.join(MODS_SNAPSHOT_INDEX)).map_err(|err| err.to_string())?;
+  }
+
+  Ok(ModsRestoreResult {
+    restored,
+    stashed,
+    stash_dir,
+    missing,
+  })
+}
+
 pub(crate) fn repair_instance(instance: &Instance) -> Result<RepairResult, String> {
   let snapshot = create_snapshot(instance, Some("Before repair".to_string())).ok();
   let instance_dir = PathBuf::from(&instance.directory);
@@ -402,13 +1004,32 @@ pub(crate) fn repair_instance(instance: &Instance) -> Result<RepairResult, Strin
   fs::write(&manifest_path, payload).map_err(|err| err.to_string())?;
 
   let mut cleared = Vec::new();
-  for relative in ["install.json", "versions", "libraries", "natives", "installers"] {
+  for relative in ["install.json", "libraries", "installers"] {
     let path = instance_dir.join(relative);
     if path.exists() {
       remove_path_if_exists(&path)?;
       cleared.push(relative.to_string());
     }
   }
+  // A user-supplied version cannot be downloaded again, so it survives the repair.
+  let keep = manifest.custom_version.as_deref();
+  for relative in ["versions", "natives"] {
+    let root = instance_dir.join(relative);
+    let Ok(entries) = fs::read_dir(&root) else {
+      continue;
+    };
+    let mut removed_any = false;
+    for entry in entries.flatten() {
+      if keep.is_some_and(|id| entry.file_name().to_string_lossy() == id) {
+        continue;
+      }
+      remove_path_if_exists(&entry.path())?;
+      removed_any = true;
+    }
+    if removed_any {
+      cleared.push(relative.to_string());
+    }
+  }
 
   Ok(RepairResult {
     snapshot,
@@ -417,6 +1038,42 @@ pub(crate) fn repair_instance(instance: &Instance) -> Result<RepairResult, Strin
   })
 }
 
+pub(crate) fn classify_install_failure(error: &str) -> InstallRetryResult {
+  let lower = error.to_ascii_lowercase();
+  let version_markers = [
+    "not found",
+    "version is required",
+    "no matching",
+    "not available",
+    "unsupported",
+    "status code 404",
+  ];
+  let network_markers = [
+    "download failed",
+    "could not be downloaded",
+    "timed out",
+    "connection",
+    "dns",
+    "network",
+    "failed size/hash verification",
+    "download is corrupt",
+  ];
+  let (kind, retryable) = if version_markers.iter().any(|marker| lower.contains(marker)) {
+    ("version", false)
+  } else if network_markers.iter().any(|marker| lower.contains(marker)) {
+    ("network", true)
+  } else {
+    ("other", true)
+  };
+  InstallRetryResult {
+    success: false,
+    failure_kind: Some(kind.to_string()),
+    retryable,
+    error: Some(error.to_string()),
+    summary: None,
+  }
+}
+
 pub(crate) fn classify_launch_failure(
   config: &AppConfig,
   instance: &Instance,
@@ -494,6 +1151,8 @@ fn inspect_mods(instance: &Instance) -> Vec<InstanceDiagnostic> {
   }
 
   let mut loaded_ids: HashMap<String, Vec<String>> = HashMap::new();
+  let mut loaded_versions: HashMap<String, String> = HashMap::new();
+  let mut version_ranges: Vec<(String, String, String)> = Vec::new();
   let mut dependency_graph: BTreeMap<String, Vec<String>> = BTreeMap::new();
   let mut ecosystems: Vec<(String, BTreeSet<String>, Option<String>)> = Vec::new();
 
@@ -529,6 +1188,12 @@ fn inspect_mods(instance: &Instance) -> Vec<InstanceDiagnostic> {
             .entry(id.clone())
             .or_default()
             .extend(inspection.dependencies.iter().cloned());
+          if let Some(version) = &inspection.version {
+            loaded_versions.entry(id.clone()).or_insert_with(|| version.clone());
+          }
+        }
+        for (dependency, range) in &inspection.version_ranges {
+          version_ranges.push((ids[0].clone(), dependency.clone(), range.clone()));
         }
         ecosystems.push((file_name.clone(), inspection.ecosystems, inspection.name));
       }
@@ -567,18 +1232,46 @@ fn inspect_mods(instance: &Instance) -> Vec<InstanceDiagnostic> {
     }
   }
 
+  let platform_versions = platform_dependency_versions(instance);
+  for (mod_id, dependency, range) in version_ranges {
+    let Some(installed) = loaded_versions
+      .get(&dependency)
+      .or_else(|| platform_versions.get(dependency.as_str()))
+    else {
+      continue;
+    };
+    if version_range_allows(&range, installed) != Some(false) {
+      continue;
+    }
+    diagnostics.push(InstanceDiagnostic {
+      code: format!("dependency_version_{}_{}", mod_id, dependency),
+      severity: "error".to_string(),
+      title: "Mod dependency version mismatch".to_string(),
+      summary: format!(
+        "Mod '{}' requires {} {} but {} is installed.",
+        mod_id, dependency, range, installed
+      ),
+      suggested_fix: Some(format!(
+        "Install a version of {} within {}, or update '{}'.",
+        dependency, range, mod_id
+      )),
+    });
+  }
+
   for (file_name, mod_ecosystems, display_name) in ecosystems {
     if mod_ecosystems.is_empty() {
       continue;
     }
     let declares_target_loader = match instance.loader {
       Loader::Fabric => mod_ecosystems.contains("fabric"),
+      // Quilt loads Fabric mods as well as its own.
+      Loader::Quilt => mod_ecosystems.contains("quilt") || mod_ecosystems.contains("fabric"),
       Loader::Forge => mod_ecosystems.contains("forge"),
       Loader::NeoForge => mod_ecosystems.contains("neoforge"),
-      Loader::Vanilla => false,
+      Loader::Vanilla | Loader::Paper | Loader::Folia | Loader::Purpur => false,
     };
     let incompatible = match instance.loader {
-      Loader::Fabric => {
+      Loader::Fabric | Loader::Quilt => {
         !declares_target_loader
           && (mod_ecosystems.contains("forge") || mod_ecosystems.contains("neoforge"))
       }
@@ -594,7 +1287,7 @@ fn inspect_mods(instance: &Instance) -> Vec<InstanceDiagnostic> {
             || mod_ecosystems.contains("quilt")
             || mod_ecosystems.contains("forge"))
       }
-      Loader::Vanilla => true,
+      Loader::Vanilla | Loader::Paper | Loader::Folia | Loader::Purpur => true,
     };
     if incompatible {
       let declared = mod_ecosystems.iter().cloned().collect::<Vec<_>>().join(", ");
@@ -616,6 +1309,130 @@ fn inspect_mods(instance: &Instance) -> Vec<InstanceDiagnostic> {
   diagnostics
 }
 
+/// Builds "requires" edges between the mods in `mods/`, including disabled jars.
+/// Dependencies that no file provides become `missing` nodes; `modrinth_projects`
+/// maps jar filenames to project ids.
+pub(crate) fn build_mod_dependency_graph(
+  instance_dir: &Path,
+  modrinth_projects: &HashMap<String, String>,
+) -> ModDependencyGraph {
+  let mut nodes: BTreeMap<String, ModGraphNode> = BTreeMap::new();
+  let mut provided_by: HashMap<String, String> = HashMap::new();
+  let mut requirements: Vec<(String, Vec<String>)> = Vec::new();
+
+  let mut files = fs::read_dir(instance_dir.join("mods"))
+    .map(|entries| entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>())
+    .unwrap_or_default();
+  files.sort();
+  for path in files {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()).map(str::to_string)
+    else {
+      continue;
+    };
+    let lower = file_name.to_ascii_lowercase();
+    let enabled = lower.ends_with(".jar");
+    if !enabled && !lower.ends_with(".jar.disabled") {
+      continue;
+    }
+    let inspection = inspect_mod_archive(&path);
+    let ids = if inspection.ids.is_empty() {
+      vec![normalize_mod_id(&file_name)]
+    } else {
+      inspection.ids.clone()
+    };
+    let primary = ids[0].clone();
+    for id in &ids {
+      // Enabled jars win when a disabled copy provides the same id.
+      if enabled || !provided_by.contains_key(id) {
+        provided_by.insert(id.clone(), primary.clone());
+      }
+    }
+    let jar_name = file_name.trim_end_matches(".disabled").to_string();
+    nodes.insert(
+      primary.clone(),
+      ModGraphNode {
+        id: primary.clone(),
+        name: inspection.name.clone(),
+        file_name: Some(file_name.clone()),
+        enabled,
+        missing: false,
+        modrinth_project_id: modrinth_projects.get(&jar_name).cloned(),
+      },
+    );
+    requirements.push((primary, inspection.dependencies));
+  }
+
+  let mut edges = BTreeSet::new();
+  for (from, dependencies) in requirements {
+    for dependency in dependencies {
+      if builtin_dependency(&dependency) {
+        continue;
+      }
+      let to = match provided_by.get(&dependency) {
+        Some(target) => target.clone(),
+        None => {
+          nodes.entry(dependency.clone()).or_insert_with(|| ModGraphNode {
+            id: dependency.clone(),
+            name: None,
+            file_name: None,
+            enabled: false,
+            missing: true,
+            modrinth_project_id: None,
+          });
+          dependency
+        }
+      };
+      if to != from {
+        edges.insert((from.clone(), to));
+      }
+    }
+  }
+
+  ModDependencyGraph {
+    nodes: nodes.into_values().collect(),
+    edges: edges
+      .into_iter()
+      .map(|(from, to)| ModGraphEdge { from, to })
+      .collect(),
+  }
+}
+
+/// File names of enabled mods that (directly or transitively) require the mod in `file_name`.
+pub(crate) fn enabled_mod_dependents(graph: &ModDependencyGraph, file_name: &str) -> Vec<String> {
+  let Some(root) = graph
+    .nodes
+    .iter()
+    .find(|node| node.file_name.as_deref() == Some(file_name))
+    .map(|node| node.id.clone())
+  else {
+    return Vec::new();
+  };
+  let nodes = graph
+    .nodes
+    .iter()
+    .map(|node| (node.id.as_str(), node))
+    .collect::<HashMap<_, _>>();
+  let mut visited = BTreeSet::from([root.clone()]);
+  let mut queue = vec![root];
+  let mut dependents = Vec::new();
+  while let Some(current) = queue.pop() {
+    for edge in graph.edges.iter().filter(|edge| edge.to == current) {
+      let Some(node) = nodes.get(edge.from.as_str()) else {
+        continue;
+      };
+      if !node.enabled || !visited.insert(edge.from.clone()) {
+        continue;
+      }
+      if let Some(name) = &node.file_name {
+        dependents.push(name.clone());
+      }
+      queue.push(edge.from.clone());
+    }
+  }
+  dependents.sort();
+  dependents
+}
+
 fn inspect_mod_archive(path: &Path) -> ModInspection {
   let file = match fs::File::open(path) {
     Ok(file) => file,
@@ -639,6 +1456,16 @@ fn inspect_mod_archive(path: &Path) -> ModInspection {
   if let Some(contents) = load_zip_entry(&mut zip, "META-INF/neoforge.mods.toml") {
     merge_mods_toml(&mut inspection, &contents, "neoforge");
   }
+  // mods.toml usually declares `${file.jarVersion}`, which Forge reads from the manifest.
+  if inspection.version.is_none() {
+    inspection.version = load_zip_entry(&mut zip, "META-INF/MANIFEST.MF").and_then(|manifest| {
+      manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("Implementation-Version:"))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    });
+  }
 
   inspection.ids.sort();
   inspection.ids.dedup();
@@ -756,6 +1583,14 @@ fn merge_mods_toml(inspection: &mut ModInspection, contents: &str, ecosystem: &s
           if let Some(mod_id) = table.get("modId").and_then(|value| value.as_str()) {
             let normalized = normalize_mod_id(mod_id);
             if !normalized.is_empty() {
+              if let Some(range) = table
+                .get("versionRange")
+                .and_then(|value| value.as_str())
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty() && *value != "*")
+              {
+                inspection.version_ranges.push((normalized.clone(), range.to_string()));
+              }
               inspection.dependencies.push(normalized);
             }
           }
@@ -765,6 +1600,149 @@ fn merge_mods_toml(inspection: &mut ModInspection, contents: &str, ecosystem: &s
   }
 }
 
+/// Versions of the game and loader, which mods.toml lists as ordinary dependencies.
+fn platform_dependency_versions(instance: &Instance) -> HashMap<&'static str, String> {
+  let mut versions = HashMap::from([("minecraft", instance.version.clone())]);
+  if let Some(loader_version) = &instance.loader_version {
+    match instance.loader {
+      Loader::Forge => {
+        let forge_version = loader_version
+          .strip_prefix(&format!("{}-", instance.version))
+          .unwrap_or(loader_version);
+        versions.insert("forge", forge_version.to_string());
+      }
+      Loader::NeoForge => {
+        versions.insert("neoforge", loader_version.clone());
+      }
+      _ => {}
+    }
+  }
+  versions
+}
+
+fn compare_mod_versions(a: &str, b: &str) -> std::cmp::Ordering {
+  use std::cmp::Ordering;
+
+  let numbers = |value: &str| {
+    value
+      .split(|ch: char| !ch.is_ascii_digit())
+      .filter(|chunk| !chunk.is_empty())
+      .filter_map(|chunk| chunk.parse::<u64>().ok())
+      .collect::<Vec<_>>()
+  };
+  let compare_numbers = |a: &str, b: &str| {
+    let (a, b) = (numbers(a), numbers(b));
+    (0..a.len().max(b.len()))
+      .map(|idx| a.get(idx).unwrap_or(&0).cmp(b.get(idx).unwrap_or(&0)))
+      .find(|ordering| *ordering != Ordering::Equal)
+      .unwrap_or(Ordering::Equal)
+  };
+  let ((a_release, a_pre), (b_release, b_pre)) = (split_prerelease(a), split_prerelease(b));
+  compare_numbers(a_release, b_release).then_with(|| match (a_pre, b_pre) {
+    (None, None) => Ordering::Equal,
+    (None, Some(_)) => Ordering::Greater,
+    (Some(_), None) => Ordering::Less,
+    (Some(a_pre), Some(b_pre)) => {
+      let label = |value: &str| {
+        value
+          .split(|ch: char| !ch.is_ascii_alphabetic())
+          .next()
+          .unwrap_or_default()
+          .to_ascii_lowercase()
+      };
+      label(a_pre)
+        .cmp(&label(b_pre))
+        .then_with(|| compare_numbers(a_pre, b_pre))
+    }
+  })
+}
+
+/// Splits `1.2.0-beta.3+build` into `1.2.0` and `beta.3`. A dash followed by a digit is
+/// part of the release, as in `1.20.1-47.2.0`.
+fn split_prerelease(version: &str) -> (&str, Option<&str>) {
+  let version = version.split('+').next().unwrap_or_default().trim();
+  let prerelease_start = version
+    .match_indices('-')
+    .find(|(idx, _)| version[idx + 1..].starts_with(|ch: char| ch.is_ascii_alphabetic()))
+    .map(|(idx, _)| idx);
+  match prerelease_start {
+    Some(idx) => (&version[..idx], Some(&version[idx + 1..])),
+    None => (version, None),
+  }
+}
+
+/// Evaluates a Maven version range such as `[47,)` or `[1.0,2.0),[3.0,)`, plus the `>=1.0`
+/// and `*` forms some mods write instead. Returns `None` when the range or version cannot be
+/// interpreted.
+fn version_range_allows(range: &str, version: &str) -> Option<bool> {
+  use std::cmp::Ordering;
+
+  let range = range.trim();
+  let has_digits = |value: &str| value.contains(|ch: char| ch.is_ascii_digit());
+  if version.contains("${") || !has_digits(version) {
+    return None;
+  }
+  if range == "*" {
+    return Some(true);
+  }
+  for (operator, accepts) in [
+    (">=", &[Ordering::Greater, Ordering::Equal][..]),
+    ("<=", &[Ordering::Less, Ordering::Equal][..]),
+    (">", &[Ordering::Greater][..]),
+    ("<", &[Ordering::Less][..]),
+    ("=", &[Ordering::Equal][..]),
+  ] {
+    if let Some(bound) = range.strip_prefix(operator) {
+      let bound = bound.trim();
+      if !has_digits(bound) {
+        return None;
+      }
+      return Some(accepts.contains(&compare_mod_versions(version, bound)));
+    }
+  }
+  // A bare version is only a recommendation in Maven terms.
+  if !range.starts_with('[') && !range.starts_with('(') {
+    return Some(true);
+  }
+  let mut remaining = range;
+  let mut any_set = false;
+  while let Some(open_idx) = remaining.find(['[', '(']) {
+    let close_idx = remaining[open_idx..].find([']', ')'])? + open_idx;
+    let lower_inclusive = remaining[open_idx..].starts_with('[');
+    let upper_inclusive = remaining[close_idx..].starts_with(']');
+    let inner = &remaining[open_idx + 1..close_idx];
+    any_set = true;
+    let allowed = match inner.split_once(',') {
+      None => compare_mod_versions(version, inner.trim()) == Ordering::Equal,
+      Some((lower, upper)) => {
+        let (lower, upper) = (lower.trim(), upper.trim());
+        let above = lower.is_empty()
+          || match compare_mod_versions(version, lower) {
+            Ordering::Greater => true,
+            Ordering::Equal => lower_inclusive,
+            Ordering::Less => false,
+          };
+        let below = upper.is_empty()
+          || match compare_mod_versions(version, upper) {
+            Ordering::Less => true,
+            Ordering::Equal => upper_inclusive,
+            Ordering::Greater => false,
+          };
+        above && below
+      }
+    };
+    if allowed {
+      return Some(true);
+    }
+    remaining = &remaining[close_idx + 1..];
+  }
+  if any_set {
+    Some(false)
+  } else {
+    None
+  }
+}
+
 fn load_zip_entry(zip: &mut ZipArchive<fs::File>, name: &str) -> Option<String> {
   let mut file = zip.by_name(name).ok()?;
   let mut contents = String::new();
@@ -783,8 +1761,12 @@ fn loader_name(loader: &Loader) -> &'static str {
   match loader {
     Loader::Vanilla => "vanilla",
     Loader::Fabric => "fabric",
+    Loader::Quilt => "quilt",
     Loader::Forge => "forge",
     Loader::NeoForge => "neoforge",
+    Loader::Paper => "paper",
+    Loader::Folia => "folia",
+    Loader::Purpur => "purpur",
   }
 }
 
@@ -818,7 +1800,7 @@ fn latest_log_excerpt(instance_dir: &Path) -> Option<String> {
   Some(lines.into_iter().rev().collect::<Vec<_>>().join("\n"))
 }
 
-fn recommended_java_major(game_version: &str) -> u32 {
+pub(crate) fn recommended_java_major(game_version: &str) -> u32 {
   let core = game_version
     .split(|ch| ch == '-' || ch == ' ')
     .next()
@@ -854,6 +1836,11 @@ fn expected_version_json_path(instance: &Instance, instance_dir: &Path) -> PathB
       .as_ref()
       .map(|loader| format!("fabric-loader-{}-{}", loader, instance.version))
       .unwrap_or_else(|| instance.version.clone()),
+    Loader::Quilt => instance
+      .loader_version
+      .as_ref()
+      .map(|loader| format!("quilt-loader-{}-{}", loader, instance.version))
+      .unwrap_or_else(|| instance.version.clone()),
     Loader::Forge => {
       let loader = instance
         .loader_version
@@ -871,6 +1858,7 @@ fn expected_version_json_path(instance: &Instance, instance_dir: &Path) -> PathB
       .as_ref()
       .map(|loader| format!("neoforge-{}", loader))
       .unwrap_or_else(|| instance.version.clone()),
+    Loader::Paper | Loader::Folia | Loader::Purpur => instance.version.clone(),
   };
   instance_dir
     .join("versions")
@@ -899,10 +1887,322 @@ fn snapshot_paths() -> &'static [&'static str] {
   ]
 }
 
+/// Zips every world whose `level.dat` changed since its newest backup and keeps
+/// the last few archives per world. Returns the names of the worlds backed up.
+pub(crate) fn backup_changed_worlds(instance_dir: &Path) -> Result<Vec<String>, String> {
+  let saves_dir = instance_dir.join("saves");
+  if !saves_dir.is_dir() {
+    return Ok(Vec::new());
+  }
+  let mut backed_up = Vec::new();
+  for entry in fs::read_dir(&saves_dir).map_err(|err| err.to_string())? {
+    let world_dir = entry.map_err(|err| err.to_string())?.path();
+    let Some(modified) = modified_unix(&world_dir.join("level.dat")) else {
+      continue;
+    };
+    let world_name = world_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let backup_dir = world_backups_root(instance_dir).join(&world_name);
+    let mut archives = list_world_backups(&backup_dir)?;
+    let newest = archives.last().and_then(|path| modified_unix(path)).unwrap_or(0);
+    if newest >= modified {
+      continue;
+    }
+    fs::create_dir_all(&backup_dir).map_err(|err| err.to_string())?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let archive_path = backup_dir.join(format!("world-{}.zip", now.as_millis()));
+    let archive_file = fs::File::create(&archive_path).map_err(|err| err.to_string())?;
+    let mut writer = ZipWriter::new(archive_file);
+    zip_directory(&mut writer, &world_dir, &world_dir)?;
+    writer.finish().map_err(|err| err.to_string())?;
+    archives.push(archive_path);
+    while archives.len() > WORLD_BACKUPS_KEEP {
+      let oldest = archives.remove(0);
+      let _ = fs::remove_file(oldest);
+    }
+    backed_up.push(world_name);
+  }
+  backed_up.sort();
+  Ok(backed_up)
+}
+
+/// Deletes rotated game logs and crash reports older than `max_age_days`.
+pub(crate) fn prune_instance_logs(instance_dir: &Path, max_age_days: u32) -> Result<usize, String> {
+  let cutoff = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs()
+    .saturating_sub(u64::from(max_age_days) * 86_400);
+  let mut removed = 0;
+  for dir in LOG_DIRS {
+    let log_dir = instance_dir.join(dir);
+    if !log_dir.is_dir() {
+      continue;
+    }
+    for entry in fs::read_dir(&log_dir).map_err(|err| err.to_string())? {
+      let path = entry.map_err(|err| err.to_string())?.path();
+      if !path.is_file() || path.file_name().map(|name| name == "latest.log").unwrap_or(false) {
+        continue;
+      }
+      if modified_unix(&path).map(|value| value < cutoff).unwrap_or(false)
+        && fs::remove_file(&path).is_ok()
+      {
+        removed += 1;
+      }
+    }
+  }
+  Ok(removed)
+}
+
+/// Summarizes collector pauses from `logs/gc.log` and its rotated `gc.log.N` files.
+pub(crate) fn summarize_gc_log(instance_dir: &Path) -> Result<GcLogSummary, String> {
+  let logs_dir = instance_dir.join("logs");
+  let rotated_prefix = format!("{}.", GC_LOG_FILE);
+  let mut paths: Vec<PathBuf> = fs::read_dir(&logs_dir)
+    .map(|entries| {
+      entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+          path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+            name == GC_LOG_FILE
+              || name
+                .strip_prefix(&rotated_prefix)
+                .is_some_and(|index| index.parse::<u32>().is_ok())
+          })
+        })
+        .filter(|path| path.is_file())
+        .collect()
+    })
+    .unwrap_or_default();
+  if paths.is_empty() {
+    return Err("no GC log yet. Enable GC logging and launch the instance first.".to_string());
+  }
+  paths.sort();
+  let mut text = String::new();
+  for path in paths {
+    text.push_str(&fs::read_to_string(&path).map_err(|err| err.to_string())?);
+    text.push('\n');
+  }
+  summarize_gc_text(&text)
+}
+
+fn summarize_gc_text(text: &str) -> Result<GcLogSummary, String> {
+  let unified = regex::Regex::new(
+    r"GC\(\d+\) Pause ([A-Za-z ]+?)(?: \(.*\))? (\d+)M->(\d+)M\((\d+)M\) ([\d.]+)ms",
+  )
+  .map_err(|err| err.to_string())?;
+  let legacy =
+    regex::Regex::new(r"\[(Full GC|GC)\b.*, ([\d.]+) secs\]").map_err(|err| err.to_string())?;
+  let metaspace = regex::Regex::new(r"\[Metaspace: [^\]]*\]").map_err(|err| err.to_string())?;
+  let transition = regex::Regex::new(r"(\d+)([KMG])->(\d+)([KMG])\((\d+)([KMG])\)")
+    .map_err(|err| err.to_string())?;
+  let to_mb = |value: &str, unit: &str| {
+    let value = value.parse::<u64>().unwrap_or(0);
+    match unit {
+      "K" => value / 1024,
+      "G" => value * 1024,
+      _ => value,
+    }
+  };
+
+  let mut pauses = Vec::new();
+  let mut full_gc_count = 0;
+  let mut heap_capacity_mb = None;
+  let mut peak_heap_after_gc_mb: Option<u64> = None;
+  for line in text.lines() {
+    if let Some(captures) = unified.captures(line) {
+      if captures[1].trim() == "Full" {
+        full_gc_count += 1;
+      }
+      let after = captures[3].parse::<u64>().unwrap_or(0);
+      peak_heap_after_gc_mb = Some(peak_heap_after_gc_mb.map_or(after, |peak| peak.max(after)));
+      heap_capacity_mb = captures[4].parse::<u64>().ok().or(heap_capacity_mb);
+      pauses.push(captures[5].parse::<f64>().unwrap_or(0.0));
+    } else if let Some(captures) = legacy.captures(line) {
+      if &captures[1] == "Full GC" {
+        full_gc_count += 1;
+      }
+      let stripped = metaspace.replace_all(line, "");
+      // The last transition on the line covers the whole heap rather than one generation.
+      if let Some(heap) = transition.captures_iter(&stripped).last() {
+        let after = to_mb(&heap[3], &heap[4]);
+        peak_heap_after_gc_mb = Some(peak_heap_after_gc_mb.map_or(after, |peak| peak.max(after)));
+        heap_capacity_mb = Some(to_mb(&heap[5], &heap[6]));
+      }
+      pauses.push(captures[2].parse::<f64>().unwrap_or(0.0) * 1000.0);
+    }
+  }
+
+  let pause_count = pauses.len() as u32;
+  let total_pause_ms: f64 = pauses.iter().sum();
+  let long_pause_count = pauses.iter().filter(|pause| **pause >= GC_LONG_PAUSE_MS).count() as u32;
+  pauses.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+  let max_pause_ms = pauses.last().copied().unwrap_or(0.0);
+  let p95_pause_ms = if pauses.is_empty() {
+    0.0
+  } else {
+    pauses[((pauses.len() as f64 * 0.95).ceil() as usize).saturating_sub(1)]
+  };
+  let average_pause_ms = if pause_count > 0 { total_pause_ms / pause_count as f64 } else { 0.0 };
+  let occupancy = match (peak_heap_after_gc_mb, heap_capacity_mb) {
+    (Some(peak), Some(capacity)) if capacity > 0 => Some(peak as f64 / capacity as f64),
+    _ => None,
+  };
+
+  let recommendation = if pause_count == 0 {
+    "No collector pauses were found. Play until the stutter happens, then check again."
+  } else if full_gc_count > 0 || occupancy.is_some_and(|ratio| ratio >= 0.85) {
+    "The heap stays nearly full after collections. Raise maximum RAM."
+  } else if (p95_pause_ms >= GC_LONG_PAUSE_MS || max_pause_ms >= 200.0)
+    && occupancy.is_some_and(|ratio| ratio < 0.5)
+  {
+    "Memory is plentiful but pauses are long. Lowering maximum RAM usually shortens them."
+  } else {
+    "Collector pauses look healthy, so the RAM setting is unlikely to be the cause of stutter."
+  };
+
+  Ok(GcLogSummary {
+    pause_count,
+    full_gc_count,
+    total_pause_ms,
+    average_pause_ms,
+    p95_pause_ms,
+    max_pause_ms,
+    long_pause_count,
+    heap_capacity_mb,
+    peak_heap_after_gc_mb,
+    recommendation: recommendation.to_string(),
+  })
+}
+
+/// Partial `*.tmp` downloads left in the download folders by an interrupted install.
+pub(crate) fn find_orphaned_temp_files(instance_dir: &Path) -> Vec<(PathBuf, u64)> {
+  let mut found = Vec::new();
+  let mut pending = DOWNLOAD_DIRS
+    .iter()
+    .map(|dir| instance_dir.join(dir))
+    .filter(|dir| dir.is_dir())
+    .collect::<Vec<_>>();
+  while let Some(dir) = pending.pop() {
+    let Ok(entries) = fs::read_dir(&dir) else {
+      continue;
+    };
+    for entry in entries.flatten() {
+      let path = entry.path();
+      let Ok(file_type) = entry.file_type() else {
+        continue;
+      };
+      if file_type.is_dir() {
+        pending.push(path);
+      } else if file_type.is_file()
+        && path.extension().map(|ext| ext == "tmp").unwrap_or(false)
+      {
+        let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+        found.push((path, size));
+      }
+    }
+  }
+  found.sort();
+  found
+}
+
+/// Deletes the files reported by `find_orphaned_temp_files`, returning the count and bytes freed.
+pub(crate) fn remove_orphaned_temp_files(instance_dir: &Path) -> (usize, u64) {
+  let mut removed = 0;
+  let mut bytes = 0;
+  for (path, size) in find_orphaned_temp_files(instance_dir) {
+    match fs::remove_file(&path) {
+      Ok(()) => {
+        removed += 1;
+        bytes += size;
+      }
+      Err(err) => log::warn!("failed to remove {}: {}", path.display(), err),
+    }
+  }
+  (removed, bytes)
+}
+
+fn modified_unix(path: &Path) -> Option<u64> {
+  fs::metadata(path)
+    .and_then(|meta| meta.modified())
+    .ok()
+    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+    .map(|value| value.as_secs())
+}
+
+fn list_world_backups(backup_dir: &Path) -> Result<Vec<PathBuf>, String> {
+  if !backup_dir.is_dir() {
+    return Ok(Vec::new());
+  }
+  let mut archives = Vec::new();
+  for entry in fs::read_dir(backup_dir).map_err(|err| err.to_string())? {
+    let path = entry.map_err(|err| err.to_string())?.path();
+    if path.extension().map(|ext| ext == "zip").unwrap_or(false) {
+      archives.push(path);
+    }
+  }
+  archives.sort();
+  Ok(archives)
+}
+
+fn zip_directory(writer: &mut ZipWriter<fs::File>, root: &Path, dir: &Path) -> Result<(), String> {
+  for entry in fs::read_dir(dir).map_err(|err| err.to_string())? {
+    let path = entry.map_err(|err| err.to_string())?.path();
+    if path.is_dir() {
+      zip_directory(writer, root, &path)?;
+      continue;
+    }
+    // The game holds this lock file open while the world is loaded.
+    if path.file_name().map(|name| name == "session.lock").unwrap_or(false) {
+      continue;
+    }
+    let relative = path
+      .strip_prefix(root)
+      .map_err(|err| err.to_string())?
+      .to_string_lossy()
+      .replace('\\', "/");
+    writer
+      .start_file(relative, FileOptions::default())
+      .map_err(|err| err.to_string())?;
+    let mut source = fs::File::open(&path).map_err(|err| err.to_string())?;
+    io::copy(&mut source, writer).map_err(|err| err.to_string())?;
+  }
+  Ok(())
+}
+
+fn world_backups_root(instance_dir: &Path) -> PathBuf {
+  instance_dir.join(".monolith").join("world-backups")
+}
+
 fn snapshots_root(instance_dir: &Path) -> PathBuf {
   instance_dir.join(".monolith").join("snapshots")
 }
 
+fn mods_snapshots_root(instance_dir: &Path) -> PathBuf {
+  instance_dir.join(".monolith").join("mods-snapshots")
+}
+
+fn list_mod_files(mods_dir: &Path) -> Result<Vec<PathBuf>, String> {
+  if !mods_dir.is_dir() {
+    return Ok(Vec::new());
+  }
+  let mut files = Vec::new();
+  for entry in fs::read_dir(mods_dir).map_err(|err| err.to_string())? {
+    let path = entry.map_err(|err| err.to_string())?.path();
+    if path.is_file() {
+      files.push(path);
+    }
+  }
+  Ok(files)
+}
+
+fn hash_file(path: &Path) -> Result<String, String> {
+  let mut file = fs::File::open(path).map_err(|err| err.to_string())?;
+  let mut hasher = Sha256::new();
+  io::copy(&mut file, &mut hasher).map_err(|err| err.to_string())?;
+  Ok(format!("{:x}", hasher.finalize()))
+}
+
 fn sanitize_reason(reason: Option<String>) -> Option<String> {
   reason.and_then(|value| {
     let trimmed = value.trim();
@@ -947,3 +2247,111 @@ fn load_manifest(path: &Path) -> Option<InstanceManifest> {
   let data = fs::read_to_string(path).ok()?;
   serde_json::from_str(&data).ok()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{compare_mod_versions, summarize_gc_log, summarize_gc_text, version_range_allows};
+  use std::cmp::Ordering;
+  use std::fs;
+
+  #[test]
+  fn mod_versions_order_numerically() {
+    assert_eq!(compare_mod_versions("1.10.0", "1.9.2"), Ordering::Greater);
+    assert_eq!(compare_mod_versions("1.0", "1.0.0"), Ordering::Equal);
+    assert_eq!(compare_mod_versions("47.2.0", "47.10"), Ordering::Less);
+    assert_eq!(compare_mod_versions("1.20.1-47.2.0", "1.20.1-47.1.3"), Ordering::Greater);
+  }
+
+  #[test]
+  fn prereleases_sort_before_their_release() {
+    assert_eq!(compare_mod_versions("1.0.0-beta.2", "1.0.0"), Ordering::Less);
+    assert_eq!(compare_mod_versions("1.0.0-beta.10", "1.0.0-beta.2"), Ordering::Greater);
+    assert_eq!(compare_mod_versions("1.0.0-alpha.5", "1.0.0-beta.1"), Ordering::Less);
+    assert_eq!(compare_mod_versions("1.0.0-rc.1", "0.9.9"), Ordering::Greater);
+    assert_eq!(compare_mod_versions("1.0.0+build.7", "1.0.0"), Ordering::Equal);
+  }
+
+  #[test]
+  fn maven_ranges_respect_bounds() {
+    assert_eq!(version_range_allows("[47,)", "47.2.0"), Some(true));
+    assert_eq!(version_range_allows("[47,)", "46.0.1"), Some(false));
+    assert_eq!(version_range_allows("[1.0,2.0)", "2.0"), Some(false));
+    assert_eq!(version_range_allows("[1.0,2.0)", "1.9.9"), Some(true));
+    assert_eq!(version_range_allows("(1.0,2.0]", "1.0"), Some(false));
+    assert_eq!(version_range_allows("[1.0,2.0),[3.0,)", "3.1"), Some(true));
+    assert_eq!(version_range_allows("[1.0,2.0)", "2.0.0-beta.1"), Some(true));
+    assert_eq!(version_range_allows("[1.2.3]", "1.2.3"), Some(true));
+    assert_eq!(version_range_allows("1.2", "0.1"), Some(true));
+  }
+
+  #[test]
+  fn comparator_and_wildcard_ranges() {
+    assert_eq!(version_range_allows("*", "0.1"), Some(true));
+    assert_eq!(version_range_allows(">=0.15.0", "0.15.11"), Some(true));
+    assert_eq!(version_range_allows(">=0.15.0", "0.14.25"), Some(false));
+    assert_eq!(version_range_allows("<2", "1.9"), Some(true));
+    assert_eq!(version_range_allows(">1.0", "1.0"), Some(false));
+  }
+
+  #[test]
+  fn garbage_input_is_not_judged() {
+    assert_eq!(version_range_allows("[1.0", "1.0"), None);
+    assert_eq!(version_range_allows("[1.0,)", "${file.jarVersion}"), None);
+    assert_eq!(version_range_allows("[1.0,)", "unknown"), None);
+    assert_eq!(version_range_allows(">=latest", "1.0"), None);
+    assert_eq!(version_range_allows("[,]", "1.0"), Some(true));
+  }
+
+  const UNIFIED_LOG: &str = "\
+[2024-05-01T10:00:01.000+0000][1.002s][info][gc] Using G1
+[2024-05-01T10:00:02.000+0000][2.010s][info][gc] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 120M->40M(512M) 4.000ms
+[2024-05-01T10:00:05.000+0000][5.020s][info][gc] GC(1) Pause Young (Concurrent Start) (G1 Humongous Allocation) 200M->90M(512M) 6.000ms
+[2024-05-01T10:00:09.000+0000][9.030s][info][gc] GC(2) Pause Remark 100M->95M(512M) 2.000ms
+[2024-05-01T10:00:12.000+0000][12.040s][info][gc] GC(3) Pause Full (System.gc()) 300M->180M(512M) 180.000ms
+";
+
+  const LEGACY_LOG: &str = "\
+2024-05-01T10:00:02.000+0000: 1.234: [GC (Allocation Failure) [PSYoungGen: 65536K->10720K(76288K)] 65536K->10728K(251392K), 0.0120000 secs] [Times: user=0.03 sys=0.01, real=0.01 secs]
+2024-05-01T10:00:04.000+0000: 3.456: [Full GC (Ergonomics) [PSYoungGen: 10720K->0K(76288K)] [ParOldGen: 8K->102400K(175104K)] 10728K->102400K(251392K), [Metaspace: 3000K->3000K(1056768K)], 0.0480000 secs] [Times: user=0.10 sys=0.00, real=0.05 secs]
+Total time for which application threads were stopped: 0.0481000 seconds
+";
+
+  #[test]
+  fn reads_unified_g1_pauses() {
+    let summary = summarize_gc_text(UNIFIED_LOG).unwrap();
+    assert_eq!(summary.pause_count, 4);
+    assert_eq!(summary.full_gc_count, 1);
+    assert_eq!(summary.total_pause_ms, 192.0);
+    assert_eq!(summary.max_pause_ms, 180.0);
+    assert_eq!(summary.heap_capacity_mb, Some(512));
+    assert_eq!(summary.peak_heap_after_gc_mb, Some(180));
+  }
+
+  #[test]
+  fn reads_legacy_print_gc_details_pauses() {
+    let summary = summarize_gc_text(LEGACY_LOG).unwrap();
+    assert_eq!(summary.pause_count, 2);
+    assert_eq!(summary.full_gc_count, 1);
+    assert_eq!(summary.max_pause_ms, 48.0);
+    assert_eq!(summary.heap_capacity_mb, Some(245));
+    assert_eq!(summary.peak_heap_after_gc_mb, Some(100));
+  }
+
+  #[test]
+  fn includes_rotated_gc_logs() {
+    let instance_dir =
+      std::env::temp_dir().join(format!("monolith-gc-log-test-{}", std::process::id()));
+    let logs_dir = instance_dir.join("logs");
+    fs::create_dir_all(&logs_dir).unwrap();
+    assert!(summarize_gc_log(&instance_dir).is_err());
+    let lines: Vec<&str> = UNIFIED_LOG.lines().collect();
+    fs::write(logs_dir.join("gc.log.0"), lines[..3].join("\n")).unwrap();
+    fs::write(logs_dir.join("gc.log"), lines[3..].join("\n")).unwrap();
+    fs::write(logs_dir.join("gc.log.bak"), UNIFIED_LOG).unwrap();
+    let summary = summarize_gc_log(&instance_dir);
+    let _ = fs::remove_dir_all(&instance_dir);
+    let summary = summary.unwrap();
+    assert_eq!(summary.pause_count, 4);
+    assert_eq!(summary.full_gc_count, 1);
+  }
+}
@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, System};
+use tauri::{AppHandle, Emitter};
+
+use crate::config::Settings;
+use crate::minecraft::ProgressEvent;
+
+pub(crate) const OPERATION_EVENT: &str = "operation";
+
+pub(crate) const PROGRESS_EVENT: &str = "install:progress";
+
+/// The instance list changed: an instance was added, removed, renamed or re-pinned.
+pub(crate) const INSTANCES_CHANGED_EVENT: &str = "instances:changed";
+
+/// One instance's manifest or content changed without affecting the list.
+pub(crate) const INSTANCE_UPDATED_EVENT: &str = "instance:updated";
+
+static PROGRESS_EVENTS_PER_SECOND: AtomicU32 = AtomicU32::new(20);
+static ACTIVE_INSTANCE_OPERATIONS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+const OPERATION_LOCK_FILE: &str = ".monolith-operation.lock";
+
+#[derive(Serialize, Deserialize)]
+struct OperationLock {
+  pid: u32,
+  operation: String,
+}
+
+/// Serializes installs, repairs, launches and backups on one instance. The in-memory
+/// registry covers this process; the lock file in the instance folder covers other
+/// launcher processes sharing the same instance root.
+pub(crate) struct InstanceOperationGuard {
+  instance_id: String,
+  lock_path: PathBuf,
+}
+
+impl InstanceOperationGuard {
+  pub(crate) fn acquire(
+    instance_id: &str,
+    instance_dir: &Path,
+    operation: &str,
+  ) -> Result<Self, String> {
+    let active = ACTIVE_INSTANCE_OPERATIONS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut active = active
+      .lock()
+      .map_err(|_| "operation registry lock poisoned".to_string())?;
+    if let Some(running) = active.get(instance_id) {
+      return Err(format!("instance is busy: {} in progress", running));
+    }
+    let lock_path = instance_dir.join(OPERATION_LOCK_FILE);
+    write_operation_lock(&lock_path, operation)?;
+    active.insert(instance_id.to_string(), operation.to_string());
+    Ok(Self {
+      instance_id: instance_id.to_string(),
+      lock_path,
+    })
+  }
+
+  /// Points the guard at the lock file after the instance folder was moved.
+  pub(crate) fn relocate(&mut self, instance_dir: &Path) {
+    self.lock_path = instance_dir.join(OPERATION_LOCK_FILE);
+  }
+}
+
+fn write_operation_lock(lock_path: &Path, operation: &str) -> Result<(), String> {
+  let payload = serde_json::to_vec(&OperationLock {
+    pid: std::process::id(),
+    operation: operation.to_string(),
+  })
+  .map_err(|err| err.to_string())?;
+  for _ in 0..2 {
+    match OpenOptions::new().write(true).create_new(true).open(lock_path) {
+      Ok(mut file) => return file.write_all(&payload).map_err(|err| err.to_string()),
+      Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+        let holder = fs::read_to_string(lock_path)
+          .ok()
+          .and_then(|data| serde_json::from_str::<OperationLock>(&data).ok());
+        match holder {
+          Some(holder) if holder.pid != std::process::id() && process_alive(holder.pid) => {
+            return Err(format!(
+              "instance is busy: {} in progress in another launcher (pid {})",
+              holder.operation, holder.pid
+            ));
+          }
+          // Left behind by a crashed launcher, or by this process before a restart.
+          _ => {
+            let _ = fs::remove_file(lock_path);
+          }
+        }
+      }
+      // The instance folder does not exist yet; the operation reports that itself.
+      Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+      Err(err) => return Err(format!("could not create the instance lock: {}", err)),
+    }
+  }
+  Err("could not acquire the instance lock".to_string())
+}
+
+fn process_alive(pid: u32) -> bool {
+  let mut system = System::new();
+  system.refresh_process(Pid::from_u32(pid))
+}
+
+impl Drop for InstanceOperationGuard {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.lock_path);
+    if let Some(active) = ACTIVE_INSTANCE_OPERATIONS.get() {
+      if let Ok(mut active) = active.lock() {
+        active.remove(&self.instance_id);
+      }
+    }
+  }
+}
+
+pub(crate) fn apply_progress_settings(settings: &Settings) {
+  PROGRESS_EVENTS_PER_SECOND.store(settings.progress_events_per_second.max(1), Ordering::Relaxed);
+}
+
+fn progress_interval() -> Duration {
+  let rate = PROGRESS_EVENTS_PER_SECOND.load(Ordering::Relaxed).max(1);
+  Duration::from_millis(1000 / rate as u64)
+}
+
+#[derive(Clone, Serialize)]
+struct OperationEnvelope<T: Serialize> {
+  instance_id: Option<String>,
+  operation_id: String,
+  kind: String,
+  payload: T,
+}
+
+/// When the operation and each of its progress stages were first seen.
+struct StageClock {
+  started: Instant,
+  stages: HashMap<String, Instant>,
+  current: Option<String>,
+}
+
+#[derive(Clone)]
+pub(crate) struct OperationEmitter {
+  app: AppHandle,
+  /// Shared by clones so an id bound mid-operation reaches every emitter of it.
+  instance_id: Arc<Mutex<Option<String>>>,
+  operation_id: String,
+  last_progress: Arc<Mutex<HashMap<String, Instant>>>,
+  stage_clock: Arc<Mutex<StageClock>>,
+}
+
+impl OperationEmitter {
+  pub(crate) fn new(app: &AppHandle, instance_id: Option<&str>) -> Self {
+    Self {
+      app: app.clone(),
+      instance_id: Arc::new(Mutex::new(instance_id.map(|value| value.to_string()))),
+      operation_id: format!("{:016x}", rand::random::<u64>()),
+      last_progress: Arc::new(Mutex::new(HashMap::new())),
+      stage_clock: Arc::new(Mutex::new(StageClock {
+        started: Instant::now(),
+        stages: HashMap::new(),
+        current: None,
+      })),
+    }
+  }
+
+  /// Attributes this and every cloned emitter to an instance whose id was only
+  /// allocated after the operation started, e.g. by `create_instance`.
+  pub(crate) fn bind_instance(&self, instance_id: &str) {
+    let mut bound = match self.instance_id.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    *bound = Some(instance_id.to_string());
+  }
+
+  pub(crate) fn emit_progress(&self, mut event: ProgressEvent) {
+    self.stamp_elapsed(&mut event);
+    // Unsized progress has no final step to protect, so all of it is rate-limited.
+    let intermediate = match event.total {
+      Some(total) => event.current > 0 && event.current < total,
+      None => true,
+    };
+    if intermediate {
+      let now = Instant::now();
+      let mut last = match self.last_progress.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+      };
+      if let Some(previous) = last.get(&event.stage) {
+        if now.duration_since(*previous) < progress_interval() {
+          return;
+        }
+      }
+      last.insert(event.stage.clone(), now);
+    }
+    self.emit(PROGRESS_EVENT, event);
+  }
+
+  fn stamp_elapsed(&self, event: &mut ProgressEvent) {
+    let now = Instant::now();
+    let mut clock = match self.stage_clock.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    if clock.current.as_deref() != Some(event.stage.as_str()) {
+      if let Some(previous) = clock.current.take() {
+        if let Some(started) = clock.stages.get(&previous) {
+          log::info!(
+            "operation {} stage {}: {}",
+            self.operation_id,
+            previous,
+            format_elapsed(now.duration_since(*started))
+          );
+        }
+      }
+      clock.current = Some(event.stage.clone());
+    }
+    let stage_started = *clock.stages.entry(event.stage.clone()).or_insert(now);
+    event.stage_elapsed_ms = Some(now.duration_since(stage_started).as_millis() as u64);
+    event.total_elapsed_ms = Some(now.duration_since(clock.started).as_millis() as u64);
+  }
+
+  pub(crate) fn emit<T: Serialize + Clone>(&self, kind: &str, payload: T) {
+    let instance_id = match self.instance_id.lock() {
+      Ok(guard) => guard.clone(),
+      Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let envelope = OperationEnvelope {
+      instance_id,
+      operation_id: self.operation_id.clone(),
+      kind: kind.to_string(),
+      payload,
+    };
+    let _ = self.app.emit(OPERATION_EVENT, envelope);
+  }
+}
+
+/// Compact duration for logs, e.g. `850ms`, `12s`, `2m10s`.
+pub(crate) fn format_elapsed(elapsed: Duration) -> String {
+  let secs = elapsed.as_secs();
+  if secs == 0 {
+    return format!("{}ms", elapsed.as_millis());
+  }
+  if secs < 60 {
+    return format!("{}s", secs);
+  }
+  format!("{}m{:02}s", secs / 60, secs % 60)
+}
+
+#[derive(Clone, Serialize)]
+pub(crate) struct InstanceChangeEvent {
+  instance_id: String,
+  /// Short tag for what changed, e.g. `settings`, `worlds`, `removed`.
+  change: String,
+  /// Set when a rename relocated the instance to a new id.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  previous_id: Option<String>,
+}
+
+pub(crate) fn emit_instances_changed(app: &AppHandle, instance_id: &str, change: &str) {
+  emit_instance_change(app, INSTANCES_CHANGED_EVENT, instance_id, change, None);
+}
+
+pub(crate) fn emit_instance_updated(app: &AppHandle, instance_id: &str, change: &str) {
+  emit_instance_change(app, INSTANCE_UPDATED_EVENT, instance_id, change, None);
+}
+
+pub(crate) fn emit_instance_renamed(app: &AppHandle, instance_id: &str, previous_id: &str) {
+  let previous_id = (previous_id != instance_id).then(|| previous_id.to_string());
+  emit_instance_change(app, INSTANCES_CHANGED_EVENT, instance_id, "renamed", previous_id);
+}
+
+fn emit_instance_change(
+  app: &AppHandle,
+  event: &str,
+  instance_id: &str,
+  change: &str,
+  previous_id: Option<String>,
+) {
+  let payload = InstanceChangeEvent {
+    instance_id: instance_id.to_string(),
+    change: change.to_string(),
+    previous_id,
+  };
+  let _ = app.emit(event, payload);
+}
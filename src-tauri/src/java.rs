@@ -1,13 +1,27 @@
-use crate::config::{AppConfig, Instance, JavaRuntimeEntry};
+use crate::config::{split_jvm_args, AppConfig, Instance, JavaRuntimeEntry};
 use regex::Regex;
 use std::{
-  collections::HashSet,
+  collections::{HashMap, HashSet},
   env,
   fs,
+  io::Read,
   path::{Path, PathBuf},
-  process::Command,
+  process::{Command, Stdio},
+  sync::atomic::{AtomicU64, Ordering},
+  sync::{Mutex, OnceLock},
+  thread,
+  time::{Duration, Instant},
 };
 
+const SMOKE_TEST_CLASS_NAME: &str = "MonolithJavaTest";
+const SMOKE_TEST_CLASS: &[u8] = include_bytes!("../assets/MonolithJavaTest.class");
+const SMOKE_TEST_TIMEOUT_SECS: u64 = 30;
+
+static SMOKE_TEST_RUNS: AtomicU64 = AtomicU64::new(0);
+static JAVA_IS_32BIT: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+static JAVA_UNDER_ROSETTA: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+static HOST_IS_APPLE_SILICON: OnceLock<bool> = OnceLock::new();
+
 #[derive(Clone, serde::Serialize)]
 pub(crate) struct ResolvedJavaRuntime {
   pub path: String,
@@ -27,6 +41,202 @@ pub(crate) fn detect_java_version(java_cmd: &str) -> Option<String> {
     .map(|m| m.as_str().trim().to_string())
 }
 
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct JavaInstallDetails {
+  pub vendor: Option<String>,
+  pub full_version: Option<String>,
+  pub arch: Option<String>,
+  pub is_jdk: bool,
+}
+
+fn read_java_properties(java_cmd: &str) -> Option<HashMap<String, String>> {
+  let output = Command::new(java_cmd)
+    .arg("-XshowSettings:properties")
+    .arg("-version")
+    .output()
+    .ok()?;
+  let combined = String::from_utf8_lossy(&output.stderr).to_string()
+    + &String::from_utf8_lossy(&output.stdout);
+  let properties: HashMap<String, String> = combined
+    .lines()
+    .filter_map(|line| {
+      let (key, value) = line.trim().split_once('=')?;
+      Some((key.trim().to_string(), value.trim().to_string()))
+    })
+    .collect();
+  if properties.is_empty() {
+    None
+  } else {
+    Some(properties)
+  }
+}
+
+fn detect_java_property(java_cmd: &str, property: &str) -> Option<String> {
+  read_java_properties(java_cmd)?.remove(property)
+}
+
+pub(crate) fn detect_java_details(java_cmd: &str) -> Option<JavaInstallDetails> {
+  let binary = resolve_java_binary_path(Path::new(java_cmd));
+  let mut properties = read_java_properties(&binary.to_string_lossy())?;
+  let is_jdk = properties
+    .get("java.home")
+    .map(|home| {
+      let javac = if cfg!(target_os = "windows") { "javac.exe" } else { "javac" };
+      Path::new(home).join("bin").join(javac).is_file()
+    })
+    .unwrap_or(false);
+  Some(JavaInstallDetails {
+    vendor: properties
+      .remove("java.vendor")
+      .or_else(|| properties.remove("java.vm.vendor")),
+    full_version: properties
+      .remove("java.runtime.version")
+      .or_else(|| properties.remove("java.version")),
+    arch: properties.remove("os.arch"),
+    is_jdk,
+  })
+}
+
+pub(crate) fn detect_java_arch(java_cmd: &str) -> Option<String> {
+  detect_java_property(java_cmd, "os.arch")
+}
+
+pub(crate) fn detect_java_is_32bit(java_cmd: &str) -> bool {
+  cached_java_probe(&JAVA_IS_32BIT, java_cmd, |java_cmd| {
+    detect_java_property(java_cmd, "sun.arch.data.model").as_deref() == Some("32")
+  })
+}
+
+/// Runs `probe` once per runtime binary; later calls reuse the answer.
+fn cached_java_probe(
+  cache: &OnceLock<Mutex<HashMap<PathBuf, bool>>>,
+  java_cmd: &str,
+  probe: impl FnOnce(&str) -> bool,
+) -> bool {
+  let key = resolve_java_binary_path(Path::new(java_cmd.trim()));
+  let cache = cache.get_or_init(|| Mutex::new(HashMap::new()));
+  if let Some(value) = cache.lock().ok().and_then(|cache| cache.get(&key).copied()) {
+    return value;
+  }
+  let value = probe(java_cmd);
+  if let Ok(mut cache) = cache.lock() {
+    cache.insert(key, value);
+  }
+  value
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct JavaSmokeTestResult {
+  pub success: bool,
+  pub exit_code: Option<i32>,
+  pub stdout: String,
+  pub stderr: String,
+  pub duration_ms: u64,
+}
+
+pub(crate) fn run_java_smoke_test(
+  java_path: &str,
+  jvm_args: &str,
+) -> Result<JavaSmokeTestResult, String> {
+  let binary = resolve_java_binary_path(Path::new(java_path.trim()));
+  if !binary.is_file() && find_in_path(&binary.to_string_lossy()).is_none() {
+    return Err(format!("Java executable not found at {}.", binary.display()));
+  }
+
+  let class_dir = env::temp_dir().join(format!(
+    "monolith-java-test-{}-{}",
+    std::process::id(),
+    SMOKE_TEST_RUNS.fetch_add(1, Ordering::Relaxed)
+  ));
+  fs::create_dir_all(&class_dir).map_err(|err| err.to_string())?;
+  fs::write(
+    class_dir.join(format!("{}.class", SMOKE_TEST_CLASS_NAME)),
+    SMOKE_TEST_CLASS,
+  )
+  .map_err(|err| err.to_string())?;
+
+  let started = Instant::now();
+  let mut child = Command::new(&binary)
+    .args(split_jvm_args(jvm_args))
+    .arg("-cp")
+    .arg(&class_dir)
+    .arg(SMOKE_TEST_CLASS_NAME)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| format!("failed to start {}: {}", binary.display(), err))?;
+
+  // Drained on threads so a chatty JVM can't fill the pipe and stall until the timeout.
+  let stdout_reader = drain_pipe(child.stdout.take());
+  let stderr_reader = drain_pipe(child.stderr.take());
+  let timeout = Duration::from_secs(SMOKE_TEST_TIMEOUT_SECS);
+  let status = loop {
+    match child.try_wait().map_err(|err| err.to_string())? {
+      Some(status) => break Some(status),
+      None if started.elapsed() >= timeout => {
+        let _ = child.kill();
+        let _ = child.wait();
+        break None;
+      }
+      None => thread::sleep(Duration::from_millis(50)),
+    }
+  };
+
+  let stdout = stdout_reader.join().unwrap_or_default();
+  let mut stderr = stderr_reader.join().unwrap_or_default();
+  let _ = fs::remove_dir_all(&class_dir);
+
+  if status.is_none() {
+    stderr.push_str(&format!(
+      "\nJava did not exit within {} seconds and was stopped.",
+      SMOKE_TEST_TIMEOUT_SECS
+    ));
+  }
+
+  Ok(JavaSmokeTestResult {
+    success: status.map(|status| status.success()).unwrap_or(false),
+    exit_code: status.and_then(|status| status.code()),
+    stdout,
+    stderr,
+    duration_ms: started.elapsed().as_millis() as u64,
+  })
+}
+
+fn drain_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+  thread::spawn(move || {
+    let mut output = String::new();
+    if let Some(mut pipe) = pipe {
+      let _ = pipe.read_to_string(&mut output);
+    }
+    output
+  })
+}
+
+pub(crate) fn host_is_apple_silicon() -> bool {
+  if !cfg!(target_os = "macos") {
+    return false;
+  }
+  *HOST_IS_APPLE_SILICON.get_or_init(|| {
+    Command::new("sysctl")
+      .args(["-n", "hw.optional.arm64"])
+      .output()
+      .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+      .unwrap_or(false)
+  })
+}
+
+pub(crate) fn java_runs_under_rosetta(java_cmd: &str) -> bool {
+  if !host_is_apple_silicon() {
+    return false;
+  }
+  cached_java_probe(&JAVA_UNDER_ROSETTA, java_cmd, |java_cmd| {
+    matches!(
+      detect_java_arch(java_cmd).as_deref(),
+      Some("x86_64") | Some("amd64")
+    )
+  })
+}
+
 pub(crate) fn parse_java_major(version: &str) -> Option<u32> {
   let trimmed = version.trim();
   if trimmed.is_empty() {
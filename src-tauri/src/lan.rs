@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tauri::Manager;
+
+use crate::events::OperationEmitter;
+
+const LAN_MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 2, 60);
+const LAN_MULTICAST_PORT: u16 = 4445;
+const LAN_GAME_EXPIRY_SECS: u64 = 15;
+const LAN_POLL_INTERVAL_MS: u64 = 1000;
+
+static LAN_GAMES: OnceLock<Mutex<HashMap<String, LanGame>>> = OnceLock::new();
+static LAN_LISTENER_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct LanGame {
+  address: String,
+  host: String,
+  port: u16,
+  motd: String,
+  last_seen_unix: u64,
+}
+
+fn lan_games() -> &'static Mutex<HashMap<String, LanGame>> {
+  LAN_GAMES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn now_unix() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|value| value.as_secs())
+    .unwrap_or(0)
+}
+
+fn extract_tag<'a>(payload: &'a str, tag: &str) -> Option<&'a str> {
+  let open = format!("[{}]", tag);
+  let close = format!("[/{}]", tag);
+  let start = payload.find(&open)? + open.len();
+  let end = payload[start..].find(&close)? + start;
+  Some(&payload[start..end])
+}
+
+fn parse_announcement(payload: &str, sender: SocketAddr) -> Option<LanGame> {
+  let motd = extract_tag(payload, "MOTD").unwrap_or("LAN World").trim().to_string();
+  let port = extract_tag(payload, "AD")?.trim().parse::<u16>().ok()?;
+  let host = sender.ip().to_string();
+  Some(LanGame {
+    address: format!("{}:{}", host, port),
+    host,
+    port,
+    motd,
+    last_seen_unix: now_unix(),
+  })
+}
+
+fn bind_lan_socket() -> Result<UdpSocket, String> {
+  let socket =
+    Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP)).map_err(|err| err.to_string())?;
+  // The game binds the same port for its own LAN list, so share it instead of taking it over.
+  socket.set_reuse_address(true).map_err(|err| err.to_string())?;
+  #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+  socket.set_reuse_port(true).map_err(|err| err.to_string())?;
+  let bind_addr = SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, LAN_MULTICAST_PORT);
+  socket
+    .bind(&SocketAddr::V4(bind_addr).into())
+    .map_err(|err| err.to_string())?;
+  socket
+    .join_multicast_v4(&LAN_MULTICAST_GROUP, &Ipv4Addr::UNSPECIFIED)
+    .map_err(|err| err.to_string())?;
+  socket
+    .set_read_timeout(Some(Duration::from_millis(LAN_POLL_INTERVAL_MS)))
+    .map_err(|err| err.to_string())?;
+  Ok(socket.into())
+}
+
+fn any_instance_running(app: &tauri::AppHandle) -> bool {
+  let running = app.state::<Mutex<HashMap<String, u32>>>();
+  let map = match running.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  !map.is_empty()
+}
+
+fn snapshot_games() -> Vec<LanGame> {
+  let games = match lan_games().lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  let mut list = games.values().cloned().collect::<Vec<_>>();
+  list.sort_by_key(|game| game.motd.to_lowercase());
+  list
+}
+
+fn record_game(game: LanGame) -> bool {
+  let mut games = match lan_games().lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  let changed = games
+    .get(&game.address)
+    .map(|existing| existing.motd != game.motd)
+    .unwrap_or(true);
+  games.insert(game.address.clone(), game);
+  changed
+}
+
+fn prune_games(max_age_secs: u64) -> bool {
+  let cutoff = now_unix().saturating_sub(max_age_secs);
+  let mut games = match lan_games().lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  let before = games.len();
+  games.retain(|_, game| game.last_seen_unix >= cutoff);
+  games.len() != before
+}
+
+fn clear_games() {
+  let mut games = match lan_games().lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  games.clear();
+}
+
+/// Listens for LAN world announcements until no instance is running anymore.
+pub(crate) fn start_lan_discovery(app: tauri::AppHandle) {
+  if LAN_LISTENER_ACTIVE.swap(true, Ordering::SeqCst) {
+    return;
+  }
+  thread::spawn(move || {
+    let ops = OperationEmitter::new(&app, None);
+    let socket = match bind_lan_socket() {
+      Ok(socket) => socket,
+      Err(err) => {
+        log::warn!("LAN discovery unavailable: {}", err);
+        LAN_LISTENER_ACTIVE.store(false, Ordering::SeqCst);
+        return;
+      }
+    };
+    let mut buffer = [0u8; 1024];
+    while any_instance_running(&app) {
+      let mut changed = match socket.recv_from(&mut buffer) {
+        Ok((len, sender)) => {
+          let payload = String::from_utf8_lossy(&buffer[..len]);
+          match parse_announcement(&payload, sender) {
+            Some(game) => record_game(game),
+            None => false,
+          }
+        }
+        Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => false,
+        Err(err) => {
+          log::warn!("LAN discovery stopped: {}", err);
+          break;
+        }
+      };
+      changed |= prune_games(LAN_GAME_EXPIRY_SECS);
+      if changed {
+        ops.emit("lan:games", snapshot_games());
+      }
+    }
+    clear_games();
+    ops.emit("lan:games", Vec::<LanGame>::new());
+    LAN_LISTENER_ACTIVE.store(false, Ordering::SeqCst);
+  });
+}
+
+#[tauri::command]
+pub(crate) fn list_lan_games() -> Vec<LanGame> {
+  prune_games(LAN_GAME_EXPIRY_SECS);
+  snapshot_games()
+}
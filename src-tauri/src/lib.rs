@@ -1,29 +1,48 @@
 mod config;
 mod commands;
 mod diagnostics;
+mod events;
 mod java;
+mod lan;
 mod minecraft;
 mod modrinth;
+mod scheduler;
+mod sessions;
+mod sync;
+mod watcher;
 
-use config::{AppConfig, ConfigStore, DiscordPresenceMode, Instance, Loader};
+use config::{
+  AppConfig, ConfigStore, DiscordPresenceMode, InstallSummary, Instance, InstanceKind, Loader,
+};
+use commands::server_instances::ServerConsole;
 use diagnostics::classify_launch_failure;
+use events::{InstanceOperationGuard, OperationEmitter};
 use minecraft::{
-  create_instance as create_instance_impl, list_fabric_game_versions as list_fabric_games_impl,
+  create_instance as create_instance_impl, ensure_instance_ready,
+  fetch_version_patch_notes as fetch_patch_notes_impl,
+  list_fabric_game_versions as list_fabric_games_impl,
   list_fabric_loader_versions as list_fabric_loaders_impl,
   list_forge_versions as list_forge_versions_impl,
   list_neoforge_versions as list_neoforge_versions_impl,
+  list_quilt_loader_versions as list_quilt_loaders_impl,
+  list_server_builds as list_server_builds_impl,
+  list_server_game_versions as list_server_game_versions_impl,
+  list_supported_loaders as list_supported_loaders_impl,
   list_vanilla_versions as list_vanilla_versions_impl, launch_instance as launch_instance_impl,
-  ForgeVersionSummary, LoaderVersionSummary, NewInstanceRequest, ProgressEvent, VersionSummary,
+  ForgeVersionSummary, LoaderAvailability, LoaderVersionSummary, NewInstanceRequest, ProgressEvent,
+  VersionPatchNotes, VersionRangeFilter, VersionSummary,
 };
 use std::{
-  collections::HashMap,
+  cell::RefCell,
+  collections::{BTreeSet, HashMap},
   io::{Read, Write},
   net::TcpListener,
-  path::PathBuf,
+  path::{Path, PathBuf},
   process::Command,
+  sync::atomic::{AtomicBool, Ordering},
   sync::{Arc, Mutex, mpsc},
   thread,
-  time::{SystemTime, UNIX_EPOCH},
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use serde::Serialize;
 use tauri::{Emitter, Manager};
@@ -87,6 +106,14 @@ fn discord_track_runtime_signal(
   guard.update_runtime_signal(line);
 }
 
+/// Log markers printed once the client has a session and its render thread is up.
+fn is_game_ready_line(line: &str) -> bool {
+  let lower = line.to_ascii_lowercase();
+  lower.contains("setting user:")
+    || (lower.contains("render thread") && lower.contains("backend library:"))
+    || lower.contains("sound engine started")
+}
+
 fn resolve_presence_instance_label(instance: &Instance) -> String {
   format!("{} {}", loader_presence_label(&instance.loader), instance.version)
 }
@@ -95,8 +122,12 @@ fn loader_presence_label(loader: &Loader) -> &'static str {
   match loader {
     Loader::Vanilla => "Vanilla",
     Loader::Fabric => "Fabric",
+    Loader::Quilt => "Quilt",
     Loader::Forge => "Forge",
     Loader::NeoForge => "NeoForge",
+    Loader::Paper => "Paper",
+    Loader::Folia => "Folia",
+    Loader::Purpur => "Purpur",
   }
 }
 
@@ -237,6 +268,9 @@ fn generate_pkce_pair() -> Result<(String, String), String> {
   Ok((verifier, challenge))
 }
 
+const MICROSOFT_LOGIN_TIMEOUT_SECS: u64 = 300;
+const MICROSOFT_LOGIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 #[tauri::command]
 fn start_microsoft_login(
   window: tauri::Window,
@@ -246,7 +280,12 @@ fn start_microsoft_login(
   let redirect_uri = "http://localhost:6542";
   let scope = "XboxLive.signin offline_access openid profile";
   let (verifier, challenge) = generate_pkce_pair()?;
+  let cancel = Arc::new(AtomicBool::new(false));
   if let Ok(mut state) = login_state.lock() {
+    // A second sign-in replaces the first; its listener has to release the port.
+    if let Some(previous) = state.cancel.replace(cancel.clone()) {
+      previous.store(true, Ordering::SeqCst);
+    }
     state.code_verifier = Some(verifier);
   }
   let authorize_url = format!(
@@ -259,29 +298,108 @@ fn start_microsoft_login(
 
   let handle = window.clone();
   thread::spawn(move || {
-    let listener = match TcpListener::bind("127.0.0.1:6542") {
-      Ok(listener) => listener,
-      Err(_) => {
-        let _ = handle.emit("microsoft:error", "Unable to bind localhost:6542");
+    let Some(listener) = bind_login_listener() else {
+      let _ = handle.emit("microsoft:error", "Unable to bind localhost:6542");
+      return;
+    };
+    let deadline = Instant::now() + Duration::from_secs(MICROSOFT_LOGIN_TIMEOUT_SECS);
+    loop {
+      if cancel.load(Ordering::SeqCst) {
         return;
       }
-    };
-    if let Ok((mut stream, _)) = listener.accept() {
-      let mut buffer = [0u8; 8192];
-      let read = stream.read(&mut buffer).unwrap_or(0);
-      let request = String::from_utf8_lossy(&buffer[..read]).to_string();
-      if let Some(code) = parse_code_from_request(&request) {
-        let _ = handle.emit("microsoft:code", code);
-      } else {
-        let _ = handle.emit("microsoft:error", "Missing code in callback");
+      if Instant::now() >= deadline {
+        release_microsoft_login(&handle, &cancel, true);
+        let _ = handle.emit("microsoft:error", "Microsoft sign-in timed out");
+        return;
+      }
+      match listener.accept() {
+        Ok((mut stream, _)) => {
+          release_microsoft_login(&handle, &cancel, false);
+          let _ = stream.set_nonblocking(false);
+          let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+          let mut buffer = [0u8; 8192];
+          let read = stream.read(&mut buffer).unwrap_or(0);
+          let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+          if let Some(code) = parse_code_from_request(&request) {
+            let _ = handle.emit("microsoft:code", code);
+          } else {
+            let _ = handle.emit("microsoft:error", "Missing code in callback");
+          }
+          respond_ok(stream);
+          return;
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+          thread::sleep(MICROSOFT_LOGIN_POLL_INTERVAL);
+        }
+        Err(_) => {
+          release_microsoft_login(&handle, &cancel, true);
+          let _ = handle.emit("microsoft:error", "Microsoft sign-in callback failed");
+          return;
+        }
       }
-      respond_ok(stream);
     }
   });
 
   Ok(authorize_url)
 }
 
+/// Stops a pending sign-in: the callback listener exits on its next poll and frees the
+/// port, and the PKCE verifier is dropped so a stale code can no longer be redeemed.
+#[tauri::command]
+fn cancel_microsoft_login(
+  window: tauri::Window,
+  login_state: tauri::State<'_, Mutex<MicrosoftLoginState>>,
+) -> Result<bool, String> {
+  let mut state = login_state
+    .lock()
+    .map_err(|_| "login state lock poisoned".to_string())?;
+  state.code_verifier = None;
+  let Some(cancel) = state.cancel.take() else {
+    return Ok(false);
+  };
+  cancel.store(true, Ordering::SeqCst);
+  let _ = window.emit("microsoft:cancelled", ());
+  Ok(true)
+}
+
+/// Non-blocking so the listener thread can notice cancellation. Retries briefly because
+/// a replaced sign-in may still hold the port until its next poll.
+fn bind_login_listener() -> Option<TcpListener> {
+  for _ in 0..10 {
+    if let Ok(listener) = TcpListener::bind("127.0.0.1:6542") {
+      if listener.set_nonblocking(true).is_ok() {
+        return Some(listener);
+      }
+      return None;
+    }
+    thread::sleep(MICROSOFT_LOGIN_POLL_INTERVAL);
+  }
+  None
+}
+
+/// Detaches the listener from the login state, provided it still belongs to the attempt
+/// holding `cancel`. The verifier survives a received callback for the token exchange.
+fn release_microsoft_login(
+  window: &tauri::Window,
+  cancel: &Arc<AtomicBool>,
+  clear_verifier: bool,
+) {
+  let login_state = window.state::<Mutex<MicrosoftLoginState>>();
+  let Ok(mut state) = login_state.lock() else {
+    return;
+  };
+  if state
+    .cancel
+    .as_ref()
+    .is_some_and(|current| Arc::ptr_eq(current, cancel))
+  {
+    state.cancel = None;
+    if clear_verifier {
+      state.code_verifier = None;
+    }
+  }
+}
+
 fn format_ureq_error(err: ureq::Error) -> String {
   match err {
     ureq::Error::Status(code, response) => {
@@ -311,7 +429,9 @@ fn post_json<T: serde::Serialize>(url: &str, body: &T) -> Result<ureq::Response,
     .map_err(format_ureq_error)
 }
 
-fn minecraft_login_with_microsoft(access_token: &str) -> Result<MinecraftLoginResponse, String> {
+fn minecraft_login_with_microsoft(
+  access_token: &str,
+) -> Result<(MinecraftLoginResponse, Option<String>), String> {
   let xbl_body = serde_json::json!({
     "Properties": {
       "AuthMethod": "RPS",
@@ -335,6 +455,7 @@ fn minecraft_login_with_microsoft(access_token: &str) -> Result<MinecraftLoginRe
     .ok_or_else(|| "missing xbox user hash".to_string())?
     .uhs
     .clone();
+  let gamertag = fetch_xbox_gamertag(&xbl_response.token).ok().flatten();
 
   let xsts_body = serde_json::json!({
     "Properties": {
@@ -360,7 +481,29 @@ fn minecraft_login_with_microsoft(access_token: &str) -> Result<MinecraftLoginRe
   .into_json()
   .map_err(|err| err.to_string())?;
 
-  Ok(mc_response)
+  Ok((mc_response, gamertag))
+}
+
+fn fetch_xbox_gamertag(xbl_token: &str) -> Result<Option<String>, String> {
+  let body = serde_json::json!({
+    "Properties": {
+      "SandboxId": "RETAIL",
+      "UserTokens": [xbl_token]
+    },
+    "RelyingParty": "http://xboxlive.com",
+    "TokenType": "JWT"
+  });
+  let response: XblResponse = post_json("https://xsts.auth.xboxlive.com/xsts/authorize", &body)?
+    .into_json()
+    .map_err(|err| err.to_string())?;
+  Ok(
+    response
+      .display_claims
+      .xui
+      .into_iter()
+      .find_map(|user| user.gtg)
+      .filter(|gamertag| !gamertag.trim().is_empty()),
+  )
 }
 
 fn refresh_microsoft_token(
@@ -423,7 +566,7 @@ fn complete_microsoft_login(
   .into_json()
   .map_err(|err| err.to_string())?;
 
-  let mc_response = minecraft_login_with_microsoft(&token_response.access_token)?;
+  let (mc_response, gamertag) = minecraft_login_with_microsoft(&token_response.access_token)?;
 
   let profile: MinecraftProfile = ureq::get("https://api.minecraftservices.com/minecraft/profile")
     .set("Authorization", &format!("Bearer {}", mc_response.access_token))
@@ -451,6 +594,7 @@ fn complete_microsoft_login(
     expires_at: Some(expires_at),
     uuid: Some(profile.id),
     owns_minecraft,
+    gamertag,
   };
 
   let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
@@ -462,41 +606,69 @@ fn complete_microsoft_login(
   Ok(account)
 }
 
-fn refresh_microsoft_accounts_inner(config: &mut AppConfig) -> Result<usize, String> {
+fn refresh_microsoft_accounts_inner(config: &mut AppConfig) -> Vec<AccountRefreshStatus> {
   let client_id = config.settings.microsoft_client_id.clone();
   let now = SystemTime::now()
     .duration_since(UNIX_EPOCH)
     .unwrap_or_default()
     .as_secs();
-  let mut refreshed = 0usize;
 
-  for account in &mut config.accounts {
+  let mut statuses = Vec::new();
+  let mut pending = Vec::new();
+  for (index, account) in config.accounts.iter().enumerate() {
     if account.kind != config::AccountKind::Microsoft {
       continue;
     }
-    let needs_refresh = account
-      .expires_at
-      .map(|ts| ts <= now.saturating_add(60))
-      .unwrap_or(true)
-      || account.access_token.is_none();
-    if !needs_refresh {
-      continue;
+    let needs_refresh = account.session_expiring(now);
+    let refresh_token = account.refresh_token.clone().filter(|_| needs_refresh);
+    match refresh_token {
+      Some(token) => pending.push((index, token)),
+      None => statuses.push(AccountRefreshStatus {
+        account_id: account.id.clone(),
+        display_name: account.display_name.clone(),
+        status: if needs_refresh { "error" } else { "valid" }.to_string(),
+        error: needs_refresh.then(|| "no refresh token stored; please re-login".to_string()),
+      }),
     }
-    let refresh = match &account.refresh_token {
-      Some(token) => token.clone(),
-      None => continue,
+  }
+
+  let results: Vec<(usize, Result<RefreshedMicrosoftSession, String>)> = thread::scope(|scope| {
+    let handles: Vec<_> = pending
+      .iter()
+      .map(|(index, token)| {
+        let client_id = client_id.as_str();
+        (
+          *index,
+          scope.spawn(move || refresh_microsoft_session(client_id, token)),
+        )
+      })
+      .collect();
+    handles
+      .into_iter()
+      .map(|(index, handle)| {
+        let result = handle
+          .join()
+          .unwrap_or_else(|_| Err("account refresh task failed".to_string()));
+        (index, result)
+      })
+      .collect()
+  });
+
+  for (index, result) in results {
+    let account = &mut config.accounts[index];
+    let error = match result {
+      Ok(session) => {
+        apply_refreshed_session(account, session, now);
+        None
+      }
+      Err(err) => Some(err),
     };
-    let token_response = refresh_microsoft_token(&client_id, &refresh)?;
-    let mc_response = minecraft_login_with_microsoft(&token_response.access_token)?;
-    account.access_token = Some(mc_response.access_token);
-    if let Some(token) = account.access_token.as_ref() {
-      account.owns_minecraft = check_entitlements(token).ok();
-    }
-    if let Some(next_refresh) = token_response.refresh_token {
-      account.refresh_token = Some(next_refresh);
-    }
-    account.expires_at = Some(now.saturating_add(mc_response.expires_in));
-    refreshed += 1;
+    statuses.push(AccountRefreshStatus {
+      account_id: account.id.clone(),
+      display_name: account.display_name.clone(),
+      status: if error.is_some() { "error" } else { "refreshed" }.to_string(),
+      error,
+    });
   }
 
   for account in &mut config.accounts {
@@ -511,7 +683,39 @@ fn refresh_microsoft_accounts_inner(config: &mut AppConfig) -> Result<usize, Str
     }
   }
 
-  Ok(refreshed)
+  statuses
+}
+
+fn apply_refreshed_session(
+  account: &mut config::Account,
+  session: RefreshedMicrosoftSession,
+  now: u64,
+) {
+  account.access_token = Some(session.access_token);
+  account.owns_minecraft = session.owns_minecraft;
+  if let Some(next_refresh) = session.refresh_token {
+    account.refresh_token = Some(next_refresh);
+  }
+  if session.gamertag.is_some() {
+    account.gamertag = session.gamertag;
+  }
+  account.expires_at = Some(now.saturating_add(session.expires_in));
+}
+
+fn refresh_microsoft_session(
+  client_id: &str,
+  refresh_token: &str,
+) -> Result<RefreshedMicrosoftSession, String> {
+  let token_response = refresh_microsoft_token(client_id, refresh_token)?;
+  let (mc_response, gamertag) = minecraft_login_with_microsoft(&token_response.access_token)?;
+  let owns_minecraft = check_entitlements(&mc_response.access_token).ok();
+  Ok(RefreshedMicrosoftSession {
+    access_token: mc_response.access_token,
+    refresh_token: token_response.refresh_token,
+    expires_in: mc_response.expires_in,
+    owns_minecraft,
+    gamertag,
+  })
 }
 
 fn ensure_active_microsoft_session(config: &mut AppConfig) -> Result<(), String> {
@@ -526,7 +730,14 @@ fn ensure_active_microsoft_session(config: &mut AppConfig) -> Result<(), String>
   if !is_microsoft {
     return Ok(());
   }
-  refresh_microsoft_accounts_inner(config)?;
+  let statuses = refresh_microsoft_accounts_inner(config);
+  if let Some(err) = statuses
+    .into_iter()
+    .find(|status| status.account_id == active_id)
+    .and_then(|status| status.error)
+  {
+    return Err(err);
+  }
   let account = config.accounts.iter().find(|item| item.id == active_id);
   let Some(account) = account else {
     return Ok(());
@@ -540,12 +751,151 @@ fn ensure_active_microsoft_session(config: &mut AppConfig) -> Result<(), String>
 #[tauri::command]
 fn refresh_microsoft_accounts(
   state: tauri::State<'_, Mutex<ConfigStore>>,
-) -> Result<usize, String> {
+) -> Result<Vec<AccountRefreshStatus>, String> {
   let mut store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
   let mut config = store.get();
-  let refreshed = refresh_microsoft_accounts_inner(&mut config)?;
+  let statuses = refresh_microsoft_accounts_inner(&mut config);
   store.set(config).map_err(|err| err.to_string())?;
-  Ok(refreshed)
+  Ok(statuses)
+}
+
+const ACCOUNT_STALE_AFTER_DAYS: u64 = 30;
+
+#[derive(Serialize)]
+struct AccountUsage {
+  account_id: String,
+  display_name: String,
+  kind: config::AccountKind,
+  last_used: Option<String>,
+  days_since_used: Option<u64>,
+  stale: bool,
+}
+
+fn record_account_used(state: &Mutex<ConfigStore>, account_id: &str) {
+  let Ok(mut store) = state.lock() else {
+    return;
+  };
+  let mut config = store.get();
+  let Some(account) = config.accounts.iter_mut().find(|account| account.id == account_id) else {
+    return;
+  };
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
+  account.last_used = Some(config::format_utc_timestamp(now));
+  if let Err(err) = store.set(config) {
+    log::warn!("failed to record account usage: {}", err);
+  }
+}
+
+/// Accounts ordered by most recent launch; never-used accounts come last and count as stale.
+#[tauri::command]
+fn get_account_usage(
+  stale_after_days: Option<u64>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<AccountUsage>, String> {
+  let config = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store.get()
+  };
+  let stale_after_days = stale_after_days.unwrap_or(ACCOUNT_STALE_AFTER_DAYS);
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
+  let mut usage = config
+    .accounts
+    .into_iter()
+    .map(|account| {
+      let last_used_unix = account.last_used.as_deref().and_then(config::parse_utc_timestamp);
+      let days_since_used = last_used_unix.map(|unix| now.saturating_sub(unix) / 86_400);
+      (
+        last_used_unix,
+        AccountUsage {
+          account_id: account.id,
+          display_name: account.display_name,
+          kind: account.kind,
+          last_used: account.last_used,
+          days_since_used,
+          stale: days_since_used.map_or(true, |days| days >= stale_after_days),
+        },
+      )
+    })
+    .collect::<Vec<_>>();
+  usage.sort_by_key(|(last_used_unix, _)| std::cmp::Reverse(*last_used_unix));
+  Ok(usage.into_iter().map(|(_, entry)| entry).collect())
+}
+
+const ACCOUNT_EXPIRING_SOON_SECS: u64 = 3_600;
+
+#[derive(Serialize)]
+struct AccountStatus {
+  account_id: String,
+  display_name: String,
+  kind: config::AccountKind,
+  token_valid: bool,
+  expires_at: Option<u64>,
+  expiring_soon: bool,
+  can_refresh: bool,
+  owns_minecraft: Option<bool>,
+}
+
+/// Token and ownership state for every account, computed from stored data only so the
+/// accounts screen can badge them without hitting Microsoft. Offline accounts have no
+/// token to expire and always report as valid.
+#[tauri::command]
+fn get_account_status(
+  expiring_within_secs: Option<u64>,
+  state: tauri::State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<AccountStatus>, String> {
+  let config = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store.get()
+  };
+  let expiring_within_secs = expiring_within_secs.unwrap_or(ACCOUNT_EXPIRING_SOON_SECS);
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
+  Ok(
+    config
+      .accounts
+      .into_iter()
+      .map(|account| {
+        if account.kind != config::AccountKind::Microsoft {
+          return AccountStatus {
+            account_id: account.id,
+            display_name: account.display_name,
+            kind: account.kind,
+            token_valid: true,
+            expires_at: None,
+            expiring_soon: false,
+            can_refresh: false,
+            owns_minecraft: account.owns_minecraft,
+          };
+        }
+        let token_valid = account.access_token.is_some()
+          && account.expires_at.is_some_and(|expires_at| expires_at > now);
+        AccountStatus {
+          token_valid,
+          expiring_soon: token_valid
+            && account
+              .expires_at
+              .is_some_and(|expires_at| expires_at <= now.saturating_add(expiring_within_secs)),
+          can_refresh: account
+            .refresh_token
+            .as_deref()
+            .is_some_and(|token| !token.trim().is_empty()),
+          expires_at: account.expires_at,
+          account_id: account.id,
+          display_name: account.display_name,
+          kind: account.kind,
+          owns_minecraft: account.owns_minecraft,
+        }
+      })
+      .collect(),
+  )
 }
 
 #[tauri::command]
@@ -599,8 +949,14 @@ struct InstanceMetrics {
   rss_mb: f32,
   cpu_load_pct: f32,
   gpu_load_pct: Option<f32>,
+  process_count: u32,
 }
 
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const STOP_KILL_TIMEOUT: Duration = Duration::from_secs(10);
+
+static STOPPING_INSTANCES: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
 const DISCORD_APP_ID: u64 = 1468203692716064883;
 const DISCORD_LARGE_IMAGE: &str = "monolithicon";
 
@@ -819,6 +1175,7 @@ impl DiscordRpcWorkerState {
 #[derive(Default)]
 struct MicrosoftLoginState {
   code_verifier: Option<String>,
+  cancel: Option<Arc<AtomicBool>>,
 }
 
 #[derive(serde::Deserialize)]
@@ -846,6 +1203,24 @@ struct XblClaims {
 #[derive(serde::Deserialize)]
 struct XblUser {
   uhs: String,
+  #[serde(default)]
+  gtg: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct AccountRefreshStatus {
+  account_id: String,
+  display_name: String,
+  status: String,
+  error: Option<String>,
+}
+
+struct RefreshedMicrosoftSession {
+  access_token: String,
+  refresh_token: Option<String>,
+  expires_in: u64,
+  owns_minecraft: Option<bool>,
+  gamertag: Option<String>,
 }
 
 #[derive(serde::Deserialize)]
@@ -862,12 +1237,25 @@ struct InstanceLogEvent {
   stream: String,
 }
 
+#[derive(Clone, Serialize)]
+struct GameReadyEvent {
+  instance_id: String,
+  ready_after_ms: u64,
+}
+
 #[derive(Clone, Serialize)]
 struct LaunchEndedEvent {
   instance_id: String,
   pid: u32,
 }
 
+#[derive(Clone, Serialize)]
+struct LaunchStoppedEvent {
+  instance_id: String,
+  pid: u32,
+  forced: bool,
+}
+
 #[derive(serde::Deserialize)]
 struct MinecraftProfile {
   id: String,
@@ -901,23 +1289,54 @@ fn get_instance_metrics(
   let mut system = metrics_system
     .lock()
     .map_err(|_| "metrics system lock poisoned".to_string())?;
-  let refreshed = system.refresh_process(Pid::from_u32(pid));
-  if !refreshed {
+  // Launch wrappers can hand the game off to child JVMs, so measure the whole tree.
+  system.refresh_processes();
+  let root = Pid::from_u32(pid);
+  if system.process(root).is_none() {
     return Ok(None);
   }
-  let process = system.process(Pid::from_u32(pid));
-  if let Some(proc) = process {
-    let rss_bytes = proc.memory();
-    let rss_mb = rss_bytes as f32 / (1024.0 * 1024.0);
-    let cpu_load_pct = proc.cpu_usage().clamp(0.0, 100.0);
-    let gpu_load_pct = read_gpu_load_pct();
-    return Ok(Some(InstanceMetrics {
-      rss_mb,
-      cpu_load_pct,
-      gpu_load_pct,
-    }));
+  let tree = process_tree(&system, root);
+  let (rss_bytes, cpu_usage) = tree
+    .iter()
+    .filter_map(|pid| system.process(*pid))
+    .fold((0u64, 0f32), |(memory, cpu), proc| {
+      (memory + proc.memory(), cpu + proc.cpu_usage())
+    });
+  let rss_mb = rss_bytes as f32 / (1024.0 * 1024.0);
+  let cpu_load_pct = cpu_usage.clamp(0.0, 100.0);
+  let gpu_load_pct = read_gpu_load_pct();
+  Ok(Some(InstanceMetrics {
+    rss_mb,
+    cpu_load_pct,
+    gpu_load_pct,
+    process_count: tree.len() as u32,
+  }))
+}
+
+fn process_tree(system: &System, root: Pid) -> Vec<Pid> {
+  let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+  for (pid, process) in system.processes() {
+    // Linux reports threads as tasks sharing the parent's memory; only count real processes.
+    if process.thread_kind().is_some() {
+      continue;
+    }
+    if let Some(parent) = process.parent() {
+      children.entry(parent).or_default().push(*pid);
+    }
+  }
+  let mut tree = vec![root];
+  let mut index = 0;
+  while index < tree.len() {
+    if let Some(next) = children.get(&tree[index]) {
+      for pid in next {
+        if !tree.contains(pid) {
+          tree.push(*pid);
+        }
+      }
+    }
+    index += 1;
   }
-  Ok(None)
+  tree
 }
 
 fn read_gpu_load_pct() -> Option<f32> {
@@ -977,9 +1396,20 @@ fn signal_process(pid: u32, force: bool) -> Result<(), String> {
 
 fn handle_instance_exit(
   app_handle: &tauri::AppHandle,
+  ops: &OperationEmitter,
   instance_id: &str,
   pid: u32,
 ) {
+  {
+    let consoles = app_handle.state::<Mutex<HashMap<String, ServerConsole>>>();
+    let mut consoles = match consoles.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    if consoles.get(instance_id).map(|console| console.pid) == Some(pid) {
+      consoles.remove(instance_id);
+    }
+  }
   let running_state = app_handle.state::<Mutex<HashMap<String, u32>>>();
   let mut map = match running_state.lock() {
     Ok(guard) => guard,
@@ -989,32 +1419,103 @@ fn handle_instance_exit(
     return;
   }
   map.remove(instance_id);
+  drop(map);
   let discord_state = app_handle.state::<Mutex<DiscordRpcState>>();
   discord_set_menu_activity(&discord_state);
   let payload = LaunchEndedEvent {
     instance_id: instance_id.to_string(),
     pid,
   };
-  let _ = app_handle.emit("launch:ended", payload);
+  ops.emit("launch:ended", payload);
+  scheduler::handle_session_ended(app_handle, instance_id);
 }
 
 #[tauri::command]
 fn stop_instance(
   instance_id: String,
+  app: tauri::AppHandle,
   running: tauri::State<'_, Mutex<HashMap<String, u32>>>,
-  discord: tauri::State<'_, Mutex<DiscordRpcState>>,
+  store: tauri::State<'_, Mutex<ConfigStore>>,
 ) -> Result<(), String> {
-  let mut map = running.lock().map_err(|_| "process map lock poisoned".to_string())?;
-  let pid = map
-    .get(&instance_id)
-    .copied()
-    .ok_or_else(|| "instance not running".to_string())?;
+  let pid = {
+    let map = running.lock().map_err(|_| "process map lock poisoned".to_string())?;
+    map
+      .get(&instance_id)
+      .copied()
+      .ok_or_else(|| "instance not running".to_string())?
+  };
   signal_process(pid, false)?;
-  map.remove(&instance_id);
-  discord_set_menu_activity(&discord);
+  {
+    let mut stopping = STOPPING_INSTANCES
+      .lock()
+      .map_err(|_| "stop tracker lock poisoned".to_string())?;
+    if !stopping.insert(instance_id.clone()) {
+      return Ok(());
+    }
+  }
+  let grace = {
+    let store = store.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    Duration::from_secs(store.get().settings.stop_grace_period_secs)
+  };
+  thread::spawn(move || {
+    let forced = watch_stopping_instance(&app, &instance_id, pid, grace);
+    if let Ok(mut stopping) = STOPPING_INSTANCES.lock() {
+      stopping.remove(&instance_id);
+    }
+    let Some(forced) = forced else {
+      return;
+    };
+    let payload = LaunchStoppedEvent {
+      instance_id: instance_id.clone(),
+      pid,
+      forced,
+    };
+    OperationEmitter::new(&app, Some(&instance_id)).emit("launch:stopped", payload);
+  });
   Ok(())
 }
 
+fn instance_pid_tracked(app_handle: &tauri::AppHandle, instance_id: &str, pid: u32) -> bool {
+  let running_state = app_handle.state::<Mutex<HashMap<String, u32>>>();
+  let map = match running_state.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  map.get(instance_id).copied() == Some(pid)
+}
+
+/// Waits for a stopped instance to exit, killing it once `grace` runs out.
+/// Returns whether the kill was needed, or `None` if the process never went away.
+fn watch_stopping_instance(
+  app_handle: &tauri::AppHandle,
+  instance_id: &str,
+  pid: u32,
+  grace: Duration,
+) -> Option<bool> {
+  let started = Instant::now();
+  let mut forced = false;
+  while instance_pid_tracked(app_handle, instance_id, pid) {
+    let elapsed = started.elapsed();
+    if !forced && elapsed >= grace {
+      log::warn!(
+        "instance {} (pid {}) ignored stop request for {}s; killing",
+        instance_id,
+        pid,
+        grace.as_secs()
+      );
+      if let Err(err) = signal_process(pid, true) {
+        log::warn!("failed to kill pid {}: {}", pid, err);
+      }
+      forced = true;
+    } else if forced && elapsed >= grace + STOP_KILL_TIMEOUT {
+      log::warn!("instance {} (pid {}) still running after kill", instance_id, pid);
+      return None;
+    }
+    thread::sleep(STOP_POLL_INTERVAL);
+  }
+  Some(forced)
+}
+
 #[tauri::command]
 fn kill_instance(
   instance_id: String,
@@ -1033,17 +1534,32 @@ fn kill_instance(
 }
 
 #[tauri::command]
-async fn list_vanilla_versions(include_snapshots: bool) -> Result<Vec<VersionSummary>, String> {
-  tauri::async_runtime::spawn_blocking(move || list_vanilla_versions_impl(include_snapshots))
-    .await
-    .map_err(|_| "version task failed".to_string())?
+async fn list_vanilla_versions(
+  include_snapshots: bool,
+  include_legacy: Option<bool>,
+  filter: Option<VersionRangeFilter>,
+) -> Result<Vec<VersionSummary>, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    list_vanilla_versions_impl(
+      include_snapshots,
+      include_legacy.unwrap_or(false),
+      &filter.unwrap_or_default(),
+    )
+  })
+  .await
+  .map_err(|_| "version task failed".to_string())?
 }
 
 #[tauri::command]
-async fn list_fabric_game_versions(include_snapshots: bool) -> Result<Vec<VersionSummary>, String> {
-  tauri::async_runtime::spawn_blocking(move || list_fabric_games_impl(include_snapshots))
-    .await
-    .map_err(|_| "version task failed".to_string())?
+async fn list_fabric_game_versions(
+  include_snapshots: bool,
+  filter: Option<VersionRangeFilter>,
+) -> Result<Vec<VersionSummary>, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    list_fabric_games_impl(include_snapshots, &filter.unwrap_or_default())
+  })
+  .await
+  .map_err(|_| "version task failed".to_string())?
 }
 
 #[tauri::command]
@@ -1058,6 +1574,18 @@ async fn list_fabric_loader_versions(
   .map_err(|_| "version task failed".to_string())?
 }
 
+#[tauri::command]
+async fn list_quilt_loader_versions(
+  game_version: String,
+  include_snapshots: bool,
+) -> Result<Vec<LoaderVersionSummary>, String> {
+  tauri::async_runtime::spawn_blocking(move || {
+    list_quilt_loaders_impl(&game_version, include_snapshots)
+  })
+  .await
+  .map_err(|_| "version task failed".to_string())?
+}
+
 #[tauri::command]
 async fn list_forge_versions(game_version: String) -> Result<Vec<ForgeVersionSummary>, String> {
   tauri::async_runtime::spawn_blocking(move || list_forge_versions_impl(&game_version))
@@ -1072,12 +1600,49 @@ async fn list_neoforge_versions(game_version: String) -> Result<Vec<ForgeVersion
     .map_err(|_| "version task failed".to_string())?
 }
 
+#[tauri::command]
+async fn list_supported_loaders(game_version: String) -> Result<Vec<LoaderAvailability>, String> {
+  tauri::async_runtime::spawn_blocking(move || list_supported_loaders_impl(&game_version))
+    .await
+    .map_err(|_| "version task failed".to_string())
+}
+
+#[tauri::command]
+async fn list_server_game_versions(loader: Loader) -> Result<Vec<VersionSummary>, String> {
+  tauri::async_runtime::spawn_blocking(move || list_server_game_versions_impl(&loader))
+    .await
+    .map_err(|_| "version task failed".to_string())?
+}
+
+#[tauri::command]
+async fn list_server_builds(
+  loader: Loader,
+  game_version: String,
+) -> Result<Vec<LoaderVersionSummary>, String> {
+  tauri::async_runtime::spawn_blocking(move || list_server_builds_impl(&loader, &game_version))
+    .await
+    .map_err(|_| "version task failed".to_string())?
+}
+
+#[tauri::command]
+async fn get_version_patch_notes(game_version: String) -> Result<VersionPatchNotes, String> {
+  tauri::async_runtime::spawn_blocking(move || fetch_patch_notes_impl(&game_version))
+    .await
+    .map_err(|_| "version task failed".to_string())?
+}
+
+#[derive(Serialize)]
+struct CreatedInstance {
+  instance: Instance,
+  summary: Option<InstallSummary>,
+}
+
 #[tauri::command]
 async fn create_instance(
   window: tauri::Window,
   request: NewInstanceRequest,
   state: tauri::State<'_, Mutex<ConfigStore>>,
-) -> Result<Instance, String> {
+) -> Result<CreatedInstance, String> {
   let config = {
     let mut store = state
       .lock()
@@ -1088,28 +1653,54 @@ async fn create_instance(
     config
   };
 
-  let progress_window = window.clone();
+  let ops = OperationEmitter::new(window.app_handle(), None);
+  let progress_ops = ops.clone();
   let result = tauri::async_runtime::spawn_blocking(move || {
     let mut config = config;
     let emitter = |event: ProgressEvent| {
-      let _ = progress_window.emit("install:progress", event);
+      progress_ops.emit_progress(event);
+    };
+    let guard = RefCell::new(None);
+    let reserved = |instance_id: &str, instance_dir: &Path| {
+      progress_ops.bind_instance(instance_id);
+      let acquired = InstanceOperationGuard::acquire(instance_id, instance_dir, "create")?;
+      *guard.borrow_mut() = Some(acquired);
+      Ok(())
     };
-    create_instance_impl(request, &mut config, &emitter).map(|instance| (instance, config))
+    let instance = create_instance_impl(request, &mut config, &reserved, &emitter)?;
+    let wants_fabric_api = minecraft::new_instance_defaults(&config)
+      .is_some_and(|defaults| defaults.install_fabric_api);
+    if wants_fabric_api
+      && instance.kind == InstanceKind::Client
+      && instance.loader == Loader::Fabric
+    {
+      emitter(ProgressEvent {
+        stage: "defaults".to_string(),
+        message: "Installing Fabric API".to_string(),
+        current: 0,
+        ..Default::default()
+      });
+      // The instance is usable without it, so a failed download only gets logged.
+      let installed =
+        modrinth::install_fabric_api(Path::new(&instance.directory), &instance.version);
+      if let Err(err) = installed {
+        log::warn!("failed to install Fabric API into {}: {}", instance.id, err);
+      }
+    }
+    let summary = ensure_instance_ready(&instance, &emitter)?;
+    Ok::<_, String>(CreatedInstance { instance, summary })
   })
   .await
   .map_err(|_| "install task cancelled".to_string())?;
 
   match result {
-    Ok((instance, updated_config)) => {
-      let mut store = state
-        .lock()
-        .map_err(|_| "config store lock poisoned".to_string())?;
-      store.set(updated_config).map_err(|err| err.to_string())?;
-      let _ = window.emit("install:done", &instance);
-      Ok(instance)
+    Ok(created) => {
+      ops.emit("install:done", &created.instance);
+      events::emit_instances_changed(window.app_handle(), &created.instance.id, "created");
+      Ok(created)
     }
     Err(err) => {
-      let _ = window.emit("install:error", err.clone());
+      ops.emit("install:error", err.clone());
       Err(err)
     }
   }
@@ -1120,6 +1711,7 @@ async fn launch_instance(
   window: tauri::Window,
   instance_id: String,
   player_name: Option<String>,
+  join_server: Option<String>,
   state: tauri::State<'_, Mutex<ConfigStore>>,
   discord: tauri::State<'_, Mutex<DiscordRpcState>>,
   running: tauri::State<'_, Mutex<HashMap<String, u32>>>,
@@ -1135,17 +1727,38 @@ async fn launch_instance(
   };
   let config_for_error = config.clone();
 
-  let launch_window = window.clone();
+  let app_handle = window.app_handle();
+  let ops = OperationEmitter::new(app_handle, Some(&instance_id));
+  let launch_ops = ops.clone();
   let instance_id_clone = instance_id.clone();
-  let log_window = window.clone();
+  let log_ops = ops.clone();
   let log_instance_id = instance_id.clone();
-  let app_handle = window.app_handle();
   let log_handle = app_handle.clone();
   let exit_instance_id = instance_id.clone();
   let exit_handle = app_handle.clone();
+  let exit_ops = ops.clone();
+  let console_instance_id = instance_id.clone();
+  let console_handle = app_handle.clone();
+  let session_handle = app_handle.clone();
+  let game_ready = Arc::new(AtomicBool::new(false));
+  let launched_at = Arc::new(Mutex::new(Instant::now()));
+  let ready_launched_at = launched_at.clone();
+  let session_launched_at = launched_at.clone();
+  let instance_dir = config
+    .instances
+    .iter()
+    .find(|item| item.id == instance_id)
+    .map(|item| PathBuf::from(&item.directory))
+    .ok_or_else(|| "instance not found".to_string())?;
+  let guard = InstanceOperationGuard::acquire(&instance_id, &instance_dir, "launch")?;
+  let screenshots =
+    watcher::spawn_screenshot_watcher(ops.clone(), instance_id.clone(), &instance_dir);
+  // Held by the exit callback so repairs and restores stay locked out while the game runs.
+  // If the launch fails before spawning, dropping the callback releases it.
+  let session_guard = Mutex::new(Some(guard));
   let result = tauri::async_runtime::spawn_blocking(move || {
     let emitter = |event: ProgressEvent| {
-      let _ = launch_window.emit("install:progress", event);
+      launch_ops.emit_progress(event);
     };
     let log = Arc::new(move |stream: &str, line: &str| {
       let payload = InstanceLogEvent {
@@ -1153,16 +1766,103 @@ async fn launch_instance(
         line: line.to_string(),
         stream: stream.to_string(),
       };
-      let _ = log_window.emit("instance:log", payload);
+      log_ops.emit("instance:log", payload);
       if stream == "stdout" || stream == "stderr" {
         let discord_state = log_handle.state::<Mutex<DiscordRpcState>>();
         discord_track_runtime_signal(&discord_state, line);
+        if !game_ready.load(Ordering::Relaxed)
+          && is_game_ready_line(line)
+          && !game_ready.swap(true, Ordering::SeqCst)
+        {
+          let started = match ready_launched_at.lock() {
+            Ok(guard) => *guard,
+            Err(poisoned) => *poisoned.into_inner(),
+          };
+          log_ops.emit(
+            "game:ready",
+            GameReadyEvent {
+              instance_id: log_instance_id.clone(),
+              ready_after_ms: started.elapsed().as_millis() as u64,
+            },
+          );
+        }
       }
     });
-    let on_exit = Arc::new(move |pid: u32| {
-      handle_instance_exit(&exit_handle, &exit_instance_id, pid);
+    let on_exit = Arc::new(move |pid: u32, exit_code: Option<i32>| {
+      let played = match session_launched_at.lock() {
+        Ok(guard) => guard.elapsed(),
+        Err(poisoned) => poisoned.into_inner().elapsed(),
+      };
+      let started_at_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|value| value.as_secs())
+        .unwrap_or(0)
+        .saturating_sub(played.as_secs());
+      screenshots.stop();
+      sessions::record_session(&exit_handle, &exit_instance_id, started_at_unix, exit_code);
+      // Released before the exit hooks so the session-end world backup can take it.
+      match session_guard.lock() {
+        Ok(mut guard) => guard.take(),
+        Err(poisoned) => poisoned.into_inner().take(),
+      };
+      handle_instance_exit(&exit_handle, &exit_ops, &exit_instance_id, pid);
+    });
+    let on_stdin = Arc::new(move |pid: u32, stdin: std::process::ChildStdin| {
+      let consoles = console_handle.state::<Mutex<HashMap<String, ServerConsole>>>();
+      let mut map = match consoles.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+      };
+      map.insert(console_instance_id.clone(), ServerConsole { pid, stdin });
+    });
+    let refresh_session = Arc::new(move |config: &mut AppConfig| {
+      ensure_active_microsoft_session(config)?;
+      let store_state = session_handle.state::<Mutex<ConfigStore>>();
+      let mut store = store_state
+        .lock()
+        .map_err(|_| "config store lock poisoned".to_string())?;
+      let mut persisted = store.get();
+      for account in &mut persisted.accounts {
+        let refreshed = config
+          .accounts
+          .iter()
+          .find(|item| item.id == account.id && item.access_token != account.access_token);
+        if let Some(refreshed) = refreshed {
+          *account = refreshed.clone();
+        }
+      }
+      store.set(persisted).map_err(|err| err.to_string())
     });
-    launch_instance_impl(&instance_id_clone, player_name, &config, &emitter, log, Some(on_exit))
+    let auto_update = config
+      .instances
+      .iter()
+      .find(|item| item.id == instance_id_clone && item.auto_update_mods);
+    if let Some(instance) = auto_update {
+      match modrinth::auto_update_instance_mods(instance, &emitter) {
+        Ok(result) if !result.is_empty() => log("launcher", &result.summary()),
+        Ok(_) => {}
+        Err(err) => log("launcher", &format!("Warning: mod auto-update skipped: {}", err)),
+      }
+    }
+    let launched = launch_instance_impl(
+      &instance_id_clone,
+      player_name,
+      join_server,
+      &config,
+      &emitter,
+      log,
+      Some(on_exit),
+      Some(on_stdin),
+      Some(refresh_session),
+    );
+    // Returns as soon as the process is spawned, so install and prep time stay out of
+    // the session length and the time to ready.
+    if launched.is_ok() {
+      if let Ok(mut started) = launched_at.lock() {
+        *started = Instant::now();
+      }
+    }
+    launched
   })
   .await
   .map_err(|_| "launch task cancelled".to_string())?;
@@ -1181,7 +1881,11 @@ async fn launch_instance(
       } else {
         discord_set_menu_activity(&discord);
       }
-      let _ = window.emit("launch:started", pid);
+      if let Some(account_id) = config_for_error.active_account_id.as_deref() {
+        record_account_used(&state, account_id);
+      }
+      ops.emit("launch:started", pid);
+      lan::start_lan_discovery(app_handle.clone());
       Ok(pid)
     }
     Err(err) => {
@@ -1192,7 +1896,7 @@ async fn launch_instance(
         .and_then(|instance| classify_launch_failure(&config_for_error, instance, &err))
         .map(|diagnostic| format!("{} Suggested fix: {}", err, diagnostic.suggested_fix.unwrap_or(diagnostic.summary)))
         .unwrap_or(err.clone());
-      let _ = window.emit("launch:error", enriched.clone());
+      ops.emit("launch:error", enriched.clone());
       Err(enriched)
     }
   }
@@ -1214,15 +1918,23 @@ pub fn run() {
       let config_path = app.path().app_config_dir()?.join("config.json");
       let store = ConfigStore::load(config_path)?;
       let runtime_config = store.get();
+      minecraft::apply_network_settings(&runtime_config.settings);
+      events::apply_progress_settings(&runtime_config.settings);
+      modrinth::apply_content_blacklist(&runtime_config.settings);
       let discord_enabled = runtime_config.settings.discord_presence;
       let discord_mode = runtime_config.settings.discord_presence_mode;
       let mut metrics_system = System::new();
       metrics_system.refresh_processes();
       app.manage(Mutex::new(store));
       app.manage(Mutex::new(HashMap::<String, u32>::new()));
+      app.manage(Mutex::new(HashMap::<String, ServerConsole>::new()));
       app.manage(Mutex::new(metrics_system));
       app.manage(Mutex::new(MicrosoftLoginState::default()));
       app.manage(Mutex::new(DiscordRpcState::new(discord_enabled, discord_mode)));
+      watcher::spawn_config_watcher(app.handle().clone());
+      sync::spawn_startup_sync(app.handle().clone());
+      scheduler::spawn_scheduler(app.handle().clone());
+      commands::instances::spawn_startup_temp_scan(app.handle().clone());
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
@@ -1232,37 +1944,80 @@ pub fn run() {
       commands::instances::open_instance_folder,
       commands::instances::rename_instance,
       commands::instances::set_instance_pinned,
+      commands::instances::set_instance_auto_update_mods,
+      commands::instances::set_instance_release_channel,
+      commands::instances::get_instance_install_summary,
+      commands::instances::get_instance_description,
+      commands::instances::read_instance_screenshot,
+      commands::instances::get_instance_search_defaults,
+      commands::instances::set_instance_search_defaults,
+      commands::instances::clean_orphaned_temp_files,
+      commands::instances::add_custom_version,
+      commands::instances::set_instance_custom_version,
+      commands::instances::set_instance_launch_overrides,
+      commands::instances::set_instance_branding,
+      commands::instances::install_optifine,
+      commands::instances::summarize_gc_log,
       commands::instances::remove_instance,
       commands::instances::repair_instance,
+      commands::instances::retry_instance_install,
       commands::instances::get_instance_preflight,
+      commands::instances::validate_instance,
+      commands::instances::get_mod_dependency_graph,
+      commands::instances::export_launch_script,
       commands::instances::list_instance_snapshots,
       commands::instances::create_instance_snapshot,
       commands::instances::restore_instance_snapshot,
       commands::instances::delete_instance_snapshot,
+      commands::instances::list_mods_snapshots,
+      commands::instances::create_mods_snapshot,
+      commands::instances::restore_mods_snapshot,
       commands::instances::set_instance_java_override,
+      commands::instances::switch_instance_java_to_arm64,
       commands::system::open_external,
       commands::system::check_latest_release,
       commands::system::detect_java,
+      commands::system::inspect_java_runtime,
+      commands::system::test_java,
       commands::system::scan_java_runtimes,
       commands::config::export_config,
       commands::instances::import_instance,
+      commands::instances::preview_import_instance,
+      commands::recipes::export_instance_recipe,
+      commands::recipes::import_instance_recipe,
+      commands::recipes::preview_instance_recipe_import,
       start_microsoft_login,
+      cancel_microsoft_login,
       complete_microsoft_login,
       refresh_microsoft_accounts,
       check_minecraft_ownership,
+      get_account_usage,
+      get_account_status,
       commands::packs::list_instance_mods,
       commands::packs::toggle_mod,
       commands::packs::delete_mod,
       commands::packs::list_instance_packs,
       commands::packs::toggle_instance_pack,
       commands::packs::delete_instance_pack,
+      commands::packs::get_resource_pack_order,
+      commands::packs::set_resource_pack_order,
       commands::packs::list_instance_datapacks,
       commands::packs::toggle_instance_datapack,
       commands::packs::delete_instance_datapack,
       commands::worlds::list_instance_worlds,
+      commands::worlds::list_vanilla_worlds,
+      commands::worlds::import_vanilla_worlds,
       commands::servers::list_instance_servers,
       commands::servers::save_instance_servers,
       commands::servers::analyze_server_latency,
+      commands::server_instances::get_server_properties,
+      commands::server_instances::set_server_properties,
+      commands::server_instances::send_server_command,
+      commands::server_instances::get_server_eula_status,
+      commands::server_instances::accept_server_eula,
+      commands::server_instances::list_server_players,
+      commands::server_instances::add_server_player,
+      commands::server_instances::remove_server_player,
       commands::worlds::update_instance_world,
       commands::instances::open_instance_path,
       commands::packs::open_instance_datapacks,
@@ -1270,19 +2025,39 @@ pub fn run() {
       commands::instances::update_instance_loader_version,
       modrinth::search_modrinth_projects,
       modrinth::get_modrinth_install_plan,
+      modrinth::check_modrinth_compatibility,
       modrinth::install_modrinth_project,
+      modrinth::install_shader_preset,
       modrinth::update_modrinth_project,
+      modrinth::update_modrinth_projects,
       modrinth::uninstall_modrinth_project,
+      modrinth::deploy_staged_datapacks,
       modrinth::list_modrinth_installs,
       modrinth::list_modrinth_updates,
+      modrinth::list_modrinth_update_changelogs,
+      modrinth::set_modrinth_project_pinned,
+      modrinth::list_modrinth_pinned,
+      modrinth::rollback_modrinth_update,
+      modrinth::list_modrinth_rollbacks,
       get_instance_metrics,
       list_vanilla_versions,
       list_fabric_game_versions,
       list_fabric_loader_versions,
+      list_quilt_loader_versions,
       list_forge_versions,
       list_neoforge_versions,
+      list_supported_loaders,
+      list_server_game_versions,
+      list_server_builds,
+      get_version_patch_notes,
       create_instance,
       launch_instance,
+      lan::list_lan_games,
+      sessions::list_play_sessions,
+      sessions::get_playtime_stats,
+      sessions::export_playtime_csv,
+      sync::run_sync_now,
+      scheduler::run_task_now,
       stop_instance,
       kill_instance
     ])
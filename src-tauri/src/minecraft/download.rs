@@ -1,11 +1,81 @@
+use crate::config::{resolve_home_dir, Settings};
+use crate::minecraft::summary::record_download;
+use crate::minecraft::util::long_path;
+use rand::Rng;
 use serde::de::DeserializeOwned;
-use std::{fs, io, path::Path, thread, time::Duration};
+use sha1::{Digest, Sha1};
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+  thread,
+  time::Duration,
+};
 use std::fs::OpenOptions;
+use std::sync::{Mutex, OnceLock};
+
+const POLITE_DOWNLOAD_WORKERS: usize = 2;
+const POLITE_JITTER_MS: std::ops::Range<u64> = 100..400;
+
+#[derive(Clone)]
+struct NetworkPolicy {
+  retry_delays_ms: Vec<u64>,
+  connect_timeout_secs: u64,
+  request_timeout_secs: u64,
+  polite_downloads: bool,
+}
+
+static NETWORK_POLICY: OnceLock<Mutex<NetworkPolicy>> = OnceLock::new();
+
+fn network_policy_slot() -> &'static Mutex<NetworkPolicy> {
+  NETWORK_POLICY.get_or_init(|| {
+    Mutex::new(NetworkPolicy {
+      retry_delays_ms: vec![200, 500, 1000, 2000, 4000],
+      connect_timeout_secs: 10,
+      request_timeout_secs: 120,
+      polite_downloads: false,
+    })
+  })
+}
+
+fn network_policy() -> NetworkPolicy {
+  network_policy_slot()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .clone()
+}
+
+pub(crate) fn apply_network_settings(settings: &Settings) {
+  let mut policy = network_policy_slot()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  policy.retry_delays_ms = settings.network_retry_delays_ms.clone();
+  policy.connect_timeout_secs = settings.network_connect_timeout_secs;
+  policy.request_timeout_secs = settings.network_request_timeout_secs;
+  policy.polite_downloads = settings.polite_downloads;
+}
+
+/// Worker count for bulk downloads; polite mode caps it to spare throttling CDNs.
+pub(crate) fn download_parallelism(default: usize) -> usize {
+  if network_policy().polite_downloads {
+    default.min(POLITE_DOWNLOAD_WORKERS)
+  } else {
+    default
+  }
+}
+
+/// In polite mode, sleeps for a short random interval between bulk downloads.
+pub(crate) fn polite_download_pause() {
+  if network_policy().polite_downloads {
+    let jitter = rand::thread_rng().gen_range(POLITE_JITTER_MS);
+    thread::sleep(Duration::from_millis(jitter));
+  }
+}
 
 fn build_agent() -> ureq::Agent {
+  let policy = network_policy();
   ureq::AgentBuilder::new()
-    .timeout_connect(Duration::from_secs(10))
-    .timeout(Duration::from_secs(120))
+    .timeout_connect(Duration::from_secs(policy.connect_timeout_secs))
+    .timeout(Duration::from_secs(policy.request_timeout_secs))
     .build()
 }
 
@@ -26,6 +96,7 @@ pub(crate) fn fetch_text(url: &str) -> Result<String, String> {
 }
 
 pub(crate) fn download_to(url: &str, dest: &Path) -> Result<(), String> {
+  let dest = &long_path(dest);
   if dest.exists() {
     return Ok(());
   }
@@ -34,7 +105,7 @@ pub(crate) fn download_to(url: &str, dest: &Path) -> Result<(), String> {
   }
 
   let tmp = dest.with_extension("tmp");
-  let delays = [200_u64, 500, 1000, 2000, 4000];
+  let delays = network_policy().retry_delays_ms;
 
   for (idx, delay) in delays.iter().enumerate() {
     let resume_from = match fs::metadata(&tmp) {
@@ -44,6 +115,7 @@ pub(crate) fn download_to(url: &str, dest: &Path) -> Result<(), String> {
     match download_once(url, &tmp, resume_from) {
       Ok(()) => {
         fs::rename(&tmp, dest).map_err(|err| err.to_string())?;
+        record_download(fs::metadata(dest).map(|meta| meta.len()).unwrap_or(0));
         return Ok(());
       }
       Err(err) => {
@@ -61,6 +133,105 @@ pub(crate) fn download_to(url: &str, dest: &Path) -> Result<(), String> {
   Err(format!("download failed for {}", url))
 }
 
+pub(crate) fn download_verified(
+  url: &str,
+  dest: &Path,
+  size: Option<u64>,
+  sha1: Option<&str>,
+) -> Result<(), String> {
+  let dest = &long_path(dest);
+  if dest.exists() && !file_matches(dest, size, sha1) {
+    fs::remove_file(dest).map_err(|err| err.to_string())?;
+  }
+  download_to(url, dest)?;
+  if file_matches(dest, size, sha1) {
+    return Ok(());
+  }
+  let _ = fs::remove_file(dest);
+  Err(format!("downloaded file from {} failed size/hash verification", url))
+}
+
+fn content_cache_path(sha1: &str) -> Option<PathBuf> {
+  let sha1 = sha1.trim().to_ascii_lowercase();
+  if sha1.len() != 40 || !sha1.chars().all(|ch| ch.is_ascii_hexdigit()) {
+    return None;
+  }
+  resolve_home_dir().map(|home| {
+    home
+      .join(".monolith")
+      .join("cache")
+      .join("content")
+      .join(&sha1[..2])
+      .join(sha1)
+  })
+}
+
+fn link_or_copy(source: &Path, dest: &Path) -> io::Result<()> {
+  if fs::hard_link(source, dest).is_ok() {
+    return Ok(());
+  }
+  fs::copy(source, dest).map(|_| ())
+}
+
+/// Like `download_verified`, but consults the shared content cache by sha1 first
+/// and adds the file to it afterwards, so the same jar is only fetched once.
+pub(crate) fn download_cached(url: &str, dest: &Path, sha1: Option<&str>) -> Result<(), String> {
+  let Some(cached) = sha1.and_then(content_cache_path) else {
+    return download_to(url, dest);
+  };
+  let dest = &long_path(dest);
+  if file_matches(dest, None, sha1) {
+    return Ok(());
+  }
+  // Removing first keeps a bad copy hard-linked from the cache from being written through.
+  let _ = fs::remove_file(dest);
+  if file_matches(&cached, None, sha1) {
+    if let Some(parent) = dest.parent() {
+      fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    match link_or_copy(&cached, dest) {
+      Ok(()) => {
+        log::info!("reused cached {} for {}", cached.display(), dest.display());
+        return Ok(());
+      }
+      Err(err) => log::warn!("failed to reuse cached {}: {}", cached.display(), err),
+    }
+  }
+  download_verified(url, dest, None, sha1)?;
+  if let Some(parent) = cached.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  let _ = fs::remove_file(&cached);
+  if let Err(err) = link_or_copy(dest, &cached) {
+    log::warn!("failed to cache {}: {}", dest.display(), err);
+  }
+  Ok(())
+}
+
+fn file_matches(path: &Path, size: Option<u64>, sha1: Option<&str>) -> bool {
+  let meta = match fs::metadata(path) {
+    Ok(meta) if meta.is_file() => meta,
+    _ => return false,
+  };
+  if let Some(size) = size {
+    if meta.len() != size {
+      return false;
+    }
+  }
+  let Some(expected) = sha1 else {
+    return true;
+  };
+  let mut file = match fs::File::open(path) {
+    Ok(file) => file,
+    Err(_) => return false,
+  };
+  let mut hasher = Sha1::new();
+  if io::copy(&mut file, &mut hasher).is_err() {
+    return false;
+  }
+  format!("{:x}", hasher.finalize()).eq_ignore_ascii_case(expected)
+}
+
 #[derive(Debug)]
 enum DownloadError {
   Http(ureq::Error),
@@ -151,7 +322,7 @@ pub(crate) fn request_with_retry<F>(label: &str, mut op: F) -> Result<ureq::Resp
 where
   F: FnMut() -> Result<ureq::Response, ureq::Error>,
 {
-  let delays = [200_u64, 500, 1000, 2000, 4000];
+  let delays = network_policy().retry_delays_ms;
 
   for (idx, delay) in delays.iter().enumerate() {
     match op() {
@@ -0,0 +1,467 @@
+use crate::minecraft::download::{load_json, map_json_error};
+use crate::minecraft::install::{download_mojang_libraries, download_profile_libraries};
+use crate::minecraft::journal::InstallJournal;
+use crate::minecraft::models::{
+  ForgeInstallProfile, ForgeProcessor, LegacyForgeInstall, LegacyForgeLibrary, ProfileLibrary,
+  ProgressEvent, VersionFile,
+};
+use crate::minecraft::util::{classpath_separator, long_path};
+use sha1::{Digest, Sha1};
+use std::{
+  collections::{HashMap, HashSet},
+  fs::{self, File},
+  io::{self, Read},
+  path::{Path, PathBuf},
+  process::Command,
+};
+use zip::ZipArchive;
+
+const INSTALL_PROFILE_ENTRY: &str = "install_profile.json";
+const MAVEN_PREFIX: &str = "maven/";
+const PROCESSOR_OUTPUT_TAIL: usize = 20;
+const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net";
+
+/// Installs Forge from its installer jar without running the installer GUI. 1.13+
+/// installers get their bundled artifacts extracted, libraries downloaded and client
+/// processors executed directly; older ones are handled by [`install_legacy_forge`].
+pub(crate) fn install_forge_from_profile(
+  installer_path: &Path,
+  instance_dir: &Path,
+  full_version: &str,
+  journal: &InstallJournal,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let file = File::open(installer_path).map_err(|err| err.to_string())?;
+  let mut archive = ZipArchive::new(file).map_err(|err| err.to_string())?;
+  let profile_text = read_entry(&mut archive, INSTALL_PROFILE_ENTRY)?;
+  let raw: serde_json::Value = serde_json::from_str(&profile_text).map_err(map_json_error)?;
+  if raw.get("versionInfo").is_some() {
+    return install_legacy_forge(&mut archive, raw, instance_dir, full_version, journal, emit);
+  }
+  let profile: ForgeInstallProfile = serde_json::from_value(raw).map_err(map_json_error)?;
+  let mut version_document: serde_json::Value =
+    serde_json::from_str(&read_entry(&mut archive, profile.json.trim_start_matches('/'))?)
+      .map_err(map_json_error)?;
+  let version: VersionFile =
+    serde_json::from_value(version_document.clone()).map_err(map_json_error)?;
+
+  let libraries_dir = instance_dir.join("libraries");
+  extract_bundled_maven(&mut archive, &libraries_dir)?;
+
+  // Artifacts without a URL come from the installer's maven folder or a processor.
+  let downloadable = profile
+    .libraries
+    .iter()
+    .chain(version.libraries.iter())
+    .filter(|library| {
+      library
+        .downloads
+        .as_ref()
+        .and_then(|downloads| downloads.artifact.as_ref())
+        .map_or(true, |artifact| {
+          artifact.url.as_deref().map_or(true, |url| !url.trim().is_empty())
+        })
+    })
+    .cloned()
+    .collect::<Vec<_>>();
+  download_mojang_libraries(&downloadable, &libraries_dir, journal, emit)?;
+
+  let version_id = format!("forge-{}", full_version);
+  let data_dir = instance_dir.join("installers").join(&version_id);
+  let data = resolve_data(
+    &profile,
+    &mut archive,
+    installer_path,
+    instance_dir,
+    &libraries_dir,
+    &data_dir,
+  )?;
+
+  let processors = profile
+    .processors
+    .iter()
+    .filter(|processor| {
+      processor
+        .sides
+        .as_ref()
+        .map_or(true, |sides| sides.iter().any(|side| side == "client"))
+    })
+    .collect::<Vec<_>>();
+  let total = processors.len() as u64;
+  for (index, processor) in processors.into_iter().enumerate() {
+    run_processor(
+      processor,
+      &data,
+      &libraries_dir,
+      instance_dir,
+      index as u64 + 1,
+      total,
+      emit,
+    )?;
+  }
+
+  // Written last: its presence is what marks the Forge install as complete.
+  if let Some(map) = version_document.as_object_mut() {
+    map.insert("id".to_string(), serde_json::Value::String(version_id.clone()));
+  }
+  let json_path = instance_dir
+    .join("versions")
+    .join(&version_id)
+    .join(format!("{}.json", version_id));
+  if let Some(parent) = json_path.parent() {
+    fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+  }
+  let payload = serde_json::to_vec_pretty(&version_document).map_err(map_json_error)?;
+  fs::write(long_path(&json_path), payload).map_err(|err| err.to_string())?;
+  let _ = fs::remove_dir_all(&data_dir);
+  Ok(())
+}
+
+/// Pre-1.13 installers carry the universal jar and a complete version JSON
+/// (`versionInfo`) instead of processors. The universal jar goes into `libraries`, and
+/// the version JSON is rebased onto the vanilla version so shared libraries and natives
+/// come from there. Its `minecraftArguments` already include the FML tweaker.
+fn install_legacy_forge(
+  archive: &mut ZipArchive<File>,
+  mut raw: serde_json::Value,
+  instance_dir: &Path,
+  full_version: &str,
+  journal: &InstallJournal,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let install: LegacyForgeInstall = raw
+    .get("install")
+    .cloned()
+    .ok_or_else(|| "Forge installer profile has no install block".to_string())
+    .and_then(|value| serde_json::from_value(value).map_err(map_json_error))?;
+  let mut version_document = raw
+    .get_mut("versionInfo")
+    .map(serde_json::Value::take)
+    .unwrap_or_default();
+  let libraries_dir = instance_dir.join("libraries");
+
+  let universal_target = libraries_dir.join(maven_artifact_path(&install.path)?);
+  let mut universal = archive
+    .by_name(&install.file_path)
+    .map_err(|_| format!("Forge installer is missing {}", install.file_path))?;
+  write_entry(&mut universal, &universal_target)?;
+  drop(universal);
+
+  let base_json = instance_dir
+    .join("versions")
+    .join(&install.minecraft)
+    .join(format!("{}.json", install.minecraft));
+  let base: VersionFile = load_json(&base_json)?;
+  let base_names = base
+    .libraries
+    .iter()
+    .map(|library| library.name.as_str())
+    .collect::<HashSet<_>>();
+  let libraries: Vec<serde_json::Value> = version_document
+    .get("libraries")
+    .and_then(|value| value.as_array())
+    .cloned()
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|value| {
+      value
+        .get("name")
+        .and_then(|name| name.as_str())
+        .is_some_and(|name| !base_names.contains(name))
+    })
+    .collect();
+
+  let mut downloads = Vec::new();
+  for value in &libraries {
+    let library: LegacyForgeLibrary =
+      serde_json::from_value(value.clone()).map_err(map_json_error)?;
+    if library.clientreq == Some(false) || library.name == install.path {
+      continue;
+    }
+    downloads.push(ProfileLibrary {
+      name: library.name,
+      url: library.url.as_deref().map(legacy_maven_url),
+    });
+  }
+  emit(ProgressEvent {
+    stage: "forge".to_string(),
+    message: "Installing legacy Forge".to_string(),
+    current: 0,
+    detail: Some(install.path.clone()),
+    ..Default::default()
+  });
+  download_profile_libraries(&downloads, &libraries_dir, journal, emit)?;
+
+  let version_id = format!("forge-{}", full_version);
+  if let Some(map) = version_document.as_object_mut() {
+    map.insert("id".to_string(), serde_json::Value::String(version_id.clone()));
+    map
+      .entry("inheritsFrom")
+      .or_insert_with(|| serde_json::Value::String(install.minecraft.clone()));
+    map
+      .entry("jar")
+      .or_insert_with(|| serde_json::Value::String(install.minecraft.clone()));
+    map.insert("libraries".to_string(), serde_json::Value::Array(libraries));
+  }
+  let json_path = instance_dir
+    .join("versions")
+    .join(&version_id)
+    .join(format!("{}.json", version_id));
+  if let Some(parent) = json_path.parent() {
+    fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+  }
+  let payload = serde_json::to_vec_pretty(&version_document).map_err(map_json_error)?;
+  fs::write(long_path(&json_path), payload).map_err(|err| err.to_string())
+}
+
+/// Old profiles point at the retired files.minecraftforge.net maven.
+fn legacy_maven_url(url: &str) -> String {
+  let trimmed = url.trim();
+  for retired in [
+    "http://files.minecraftforge.net/maven",
+    "https://files.minecraftforge.net/maven",
+    "http://maven.minecraftforge.net",
+  ] {
+    if let Some(rest) = trimmed.strip_prefix(retired) {
+      return format!("{}{}", FORGE_MAVEN_URL, rest);
+    }
+  }
+  trimmed.to_string()
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<String, String> {
+  let mut entry = archive
+    .by_name(name)
+    .map_err(|_| format!("Forge installer is missing {}", name))?;
+  let mut text = String::new();
+  entry.read_to_string(&mut text).map_err(|err| err.to_string())?;
+  Ok(text)
+}
+
+fn extract_bundled_maven(
+  archive: &mut ZipArchive<File>,
+  libraries_dir: &Path,
+) -> Result<(), String> {
+  for index in 0..archive.len() {
+    let mut entry = archive.by_index(index).map_err(|err| err.to_string())?;
+    if entry.is_dir() {
+      continue;
+    }
+    let Some(relative) = entry
+      .enclosed_name()
+      .and_then(|path| path.strip_prefix(MAVEN_PREFIX).ok().map(Path::to_path_buf))
+    else {
+      continue;
+    };
+    write_entry(&mut entry, &libraries_dir.join(relative))?;
+  }
+  Ok(())
+}
+
+fn write_entry(entry: &mut impl Read, target: &Path) -> Result<(), String> {
+  if let Some(parent) = target.parent() {
+    fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+  }
+  let mut out = File::create(long_path(target)).map_err(|err| err.to_string())?;
+  io::copy(entry, &mut out).map_err(|err| err.to_string())?;
+  Ok(())
+}
+
+/// Builds the `{KEY}` table processors refer to: the installer's built-ins plus the
+/// client side of the profile's `data` block.
+fn resolve_data(
+  profile: &ForgeInstallProfile,
+  archive: &mut ZipArchive<File>,
+  installer_path: &Path,
+  instance_dir: &Path,
+  libraries_dir: &Path,
+  data_dir: &Path,
+) -> Result<HashMap<String, String>, String> {
+  let minecraft_jar = instance_dir
+    .join("versions")
+    .join(&profile.minecraft)
+    .join(format!("{}.jar", profile.minecraft));
+  let mut data = HashMap::from([
+    ("SIDE".to_string(), "client".to_string()),
+    ("MINECRAFT_JAR".to_string(), path_string(&minecraft_jar)),
+    ("MINECRAFT_VERSION".to_string(), profile.minecraft.clone()),
+    ("ROOT".to_string(), path_string(instance_dir)),
+    ("INSTALLER".to_string(), path_string(installer_path)),
+    ("LIBRARY_DIR".to_string(), path_string(libraries_dir)),
+  ]);
+  for (key, entry) in &profile.data {
+    let value = entry.client.trim();
+    let resolved = if let Some(coordinate) = strip_wrapping(value, '[', ']') {
+      path_string(&libraries_dir.join(maven_artifact_path(coordinate)?))
+    } else if let Some(literal) = strip_wrapping(value, '\'', '\'') {
+      literal.to_string()
+    } else if let Some(entry_name) = value.strip_prefix('/') {
+      let target = data_dir.join(entry_name);
+      let mut entry = archive
+        .by_name(entry_name)
+        .map_err(|_| format!("Forge installer is missing {}", entry_name))?;
+      write_entry(&mut entry, &target)?;
+      path_string(&target)
+    } else {
+      value.to_string()
+    };
+    data.insert(key.clone(), resolved);
+  }
+  Ok(data)
+}
+
+fn run_processor(
+  processor: &ForgeProcessor,
+  data: &HashMap<String, String>,
+  libraries_dir: &Path,
+  instance_dir: &Path,
+  current: u64,
+  total: u64,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let jar_path = libraries_dir.join(maven_artifact_path(&processor.jar)?);
+  let outputs = processor
+    .outputs
+    .iter()
+    .map(|(path, sha1)| {
+      Ok((
+        resolve_arg(path, data, libraries_dir)?,
+        resolve_arg(sha1, data, libraries_dir)?,
+      ))
+    })
+    .collect::<Result<Vec<(String, String)>, String>>()?;
+  if !outputs.is_empty() && outputs.iter().all(|(path, sha1)| sha1_matches(Path::new(path), sha1)) {
+    return Ok(());
+  }
+
+  let main_class = read_main_class(&jar_path)?;
+  emit(ProgressEvent {
+    stage: "forge".to_string(),
+    message: format!("Running Forge processor {}/{}", current, total),
+    current,
+    total: Some(total),
+    detail: Some(main_class.clone()),
+    ..Default::default()
+  });
+
+  let mut classpath = vec![path_string(&jar_path)];
+  for coordinate in &processor.classpath {
+    classpath.push(path_string(&libraries_dir.join(maven_artifact_path(coordinate)?)));
+  }
+  let args = processor
+    .args
+    .iter()
+    .map(|arg| resolve_arg(arg, data, libraries_dir))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let output = Command::new("java")
+    .arg("-cp")
+    .arg(classpath.join(classpath_separator()))
+    .arg(&main_class)
+    .args(&args)
+    .current_dir(instance_dir)
+    .output()
+    .map_err(|err| format!("failed to run Forge processor {}: {}", main_class, err))?;
+  if !output.status.success() {
+    let combined = format!(
+      "{}\n{}",
+      String::from_utf8_lossy(&output.stdout),
+      String::from_utf8_lossy(&output.stderr)
+    );
+    let lines = combined.lines().filter(|line| !line.trim().is_empty()).collect::<Vec<_>>();
+    let tail = lines[lines.len().saturating_sub(PROCESSOR_OUTPUT_TAIL)..].join("\n");
+    return Err(format!(
+      "Forge processor {} failed (code {:?}): {}",
+      main_class,
+      output.status.code(),
+      tail.trim()
+    ));
+  }
+
+  for (path, sha1) in &outputs {
+    if !sha1_matches(Path::new(path), sha1) {
+      return Err(format!("Forge processor {} produced an invalid {}", main_class, path));
+    }
+  }
+  Ok(())
+}
+
+/// Expands `{KEY}` and `[maven:coordinate]` references in a processor argument.
+fn resolve_arg(
+  value: &str,
+  data: &HashMap<String, String>,
+  libraries_dir: &Path,
+) -> Result<String, String> {
+  if let Some(key) = strip_wrapping(value, '{', '}') {
+    return data
+      .get(key)
+      .cloned()
+      .ok_or_else(|| format!("Forge installer references unknown data '{}'", key));
+  }
+  if let Some(coordinate) = strip_wrapping(value, '[', ']') {
+    return Ok(path_string(&libraries_dir.join(maven_artifact_path(coordinate)?)));
+  }
+  if let Some(literal) = strip_wrapping(value, '\'', '\'') {
+    return Ok(literal.to_string());
+  }
+  let mut resolved = value.to_string();
+  for (key, replacement) in data {
+    resolved = resolved.replace(&format!("{{{}}}", key), replacement);
+  }
+  Ok(resolved)
+}
+
+fn strip_wrapping(value: &str, open: char, close: char) -> Option<&str> {
+  value
+    .strip_prefix(open)
+    .and_then(|rest| rest.strip_suffix(close))
+    .filter(|inner| !inner.is_empty())
+}
+
+/// `group:artifact:version[:classifier][@ext]` to its repository-relative path.
+fn maven_artifact_path(coordinate: &str) -> Result<PathBuf, String> {
+  let (name, extension) = coordinate.split_once('@').unwrap_or((coordinate, "jar"));
+  let parts = name.split(':').collect::<Vec<_>>();
+  if parts.len() < 3 || parts.iter().any(|part| part.is_empty() || part.contains("..")) {
+    return Err(format!("invalid maven coordinate '{}'", coordinate));
+  }
+  let classifier = parts.get(3).map(|value| format!("-{}", value)).unwrap_or_default();
+  let mut path = PathBuf::new();
+  for segment in parts[0].split('.') {
+    path.push(segment);
+  }
+  path.push(parts[1]);
+  path.push(parts[2]);
+  path.push(format!("{}-{}{}.{}", parts[1], parts[2], classifier, extension));
+  Ok(path)
+}
+
+fn read_main_class(jar_path: &Path) -> Result<String, String> {
+  let file = File::open(jar_path)
+    .map_err(|err| format!("Forge processor '{}' missing: {}", jar_path.display(), err))?;
+  let mut archive = ZipArchive::new(file).map_err(|err| err.to_string())?;
+  let manifest = read_entry(&mut archive, "META-INF/MANIFEST.MF")?;
+  // Manifest lines wrap at 72 bytes with a leading space on continuation lines.
+  let unfolded = manifest.replace("\r\n", "\n").replace("\n ", "");
+  unfolded
+    .lines()
+    .find_map(|line| line.strip_prefix("Main-Class:"))
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+    .ok_or_else(|| format!("no Main-Class in {}", jar_path.display()))
+}
+
+fn sha1_matches(path: &Path, expected: &str) -> bool {
+  let Ok(mut file) = File::open(path) else {
+    return false;
+  };
+  let mut hasher = Sha1::new();
+  if io::copy(&mut file, &mut hasher).is_err() {
+    return false;
+  }
+  format!("{:x}", hasher.finalize()).eq_ignore_ascii_case(expected.trim())
+}
+
+fn path_string(path: &Path) -> String {
+  path.to_string_lossy().to_string()
+}
@@ -1,39 +1,53 @@
-use crate::minecraft::download::{download_to, fetch_json, fetch_text, load_json};
+use crate::minecraft::download::{
+  download_parallelism, download_to, download_verified, fetch_json, fetch_text, load_json,
+  polite_download_pause,
+};
+use crate::minecraft::forge::install_forge_from_profile;
+use crate::minecraft::journal::InstallJournal;
+use crate::minecraft::instance::load_manifest;
+use crate::minecraft::summary::{active_recorder, record_warning, with_recorder};
 use crate::minecraft::models::{
-  FabricProfile, ForgeProfile, MojangAssetIndexFile, MojangVersionMeta, NativeJar, ProfileLibrary,
-  ProgressEvent,
+  DownloadFailure, DownloadJob, FabricProfile, ForgeProfile, MojangAssetIndex,
+  MojangAssetIndexFile, MojangLibrary, MojangVersionMeta, NativeJar, ProfileLibrary, ProgressEvent,
+  VersionFile,
 };
 use crate::minecraft::util::{
   build_maven_path_url, current_arch_suffix, current_os_name, is_excluded, library_allowed,
-  parse_maven_coordinate, resolve_library_artifact,
+  long_path, parse_maven_coordinate, resolve_legacy_assets_dir, resolve_library_artifact,
+  sanitize_path_component,
 };
 use crate::minecraft::versions::{neoforge_version_matches_game, resolve_neoforge_channel};
 use crate::minecraft::{
   DEFAULT_LIBRARIES_URL, FABRIC_LOADER_URL, MOJANG_MANIFEST_URL, NEOFORGE_MAVEN_BASE,
-  RESOURCES_BASE_URL,
+  QUILT_LOADER_URL, RESOURCES_BASE_URL,
 };
 use std::{
   collections::{HashSet, VecDeque},
   fs,
-  io,
-  path::Path,
-  process::Command,
+  io::{self, BufRead, BufReader, Read},
+  path::{Path, PathBuf},
+  process::{Command, Stdio},
   sync::{mpsc, Arc, Mutex},
   thread,
 };
+use sha2::{Digest, Sha256};
 use zip::ZipArchive;
 
+const INSTALLER_OUTPUT_TAIL: usize = 40;
+const NATIVES_STATE_FILE: &str = ".monolith-natives";
+const DOWNLOAD_FAILURE_REPORT_LIMIT: usize = 20;
+
 pub(crate) fn install_vanilla(
   game_version: &str,
   instance_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   emit(ProgressEvent {
     stage: "version".to_string(),
     message: format!("Resolving {}", game_version),
     current: 0,
-    total: None,
-    detail: None,
+    ..Default::default()
   });
 
   let manifest: crate::minecraft::models::MojangManifest = fetch_json(MOJANG_MANIFEST_URL)?;
@@ -44,34 +58,213 @@ pub(crate) fn install_vanilla(
     .ok_or_else(|| format!("vanilla version '{}' not found", game_version))?;
 
   let version_dir = instance_dir.join("versions").join(&entry.id);
-  fs::create_dir_all(&version_dir).map_err(|err| err.to_string())?;
+  fs::create_dir_all(long_path(&version_dir)).map_err(|err| err.to_string())?;
 
   let version_json_path = version_dir.join(format!("{}.json", entry.id));
   download_to(&entry.url, &version_json_path)?;
 
   let version_meta: MojangVersionMeta = load_json(&version_json_path)?;
   let client_jar_path = version_dir.join(format!("{}.jar", entry.id));
-  download_zip_with_retry(&version_meta.downloads.client.url, &client_jar_path, "client jar")?;
+  let client_stage = format!("vanilla:{}:client", entry.id);
+  if !journal.is_completed(&client_stage) || !client_jar_path.is_file() {
+    emit(ProgressEvent {
+      stage: "client".to_string(),
+      message: "Downloading client jar".to_string(),
+      current: 0,
+      total: Some(1),
+      ..Default::default()
+    });
+    let client = &version_meta.downloads.client;
+    download_zip_verified(
+      &client.url,
+      &client_jar_path,
+      client.size,
+      client.sha1.as_deref(),
+      "client jar",
+    )?;
+    journal.complete(&client_stage);
+  }
 
   let libraries_dir = instance_dir.join("libraries");
   let natives_dir = instance_dir.join("natives").join(&entry.id);
-  let native_jars = download_mojang_libraries(&version_meta, &libraries_dir, emit)?;
+  let native_jars =
+    download_mojang_libraries(&version_meta.libraries, &libraries_dir, journal, emit)?;
   extract_natives(&native_jars, &natives_dir, emit)?;
+  let custom_version = load_manifest(instance_dir).and_then(|manifest| manifest.custom_version);
+  prune_stale_natives(&instance_dir.join("natives"), &entry.id, custom_version.as_deref());
 
-  download_assets(&version_meta, instance_dir, emit)?;
+  let assets_stage = format!("vanilla:{}:assets", entry.id);
+  if !journal.is_completed(&assets_stage) {
+    download_assets(&version_meta.asset_index, instance_dir, journal, emit)?;
+    journal.complete(&assets_stage);
+  }
 
   Ok(())
 }
 
+/// Copies a user-supplied version JSON (and optional client jar) into the instance and
+/// downloads whatever it references. Returns the version id to launch with.
+pub(crate) fn install_custom_version(
+  instance_dir: &Path,
+  json_source: &Path,
+  jar_source: Option<&Path>,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<String, String> {
+  let text = fs::read_to_string(json_source).map_err(|err| err.to_string())?;
+  let mut document: serde_json::Value =
+    serde_json::from_str(&text).map_err(crate::minecraft::download::map_json_error)?;
+  let version: VersionFile = serde_json::from_value(document.clone())
+    .map_err(crate::minecraft::download::map_json_error)?;
+  let version_id = version.id.trim().to_string();
+  if version_id.is_empty() || sanitize_path_component(&version_id, "") != version_id {
+    return Err(format!("version id '{}' cannot be used as a folder name", version.id));
+  }
+  if version.main_class.is_none() && version.inherits_from.is_none() {
+    return Err("version JSON needs a mainClass or inheritsFrom".to_string());
+  }
+
+  emit(ProgressEvent {
+    stage: "version".to_string(),
+    message: format!("Adding custom version {}", version_id),
+    current: 0,
+    ..Default::default()
+  });
+
+  let journal = InstallJournal::open(instance_dir);
+  let versions_dir = instance_dir.join("versions");
+  if let Some(parent) = &version.inherits_from {
+    let parent_json = versions_dir.join(parent).join(format!("{}.json", parent));
+    if !parent_json.is_file() {
+      install_vanilla(parent, instance_dir, &journal, emit).map_err(|err| {
+        format!("base version '{}' is not installed and could not be downloaded: {}", parent, err)
+      })?;
+    }
+  }
+
+  let version_dir = versions_dir.join(&version_id);
+  fs::create_dir_all(long_path(&version_dir)).map_err(|err| err.to_string())?;
+  let jar_dest = version_dir.join(format!("{}.jar", version_id));
+  if let Some(source) = jar_source {
+    fs::copy(long_path(source), long_path(&jar_dest)).map_err(|err| err.to_string())?;
+    // An inheriting version otherwise launches with its parent's jar.
+    if version.inherits_from.is_some() && version.jar.is_none() {
+      if let Some(map) = document.as_object_mut() {
+        map.insert("jar".to_string(), serde_json::Value::String(version_id.clone()));
+      }
+    }
+  } else if version.inherits_from.is_none() && !jar_dest.is_file() {
+    let client = document
+      .pointer("/downloads/client")
+      .ok_or_else(|| {
+        "a client jar is required for versions that do not inherit from another".to_string()
+      })?;
+    let url = client
+      .get("url")
+      .and_then(|value| value.as_str())
+      .ok_or_else(|| "client download url missing in version JSON".to_string())?;
+    download_zip_verified(
+      url,
+      &jar_dest,
+      client.get("size").and_then(|value| value.as_u64()),
+      client.get("sha1").and_then(|value| value.as_str()),
+      "client jar",
+    )?;
+  }
+  let payload =
+    serde_json::to_vec_pretty(&document).map_err(crate::minecraft::download::map_json_error)?;
+  fs::write(long_path(&version_dir.join(format!("{}.json", version_id))), payload)
+    .map_err(|err| err.to_string())?;
+
+  let libraries_dir = instance_dir.join("libraries");
+  let native_jars = download_mojang_libraries(&version.libraries, &libraries_dir, &journal, emit)?;
+  let os_name = current_os_name();
+  let mut maven_jobs = Vec::new();
+  for library in &version.libraries {
+    if library.downloads.is_some() || !library_allowed(library.rules.as_ref(), os_name) {
+      continue;
+    }
+    let Ok(coordinate) = parse_maven_coordinate(&library.name) else {
+      continue;
+    };
+    let base_url = library.url.as_deref().unwrap_or(DEFAULT_LIBRARIES_URL);
+    if let Ok((path, url)) = build_maven_path_url(base_url, &coordinate) {
+      maven_jobs.push(DownloadJob {
+        url,
+        dest: libraries_dir.join(path),
+        size: None,
+        sha1: None,
+      });
+    }
+  }
+  download_jobs_parallel(
+    maven_jobs,
+    &journal,
+    "libraries",
+    "Downloading custom version libraries",
+    emit,
+  )?;
+
+  // Inheriting versions share their base's natives folder, which the base install owns.
+  if version.inherits_from.is_none() {
+    extract_natives(&native_jars, &instance_dir.join("natives").join(&version_id), emit)?;
+    if let Some(asset_index) = &version.asset_index {
+      download_assets(asset_index, instance_dir, &journal, emit)?;
+    }
+  }
+
+  journal.finish();
+  Ok(version_id)
+}
+
 pub(crate) fn install_fabric(
   game_version: &str,
   loader_version: &str,
   instance_dir: &Path,
+  journal: &InstallJournal,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  install_loader_profile(
+    FABRIC_LOADER_URL,
+    "Fabric",
+    game_version,
+    loader_version,
+    instance_dir,
+    journal,
+    emit,
+  )
+}
+
+/// Quilt's meta API serves launcher profiles in the same shape as Fabric's.
+pub(crate) fn install_quilt(
+  game_version: &str,
+  loader_version: &str,
+  instance_dir: &Path,
+  journal: &InstallJournal,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  install_loader_profile(
+    QUILT_LOADER_URL,
+    "Quilt",
+    game_version,
+    loader_version,
+    instance_dir,
+    journal,
+    emit,
+  )
+}
+
+fn install_loader_profile(
+  meta_url: &str,
+  label: &str,
+  game_version: &str,
+  loader_version: &str,
+  instance_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   let url = format!(
     "{}/{}/{}/profile/json",
-    FABRIC_LOADER_URL,
+    meta_url,
     urlencoding::encode(game_version),
     urlencoding::encode(loader_version)
   );
@@ -80,19 +273,23 @@ pub(crate) fn install_fabric(
   let profile: FabricProfile = serde_json::from_str(&profile_text)
     .map_err(crate::minecraft::download::map_json_error)?;
   let profile_dir = instance_dir.join("versions").join(&profile.id);
-  fs::create_dir_all(&profile_dir).map_err(|err| err.to_string())?;
+  fs::create_dir_all(long_path(&profile_dir)).map_err(|err| err.to_string())?;
 
   let profile_path = profile_dir.join(format!("{}.json", profile.id));
-  fs::write(&profile_path, profile_text).map_err(|err| err.to_string())?;
+  fs::write(long_path(&profile_path), profile_text).map_err(|err| err.to_string())?;
 
   let base_version = profile
     .inherits_from
     .clone()
     .unwrap_or_else(|| game_version.to_string());
-  install_vanilla(&base_version, instance_dir, emit)?;
+  install_vanilla(&base_version, instance_dir, journal, emit)?;
 
   let libraries_dir = instance_dir.join("libraries");
-  download_fabric_libraries(&profile, &libraries_dir, emit)?;
+  let libraries_stage = format!("{}:libraries", profile.id);
+  if !journal.is_completed(&libraries_stage) {
+    download_loader_profile_libraries(&profile, label, &libraries_dir, journal, emit)?;
+    journal.complete(&libraries_stage);
+  }
 
   Ok(())
 }
@@ -101,14 +298,29 @@ pub(crate) fn install_forge(
   game_version: &str,
   loader_version: &str,
   instance_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
-  let full_version = if loader_version.contains(game_version) && loader_version.contains('-') {
+  let full_version = forge_full_version(game_version, loader_version);
+  let installer_path = download_forge_installer(&full_version, instance_dir)?;
+
+  install_vanilla(game_version, instance_dir, journal, emit)?;
+  run_forge_installer(&installer_path, instance_dir, &full_version, journal, emit)?;
+  Ok(())
+}
+
+pub(crate) fn forge_full_version(game_version: &str, loader_version: &str) -> String {
+  if loader_version.contains(game_version) && loader_version.contains('-') {
     loader_version.to_string()
   } else {
     format!("{}-{}", game_version, loader_version)
-  };
+  }
+}
 
+pub(crate) fn download_forge_installer(
+  full_version: &str,
+  instance_dir: &Path,
+) -> Result<PathBuf, String> {
   let installer_url = format!(
     "https://maven.minecraftforge.net/net/minecraftforge/forge/{0}/forge-{0}-installer.jar",
     full_version
@@ -116,17 +328,28 @@ pub(crate) fn install_forge(
   let installer_path = instance_dir
     .join("installers")
     .join(format!("forge-{}-installer.jar", full_version));
-  download_zip_with_retry(&installer_url, &installer_path, "forge installer")?;
+  // The installer runs with the user's permissions, so never execute an unverified jar.
+  let sha1 = fetch_published_sha1(&installer_url)
+    .map_err(|err| format!("could not verify forge installer: {}", err))?;
+  download_zip_verified(&installer_url, &installer_path, None, Some(&sha1), "forge installer")?;
+  Ok(installer_path)
+}
 
-  install_vanilla(game_version, instance_dir, emit)?;
-  run_forge_installer(&installer_path, instance_dir, &full_version, emit)?;
-  Ok(())
+/// Checksum Maven publishes next to an artifact (`<url>.sha1`).
+fn fetch_published_sha1(artifact_url: &str) -> Result<String, String> {
+  let text = fetch_text(&format!("{}.sha1", artifact_url))?;
+  let digest = text.split_whitespace().next().unwrap_or("").to_ascii_lowercase();
+  if digest.len() != 40 || !digest.chars().all(|ch| ch.is_ascii_hexdigit()) {
+    return Err(format!("no valid checksum published for {}", artifact_url));
+  }
+  Ok(digest)
 }
 
 pub(crate) fn install_neoforge(
   game_version: &str,
   loader_version: &str,
   instance_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   if !neoforge_version_matches_game(loader_version, game_version) {
@@ -137,6 +360,17 @@ pub(crate) fn install_neoforge(
     ));
   }
 
+  let installer_path = download_neoforge_installer(loader_version, instance_dir)?;
+
+  install_vanilla(game_version, instance_dir, journal, emit)?;
+  run_neoforge_installer(&installer_path, instance_dir, loader_version, journal, emit)?;
+  Ok(())
+}
+
+pub(crate) fn download_neoforge_installer(
+  loader_version: &str,
+  instance_dir: &Path,
+) -> Result<PathBuf, String> {
   let installer_url = format!(
     "{}/{}/neoforge-{}-installer.jar",
     NEOFORGE_MAVEN_BASE,
@@ -147,15 +381,13 @@ pub(crate) fn install_neoforge(
     .join("installers")
     .join(format!("neoforge-{}-installer.jar", loader_version));
   download_zip_with_retry(&installer_url, &installer_path, "neoforge installer")?;
-
-  install_vanilla(game_version, instance_dir, emit)?;
-  run_neoforge_installer(&installer_path, instance_dir, loader_version, emit)?;
-  Ok(())
+  Ok(installer_path)
 }
 
-fn download_mojang_libraries(
-  meta: &MojangVersionMeta,
+pub(crate) fn download_mojang_libraries(
+  libraries: &[MojangLibrary],
   libraries_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<Vec<NativeJar>, String> {
   let os_name = current_os_name();
@@ -165,7 +397,7 @@ fn download_mojang_libraries(
   let mut seen = HashSet::new();
   let mut native_jars = Vec::new();
 
-  for library in &meta.libraries {
+  for library in libraries {
     if !library_allowed(library.rules.as_ref(), os_name) {
       continue;
     }
@@ -175,7 +407,12 @@ fn download_mojang_libraries(
         if let Some((url, path)) = resolve_library_artifact(artifact, &library.name, None) {
           let dest = libraries_dir.join(path);
           if seen.insert(dest.clone()) {
-            jobs.push(crate::minecraft::models::DownloadJob { url, dest });
+            jobs.push(DownloadJob {
+              url,
+              dest,
+              size: artifact.size,
+              sha1: artifact.sha1.clone(),
+            });
           }
         }
       }
@@ -183,22 +420,35 @@ fn download_mojang_libraries(
       if let Some(natives) = &library.natives {
         if let Some(template) = natives.get(os_name) {
           let classifier = template.replace("${arch}", arch);
-          if let Some(classifiers) = &downloads.classifiers {
-            if let Some(native_artifact) = classifiers.get(&classifier) {
-              if let Some((url, path)) =
-                resolve_library_artifact(native_artifact, &library.name, Some(&classifier))
-              {
-                let excludes = library
-                  .extract
-                  .as_ref()
-                  .and_then(|extract| extract.exclude.clone())
-                  .unwrap_or_default();
-                let dest = libraries_dir.join(path);
-                if seen.insert(dest.clone()) {
-                  jobs.push(crate::minecraft::models::DownloadJob { url, dest: dest.clone() });
-                }
-                native_jars.push(NativeJar { path: dest, excludes });
+          let native_artifact = downloads
+            .classifiers
+            .as_ref()
+            .and_then(|classifiers| classifiers.get(&classifier));
+          if native_artifact.is_none() {
+            record_warning(format!(
+              "skipped natives for {}: no '{}' classifier",
+              library.name, classifier
+            ));
+          }
+          if let Some(native_artifact) = native_artifact {
+            if let Some((url, path)) =
+              resolve_library_artifact(native_artifact, &library.name, Some(&classifier))
+            {
+              let excludes = library
+                .extract
+                .as_ref()
+                .and_then(|extract| extract.exclude.clone())
+                .unwrap_or_default();
+              let dest = libraries_dir.join(path);
+              if seen.insert(dest.clone()) {
+                jobs.push(DownloadJob {
+                  url,
+                  dest: dest.clone(),
+                  size: native_artifact.size,
+                  sha1: native_artifact.sha1.clone(),
+                });
               }
+              native_jars.push(NativeJar { path: dest, excludes });
             }
           }
         }
@@ -206,7 +456,7 @@ fn download_mojang_libraries(
     }
   }
 
-  download_jobs_parallel(jobs, "libraries", "Downloading libraries", emit)?;
+  download_jobs_parallel(jobs, journal, "libraries", "Downloading libraries", emit)?;
 
   Ok(native_jars)
 }
@@ -220,7 +470,23 @@ fn extract_natives(
     return Ok(());
   }
 
-  fs::create_dir_all(natives_dir).map_err(|err| err.to_string())?;
+  let fingerprint = natives_fingerprint(native_jars);
+  let state_path = natives_dir.join(NATIVES_STATE_FILE);
+  if fs::read_to_string(long_path(&state_path)).ok().as_deref() == Some(fingerprint.as_str()) {
+    emit(ProgressEvent {
+      stage: "natives".to_string(),
+      message: "Natives up to date".to_string(),
+      current: native_jars.len() as u64,
+      total: Some(native_jars.len() as u64),
+      ..Default::default()
+    });
+    return Ok(());
+  }
+
+  if natives_dir.exists() {
+    fs::remove_dir_all(long_path(natives_dir)).map_err(|err| err.to_string())?;
+  }
+  fs::create_dir_all(long_path(natives_dir)).map_err(|err| err.to_string())?;
   let total = native_jars.len() as u64;
 
   for (idx, native) in native_jars.iter().enumerate() {
@@ -229,10 +495,10 @@ fn extract_natives(
       message: format!("Extracting natives ({}/{})", idx + 1, total),
       current: (idx + 1) as u64,
       total: Some(total),
-      detail: None,
+      ..Default::default()
     });
 
-    let file = fs::File::open(&native.path).map_err(|err| err.to_string())?;
+    let file = fs::File::open(long_path(&native.path)).map_err(|err| err.to_string())?;
     let mut archive = ZipArchive::new(file).map_err(|err| err.to_string())?;
 
     for i in 0..archive.len() {
@@ -244,33 +510,70 @@ fn extract_natives(
 
       let dest_path = natives_dir.join(&name);
       if let Some(parent) = dest_path.parent() {
-        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
       }
-      let mut out_file = fs::File::create(&dest_path).map_err(|err| err.to_string())?;
+      let mut out_file = fs::File::create(long_path(&dest_path)).map_err(|err| err.to_string())?;
       io::copy(&mut file, &mut out_file).map_err(|err| err.to_string())?;
     }
   }
 
+  fs::write(long_path(&state_path), fingerprint).map_err(|err| err.to_string())?;
   Ok(())
 }
 
+fn natives_fingerprint(native_jars: &[NativeJar]) -> String {
+  let mut entries = native_jars
+    .iter()
+    .map(|native| {
+      let size = fs::metadata(long_path(&native.path)).map(|meta| meta.len()).unwrap_or(0);
+      format!(
+        "{}|{}|{}",
+        native.path.to_string_lossy(),
+        size,
+        native.excludes.join(",")
+      )
+    })
+    .collect::<Vec<_>>();
+  entries.sort();
+  let mut hasher = Sha256::new();
+  for entry in entries {
+    hasher.update(entry.as_bytes());
+    hasher.update(b"\n");
+  }
+  format!("{:x}", hasher.finalize())
+}
+
+fn prune_stale_natives(natives_root: &Path, current_id: &str, custom_version: Option<&str>) {
+  let Ok(entries) = fs::read_dir(long_path(natives_root)) else {
+    return;
+  };
+  for entry in entries.flatten() {
+    let path = entry.path();
+    let name = entry.file_name().to_string_lossy().to_string();
+    if !path.is_dir() || name == current_id || custom_version == Some(name.as_str()) {
+      continue;
+    }
+    let _ = fs::remove_dir_all(long_path(&path));
+  }
+}
+
 fn download_assets(
-  meta: &MojangVersionMeta,
+  asset_index: &MojangAssetIndex,
   instance_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   emit(ProgressEvent {
     stage: "assets".to_string(),
     message: "Downloading asset index".to_string(),
     current: 0,
-    total: None,
-    detail: None,
+    ..Default::default()
   });
 
   let asset_index_path = instance_dir
     .join("assets/indexes")
-    .join(format!("{}.json", meta.asset_index.id));
-  download_to(&meta.asset_index.url, &asset_index_path)?;
+    .join(format!("{}.json", asset_index.id));
+  download_to(&asset_index.url, &asset_index_path)?;
 
   let index: MojangAssetIndexFile = load_json(&asset_index_path)?;
   let mut jobs = Vec::with_capacity(index.objects.len());
@@ -286,20 +589,95 @@ fn download_assets(
       .join(hash);
 
     let url = format!("{}/{}/{}", RESOURCES_BASE_URL, prefix, hash);
-    jobs.push(crate::minecraft::models::DownloadJob { url, dest });
+    jobs.push(DownloadJob {
+      url,
+      dest,
+      size: object.size,
+      sha1: Some(object.hash.clone()),
+    });
   }
 
-  download_jobs_parallel(jobs, "assets", "Downloading assets", emit)?;
+  download_jobs_parallel(jobs, journal, "assets", "Downloading assets", emit)?;
+
+  if let Some(legacy_dir) = resolve_legacy_assets_dir(instance_dir, &asset_index.id) {
+    copy_legacy_assets(&index, &instance_dir.join("assets/objects"), &legacy_dir, false)?;
+  }
   Ok(())
 }
 
-fn download_zip_with_retry(url: &str, dest: &Path, label: &str) -> Result<(), String> {
-  download_to(url, dest)?;
-  if is_valid_zip(dest) {
+/// Pre-1.6 (`map_to_resources`) and 1.6/1.7 (`virtual`) indexes read assets by name
+/// rather than from the hashed store. Re-copies anything missing from that layout, so
+/// a cleared `resources` folder does not leave old versions silent. Returns the
+/// directory the game should use as `${game_assets}`, if the index needs one.
+pub(crate) fn materialize_legacy_assets(
+  instance_dir: &Path,
+  index_id: &str,
+) -> Result<Option<PathBuf>, String> {
+  let Some(legacy_dir) = resolve_legacy_assets_dir(instance_dir, index_id) else {
+    return Ok(None);
+  };
+  let index: MojangAssetIndexFile = load_json(
+    &instance_dir
+      .join("assets/indexes")
+      .join(format!("{}.json", index_id)),
+  )?;
+  copy_legacy_assets(&index, &instance_dir.join("assets/objects"), &legacy_dir, true)?;
+  Ok(Some(legacy_dir))
+}
+
+/// `skip_missing` lets a launch go ahead with whatever objects are present; right after the
+/// asset download every object must be there.
+fn copy_legacy_assets(
+  index: &MojangAssetIndexFile,
+  objects_dir: &Path,
+  legacy_dir: &Path,
+  skip_missing: bool,
+) -> Result<(), String> {
+  for (name, object) in &index.objects {
+    let hash = object.hash.as_str();
+    if hash.len() < 2 || name.split('/').any(|part| part == "..") {
+      continue;
+    }
+    let source = objects_dir.join(&hash[0..2]).join(hash);
+    if !long_path(&source).is_file() {
+      if skip_missing {
+        continue;
+      }
+      return Err(format!("asset object {} for '{}' is missing", hash, name));
+    }
+    let dest = legacy_dir.join(name);
+    let up_to_date = match (fs::metadata(long_path(&dest)), object.size) {
+      (Ok(meta), Some(size)) => meta.len() == size,
+      (Ok(_), None) => true,
+      (Err(_), _) => false,
+    };
+    if up_to_date {
+      continue;
+    }
+    if let Some(parent) = dest.parent() {
+      fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+    }
+    fs::copy(long_path(&source), long_path(&dest)).map_err(|err| err.to_string())?;
+  }
+  Ok(())
+}
+
+pub(crate) fn download_zip_with_retry(url: &str, dest: &Path, label: &str) -> Result<(), String> {
+  download_zip_verified(url, dest, None, None, label)
+}
+
+pub(crate) fn download_zip_verified(
+  url: &str,
+  dest: &Path,
+  size: Option<u64>,
+  sha1: Option<&str>,
+  label: &str,
+) -> Result<(), String> {
+  if download_verified(url, dest, size, sha1).is_ok() && is_valid_zip(dest) {
     return Ok(());
   }
-  let _ = fs::remove_file(dest);
-  download_to(url, dest)?;
+  let _ = fs::remove_file(long_path(dest));
+  download_verified(url, dest, size, sha1)?;
   if is_valid_zip(dest) {
     return Ok(());
   }
@@ -307,16 +685,18 @@ fn download_zip_with_retry(url: &str, dest: &Path, label: &str) -> Result<(), St
 }
 
 fn is_valid_zip(path: &Path) -> bool {
-  let file = match fs::File::open(path) {
+  let file = match fs::File::open(long_path(path)) {
     Ok(file) => file,
     Err(_) => return false,
   };
   ZipArchive::new(file).is_ok()
 }
 
-fn download_fabric_libraries(
+fn download_loader_profile_libraries(
   profile: &FabricProfile,
+  label: &str,
   libraries_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   if profile.libraries.is_empty() {
@@ -331,14 +711,17 @@ fn download_fabric_libraries(
     };
     let base_url = library.url.as_deref().unwrap_or(DEFAULT_LIBRARIES_URL);
     if let Ok((path, url)) = build_maven_path_url(base_url, &coordinate) {
-      jobs.push(crate::minecraft::models::DownloadJob {
+      jobs.push(DownloadJob {
         url,
         dest: libraries_dir.join(path),
+        size: None,
+        sha1: None,
       });
     }
   }
 
-  download_jobs_parallel(jobs, "libraries", "Downloading Fabric libraries", emit)?;
+  let message = format!("Downloading {} libraries", label);
+  download_jobs_parallel(jobs, journal, "libraries", &message, emit)?;
 
   Ok(())
 }
@@ -347,6 +730,7 @@ fn run_forge_installer(
   installer_path: &Path,
   instance_dir: &Path,
   full_version: &str,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   let forge_version_id = format!("forge-{}", full_version);
@@ -355,21 +739,23 @@ fn run_forge_installer(
     .join(&forge_version_id)
     .join(format!("{}.json", forge_version_id));
 
+  let libraries_stage = format!("{}:libraries", forge_version_id);
   if !forge_json_path.exists() {
     emit(ProgressEvent {
       stage: "forge".to_string(),
-      message: "Running Forge installer".to_string(),
+      message: "Installing Forge".to_string(),
       current: 0,
-      total: None,
-      detail: None,
+      ..Default::default()
     });
-    run_java_installer(installer_path, instance_dir, "forge")?;
+    install_forge_from_profile(installer_path, instance_dir, full_version, journal, emit)?;
+    journal.complete(&libraries_stage);
   }
 
-  if forge_json_path.exists() {
+  if forge_json_path.exists() && !journal.is_completed(&libraries_stage) {
     if let Ok(profile) = load_json::<ForgeProfile>(&forge_json_path) {
       let libraries_dir = instance_dir.join("libraries");
-      download_profile_libraries(&profile.libraries, &libraries_dir, emit)?;
+      download_profile_libraries(&profile.libraries, &libraries_dir, journal, emit)?;
+      journal.complete(&libraries_stage);
     }
   }
 
@@ -380,6 +766,7 @@ fn run_neoforge_installer(
   installer_path: &Path,
   instance_dir: &Path,
   loader_version: &str,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   let neoforge_version_id = format!("neoforge-{}", loader_version);
@@ -393,10 +780,16 @@ fn run_neoforge_installer(
       stage: "neoforge".to_string(),
       message: "Running NeoForge installer".to_string(),
       current: 0,
-      total: None,
-      detail: None,
+      ..Default::default()
     });
-    run_java_installer(installer_path, instance_dir, "neoforge")?;
+    run_java_installer(
+      installer_path,
+      instance_dir,
+      "--installClient",
+      "neoforge",
+      "Running NeoForge installer",
+      emit,
+    )?;
   }
 
   if !neoforge_json_path.exists() {
@@ -406,40 +799,87 @@ fn run_neoforge_installer(
     ));
   }
 
+  let libraries_stage = format!("{}:libraries", neoforge_version_id);
+  if journal.is_completed(&libraries_stage) {
+    return Ok(());
+  }
   if let Ok(profile) = load_json::<ForgeProfile>(&neoforge_json_path) {
     let libraries_dir = instance_dir.join("libraries");
-    download_profile_libraries(&profile.libraries, &libraries_dir, emit)?;
+    download_profile_libraries(&profile.libraries, &libraries_dir, journal, emit)?;
+    journal.complete(&libraries_stage);
   }
 
   Ok(())
 }
 
-fn run_java_installer(
+pub(crate) fn run_java_installer(
   installer_path: &Path,
   instance_dir: &Path,
+  install_flag: &str,
   loader_label: &str,
+  message: &str,
+  emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   ensure_launcher_profile(instance_dir)?;
 
-  let output = Command::new("java")
+  let mut child = Command::new("java")
     .arg("-jar")
     .arg(installer_path)
-    .arg("--installClient")
+    .arg(install_flag)
     .arg(instance_dir)
     .current_dir(instance_dir)
-    .output()
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| format!("failed to run {} installer: {}", loader_label, err))?;
+
+  let stderr_handle = child.stderr.take().map(|stderr| {
+    thread::spawn(move || {
+      let mut buffer = String::new();
+      let _ = BufReader::new(stderr).read_to_string(&mut buffer);
+      buffer
+    })
+  });
+
+  let mut recent_lines: VecDeque<String> = VecDeque::new();
+  let mut line_count = 0_u64;
+  if let Some(stdout) = child.stdout.take() {
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+      let trimmed = line.trim();
+      if trimmed.is_empty() {
+        continue;
+      }
+      line_count += 1;
+      emit(ProgressEvent {
+        stage: loader_label.to_string(),
+        message: message.to_string(),
+        current: line_count,
+        detail: Some(trimmed.to_string()),
+        ..Default::default()
+      });
+      recent_lines.push_back(trimmed.to_string());
+      if recent_lines.len() > INSTALLER_OUTPUT_TAIL {
+        recent_lines.pop_front();
+      }
+    }
+  }
+
+  let status = child
+    .wait()
     .map_err(|err| format!("failed to run {} installer: {}", loader_label, err))?;
+  let stderr = stderr_handle
+    .and_then(|handle| handle.join().ok())
+    .unwrap_or_default();
 
-  if output.status.success() {
+  if status.success() {
     return Ok(());
   }
 
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let stderr = String::from_utf8_lossy(&output.stderr);
+  let stdout = recent_lines.into_iter().collect::<Vec<_>>().join("\n");
   Err(format!(
     "{} installer failed (code {:?}): {} {}",
     loader_label,
-    output.status.code(),
+    status.code(),
     stdout.trim(),
     stderr.trim()
   ))
@@ -457,7 +897,7 @@ fn ensure_launcher_profile(instance_dir: &Path) -> Result<(), String> {
   "version": 3
 }
 "#;
-  fs::write(&profile_path, payload).map_err(|err| {
+  fs::write(long_path(&profile_path), payload).map_err(|err| {
     format!(
       "failed to initialize launcher profile '{}': {}",
       profile_path.display(),
@@ -466,9 +906,10 @@ fn ensure_launcher_profile(instance_dir: &Path) -> Result<(), String> {
   })
 }
 
-fn download_profile_libraries(
+pub(crate) fn download_profile_libraries(
   libraries: &[ProfileLibrary],
   libraries_dir: &Path,
+  journal: &InstallJournal,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
   if libraries.is_empty() {
@@ -483,30 +924,68 @@ fn download_profile_libraries(
     };
     let base_url = library.url.as_deref().unwrap_or(DEFAULT_LIBRARIES_URL);
     if let Ok((path, url)) = build_maven_path_url(base_url, &coordinate) {
-      jobs.push(crate::minecraft::models::DownloadJob {
+      jobs.push(DownloadJob {
         url,
         dest: libraries_dir.join(path),
+        size: None,
+        sha1: None,
       });
     }
   }
 
-  download_jobs_parallel(jobs, "libraries", "Downloading Forge libraries", emit)?;
+  download_jobs_parallel(jobs, journal, "libraries", "Downloading Forge libraries", emit)?;
 
   Ok(())
 }
 
 fn download_jobs_parallel(
-  jobs: Vec<crate::minecraft::models::DownloadJob>,
+  jobs: Vec<DownloadJob>,
+  journal: &InstallJournal,
   stage: &str,
   label: &str,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<(), String> {
+  let jobs: Vec<_> = jobs
+    .into_iter()
+    .filter(|job| !journal.is_verified(&job.dest))
+    .collect();
   if jobs.is_empty() {
     return Ok(());
   }
 
+  let failed = run_download_pass(jobs, journal, stage, label, emit)?;
+  if failed.is_empty() {
+    return Ok(());
+  }
+
+  let retry_label = format!("Retrying {} failed downloads", failed.len());
+  record_warning(format!("{}: {} downloads needed a second attempt", stage, failed.len()));
+  let retry_jobs = failed.into_iter().map(|(job, _)| job).collect();
+  let failed = run_download_pass(retry_jobs, journal, stage, &retry_label, emit)?;
+  if failed.is_empty() {
+    return Ok(());
+  }
+
+  let failures: Vec<DownloadFailure> = failed
+    .into_iter()
+    .map(|(job, reason)| DownloadFailure {
+      file: job.dest.to_string_lossy().to_string(),
+      url: job.url,
+      reason,
+    })
+    .collect();
+  Err(format_download_failures(label, &failures))
+}
+
+fn run_download_pass(
+  jobs: Vec<DownloadJob>,
+  journal: &InstallJournal,
+  stage: &str,
+  label: &str,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<Vec<(DownloadJob, String)>, String> {
   struct DownloadResult {
-    job: crate::minecraft::models::DownloadJob,
+    job: DownloadJob,
     error: Option<String>,
   }
 
@@ -514,32 +993,39 @@ fn download_jobs_parallel(
   let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
   let (tx, rx) = mpsc::channel::<DownloadResult>();
 
-  let workers = thread::available_parallelism()
-    .map(|n| n.get())
-    .unwrap_or(4)
-    .clamp(2, 8);
+  let workers = download_parallelism(
+    thread::available_parallelism()
+      .map(|n| n.get())
+      .unwrap_or(4)
+      .clamp(2, 8),
+  );
 
+  let recorder = active_recorder();
   let mut handles = Vec::with_capacity(workers);
   for _ in 0..workers {
     let queue = Arc::clone(&queue);
     let tx = tx.clone();
-    handles.push(thread::spawn(move || loop {
-      let job = {
-        let mut guard = match queue.lock() {
-          Ok(guard) => guard,
-          Err(poisoned) => poisoned.into_inner(),
+    let recorder = recorder.clone();
+    handles.push(thread::spawn(move || {
+      with_recorder(recorder.as_ref(), || loop {
+        let job = {
+          let mut guard = match queue.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+          };
+          guard.pop_front()
         };
-        guard.pop_front()
-      };
-      let Some(job) = job else { break };
-      let error = download_to(&job.url, &job.dest).err();
-      let _ = tx.send(DownloadResult { job, error });
+        let Some(job) = job else { break };
+        polite_download_pause();
+        let error = download_verified(&job.url, &job.dest, job.size, job.sha1.as_deref()).err();
+        let _ = tx.send(DownloadResult { job, error });
+      })
     }));
   }
   drop(tx);
 
   let mut completed = 0_u64;
-  let mut first_error: Option<String> = None;
+  let mut failed = Vec::new();
 
   for _ in 0..total {
     let result = rx.recv().map_err(|_| "download worker stopped".to_string())?;
@@ -559,22 +1045,30 @@ fn download_jobs_parallel(
       current: completed,
       total: Some(total),
       detail: Some(detail),
+      ..Default::default()
     });
 
-    if let Some(err) = result.error {
-      if first_error.is_none() {
-        first_error = Some(err);
-      }
+    match result.error {
+      Some(err) => failed.push((result.job, err)),
+      None => journal.record_verified(&result.job.dest),
     }
   }
 
   for handle in handles {
     let _ = handle.join();
   }
+  journal.save();
 
-  if let Some(err) = first_error {
-    return Err(err);
-  }
+  Ok(failed)
+}
 
-  Ok(())
+fn format_download_failures(label: &str, failures: &[DownloadFailure]) -> String {
+  let mut lines = vec![format!("{}: {} file(s) could not be downloaded", label, failures.len())];
+  for failure in failures.iter().take(DOWNLOAD_FAILURE_REPORT_LIMIT) {
+    lines.push(format!("- {} ({}): {}", failure.file, failure.url, failure.reason));
+  }
+  if failures.len() > DOWNLOAD_FAILURE_REPORT_LIMIT {
+    lines.push(format!("...and {} more", failures.len() - DOWNLOAD_FAILURE_REPORT_LIMIT));
+  }
+  lines.join("\n")
 }
@@ -1,76 +1,201 @@
-use crate::config::{AppConfig, Instance, InstanceManifest, Loader, INSTANCE_CONFIG_FILE};
-use crate::minecraft::install::{install_fabric, install_forge, install_neoforge, install_vanilla};
+use crate::config::{
+  load_instances_from_roots, AppConfig, InstallSummary, Instance, InstanceKind, InstanceManifest,
+  Loader, NewInstanceDefaults, INSTANCE_CONFIG_FILE,
+};
+use crate::minecraft::install::{
+  install_fabric, install_forge, install_neoforge, install_quilt, install_vanilla,
+};
+use crate::minecraft::journal::InstallJournal;
 use crate::minecraft::models::{InstallState, NewInstanceRequest, ProgressEvent};
-use std::{fs, path::Path, path::PathBuf, time::{SystemTime, UNIX_EPOCH}};
-
+use crate::minecraft::optifine::restore_optifine;
+use crate::minecraft::progress::WeightedProgress;
+use crate::minecraft::util::{sanitize_path_component, slugify};
+use crate::minecraft::server::{create_server_layout, install_server, resolve_server_launch_target};
+use crate::minecraft::summary::InstallRecorder;
+use crate::minecraft::versions::{neoforge_version_matches_game, resolve_neoforge_channel};
+use std::{
+  fs,
+  path::Path,
+  path::PathBuf,
+  sync::Mutex,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+const INSTANCE_RESERVE_ATTEMPTS: usize = 8;
+
+// Serializes the final name check with the manifest write across concurrent creates.
+static INSTANCE_COMMIT_LOCK: Mutex<()> = Mutex::new(());
+
+/// `reserved` is called with the new instance id and folder as soon as the folder is claimed.
 pub fn create_instance(
   request: NewInstanceRequest,
   config: &mut AppConfig,
+  reserved: &dyn Fn(&str, &Path) -> Result<(), String>,
   emit: &dyn Fn(ProgressEvent),
 ) -> Result<Instance, String> {
-  if request.name.trim().is_empty() {
-    return Err("instance name cannot be empty".to_string());
-  }
-  let requested_name = request.name.trim();
-  if config
-    .instances
-    .iter()
-    .any(|instance| instance.name.eq_ignore_ascii_case(requested_name))
-  {
-    return Err("instance name already exists".to_string());
+  let (root_id, instance_id, directory) = reserve_instance_directory(&request, config)?;
+  if let Err(err) = reserved(&instance_id, &directory) {
+    let _ = fs::remove_dir_all(&directory);
+    return Err(err);
   }
 
-  if matches!(request.loader, Loader::Fabric | Loader::Forge | Loader::NeoForge)
-    && request.loader_version.is_none()
-  {
-    return Err("loader version is required".to_string());
-  }
-
-  let root_id = resolve_root_id(&request, config)?;
-  let root_path = resolve_root_path(config, &root_id)?;
-  let instance_id = allocate_instance_id(&request.name, config, &root_path)?;
-  let directory = root_path.join(&instance_id);
-
   emit(ProgressEvent {
     stage: "prepare".to_string(),
     message: "Preparing instance layout".to_string(),
     current: 0,
-    total: None,
-    detail: None,
+    ..Default::default()
   });
-  create_instance_layout(&directory)?;
+  match request.kind {
+    InstanceKind::Client => create_instance_layout(&directory)?,
+    InstanceKind::Server => create_server_layout(&directory, &request.loader)?,
+  }
 
   let created_at = SystemTime::now()
     .duration_since(UNIX_EPOCH)
     .unwrap_or_default()
     .as_secs();
 
+  let defaults = new_instance_defaults(config).cloned().unwrap_or_default();
+  if request.kind == InstanceKind::Client {
+    seed_options_txt(&directory, &defaults)?;
+  }
   let instance = Instance {
     id: instance_id,
     name: request.name,
+    kind: request.kind,
     version: request.game_version,
     loader: request.loader,
     loader_version: request.loader_version,
-    show_snapshots: request.show_snapshots,
+    show_snapshots: request.show_snapshots.unwrap_or(defaults.show_snapshots),
     pinned: false,
     root_id: Some(root_id),
     directory: directory.to_string_lossy().to_string(),
-    java_min_ram_mb: None,
+    java_min_ram_mb: defaults.java_min_ram_mb,
     java_min_ram_gb: None,
-    java_max_ram_mb: None,
+    java_max_ram_mb: defaults.java_max_ram_mb,
     java_max_ram_gb: None,
-    jvm_args: None,
+    jvm_args: defaults.jvm_args.filter(|args| !args.is_empty()),
+    auto_update_mods: false,
+    custom_version: None,
+    main_class_override: None,
+    client_jar_override: None,
+    release_channel: None,
+    window_title: None,
+    brand_name: None,
+    brand_version: None,
+    created_at_unix: Some(created_at),
+    optifine: None,
   };
 
-  write_instance_manifest(&directory, &instance, created_at)?;
+  {
+    let _commit = INSTANCE_COMMIT_LOCK
+      .lock()
+      .map_err(|_| "instance commit lock poisoned".to_string())?;
+    let name_taken = load_instances_from_roots(config)
+      .iter()
+      .any(|existing| existing.name.eq_ignore_ascii_case(instance.name.trim()));
+    if name_taken {
+      let _ = fs::remove_dir_all(&directory);
+      return Err("instance name already exists".to_string());
+    }
+    write_instance_manifest(&directory, &instance, created_at)?;
+  }
   config.instances.push(instance.clone());
   Ok(instance)
 }
 
+pub fn plan_instance_directory(
+  request: &NewInstanceRequest,
+  config: &AppConfig,
+) -> Result<(String, String, PathBuf), String> {
+  if request.name.trim().is_empty() {
+    return Err("instance name cannot be empty".to_string());
+  }
+  let requested_name = request.name.trim();
+  if config
+    .instances
+    .iter()
+    .any(|instance| instance.name.eq_ignore_ascii_case(requested_name))
+  {
+    return Err("instance name already exists".to_string());
+  }
+
+  let loader_version = request
+    .loader_version
+    .as_deref()
+    .map(str::trim)
+    .filter(|value| !value.is_empty());
+  if matches!(
+    request.loader,
+    Loader::Fabric | Loader::Quilt | Loader::Forge | Loader::NeoForge
+  )
+    && loader_version.is_none()
+  {
+    return Err("loader version is required".to_string());
+  }
+  if let (Loader::NeoForge, Some(loader_version)) = (&request.loader, loader_version) {
+    if !neoforge_version_matches_game(loader_version, &request.game_version) {
+      return Err(format!(
+        "NeoForge version '{}' is incompatible with Minecraft '{}'. Expected NeoForge {}.x.",
+        loader_version,
+        request.game_version,
+        resolve_neoforge_channel(&request.game_version)
+      ));
+    }
+  }
+
+  if request.loader.server_only() && request.kind != InstanceKind::Server {
+    return Err("this server software is only available for server instances".to_string());
+  }
+
+  if request.loader == Loader::Quilt && request.kind == InstanceKind::Server {
+    return Err("Quilt is only supported for client instances".to_string());
+  }
+
+  let root_id = resolve_root_id(request, config)?;
+  let root_path = resolve_root_path(config, &root_id)?;
+  let instance_id = allocate_instance_id(&request.name, config, &root_path)?;
+  let directory = root_path.join(&instance_id);
+  Ok((root_id, instance_id, directory))
+}
+
+fn reserve_instance_directory(
+  request: &NewInstanceRequest,
+  config: &AppConfig,
+) -> Result<(String, String, PathBuf), String> {
+  for _ in 0..INSTANCE_RESERVE_ATTEMPTS {
+    let (root_id, instance_id, directory) = plan_instance_directory(request, config)?;
+    if let Some(parent) = directory.parent() {
+      fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    match fs::create_dir(&directory) {
+      Ok(()) => return Ok((root_id, instance_id, directory)),
+      Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+      Err(err) => return Err(err.to_string()),
+    }
+  }
+  Err("unable to reserve an instance folder".to_string())
+}
+
+/// Installs whatever the instance is missing. Returns a summary when an install actually ran;
+/// the same summary is stored in the instance manifest.
 pub fn ensure_instance_ready(
   instance: &Instance,
   emit: &dyn Fn(ProgressEvent),
-) -> Result<(), String> {
+) -> Result<Option<InstallSummary>, String> {
+  let recorder = InstallRecorder::start();
+  if !recorder.scope(|| install_missing_files(instance, emit))? {
+    return Ok(None);
+  }
+  let summary = recorder.finish();
+  write_install_summary(Path::new(&instance.directory), instance, &summary)?;
+  Ok(Some(summary))
+}
+
+fn install_missing_files(
+  instance: &Instance,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<bool, String> {
   let instance_dir = PathBuf::from(&instance.directory);
   if !instance_dir.exists() {
     return Err(format!(
@@ -79,50 +204,148 @@ pub fn ensure_instance_ready(
     ));
   }
 
-  create_instance_layout(&instance_dir)?;
+  if instance.kind == InstanceKind::Server {
+    let progress = WeightedProgress::server(&instance.loader);
+    let weighted = |event: ProgressEvent| emit(progress.apply(event));
+    return ensure_server_ready(instance, &instance_dir, &weighted);
+  }
 
-  let install_state_ok = install_state_matches(&instance_dir, instance)?;
-  if install_state_ok && core_version_metadata_present(&instance_dir, instance) {
-    return Ok(());
+  if install_state_matches(&instance_dir, instance)? {
+    if core_files_present(&instance_dir, instance) {
+      create_instance_layout(&instance_dir)?;
+      return Ok(false);
+    }
+    emit(ProgressEvent {
+      stage: "prepare".to_string(),
+      message: "Installed files are incomplete, repairing".to_string(),
+      current: 0,
+      ..Default::default()
+    });
+    clear_install_state(&instance_dir)?;
   }
 
+  create_instance_layout(&instance_dir)?;
+  prune_previous_loader_profile(&instance_dir, instance);
+
   emit(ProgressEvent {
     stage: "prepare".to_string(),
     message: "Preparing instance assets".to_string(),
     current: 0,
-    total: None,
-    detail: None,
+    ..Default::default()
   });
 
+  let progress = WeightedProgress::client(&instance.loader);
+  let weighted = |event: ProgressEvent| emit(progress.apply(event));
+  let emit: &dyn Fn(ProgressEvent) = &weighted;
+  let journal = InstallJournal::open(&instance_dir);
   match instance.loader {
     Loader::Vanilla => {
-      install_vanilla(&instance.version, &instance_dir, emit)?;
+      install_vanilla(&instance.version, &instance_dir, &journal, emit)?;
     }
     Loader::Fabric => {
       let loader_version = instance
         .loader_version
         .clone()
         .ok_or_else(|| "fabric loader version is required".to_string())?;
-      install_fabric(&instance.version, &loader_version, &instance_dir, emit)?;
+      install_fabric(&instance.version, &loader_version, &instance_dir, &journal, emit)?;
+    }
+    Loader::Quilt => {
+      let loader_version = instance
+        .loader_version
+        .clone()
+        .ok_or_else(|| "quilt loader version is required".to_string())?;
+      install_quilt(&instance.version, &loader_version, &instance_dir, &journal, emit)?;
     }
     Loader::Forge => {
       let loader_version = instance
         .loader_version
         .clone()
         .ok_or_else(|| "forge version is required".to_string())?;
-      install_forge(&instance.version, &loader_version, &instance_dir, emit)?;
+      install_forge(&instance.version, &loader_version, &instance_dir, &journal, emit)?;
     }
     Loader::NeoForge => {
       let loader_version = instance
         .loader_version
         .clone()
         .ok_or_else(|| "neoforge version is required".to_string())?;
-      install_neoforge(&instance.version, &loader_version, &instance_dir, emit)?;
+      install_neoforge(&instance.version, &loader_version, &instance_dir, &journal, emit)?;
+    }
+    Loader::Paper | Loader::Folia | Loader::Purpur => {
+      return Err("this server software is only available for server instances".to_string());
     }
   }
 
+  restore_optifine(instance, &instance_dir, emit)?;
   write_install_state(&instance_dir, instance)?;
-  Ok(())
+  journal.finish();
+  Ok(true)
+}
+
+fn ensure_server_ready(
+  instance: &Instance,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<bool, String> {
+  create_server_layout(instance_dir, &instance.loader)?;
+
+  let install_state_ok = install_state_matches(instance_dir, instance)?;
+  if install_state_ok && resolve_server_launch_target(instance, instance_dir).is_some() {
+    return Ok(false);
+  }
+
+  emit(ProgressEvent {
+    stage: "prepare".to_string(),
+    message: "Preparing server files".to_string(),
+    current: 0,
+    ..Default::default()
+  });
+  install_server(instance, instance_dir, emit)?;
+  write_install_state(instance_dir, instance)?;
+  Ok(true)
+}
+
+/// The new-instance template, or `None` when the user turned it off.
+pub(crate) fn new_instance_defaults(config: &AppConfig) -> Option<&NewInstanceDefaults> {
+  config
+    .settings
+    .apply_to_new_instances
+    .then_some(&config.settings.new_instance_defaults)
+}
+
+/// Writes the template's language, GUI scale and framerate into `options.txt`, keeping
+/// any other lines already there.
+fn seed_options_txt(instance_dir: &Path, defaults: &NewInstanceDefaults) -> Result<(), String> {
+  let mut entries = Vec::new();
+  if let Some(language) = defaults
+    .language
+    .as_deref()
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+  {
+    entries.push(("lang", language.to_ascii_lowercase()));
+  }
+  if let Some(scale) = defaults.gui_scale {
+    entries.push(("guiScale", scale.to_string()));
+  }
+  if let Some(framerate) = defaults.max_framerate {
+    entries.push(("maxFps", framerate.to_string()));
+  }
+  if entries.is_empty() {
+    return Ok(());
+  }
+
+  let path = instance_dir.join("options.txt");
+  let existing = fs::read_to_string(&path).unwrap_or_default();
+  let mut lines: Vec<String> = existing
+    .lines()
+    .filter(|line| {
+      let key = line.split(':').next().unwrap_or("");
+      !entries.iter().any(|(name, _)| *name == key)
+    })
+    .map(str::to_string)
+    .collect();
+  lines.extend(entries.into_iter().map(|(key, value)| format!("{}:{}", key, value)));
+  fs::write(&path, format!("{}\n", lines.join("\n"))).map_err(|err| err.to_string())
 }
 
 fn resolve_root_id(request: &NewInstanceRequest, config: &AppConfig) -> Result<String, String> {
@@ -133,6 +356,14 @@ fn resolve_root_id(request: &NewInstanceRequest, config: &AppConfig) -> Result<S
     return Err(format!("instance root '{}' not found", root_id));
   }
 
+  // A template root that was since removed falls through to the usual default.
+  let template_root = new_instance_defaults(config).and_then(|defaults| defaults.root_id.as_ref());
+  if let Some(root_id) = template_root {
+    if config.instance_roots.iter().any(|root| &root.id == root_id) {
+      return Ok(root_id.clone());
+    }
+  }
+
   config
     .default_instance_root_id
     .clone()
@@ -150,7 +381,7 @@ fn resolve_root_path(config: &AppConfig, root_id: &str) -> Result<PathBuf, Strin
   Ok(PathBuf::from(&root.path))
 }
 
-fn allocate_instance_id(
+pub fn allocate_instance_id(
   name: &str,
   config: &AppConfig,
   root_path: &Path,
@@ -165,29 +396,13 @@ fn allocate_instance_id(
     if !exists_in_config && !exists_on_disk {
       return Ok(candidate);
     }
-    candidate = format!("{} {}", base, counter);
+    candidate = format!("{}-{}", base, counter);
     counter += 1;
   }
 }
 
 fn sanitize_folder_name(name: &str) -> String {
-  let trimmed = name.trim();
-  let mut result = String::new();
-  for ch in trimmed.chars() {
-    if ch == '/' || ch == '\\' || ch == ':' || ch == '*' || ch == '?' || ch == '\"' || ch == '<'
-      || ch == '>' || ch == '|'
-    {
-      result.push('-');
-    } else {
-      result.push(ch);
-    }
-  }
-  let cleaned = result.trim().to_string();
-  if cleaned.is_empty() {
-    "Instance".to_string()
-  } else {
-    cleaned
-  }
+  sanitize_path_component(&slugify(name), "instance")
 }
 
 fn create_instance_layout(instance_dir: &Path) -> Result<(), String> {
@@ -248,6 +463,51 @@ fn core_version_metadata_present(instance_dir: &Path, instance: &Instance) -> bo
     .is_file()
 }
 
+fn core_files_present(instance_dir: &Path, instance: &Instance) -> bool {
+  let client_jar = instance_dir
+    .join("versions")
+    .join(&instance.version)
+    .join(format!("{}.jar", instance.version));
+  let libraries_populated = fs::read_dir(instance_dir.join("libraries"))
+    .map(|mut entries| entries.next().is_some())
+    .unwrap_or(false);
+  client_jar.is_file()
+    && core_version_metadata_present(instance_dir, instance)
+    && instance_dir.join("natives").is_dir()
+    && libraries_populated
+}
+
+/// Removes the version profile of the loader that was installed before a loader or
+/// loader version change, so a stale chain can't be picked up again later. The base
+/// game version and any custom version are kept.
+fn prune_previous_loader_profile(instance_dir: &Path, instance: &Instance) {
+  let Some(manifest) = load_manifest(instance_dir) else {
+    return;
+  };
+  let (Some(version), Some(loader)) = (manifest.installed_version, manifest.installed_loader) else {
+    return;
+  };
+  let mut previous = instance.clone();
+  previous.version = version;
+  previous.loader = loader;
+  previous.loader_version = manifest.installed_loader_version;
+  let previous_id = resolve_expected_version_id(&previous);
+  if previous_id == resolve_expected_version_id(instance)
+    || previous_id == previous.version
+    || previous_id == instance.version
+    || instance.custom_version.as_deref() == Some(previous_id.as_str())
+  {
+    return;
+  }
+  let stale_dir = instance_dir.join("versions").join(&previous_id);
+  if stale_dir.is_dir() {
+    match fs::remove_dir_all(&stale_dir) {
+      Ok(()) => log::info!("removed stale loader profile {}", previous_id),
+      Err(err) => log::warn!("failed to remove stale loader profile {}: {}", previous_id, err),
+    }
+  }
+}
+
 fn resolve_expected_version_id(instance: &Instance) -> String {
   match instance.loader {
     Loader::Vanilla => instance.version.clone(),
@@ -256,6 +516,11 @@ fn resolve_expected_version_id(instance: &Instance) -> String {
       .as_ref()
       .map(|loader| format!("fabric-loader-{}-{}", loader, instance.version))
       .unwrap_or_else(|| instance.version.clone()),
+    Loader::Quilt => instance
+      .loader_version
+      .as_ref()
+      .map(|loader| format!("quilt-loader-{}-{}", loader, instance.version))
+      .unwrap_or_else(|| instance.version.clone()),
     Loader::Forge => {
       let loader = instance
         .loader_version
@@ -274,6 +539,7 @@ fn resolve_expected_version_id(instance: &Instance) -> String {
       .as_ref()
       .map(|loader| format!("neoforge-{}", loader))
       .unwrap_or_else(|| instance.version.clone()),
+    Loader::Paper | Loader::Folia | Loader::Purpur => instance.version.clone(),
   }
 }
 
@@ -287,13 +553,39 @@ fn write_install_state(instance_dir: &Path, instance: &Instance) -> Result<(), S
   save_manifest(instance_dir, &manifest)
 }
 
-fn load_manifest(instance_dir: &Path) -> Option<InstanceManifest> {
+/// Forgets the recorded install so the journal-verified install below re-checks every file.
+fn clear_install_state(instance_dir: &Path) -> Result<(), String> {
+  let Some(mut manifest) = load_manifest(instance_dir) else {
+    return Ok(());
+  };
+  manifest.installed_version = None;
+  manifest.installed_loader = None;
+  manifest.installed_loader_version = None;
+  save_manifest(instance_dir, &manifest)
+}
+
+fn write_install_summary(
+  instance_dir: &Path,
+  instance: &Instance,
+  summary: &InstallSummary,
+) -> Result<(), String> {
+  let mut manifest = load_manifest(instance_dir).unwrap_or_else(|| {
+    InstanceManifest::from_instance(instance, None)
+  });
+  manifest.install_summary = Some(summary.clone());
+  save_manifest(instance_dir, &manifest)
+}
+
+pub(super) fn load_manifest(instance_dir: &Path) -> Option<InstanceManifest> {
   let path = instance_dir.join(INSTANCE_CONFIG_FILE);
   let data = fs::read_to_string(path).ok()?;
   serde_json::from_str(&data).ok()
 }
 
-fn save_manifest(instance_dir: &Path, manifest: &InstanceManifest) -> Result<(), String> {
+pub(super) fn save_manifest(
+  instance_dir: &Path,
+  manifest: &InstanceManifest,
+) -> Result<(), String> {
   let payload = serde_json::to_vec_pretty(manifest)
     .map_err(crate::minecraft::download::map_json_error)?;
   let path = instance_dir.join(INSTANCE_CONFIG_FILE);
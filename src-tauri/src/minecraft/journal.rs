@@ -0,0 +1,72 @@
+use crate::minecraft::models::InstallJournalState;
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+const INSTALL_JOURNAL_FILE: &str = ".monolith-install.json";
+
+pub(crate) struct InstallJournal {
+  path: PathBuf,
+  state: Mutex<InstallJournalState>,
+}
+
+impl InstallJournal {
+  pub(crate) fn open(instance_dir: &Path) -> Self {
+    let path = instance_dir.join(INSTALL_JOURNAL_FILE);
+    let state = fs::read_to_string(&path)
+      .ok()
+      .and_then(|data| serde_json::from_str(&data).ok())
+      .unwrap_or_default();
+    Self {
+      path,
+      state: Mutex::new(state),
+    }
+  }
+
+  pub(crate) fn is_completed(&self, stage: &str) -> bool {
+    self
+      .state
+      .lock()
+      .map(|state| state.completed.contains(stage))
+      .unwrap_or(false)
+  }
+
+  pub(crate) fn complete(&self, stage: &str) {
+    if let Ok(mut state) = self.state.lock() {
+      state.completed.insert(stage.to_string());
+    }
+    self.save();
+  }
+
+  pub(crate) fn is_verified(&self, path: &Path) -> bool {
+    let key = path.to_string_lossy();
+    path.is_file()
+      && self
+        .state
+        .lock()
+        .map(|state| state.verified.contains(key.as_ref()))
+        .unwrap_or(false)
+  }
+
+  pub(crate) fn record_verified(&self, path: &Path) {
+    if let Ok(mut state) = self.state.lock() {
+      state.verified.insert(path.to_string_lossy().to_string());
+    }
+  }
+
+  pub(crate) fn save(&self) {
+    let payload = match self.state.lock() {
+      Ok(state) => serde_json::to_vec_pretty(&*state),
+      Err(_) => return,
+    };
+    if let Ok(payload) = payload {
+      let _ = fs::write(&self.path, payload);
+    }
+  }
+
+  pub(crate) fn finish(self) {
+    let _ = fs::remove_file(&self.path);
+  }
+}
@@ -1,14 +1,20 @@
-use crate::config::{AccountKind, AppConfig, Instance, Loader};
-use crate::java::{detect_java_version, resolve_java_command};
+use crate::config::{split_jvm_args, AccountKind, AppConfig, Instance, InstanceKind, Loader};
+use crate::diagnostics::{validate_ram_settings, GC_LOG_FILE};
+use crate::java::{
+  detect_java_version, java_runs_under_rosetta, parse_java_major, resolve_java_command,
+};
 use crate::minecraft::download::{download_to, load_json};
+use crate::minecraft::install::materialize_legacy_assets;
 use crate::minecraft::instance::ensure_instance_ready;
 use crate::minecraft::models::{
   Argument, ArgumentValue, FeatureFlags, LaunchContext, MojangLibrary, ResolvedVersion,
   VersionArguments, VersionFile, VersionLogging,
 };
+use crate::minecraft::server::launch_server_instance;
+use crate::minecraft::versions::compare_versions_desc;
 use crate::minecraft::util::{
   build_maven_path_url, classpath_separator, current_os_name, library_allowed,
-  parse_maven_coordinate, resolve_library_artifact, rules_allow,
+  parse_maven_coordinate, resolve_legacy_assets_dir, resolve_library_artifact, rules_allow,
 };
 use crate::minecraft::{DEFAULT_LIBRARIES_URL};
 use std::{
@@ -17,20 +23,30 @@ use std::{
   io::{BufRead, BufReader, Read, Seek, SeekFrom},
   net::ToSocketAddrs,
   path::{Path, PathBuf},
-  process::{Command, Stdio},
+  process::{ChildStdin, Command, Stdio},
   sync::atomic::{AtomicBool, Ordering},
   sync::Arc,
   thread,
-  time::Duration,
+  time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+const SCRIPT_TOKEN_PLACEHOLDER: &str = "__MONOLITH_ACCESS_TOKEN__";
+const SCRIPT_TOKEN_VAR: &str = "MONOLITH_ACCESS_TOKEN";
+const LAUNCHER_BRAND: &str = "monolith";
+const WINDOW_TITLE_MOD_PREFIX: &str = "customwindowtitle";
+const WINDOW_TITLE_CONFIG: &str = "config/customwindowtitle-client.toml";
+const LEGACY_ASSET_INDEX: &str = "pre-1.6";
+
 pub fn launch_instance(
   instance_id: &str,
   player_name: Option<String>,
+  join_server: Option<String>,
   config: &AppConfig,
   emit: &dyn Fn(crate::minecraft::models::ProgressEvent),
   log: Arc<dyn Fn(&str, &str) + Send + Sync>,
-  on_exit: Option<Arc<dyn Fn(u32) + Send + Sync>>,
+  on_exit: Option<ExitCallback>,
+  on_stdin: Option<Arc<dyn Fn(u32, ChildStdin) + Send + Sync>>,
+  refresh_session: Option<Arc<dyn Fn(&mut AppConfig) -> Result<(), String> + Send + Sync>>,
 ) -> Result<u32, String> {
   let instance = config
     .instances
@@ -38,142 +54,54 @@ pub fn launch_instance(
     .find(|item| item.id == instance_id)
     .ok_or_else(|| format!("instance '{}' not found", instance_id))?;
 
-  ensure_instance_ready(instance, emit)?;
-
-  let instance_dir = PathBuf::from(&instance.directory);
-  apply_reference_sync(config, instance, &instance_dir, log.clone());
-
-  let version_id = resolve_version_id(instance);
-  let resolved = resolve_version_chain(&instance_dir, &version_id)?;
-  let main_class = resolved
-    .main_class
-    .ok_or_else(|| "mainClass missing in version metadata".to_string())?;
-
-  let jar_id = resolved
-    .jar
-    .clone()
-    .or(resolved.base_version_id.clone())
-    .unwrap_or_else(|| version_id.clone());
-  let jar_path = instance_dir
-    .join("versions")
-    .join(&jar_id)
-    .join(format!("{}.jar", jar_id));
-  if !jar_path.exists() {
-    return Err(format!("version jar '{}' missing", jar_path.display()));
-  }
-
-  let libraries_dir = instance_dir.join("libraries");
-  let classpath = build_classpath(&resolved.libraries, &libraries_dir, &jar_path)?;
-  let assets_root = instance_dir.join("assets");
-  let asset_index_name = resolved
-    .asset_index
-    .as_ref()
-    .map(|index| index.id.clone())
-    .or(resolved.assets.clone())
-    .unwrap_or_else(|| "legacy".to_string());
-  let natives_id = resolved
-    .base_version_id
-    .clone()
-    .unwrap_or_else(|| jar_id.clone());
-  let natives_dir = instance_dir.join("natives").join(natives_id);
-  let logging_path = resolved
-    .logging
-    .as_ref()
-    .and_then(|logging| logging.client.as_ref())
-    .and_then(|client| client.file.as_ref())
-    .map(|file| assets_root.join("log_configs").join(&file.id))
-    .unwrap_or_default();
-
-  if let Some(logging) = &resolved.logging {
-    download_logging_config(logging, &assets_root)?;
-  }
-
-  let (player, uuid, access_token, user_type, xuid) = resolve_auth(player_name, config);
-  let client_id = uuid::Uuid::new_v4().to_string();
-  let version_type = "release".to_string();
-
-  let context = LaunchContext {
-    player_name: player,
-    uuid,
-    access_token,
-    user_type,
-    xuid,
-    client_id,
-    version_name: resolved
-      .id
-      .clone()
-      .unwrap_or_else(|| version_id.clone()),
-    game_dir: instance_dir.to_string_lossy().to_string(),
-    assets_root: assets_root.to_string_lossy().to_string(),
-    library_dir: libraries_dir.to_string_lossy().to_string(),
-    asset_index_name,
-    classpath: classpath.clone(),
-    natives_dir: natives_dir.to_string_lossy().to_string(),
-    logging_path: logging_path.to_string_lossy().to_string(),
-    launcher_name: "monolith".to_string(),
-    launcher_version: env!("CARGO_PKG_VERSION").to_string(),
-    version_type,
-  };
-
-  let mut jvm_args = Vec::new();
-  let os_name = current_os_name();
-  let feature_flags = FeatureFlags::default();
-  if let Some(arguments) = &resolved.arguments {
-    jvm_args.extend(flatten_arguments(arguments.jvm.as_ref(), os_name, &feature_flags));
+  if let Some(summary) = ensure_instance_ready(instance, emit)? {
+    log(
+      "launcher",
+      &format!(
+        "Install finished in {} ms: {} files downloaded ({} bytes)",
+        summary.duration_ms, summary.files_downloaded, summary.bytes_downloaded
+      ),
+    );
+    for warning in &summary.warnings {
+      log("launcher", &format!("Install warning: {}", warning));
+    }
   }
-
-  jvm_args.retain(|arg| {
-    !(arg == "-cp"
-      || arg.contains("${classpath}")
-      || (arg.contains("${classpath_separator}") && arg.contains("${classpath}"))
-      || arg.contains("${natives_directory}"))
-  });
-
-  if !cfg!(target_os = "macos") {
-    jvm_args.retain(|arg| arg != "-XstartOnFirstThread");
+  if instance.kind == InstanceKind::Server {
+    return launch_server_instance(instance, config, log, on_exit, on_stdin);
   }
 
-  if let Some(logging) = &resolved.logging {
-    if let Some(arg) = logging.client.as_ref().and_then(|client| client.argument.clone()) {
-      jvm_args.push(arg);
+  let instance_dir = PathBuf::from(&instance.directory);
+  apply_reference_sync(config, instance, &instance_dir, log.clone());
+  prepare_client_files(instance, config, &log)?;
+
+  let PreparedClientLaunch {
+    java_cmd,
+    final_args,
+    main_class_name,
+    context,
+    min_ram_mb,
+    max_ram_mb,
+  } = prepare_client_launch(
+    instance,
+    config,
+    player_name,
+    join_server,
+    log.clone(),
+    refresh_session.as_ref(),
+    None,
+  )?;
+  for finding in validate_ram_settings(min_ram_mb, max_ram_mb, Some(&java_cmd)) {
+    if finding.severity == "error" {
+      return Err(finding.summary);
     }
+    log("launcher", &format!("Warning: {}", finding.summary));
   }
-  merge_ignore_list_with_jar_name(&mut jvm_args, &jar_path);
-
-  let min_ram_mb = instance
-    .java_min_ram_mb
-    .unwrap_or(config.settings.java.min_ram_mb);
-  let max_ram_mb = instance
-    .java_max_ram_mb
-    .unwrap_or(config.settings.java.max_ram_mb);
-  jvm_args.push(format!("-Xms{}M", min_ram_mb));
-  jvm_args.push(format!("-Xmx{}M", max_ram_mb));
-  jvm_args.push(format!("-Djava.library.path={}", context.natives_dir));
-  jvm_args.extend(config.settings.java.jvm_args.split_whitespace().map(String::from));
-  if let Some(extra) = &instance.jvm_args {
-    jvm_args.extend(extra.split_whitespace().map(String::from));
-  }
-  if config.settings.smart_network_optimization {
-    apply_smart_network_jvm_flags(&mut jvm_args);
-  }
-  jvm_args.push("-cp".to_string());
-  jvm_args.push(classpath);
-
-  let mut game_args = Vec::new();
-  if let Some(arguments) = &resolved.arguments {
-    game_args.extend(flatten_arguments(arguments.game.as_ref(), os_name, &feature_flags));
-  } else if let Some(raw) = &resolved.minecraft_arguments {
-    game_args.extend(raw.split_whitespace().map(|item| item.to_string()));
+  if java_runs_under_rosetta(&java_cmd) {
+    log(
+      "launcher",
+      "Warning: the selected Java is an x86_64 build running under Rosetta on Apple Silicon. Switch to an ARM64 runtime for full performance.",
+    );
   }
-  game_args = strip_quickplay_args(game_args);
-
-  let mut final_args = Vec::new();
-  final_args.extend(jvm_args.into_iter().map(|arg| replace_tokens(arg, &context)));
-  let main_class_name = main_class.clone();
-  final_args.push(main_class);
-  final_args.extend(game_args.into_iter().map(|arg| replace_tokens(arg, &context)));
-
-  let java_cmd = resolve_java_command(config, instance)?;
   emit_launch_preamble(
     log.clone(),
     instance,
@@ -239,6 +167,24 @@ pub fn launch_instance(
       );
     }
   }
+  if config.settings.performance_discrete_gpu {
+    if cfg!(target_os = "windows") {
+      register_windows_gpu_preference(&java_cmd, log.clone());
+    } else if cfg!(target_os = "linux") {
+      if command_exists_in_path("nvidia-smi") {
+        command.env("__NV_PRIME_RENDER_OFFLOAD", "1");
+        command.env("__GLX_VENDOR_LIBRARY_NAME", "nvidia");
+        command.env("__VK_LAYER_NV_optimus", "NVIDIA_only");
+        log(
+          "launcher",
+          "Discrete GPU preference enabled: NVIDIA PRIME render offload variables set",
+        );
+      } else {
+        command.env("DRI_PRIME", "1");
+        log("launcher", "Discrete GPU preference enabled: DRI_PRIME=1");
+      }
+    }
+  }
   command.current_dir(&instance_dir);
   command.stdout(Stdio::piped());
   command.stderr(Stdio::piped());
@@ -296,9 +242,9 @@ pub fn launch_instance(
   if let Some(callback) = on_exit {
     let active = log_tail_active.clone();
     thread::spawn(move || {
-      let _ = child.wait();
+      let exit_code = child.wait().ok().and_then(|status| status.code());
       active.store(false, Ordering::Relaxed);
-      callback(pid);
+      callback(pid, exit_code);
     });
   } else {
     let active = log_tail_active.clone();
@@ -311,6 +257,489 @@ pub fn launch_instance(
   Ok(pid)
 }
 
+/// Writes a shell (or batch on Windows) script that starts the instance with the
+/// same command the launcher would run. Microsoft access tokens are never written;
+/// the script reads them from `MONOLITH_ACCESS_TOKEN` instead.
+pub fn export_launch_script(
+  instance_id: &str,
+  config: &AppConfig,
+  destination: Option<PathBuf>,
+) -> Result<PathBuf, String> {
+  let instance = config
+    .instances
+    .iter()
+    .find(|item| item.id == instance_id)
+    .ok_or_else(|| format!("instance '{}' not found", instance_id))?;
+  if instance.kind == InstanceKind::Server {
+    return Err("launch scripts are only available for client instances".to_string());
+  }
+  let instance_dir = PathBuf::from(&instance.directory);
+  let log: LaunchLogger = Arc::new(|_: &str, _: &str| {});
+  let prepared = prepare_client_launch(
+    instance,
+    config,
+    None,
+    None,
+    log,
+    None,
+    Some(SCRIPT_TOKEN_PLACEHOLDER),
+  )?;
+  let needs_token = prepared.context.access_token == SCRIPT_TOKEN_PLACEHOLDER;
+  let windows = cfg!(target_os = "windows");
+  let script = if windows {
+    render_batch_script(instance, &instance_dir, &prepared, needs_token)
+  } else {
+    render_shell_script(instance, &instance_dir, &prepared, needs_token)
+  };
+  let script_path = destination.unwrap_or_else(|| {
+    instance_dir.join(if windows { "launch.bat" } else { "launch.sh" })
+  });
+  fs::write(&script_path, script).map_err(|err| err.to_string())?;
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))
+      .map_err(|err| err.to_string())?;
+  }
+  Ok(script_path)
+}
+
+fn render_shell_script(
+  instance: &Instance,
+  instance_dir: &Path,
+  prepared: &PreparedClientLaunch,
+  needs_token: bool,
+) -> String {
+  let quote = |value: &str| format!("'{}'", value.replace('\'', "'\\''"));
+  let mut script = String::from("#!/bin/sh\n");
+  script.push_str(&format!(
+    "# {} ({}) exported by Monolith Launcher.\n",
+    instance.name, instance.version
+  ));
+  if needs_token {
+    script.push_str(&format!(
+      ": \"${{{var}:?Set {var} to a valid Minecraft access token}}\"\n",
+      var = SCRIPT_TOKEN_VAR
+    ));
+  }
+  script.push_str(&format!("cd {} || exit 1\n", quote(&instance_dir.to_string_lossy())));
+  script.push_str(&format!("exec {}", quote(&prepared.java_cmd)));
+  for arg in &prepared.final_args {
+    script.push_str(" \\\n  ");
+    if !arg.contains(SCRIPT_TOKEN_PLACEHOLDER) {
+      script.push_str(&quote(arg));
+      continue;
+    }
+    // The token can be embedded in a longer argument, e.g. `token:<token>:<uuid>`.
+    let parts = arg
+      .split(SCRIPT_TOKEN_PLACEHOLDER)
+      .map(|part| if part.is_empty() { String::new() } else { quote(part) })
+      .collect::<Vec<_>>();
+    script.push_str(&parts.join(&format!("\"${}\"", SCRIPT_TOKEN_VAR)));
+  }
+  script.push('\n');
+  script
+}
+
+fn render_batch_script(
+  instance: &Instance,
+  instance_dir: &Path,
+  prepared: &PreparedClientLaunch,
+  needs_token: bool,
+) -> String {
+  let quote = |value: &str| format!("\"{}\"", value.replace('%', "%%").replace('"', "\"\""));
+  let mut script = String::from("@echo off\r\n");
+  script.push_str(&format!(
+    "rem {} ({}) exported by Monolith Launcher.\r\n",
+    instance.name, instance.version
+  ));
+  if needs_token {
+    script.push_str(&format!(
+      "if \"%{var}%\"==\"\" (\r\n  echo Set {var} to a valid Minecraft access token.\r\n  exit /b 1\r\n)\r\n",
+      var = SCRIPT_TOKEN_VAR
+    ));
+  }
+  script.push_str(&format!("cd /d {} || exit /b 1\r\n", quote(&instance_dir.to_string_lossy())));
+  script.push_str(&quote(&prepared.java_cmd));
+  for arg in &prepared.final_args {
+    script.push_str(" ^\r\n  ");
+    let parts = arg
+      .split(SCRIPT_TOKEN_PLACEHOLDER)
+      .map(|part| part.replace('%', "%%").replace('"', "\"\""))
+      .collect::<Vec<_>>();
+    script.push_str(&format!("\"{}\"", parts.join(&format!("%{}%", SCRIPT_TOKEN_VAR))));
+  }
+  script.push_str("\r\n");
+  script
+}
+
+struct PreparedClientLaunch {
+  java_cmd: String,
+  final_args: Vec<String>,
+  main_class_name: String,
+  context: LaunchContext,
+  min_ram_mb: u32,
+  max_ram_mb: u32,
+}
+
+type SessionRefresher = Arc<dyn Fn(&mut AppConfig) -> Result<(), String> + Send + Sync>;
+type LaunchLogger = Arc<dyn Fn(&str, &str) + Send + Sync>;
+/// Called with the pid and exit code once the game process ends.
+pub(crate) type ExitCallback = Arc<dyn Fn(u32, Option<i32>) + Send + Sync>;
+
+/// Writes what a client launch needs next to the install: the legacy asset copy, the
+/// logging config and the window title. Export leaves these alone.
+fn prepare_client_files(
+  instance: &Instance,
+  config: &AppConfig,
+  log: &LaunchLogger,
+) -> Result<(), String> {
+  let instance_dir = PathBuf::from(&instance.directory);
+  let resolved = resolve_version_chain(&instance_dir, &resolve_version_id(instance))?;
+  let asset_index_name = resolve_asset_index_name(&resolved);
+  if let Err(err) = materialize_legacy_assets(&instance_dir, &asset_index_name) {
+    log("launcher", &format!("Failed to prepare legacy assets: {}", err));
+  }
+  if let Some(logging) = &resolved.logging {
+    download_logging_config(logging, &instance_dir.join("assets"))?;
+  }
+  if let Some(title) = &instance.window_title {
+    apply_window_title(&instance_dir, title, &|message: &str| log("launcher", message));
+  }
+  if config.settings.gc_logging {
+    fs::create_dir_all(instance_dir.join("logs")).map_err(|err| err.to_string())?;
+  }
+  Ok(())
+}
+
+/// Folder the launcher points `java.library.path` at for this instance, or `None` when the
+/// version has no native jars to extract on this OS.
+pub(crate) fn resolve_natives_dir(instance: &Instance) -> Result<Option<PathBuf>, String> {
+  let instance_dir = PathBuf::from(&instance.directory);
+  let version_id = resolve_version_id(instance);
+  let resolved = resolve_version_chain(&instance_dir, &version_id)?;
+  let os_name = current_os_name();
+  let has_natives = resolved.libraries.iter().any(|library| {
+    library_allowed(library.rules.as_ref(), os_name)
+      && library
+        .natives
+        .as_ref()
+        .is_some_and(|natives| natives.contains_key(os_name))
+  });
+  Ok(has_natives.then(|| natives_dir_for(&instance_dir, &resolved, &version_id)))
+}
+
+fn natives_dir_for(instance_dir: &Path, resolved: &ResolvedVersion, version_id: &str) -> PathBuf {
+  let natives_id = resolved
+    .base_version_id
+    .clone()
+    .or(resolved.jar.clone())
+    .unwrap_or_else(|| version_id.to_string());
+  instance_dir.join("natives").join(natives_id)
+}
+
+fn resolve_asset_index_name(resolved: &ResolvedVersion) -> String {
+  resolved
+    .asset_index
+    .as_ref()
+    .map(|index| index.id.clone())
+    .or(resolved.assets.clone())
+    .unwrap_or_else(|| "legacy".to_string())
+}
+
+/// Resolves the java command and full argument list for a client instance.
+/// With `token_placeholder` set, the session is left alone and the access token
+/// is swapped for the placeholder so the command can be written to disk.
+/// Only reads the instance; `prepare_client_files` does the writing for a real launch.
+fn prepare_client_launch(
+  instance: &Instance,
+  config: &AppConfig,
+  player_name: Option<String>,
+  join_server: Option<String>,
+  log: LaunchLogger,
+  refresh_session: Option<&SessionRefresher>,
+  token_placeholder: Option<&str>,
+) -> Result<PreparedClientLaunch, String> {
+  let instance_dir = PathBuf::from(&instance.directory);
+  let version_id = resolve_version_id(instance);
+  let resolved = resolve_version_chain(&instance_dir, &version_id)?;
+  let asset_index_name = resolve_asset_index_name(&resolved);
+  let natives_dir = natives_dir_for(&instance_dir, &resolved, &version_id);
+  let main_class = instance
+    .main_class_override
+    .clone()
+    .or(resolved.main_class)
+    .ok_or_else(|| "mainClass missing in version metadata".to_string())?;
+
+  let jar_id = resolved
+    .jar
+    .clone()
+    .or(resolved.base_version_id.clone())
+    .unwrap_or_else(|| version_id.clone());
+  let jar_path = match &instance.client_jar_override {
+    Some(path) => {
+      log("launcher", &format!("Using client jar override {}", path));
+      instance_dir.join(path)
+    }
+    None => instance_dir
+      .join("versions")
+      .join(&jar_id)
+      .join(format!("{}.jar", jar_id)),
+  };
+  if !jar_path.exists() {
+    return Err(format!("version jar '{}' missing", jar_path.display()));
+  }
+
+  let libraries_dir = instance_dir.join("libraries");
+  let classpath = {
+    let log = log.clone();
+    build_classpath(
+      &resolved.libraries,
+      &resolved.loader_libraries,
+      &libraries_dir,
+      &jar_path,
+      &move |message: &str| log("launcher", message),
+    )?
+  };
+  let assets_root = instance_dir.join("assets");
+  let game_assets = resolve_legacy_assets_dir(&instance_dir, &asset_index_name)
+    .unwrap_or_else(|| assets_root.clone());
+  let logging_path = resolved
+    .logging
+    .as_ref()
+    .and_then(|logging| logging.client.as_ref())
+    .and_then(|client| client.file.as_ref())
+    .map(|file| assets_root.join("log_configs").join(&file.id))
+    .unwrap_or_default();
+
+  let refreshed_config;
+  let auth_config = match refresh_session {
+    Some(refresh) if token_placeholder.is_none() && active_session_expiring(config) => {
+      log("launcher", "Microsoft session is about to expire. Refreshing before launch.");
+      let mut next = config.clone();
+      refresh(&mut next)?;
+      refreshed_config = next;
+      &refreshed_config
+    }
+    _ => config,
+  };
+  if token_placeholder.is_none() && active_session_expiring(auth_config) {
+    return Err("Microsoft session expired. Please re-login.".to_string());
+  }
+  let (player, uuid, mut access_token, user_type, xuid) = resolve_auth(player_name, auth_config);
+  if let Some(placeholder) = token_placeholder.filter(|_| user_type == "msa") {
+    access_token = placeholder.to_string();
+  }
+  let client_id = uuid::Uuid::new_v4().to_string();
+  let version_type = "release".to_string();
+
+  let context = LaunchContext {
+    player_name: player,
+    uuid,
+    access_token,
+    user_type,
+    xuid,
+    client_id,
+    version_name: resolved
+      .id
+      .clone()
+      .unwrap_or_else(|| version_id.clone()),
+    game_dir: instance_dir.to_string_lossy().to_string(),
+    assets_root: assets_root.to_string_lossy().to_string(),
+    library_dir: libraries_dir.to_string_lossy().to_string(),
+    asset_index_name,
+    game_assets: game_assets.to_string_lossy().to_string(),
+    classpath: classpath.clone(),
+    natives_dir: natives_dir.to_string_lossy().to_string(),
+    logging_path: logging_path.to_string_lossy().to_string(),
+    launcher_name: instance
+      .brand_name
+      .clone()
+      .unwrap_or_else(|| LAUNCHER_BRAND.to_string()),
+    launcher_version: instance
+      .brand_version
+      .clone()
+      .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
+    version_type,
+  };
+
+  let mut jvm_args = Vec::new();
+  let os_name = current_os_name();
+  let feature_flags = FeatureFlags::default();
+  if let Some(arguments) = &resolved.arguments {
+    jvm_args.extend(flatten_arguments(arguments.jvm.as_ref(), os_name, &feature_flags));
+  }
+
+  jvm_args.retain(|arg| {
+    !(arg == "-cp"
+      || arg.contains("${classpath}")
+      || (arg.contains("${classpath_separator}") && arg.contains("${classpath}"))
+      || arg.contains("${natives_directory}"))
+  });
+
+  if !cfg!(target_os = "macos") {
+    jvm_args.retain(|arg| arg != "-XstartOnFirstThread");
+  }
+
+  if let Some(logging) = &resolved.logging {
+    if let Some(arg) = logging.client.as_ref().and_then(|client| client.argument.clone()) {
+      jvm_args.push(arg);
+    }
+  }
+  merge_ignore_list_with_jar_name(&mut jvm_args, &jar_path);
+
+  let min_ram_mb = instance
+    .java_min_ram_mb
+    .unwrap_or(config.settings.java.min_ram_mb);
+  let max_ram_mb = instance
+    .java_max_ram_mb
+    .unwrap_or(config.settings.java.max_ram_mb);
+  jvm_args.push(format!("-Xms{}M", min_ram_mb));
+  jvm_args.push(format!("-Xmx{}M", max_ram_mb));
+  jvm_args.push(format!("-Djava.library.path={}", context.natives_dir));
+  jvm_args.extend(split_jvm_args(&config.settings.java.jvm_args));
+  if let Some(extra) = &instance.jvm_args {
+    jvm_args.extend(extra.iter().cloned());
+  }
+  if context.asset_index_name == LEGACY_ASSET_INDEX {
+    apply_legacy_client_flags(&mut jvm_args, &context, &config.settings.legacy_resource_proxy);
+  }
+  if config.settings.smart_network_optimization {
+    apply_smart_network_jvm_flags(&mut jvm_args);
+  }
+  let java_cmd = resolve_java_command(config, instance)?;
+  if config.settings.gc_logging {
+    apply_gc_logging_flags(&mut jvm_args, &java_cmd, &instance_dir)?;
+  }
+  jvm_args.push("-cp".to_string());
+  jvm_args.push(classpath);
+
+  let mut game_args = Vec::new();
+  if let Some(arguments) = &resolved.arguments {
+    game_args.extend(flatten_arguments(arguments.game.as_ref(), os_name, &feature_flags));
+  } else if let Some(raw) = &resolved.minecraft_arguments {
+    game_args.extend(raw.split_whitespace().map(|item| item.to_string()));
+    if !game_args.iter().any(|arg| arg == "--gameDir") {
+      game_args.push("--gameDir".to_string());
+      game_args.push("${game_directory}".to_string());
+    }
+  }
+  let supports_quickplay = resolved
+    .arguments
+    .as_ref()
+    .map(|arguments| arguments_mention(arguments.game.as_ref(), "--quickPlayMultiplayer"))
+    .unwrap_or(false);
+  game_args = strip_quickplay_args(game_args);
+  if let Some(target) = join_server.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+    game_args.extend(join_server_args(target, supports_quickplay));
+  }
+
+  let mut final_args = Vec::new();
+  final_args.extend(jvm_args.into_iter().map(|arg| replace_tokens(arg, &context)));
+  let main_class_name = main_class.clone();
+  final_args.push(main_class);
+  final_args.extend(game_args.into_iter().map(|arg| replace_tokens(arg, &context)));
+
+  Ok(PreparedClientLaunch {
+    java_cmd,
+    final_args,
+    main_class_name,
+    context,
+    min_ram_mb,
+    max_ram_mb,
+  })
+}
+
+/// Alpha, beta and 1.0–1.5 clients resolve their folder through the applet property and
+/// fetch sounds and skins over plain HTTP from endpoints that no longer exist, so a
+/// configured proxy is the only way to get them back. Properties the user already set win.
+fn apply_legacy_client_flags(jvm_args: &mut Vec<String>, context: &LaunchContext, proxy: &str) {
+  if !jvm_args
+    .iter()
+    .any(|arg| arg.starts_with("-Dminecraft.applet.TargetDirectory="))
+  {
+    jvm_args.push(format!("-Dminecraft.applet.TargetDirectory={}", context.game_dir));
+  }
+  let Some((host, port)) = proxy.trim().rsplit_once(':') else {
+    return;
+  };
+  if host.is_empty() || port.parse::<u16>().is_err() {
+    return;
+  }
+  if jvm_args.iter().any(|arg| arg.starts_with("-Dhttp.proxyHost=")) {
+    return;
+  }
+  jvm_args.push(format!("-Dhttp.proxyHost={}", host));
+  jvm_args.push(format!("-Dhttp.proxyPort={}", port));
+}
+
+/// Minecraft has no launch argument for the window title, so the override is
+/// written to the Custom Window Title mod config when that mod is installed.
+/// Only the `title` key is touched; a failed write is logged and the launch goes on.
+fn apply_window_title(instance_dir: &Path, title: &str, log: &dyn Fn(&str)) {
+  if !window_title_mod_installed(instance_dir) {
+    log("Custom window title is set but the Custom Window Title mod is not installed. Skipping.");
+    return;
+  }
+  let config_path = instance_dir.join(WINDOW_TITLE_CONFIG);
+  let existing = fs::read_to_string(&config_path).unwrap_or_default();
+  let title_line = format!("title = \"{}\"", escape_toml_string(title));
+  let mut replaced = false;
+  let mut lines = existing
+    .lines()
+    .map(|line| {
+      let is_title = line
+        .split_once('=')
+        .is_some_and(|(key, _)| key.trim() == "title");
+      if is_title && !replaced {
+        replaced = true;
+        title_line.clone()
+      } else {
+        line.to_string()
+      }
+    })
+    .collect::<Vec<_>>();
+  if !replaced {
+    lines.push(title_line);
+  }
+  let written = config_path
+    .parent()
+    .map_or(Ok(()), fs::create_dir_all)
+    .and_then(|_| fs::write(&config_path, format!("{}\n", lines.join("\n"))));
+  match written {
+    Ok(()) => log(&format!("Window title set to \"{}\"", title)),
+    Err(err) => log(&format!("Could not write the window title config: {}", err)),
+  }
+}
+
+/// The window title override does nothing without the Custom Window Title mod.
+pub(crate) fn window_title_mod_installed(instance_dir: &Path) -> bool {
+  fs::read_dir(instance_dir.join("mods"))
+    .map(|entries| {
+      entries.flatten().any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        name.starts_with(WINDOW_TITLE_MOD_PREFIX) && name.ends_with(".jar")
+      })
+    })
+    .unwrap_or(false)
+}
+
+fn escape_toml_string(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len());
+  for ch in value.chars() {
+    match ch {
+      '\\' => escaped.push_str("\\\\"),
+      '"' => escaped.push_str("\\\""),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      ch if ch.is_control() => escaped.push_str(&format!("\\u{:04X}", ch as u32)),
+      ch => escaped.push(ch),
+    }
+  }
+  escaped
+}
+
 fn merge_ignore_list_with_jar_name(jvm_args: &mut [String], jar_path: &Path) {
   let Some(jar_name) = jar_path.file_name().and_then(|name| name.to_str()) else {
     return;
@@ -344,6 +773,64 @@ fn command_exists_in_path(binary: &str) -> bool {
     .any(|candidate| candidate.is_file())
 }
 
+fn resolve_command_path(command: &str) -> Option<PathBuf> {
+  let direct = PathBuf::from(command);
+  if direct.is_absolute() {
+    return direct.is_file().then_some(direct);
+  }
+  let path_var = std::env::var_os("PATH")?;
+  let candidates = if cfg!(target_os = "windows") && direct.extension().is_none() {
+    vec![format!("{}.exe", command), command.to_string()]
+  } else {
+    vec![command.to_string()]
+  };
+  std::env::split_paths(&path_var)
+    .flat_map(|base| candidates.iter().map(move |name| base.join(name)))
+    .find(|candidate| candidate.is_file())
+}
+
+fn register_windows_gpu_preference(java_cmd: &str, log: Arc<dyn Fn(&str, &str) + Send + Sync>) {
+  let Some(java_path) = resolve_command_path(java_cmd) else {
+    log(
+      "launcher",
+      "Discrete GPU preference enabled, but the Java executable could not be resolved.",
+    );
+    return;
+  };
+  let mut executables = vec![java_path.clone()];
+  if let Some(parent) = java_path.parent() {
+    for name in ["java.exe", "javaw.exe"] {
+      let sibling = parent.join(name);
+      if sibling.is_file() && !executables.contains(&sibling) {
+        executables.push(sibling);
+      }
+    }
+  }
+  for executable in executables {
+    let status = Command::new("reg")
+      .args([
+        "add",
+        "HKCU\\Software\\Microsoft\\DirectX\\UserGpuPreferences",
+        "/v",
+      ])
+      .arg(&executable)
+      .args(["/t", "REG_SZ", "/d", "GpuPreference=2;", "/f"])
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .status();
+    match status {
+      Ok(status) if status.success() => log(
+        "launcher",
+        &format!("High-performance GPU preference registered for {}", executable.display()),
+      ),
+      _ => log(
+        "launcher",
+        &format!("Failed to register GPU preference for {}", executable.display()),
+      ),
+    }
+  }
+}
+
 fn apply_smart_network_jvm_flags(jvm_args: &mut Vec<String>) {
   for (key, value) in [
     ("java.net.preferIPv4Stack", "true"),
@@ -357,6 +844,36 @@ fn apply_smart_network_jvm_flags(jvm_args: &mut Vec<String>) {
   }
 }
 
+/// Picks `-Xlog` or legacy GC flags by Java version; user-supplied GC flags win.
+fn apply_gc_logging_flags(
+  jvm_args: &mut Vec<String>,
+  java_cmd: &str,
+  instance_dir: &Path,
+) -> Result<(), String> {
+  if jvm_args
+    .iter()
+    .any(|arg| arg.starts_with("-Xlog:gc") || arg.starts_with("-Xloggc"))
+  {
+    return Ok(());
+  }
+  let log_path = instance_dir.join("logs").join(GC_LOG_FILE);
+  let major = detect_java_version(java_cmd)
+    .and_then(|version| parse_java_major(&version))
+    .unwrap_or(8);
+  if major >= 9 {
+    jvm_args.push(format!(
+      "-Xlog:gc*:file=\"{}\":time,uptime,level,tags:filecount=3,filesize=20M",
+      log_path.display()
+    ));
+  } else {
+    jvm_args.push(format!("-Xloggc:{}", log_path.display()));
+    jvm_args.push("-XX:+PrintGCDetails".to_string());
+    jvm_args.push("-XX:+PrintGCDateStamps".to_string());
+    jvm_args.push("-XX:+PrintGCApplicationStoppedTime".to_string());
+  }
+  Ok(())
+}
+
 fn emit_launch_preamble(
   log: Arc<dyn Fn(&str, &str) + Send + Sync>,
   instance: &Instance,
@@ -477,7 +994,11 @@ fn redact_sensitive_args(args: &[String]) -> Vec<String> {
       continue;
     }
     let lower = arg.to_lowercase();
-    if lower == "--accesstoken" || lower == "--clientid" || lower == "--xuid" {
+    if lower.starts_with("token:") {
+      redacted.push("token:<redacted>".to_string());
+      continue;
+    }
+    if matches!(lower.as_str(), "--accesstoken" | "--session" | "--clientid" | "--xuid") {
       redacted.push(arg.clone());
       redact_next = true;
       continue;
@@ -617,6 +1138,9 @@ fn sync_file_if_exists(source_file: &Path, target_file: &Path) -> Result<(), Str
 }
 
 fn resolve_version_id(instance: &Instance) -> String {
+  if let Some(custom) = &instance.custom_version {
+    return custom.clone();
+  }
   match instance.loader {
     Loader::Vanilla => instance.version.clone(),
     Loader::Fabric => instance
@@ -624,6 +1148,11 @@ fn resolve_version_id(instance: &Instance) -> String {
       .as_ref()
       .map(|loader| format!("fabric-loader-{}-{}", loader, instance.version))
       .unwrap_or_else(|| instance.version.clone()),
+    Loader::Quilt => instance
+      .loader_version
+      .as_ref()
+      .map(|loader| format!("quilt-loader-{}-{}", loader, instance.version))
+      .unwrap_or_else(|| instance.version.clone()),
     Loader::Forge => {
       let loader = instance
         .loader_version
@@ -642,6 +1171,7 @@ fn resolve_version_id(instance: &Instance) -> String {
       .as_ref()
       .map(|loader| format!("neoforge-{}", loader))
       .unwrap_or_else(|| instance.version.clone()),
+    Loader::Paper | Loader::Folia | Loader::Purpur => instance.version.clone(),
   }
 }
 
@@ -742,6 +1272,11 @@ fn resolve_version_chain(
       resolved.logging = version.logging;
     }
 
+    if resolved.id.as_deref() != base_version_id.as_deref() {
+      resolved
+        .loader_libraries
+        .extend(version.libraries.iter().map(|library| library.name.clone()));
+    }
     resolved.libraries.extend(version.libraries);
 
     if let Some(arguments) = version.arguments {
@@ -811,13 +1346,25 @@ fn flatten_arguments(
   flat
 }
 
+fn arguments_mention(arguments: Option<&Vec<Argument>>, needle: &str) -> bool {
+  arguments.into_iter().flatten().any(|arg| match arg {
+    Argument::String(value) => value == needle,
+    Argument::Object(obj) => match &obj.value {
+      ArgumentValue::String(value) => value == needle,
+      ArgumentValue::List(list) => list.iter().any(|value| value == needle),
+    },
+  })
+}
+
 fn build_classpath(
   libraries: &[MojangLibrary],
+  loader_libraries: &HashSet<String>,
   libraries_dir: &Path,
   version_jar: &Path,
+  log: &dyn Fn(&str),
 ) -> Result<String, String> {
   let mut entries: Vec<String> = Vec::new();
-  let mut keyed: HashMap<String, usize> = HashMap::new();
+  let mut keyed: HashMap<String, ClasspathEntry> = HashMap::new();
   let mut seen_paths: HashSet<String> = HashSet::new();
   let os_name = current_os_name();
 
@@ -836,7 +1383,9 @@ fn build_classpath(
               &mut keyed,
               &mut seen_paths,
               &library.name,
+              loader_libraries.contains(&library.name),
               jar_path.to_string_lossy().to_string(),
+              log,
             );
           }
           continue;
@@ -854,7 +1403,9 @@ fn build_classpath(
             &mut keyed,
             &mut seen_paths,
             &library.name,
+            loader_libraries.contains(&library.name),
             jar_path.to_string_lossy().to_string(),
+            log,
           );
         }
       }
@@ -866,12 +1417,20 @@ fn build_classpath(
   Ok(entries.join(classpath_separator()))
 }
 
+struct ClasspathEntry {
+  index: usize,
+  version: String,
+  loader_provided: bool,
+}
+
 fn push_unique_library(
   entries: &mut Vec<String>,
-  keyed: &mut HashMap<String, usize>,
+  keyed: &mut HashMap<String, ClasspathEntry>,
   seen_paths: &mut HashSet<String>,
   name: &str,
+  loader_provided: bool,
   path: String,
+  log: &dyn Fn(&str),
 ) {
   if let Ok(coordinate) = parse_maven_coordinate(name) {
     let key = format!(
@@ -880,11 +1439,40 @@ fn push_unique_library(
       coordinate.artifact,
       coordinate.classifier.unwrap_or_default()
     );
-    if let Some(idx) = keyed.get(&key) {
-      entries[*idx] = path;
+    if let Some(existing) = keyed.get_mut(&key) {
+      let replace = if loader_provided != existing.loader_provided {
+        loader_provided
+      } else {
+        compare_versions_desc(&coordinate.version, &existing.version) != std::cmp::Ordering::Greater
+      };
+      let (kept, dropped) = if replace {
+        (coordinate.version.as_str(), existing.version.as_str())
+      } else {
+        (existing.version.as_str(), coordinate.version.as_str())
+      };
+      if kept != dropped {
+        log(&format!(
+          "Classpath conflict for {}: keeping {}, dropped {}",
+          key.trim_end_matches(':'),
+          kept,
+          dropped
+        ));
+      }
+      if replace {
+        entries[existing.index] = path;
+        existing.version = coordinate.version;
+        existing.loader_provided = loader_provided;
+      }
       return;
     }
-    keyed.insert(key, entries.len());
+    keyed.insert(
+      key,
+      ClasspathEntry {
+        index: entries.len(),
+        version: coordinate.version,
+        loader_provided,
+      },
+    );
     entries.push(path);
     return;
   }
@@ -916,6 +1504,11 @@ fn replace_tokens(value: String, context: &LaunchContext) -> String {
     .replace("${assets_root}", &context.assets_root)
     .replace("${library_directory}", &context.library_dir)
     .replace("${assets_index_name}", &context.asset_index_name)
+    .replace("${game_assets}", &context.game_assets)
+    .replace(
+      "${auth_session}",
+      &format!("token:{}:{}", context.access_token, context.uuid),
+    )
     .replace("${auth_uuid}", &context.uuid)
     .replace("${auth_access_token}", &context.access_token)
     .replace("${auth_xuid}", &context.xuid)
@@ -931,6 +1524,22 @@ fn replace_tokens(value: String, context: &LaunchContext) -> String {
     .replace("${launcher_version}", &context.launcher_version)
 }
 
+fn join_server_args(target: &str, supports_quickplay: bool) -> Vec<String> {
+  if supports_quickplay {
+    return vec!["--quickPlayMultiplayer".to_string(), target.to_string()];
+  }
+  let (host, port) = match target.rsplit_once(':') {
+    Some((host, port)) if port.parse::<u16>().is_ok() => (host, port),
+    _ => (target, "25565"),
+  };
+  vec![
+    "--server".to_string(),
+    host.to_string(),
+    "--port".to_string(),
+    port.to_string(),
+  ]
+}
+
 fn strip_quickplay_args(args: Vec<String>) -> Vec<String> {
   let mut filtered = Vec::new();
   let mut i = 0;
@@ -953,6 +1562,24 @@ fn strip_quickplay_args(args: Vec<String>) -> Vec<String> {
   filtered
 }
 
+fn active_session_expiring(config: &AppConfig) -> bool {
+  let Some(active_id) = config.active_account_id.as_ref() else {
+    return false;
+  };
+  let Some(account) = config
+    .accounts
+    .iter()
+    .find(|account| &account.id == active_id && account.kind == AccountKind::Microsoft)
+  else {
+    return false;
+  };
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs();
+  account.session_expiring(now)
+}
+
 fn resolve_player_name(config: &AppConfig) -> Option<String> {
   let active_id = config.active_account_id.as_ref()?;
   config
@@ -1,30 +1,50 @@
 mod download;
+mod forge;
 mod install;
 mod instance;
+mod journal;
 mod launch;
 mod models;
+mod optifine;
+mod progress;
+mod server;
+mod summary;
 mod util;
 mod versions;
 
-pub(crate) use download::download_to;
-pub use instance::create_instance;
-pub use launch::launch_instance;
+pub(crate) use download::{apply_network_settings, download_cached};
+pub use instance::{
+  allocate_instance_id, create_instance, ensure_instance_ready, plan_instance_directory,
+};
+pub(crate) use instance::new_instance_defaults;
+pub(crate) use install::install_custom_version;
+pub(crate) use optifine::install_optifine;
+pub use launch::{export_launch_script, launch_instance};
+pub(crate) use launch::{resolve_natives_dir, window_title_mod_installed};
+pub(crate) use server::{server_eula_accepted, SERVER_EULA_FILE, SERVER_EULA_URL};
+pub(crate) use util::{long_path, rotate_file_backups, sanitize_path_component};
 pub use models::{
-  ForgeVersionSummary, LoaderVersionSummary, NewInstanceRequest, ProgressEvent, VersionSummary,
+  ForgeVersionSummary, LoaderAvailability, LoaderVersionSummary, NewInstanceRequest, ProgressEvent,
+  VersionPatchNotes, VersionRangeFilter, VersionSummary,
 };
 pub use versions::{
-  list_fabric_game_versions, list_fabric_loader_versions, list_forge_versions,
-  list_neoforge_versions,
-  list_vanilla_versions,
+  fetch_version_patch_notes, list_fabric_game_versions, list_fabric_loader_versions,
+  list_forge_versions, list_neoforge_versions, list_quilt_loader_versions, list_server_builds,
+  list_server_game_versions, list_supported_loaders, list_vanilla_versions,
 };
 
 const MOJANG_MANIFEST_URL: &str =
   "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+const MOJANG_LAUNCHER_CONTENT_BASE: &str = "https://launchercontent.mojang.com/v2";
 const FABRIC_GAME_VERSIONS_URL: &str = "https://meta.fabricmc.net/v2/versions/game";
 const FABRIC_LOADER_URL: &str = "https://meta.fabricmc.net/v2/versions/loader";
+const FABRIC_INSTALLER_URL: &str = "https://meta.fabricmc.net/v2/versions/installer";
+const QUILT_LOADER_URL: &str = "https://meta.quiltmc.org/v3/versions/loader";
 const FORGE_INDEX_BASE: &str = "https://files.minecraftforge.net/net/minecraftforge/forge";
 const NEOFORGE_MAVEN_METADATA_URL: &str =
   "https://maven.neoforged.net/releases/net/neoforged/neoforge/maven-metadata.xml";
 const NEOFORGE_MAVEN_BASE: &str = "https://maven.neoforged.net/releases/net/neoforged/neoforge";
+const PAPER_API_BASE: &str = "https://api.papermc.io/v2/projects";
+const PURPUR_API_BASE: &str = "https://api.purpurmc.org/v2/purpur";
 const RESOURCES_BASE_URL: &str = "https://resources.download.minecraft.net";
 const DEFAULT_LIBRARIES_URL: &str = "https://libraries.minecraft.net/";
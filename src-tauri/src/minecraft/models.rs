@@ -1,9 +1,9 @@
-use crate::config::Loader;
+use crate::config::{InstanceKind, Loader};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 
-#[derive(Clone, Serialize)]
+#[derive(Clone, Default, Serialize)]
 pub struct ProgressEvent {
   pub stage: String,
   pub message: String,
@@ -11,6 +11,12 @@ pub struct ProgressEvent {
   pub total: Option<u64>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub detail: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub overall: Option<f32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub stage_elapsed_ms: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub total_elapsed_ms: Option<u64>,
 }
 
 #[derive(Clone, Serialize)]
@@ -27,20 +33,58 @@ pub struct LoaderVersionSummary {
   pub stable: bool,
 }
 
+#[derive(Clone, Serialize)]
+pub struct VersionPatchNotes {
+  pub version: String,
+  pub title: String,
+  pub kind: String,
+  pub date: Option<String>,
+  pub short_text: Option<String>,
+  pub image_url: Option<String>,
+  pub body: String,
+}
+
 #[derive(Clone, Serialize)]
 pub struct ForgeVersionSummary {
   pub version: String,
   pub installer_url: String,
 }
 
+/// Whether a mod loader publishes builds for a given game version.
+#[derive(Clone, Serialize)]
+pub struct LoaderAvailability {
+  pub loader: String,
+  pub available: bool,
+  pub build_count: usize,
+  pub latest: Option<String>,
+  /// Set when availability could not be determined, e.g. the metadata host was unreachable.
+  pub error: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct VersionRangeFilter {
+  #[serde(default)]
+  pub min_version: Option<String>,
+  #[serde(default)]
+  pub max_version: Option<String>,
+  #[serde(default)]
+  pub released_after: Option<String>,
+  #[serde(default)]
+  pub released_before: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct NewInstanceRequest {
   pub name: String,
   pub game_version: String,
   pub loader: Loader,
   pub loader_version: Option<String>,
-  pub show_snapshots: bool,
+  /// Falls back to the new-instance defaults when omitted.
+  #[serde(default)]
+  pub show_snapshots: Option<bool>,
   pub root_id: Option<String>,
+  #[serde(default)]
+  pub kind: InstanceKind,
 }
 
 #[derive(Deserialize)]
@@ -70,11 +114,17 @@ pub(crate) struct MojangVersionMeta {
 #[derive(Deserialize)]
 pub(crate) struct MojangDownloads {
   pub client: MojangDownload,
+  #[serde(default)]
+  pub server: Option<MojangDownload>,
 }
 
 #[derive(Deserialize)]
 pub(crate) struct MojangDownload {
   pub url: String,
+  #[serde(default)]
+  pub sha1: Option<String>,
+  #[serde(default)]
+  pub size: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -86,6 +136,10 @@ pub(crate) struct MojangAssetIndex {
 #[derive(Deserialize)]
 pub(crate) struct MojangAssetIndexFile {
   pub objects: HashMap<String, MojangAssetObject>,
+  #[serde(default, rename = "virtual")]
+  pub virtual_assets: bool,
+  #[serde(default)]
+  pub map_to_resources: bool,
 }
 
 #[derive(Deserialize)]
@@ -164,6 +218,43 @@ pub(crate) struct MojangExtract {
   pub exclude: Option<Vec<String>>,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct PatchNotesIndex {
+  pub entries: Vec<PatchNotesEntry>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PatchNotesEntry {
+  pub title: String,
+  pub version: String,
+  #[serde(rename = "type")]
+  pub kind: String,
+  #[serde(default)]
+  pub date: Option<String>,
+  #[serde(default, rename = "shortText")]
+  pub short_text: Option<String>,
+  #[serde(default)]
+  pub image: Option<PatchNotesImage>,
+  #[serde(rename = "contentPath")]
+  pub content_path: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PatchNotesImage {
+  pub url: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PatchNotesContent {
+  pub body: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct MetadataCacheFile {
+  pub fetched_at: u64,
+  pub body: String,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct FabricGameVersion {
   pub version: String,
@@ -181,6 +272,56 @@ pub(crate) struct FabricLoaderVersion {
   pub stable: bool,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct FabricInstallerVersion {
+  pub version: String,
+  pub stable: bool,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PaperProject {
+  pub versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PaperBuilds {
+  pub builds: Vec<PaperBuild>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PaperBuild {
+  pub build: u64,
+  #[serde(default)]
+  pub channel: String,
+  pub downloads: HashMap<String, PaperDownload>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PaperDownload {
+  pub name: String,
+  pub sha256: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PurpurProject {
+  pub versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PurpurVersion {
+  pub builds: PurpurBuilds,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PurpurBuilds {
+  pub all: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PurpurBuild {
+  pub md5: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct FabricProfile {
   pub id: String,
@@ -190,12 +331,71 @@ pub(crate) struct FabricProfile {
   pub libraries: Vec<ProfileLibrary>,
 }
 
+/// One OptiFine build, parsed from the official jar name.
+#[derive(Clone)]
+pub(crate) struct OptiFineBuild {
+  pub game_version: String,
+  pub edition: String,
+  pub patch: String,
+  pub filename: String,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ForgeProfile {
   #[serde(default)]
   pub libraries: Vec<ProfileLibrary>,
 }
 
+/// `install_profile.json` from a 1.13+ Forge installer.
+#[derive(Deserialize)]
+pub(crate) struct ForgeInstallProfile {
+  pub json: String,
+  pub minecraft: String,
+  #[serde(default)]
+  pub data: HashMap<String, ForgeDataEntry>,
+  #[serde(default)]
+  pub processors: Vec<ForgeProcessor>,
+  #[serde(default)]
+  pub libraries: Vec<MojangLibrary>,
+}
+
+/// `install` block of a pre-1.13 Forge installer profile.
+#[derive(Deserialize)]
+pub(crate) struct LegacyForgeInstall {
+  pub path: String,
+  #[serde(rename = "filePath")]
+  pub file_path: String,
+  pub minecraft: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct LegacyForgeLibrary {
+  pub name: String,
+  #[serde(default)]
+  pub url: Option<String>,
+  #[serde(default)]
+  pub clientreq: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ForgeDataEntry {
+  #[serde(default)]
+  pub client: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ForgeProcessor {
+  #[serde(default)]
+  pub sides: Option<Vec<String>>,
+  pub jar: String,
+  #[serde(default)]
+  pub classpath: Vec<String>,
+  #[serde(default)]
+  pub args: Vec<String>,
+  #[serde(default)]
+  pub outputs: HashMap<String, String>,
+}
+
 #[derive(Deserialize, Clone)]
 pub(crate) struct ProfileLibrary {
   pub name: String,
@@ -234,6 +434,7 @@ pub(crate) struct ResolvedVersion {
   pub arguments: Option<VersionArguments>,
   pub minecraft_arguments: Option<String>,
   pub base_version_id: Option<String>,
+  pub loader_libraries: HashSet<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -276,6 +477,7 @@ pub(crate) struct LaunchContext {
   pub assets_root: String,
   pub library_dir: String,
   pub asset_index_name: String,
+  pub game_assets: String,
   pub classpath: String,
   pub natives_dir: String,
   pub logging_path: String,
@@ -301,10 +503,19 @@ pub(crate) struct LoggingFile {
   pub url: String,
 }
 
+#[derive(Serialize, Clone)]
+pub(crate) struct DownloadFailure {
+  pub file: String,
+  pub url: String,
+  pub reason: String,
+}
+
 #[derive(Clone)]
 pub(crate) struct DownloadJob {
   pub url: String,
   pub dest: PathBuf,
+  pub size: Option<u64>,
+  pub sha1: Option<String>,
 }
 
 #[derive(Clone)]
@@ -318,6 +529,16 @@ pub(crate) struct MavenCoordinate {
   pub artifact: String,
   pub version: String,
   pub classifier: Option<String>,
+  /// File extension from a trailing `@ext`, `jar` when absent.
+  pub extension: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct InstallJournalState {
+  #[serde(default)]
+  pub completed: BTreeSet<String>,
+  #[serde(default)]
+  pub verified: BTreeSet<String>,
 }
 
 #[derive(Serialize, Deserialize)]
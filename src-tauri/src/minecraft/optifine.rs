@@ -0,0 +1,326 @@
+use crate::config::{Instance, InstanceKind, Loader, OptiFineInstall};
+use crate::minecraft::download::download_to;
+use crate::minecraft::install::install_vanilla;
+use crate::minecraft::instance::{load_manifest, save_manifest};
+use crate::minecraft::journal::InstallJournal;
+use crate::minecraft::models::{OptiFineBuild, ProgressEvent};
+use crate::minecraft::summary::record_warning;
+use crate::minecraft::util::{long_path, sanitize_path_component};
+use crate::minecraft::DEFAULT_LIBRARIES_URL;
+use sha2::{Digest, Sha256};
+use std::{
+  fs::{self, File},
+  io::{self, Cursor, Read},
+  path::{Path, PathBuf},
+  process::Command,
+};
+use zip::ZipArchive;
+
+const OPTIFINE_TWEAKER: &str = "optifine.OptiFineTweaker";
+const OPTIFINE_PATCHER_ENTRY: &str = "optifine/Patcher.class";
+const LAUNCHWRAPPER_MAIN_CLASS: &str = "net.minecraft.launchwrapper.Launch";
+const MOJANG_LAUNCHWRAPPER: &str = "net.minecraft:launchwrapper:1.12";
+
+/// Installs a user-supplied OptiFine jar and records its SHA-256 for later restores.
+pub(crate) fn install_optifine(
+  instance: &Instance,
+  file_name: &str,
+  data: &[u8],
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<OptiFineInstall, String> {
+  if instance.kind == InstanceKind::Server {
+    return Err("OptiFine is only supported for client instances".to_string());
+  }
+  if !matches!(
+    instance.loader,
+    Loader::Vanilla | Loader::Forge | Loader::Fabric | Loader::Quilt
+  ) {
+    return Err("OptiFine supports vanilla, Forge, Fabric and Quilt instances only".to_string());
+  }
+  let build = parse_build_name(file_name)
+    .ok_or_else(|| format!("{} is not an OptiFine jar name", file_name))?;
+  if build.game_version != instance.version {
+    return Err(format!(
+      "{} is built for {}, not {}",
+      build.filename, build.game_version, instance.version
+    ));
+  }
+  let mut archive = ZipArchive::new(Cursor::new(data)).map_err(|err| err.to_string())?;
+  if archive.by_name(OPTIFINE_PATCHER_ENTRY).is_err() {
+    return Err(format!("{} is not an OptiFine installer", build.filename));
+  }
+  let sha256 = format!("{:x}", Sha256::digest(data));
+  let instance_dir = PathBuf::from(&instance.directory);
+  let installer_path = installer_path(&instance_dir, &build.filename);
+  if let Some(parent) = installer_path.parent() {
+    fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+  }
+  fs::write(long_path(&installer_path), data).map_err(|err| err.to_string())?;
+  emit_stage(emit, format!("Installing {}", build.filename));
+
+  let mut manifest =
+    load_manifest(&instance_dir).ok_or_else(|| "instance manifest missing".to_string())?;
+  let version_id = if instance.loader == Loader::Vanilla {
+    Some(install_optifine_version(&instance_dir, &build, &installer_path, emit)?)
+  } else {
+    let mods_dir = instance_dir.join("mods");
+    fs::create_dir_all(&mods_dir).map_err(|err| err.to_string())?;
+    if let Some(previous) = manifest.optifine.as_ref().filter(|prev| prev.version_id.is_none()) {
+      if previous.file_name != build.filename {
+        let _ = fs::remove_file(mods_dir.join(&previous.file_name));
+      }
+    }
+    fs::copy(&installer_path, mods_dir.join(&build.filename)).map_err(|err| err.to_string())?;
+    None
+  };
+
+  let record = OptiFineInstall {
+    game_version: build.game_version.clone(),
+    edition: build.edition.clone(),
+    patch: build.patch.clone(),
+    file_name: build.filename.clone(),
+    sha256: Some(sha256),
+    version_id: version_id.clone(),
+  };
+  if version_id.is_some() {
+    manifest.custom_version = version_id;
+  }
+  manifest.optifine = Some(record.clone());
+  save_manifest(&instance_dir, &manifest)?;
+  Ok(record)
+}
+
+/// Regenerates the OptiFine version after a core reinstall, or drops a stale record.
+pub(super) fn restore_optifine(
+  instance: &Instance,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let Some(mut manifest) = load_manifest(instance_dir) else {
+    return Ok(());
+  };
+  let Some(record) = manifest.optifine.clone() else {
+    return Ok(());
+  };
+  let Some(version_id) = record.version_id.clone() else {
+    return Ok(());
+  };
+  if version_json_path(instance_dir, &version_id).is_file()
+    && record.game_version == instance.version
+    && instance.loader == Loader::Vanilla
+  {
+    return Ok(());
+  }
+  let installer_path = installer_path(instance_dir, &record.file_name);
+  let reason = if record.game_version != instance.version || instance.loader != Loader::Vanilla {
+    Some(format!("the instance no longer runs vanilla {}", record.game_version))
+  } else if !installer_matches(&installer_path, record.sha256.as_deref()) {
+    Some("its jar is missing or does not match the recorded checksum".to_string())
+  } else {
+    None
+  };
+  if let Some(reason) = reason {
+    record_warning(format!("OptiFine {} was removed because {}", record.patch, reason));
+    if manifest.custom_version.as_deref() == Some(version_id.as_str()) {
+      manifest.custom_version = None;
+    }
+    manifest.optifine = None;
+    return save_manifest(instance_dir, &manifest);
+  }
+  let build = OptiFineBuild {
+    game_version: record.game_version,
+    edition: record.edition,
+    patch: record.patch,
+    filename: record.file_name,
+  };
+  install_optifine_version(instance_dir, &build, &installer_path, emit)?;
+  Ok(())
+}
+
+/// Parses official names such as `OptiFine_1.20.1_HD_U_I6.jar` and `preview_` builds.
+fn parse_build_name(file_name: &str) -> Option<OptiFineBuild> {
+  let stem = file_name.strip_suffix(".jar")?;
+  let preview = stem.starts_with("preview_");
+  let mut parts = stem
+    .trim_start_matches("preview_")
+    .strip_prefix("OptiFine_")?
+    .split('_')
+    .collect::<Vec<_>>();
+  let mut patch = parts.pop()?.to_string();
+  if preview && patch.starts_with("pre") {
+    patch = format!("{}_{}", parts.pop()?, patch);
+  }
+  if parts.len() < 2 {
+    return None;
+  }
+  let game_version = parts.remove(0).to_string();
+  Some(OptiFineBuild {
+    game_version,
+    edition: parts.join("_"),
+    patch,
+    filename: file_name.to_string(),
+  })
+}
+
+fn installer_path(instance_dir: &Path, file_name: &str) -> PathBuf {
+  instance_dir
+    .join("installers")
+    .join(sanitize_path_component(file_name, "optifine.jar"))
+}
+
+fn installer_matches(path: &Path, expected: Option<&str>) -> bool {
+  let Some(expected) = expected else {
+    return false;
+  };
+  fs::read(long_path(path))
+    .map(|data| format!("{:x}", Sha256::digest(&data)).eq_ignore_ascii_case(expected))
+    .unwrap_or(false)
+}
+
+fn install_optifine_version(
+  instance_dir: &Path,
+  build: &OptiFineBuild,
+  installer_path: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<String, String> {
+  let game_version = &build.game_version;
+  let base_jar = version_json_path(instance_dir, game_version).with_extension("jar");
+  if !base_jar.is_file() {
+    let journal = InstallJournal::open(instance_dir);
+    install_vanilla(game_version, instance_dir, &journal, emit)?;
+    journal.finish();
+  }
+
+  let build_id = format!("{}_{}_{}", game_version, build.edition, build.patch);
+  let libraries_dir = instance_dir.join("libraries");
+  let library_path = libraries_dir
+    .join("optifine")
+    .join("OptiFine")
+    .join(&build_id)
+    .join(format!("OptiFine-{}.jar", build_id));
+  if !library_path.is_file() {
+    emit_stage(emit, "Patching OptiFine".to_string());
+    run_patcher(installer_path, &base_jar, &library_path)?;
+  }
+  let launchwrapper = install_launchwrapper(installer_path, &libraries_dir)?;
+
+  let base_document: serde_json::Value =
+    crate::minecraft::download::load_json(&version_json_path(instance_dir, game_version))?;
+  let version_id = format!("{}-OptiFine_{}_{}", game_version, build.edition, build.patch);
+  let mut document = serde_json::json!({
+    "id": version_id,
+    "inheritsFrom": game_version,
+    "type": "release",
+    "mainClass": LAUNCHWRAPPER_MAIN_CLASS,
+    "libraries": [
+      { "name": format!("optifine:OptiFine:{}", build_id) },
+      { "name": launchwrapper },
+    ],
+  });
+  // Legacy versions replace `minecraftArguments` wholesale, newer ones merge `arguments`.
+  match base_document.get("minecraftArguments").and_then(|value| value.as_str()) {
+    Some(arguments) => {
+      document["minecraftArguments"] =
+        format!("{} --tweakClass {}", arguments, OPTIFINE_TWEAKER).into();
+    }
+    None => {
+      document["arguments"] = serde_json::json!({ "game": ["--tweakClass", OPTIFINE_TWEAKER] });
+    }
+  }
+  let json_path = version_json_path(instance_dir, &version_id);
+  if let Some(parent) = json_path.parent() {
+    fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+  }
+  let payload =
+    serde_json::to_vec_pretty(&document).map_err(crate::minecraft::download::map_json_error)?;
+  fs::write(long_path(&json_path), payload).map_err(|err| err.to_string())?;
+  Ok(version_id)
+}
+
+fn run_patcher(installer_path: &Path, base_jar: &Path, output: &Path) -> Result<(), String> {
+  if let Some(parent) = output.parent() {
+    fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+  }
+  let result = Command::new("java")
+    .arg("-cp")
+    .arg(installer_path)
+    .arg("optifine.Patcher")
+    .arg(base_jar)
+    .arg(installer_path)
+    .arg(output)
+    .output()
+    .map_err(|err| format!("failed to run OptiFine patcher: {}", err))?;
+  if result.status.success() && output.is_file() {
+    return Ok(());
+  }
+  let _ = fs::remove_file(output);
+  Err(format!(
+    "OptiFine patcher failed (code {:?}): {}",
+    result.status.code(),
+    String::from_utf8_lossy(&result.stderr).trim()
+  ))
+}
+
+/// Installs the bundled or Mojang launchwrapper and returns its library name.
+fn install_launchwrapper(installer_path: &Path, libraries_dir: &Path) -> Result<String, String> {
+  let file = File::open(installer_path).map_err(|err| err.to_string())?;
+  let mut archive = ZipArchive::new(file).map_err(|err| err.to_string())?;
+  let bundled = if archive.by_name("launchwrapper-2.0.jar").is_ok() {
+    Some(("launchwrapper".to_string(), "2.0".to_string()))
+  } else if let Ok(mut entry) = archive.by_name("launchwrapper-of.txt") {
+    let mut version = String::new();
+    entry.read_to_string(&mut version).map_err(|err| err.to_string())?;
+    Some(("launchwrapper-of".to_string(), version.trim().to_string()))
+  } else {
+    None
+  };
+
+  let Some((artifact, version)) = bundled else {
+    let path = libraries_dir
+      .join("net")
+      .join("minecraft")
+      .join("launchwrapper")
+      .join("1.12")
+      .join("launchwrapper-1.12.jar");
+    let url = format!(
+      "{}net/minecraft/launchwrapper/1.12/launchwrapper-1.12.jar",
+      DEFAULT_LIBRARIES_URL
+    );
+    download_to(&url, &path)?;
+    return Ok(MOJANG_LAUNCHWRAPPER.to_string());
+  };
+
+  let jar_name = format!("{}-{}.jar", artifact, version);
+  let target = libraries_dir
+    .join("optifine")
+    .join(&artifact)
+    .join(&version)
+    .join(&jar_name);
+  if !target.is_file() {
+    let mut entry = archive
+      .by_name(&jar_name)
+      .map_err(|_| format!("OptiFine installer is missing {}", jar_name))?;
+    if let Some(parent) = target.parent() {
+      fs::create_dir_all(long_path(parent)).map_err(|err| err.to_string())?;
+    }
+    let mut out = File::create(long_path(&target)).map_err(|err| err.to_string())?;
+    io::copy(&mut entry, &mut out).map_err(|err| err.to_string())?;
+  }
+  Ok(format!("optifine:{}:{}", artifact, version))
+}
+
+fn version_json_path(instance_dir: &Path, version_id: &str) -> PathBuf {
+  instance_dir
+    .join("versions")
+    .join(version_id)
+    .join(format!("{}.json", version_id))
+}
+
+fn emit_stage(emit: &dyn Fn(ProgressEvent), message: String) {
+  emit(ProgressEvent {
+    stage: "optifine".to_string(),
+    message,
+    current: 0,
+    ..Default::default()
+  });
+}
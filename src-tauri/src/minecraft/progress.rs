@@ -0,0 +1,71 @@
+use crate::config::Loader;
+use crate::minecraft::models::ProgressEvent;
+use std::sync::Mutex;
+
+pub(crate) struct WeightedProgress {
+  stages: Vec<(&'static str, f32)>,
+  fractions: Mutex<Vec<f32>>,
+}
+
+impl WeightedProgress {
+  pub(crate) fn new(stages: Vec<(&'static str, f32)>) -> Self {
+    let fractions = Mutex::new(vec![0.0; stages.len()]);
+    Self { stages, fractions }
+  }
+
+  pub(crate) fn client(loader: &Loader) -> Self {
+    let mut stages = vec![
+      ("version", 5.0),
+      ("client", 15.0),
+      ("libraries", 25.0),
+      ("natives", 5.0),
+      ("assets", 40.0),
+    ];
+    match loader {
+      Loader::Forge => stages.push(("forge", 10.0)),
+      Loader::NeoForge => stages.push(("neoforge", 10.0)),
+      _ => {}
+    }
+    Self::new(stages)
+  }
+
+  pub(crate) fn server(loader: &Loader) -> Self {
+    let mut stages = vec![("version", 10.0), ("server", 60.0)];
+    match loader {
+      Loader::Fabric => stages.push(("fabric", 30.0)),
+      Loader::Forge => stages.push(("forge", 30.0)),
+      Loader::NeoForge => stages.push(("neoforge", 30.0)),
+      _ => {}
+    }
+    Self::new(stages)
+  }
+
+  pub(crate) fn apply(&self, mut event: ProgressEvent) -> ProgressEvent {
+    let mut fractions = match self.fractions.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some(index) = self.stages.iter().position(|(stage, _)| *stage == event.stage) {
+      for earlier in fractions.iter_mut().take(index) {
+        *earlier = 1.0;
+      }
+      let fraction = match event.total {
+        Some(total) if total > 0 => (event.current as f32 / total as f32).min(1.0),
+        _ if event.current > 0 => 0.5,
+        _ => 0.0,
+      };
+      fractions[index] = fractions[index].max(fraction);
+    }
+    let total_weight: f32 = self.stages.iter().map(|(_, weight)| weight).sum();
+    let done: f32 = self
+      .stages
+      .iter()
+      .zip(fractions.iter())
+      .map(|((_, weight), fraction)| weight * fraction)
+      .sum();
+    if total_weight > 0.0 {
+      event.overall = Some((done / total_weight * 100.0).clamp(0.0, 100.0));
+    }
+    event
+  }
+}
@@ -0,0 +1,492 @@
+use crate::config::{split_jvm_args, AppConfig, Instance, Loader};
+use crate::diagnostics::validate_ram_settings;
+use crate::java::resolve_java_command;
+use crate::minecraft::download::{download_to, fetch_json, load_json};
+use crate::minecraft::install::{
+  download_forge_installer, download_neoforge_installer, download_zip_verified,
+  download_zip_with_retry, forge_full_version, run_java_installer,
+};
+use crate::minecraft::launch::ExitCallback;
+use crate::minecraft::models::{
+  FabricInstallerVersion, MojangManifest, MojangVersionMeta, PaperBuild, PaperBuilds,
+  ProgressEvent, PurpurBuild, PurpurVersion,
+};
+use crate::minecraft::versions::{
+  neoforge_version_matches_game, paper_project_name, resolve_neoforge_channel,
+};
+use crate::minecraft::{
+  FABRIC_INSTALLER_URL, FABRIC_LOADER_URL, MOJANG_MANIFEST_URL, PAPER_API_BASE, PURPUR_API_BASE,
+};
+use sha2::{Digest, Sha256};
+use std::{
+  fs,
+  io::{BufRead, BufReader},
+  path::{Path, PathBuf},
+  process::{ChildStdin, Command, Stdio},
+  sync::Arc,
+  thread,
+};
+
+pub(crate) const SERVER_JAR_FILE: &str = "server.jar";
+pub(crate) const SERVER_EULA_FILE: &str = "eula.txt";
+pub(crate) const SERVER_EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+
+pub(crate) enum ServerLaunchTarget {
+  Jar(PathBuf),
+  ArgsFile(PathBuf),
+}
+
+pub(crate) fn create_server_layout(instance_dir: &Path, loader: &Loader) -> Result<(), String> {
+  let mut paths = vec![
+    instance_dir.to_path_buf(),
+    instance_dir.join("versions"),
+    instance_dir.join("libraries"),
+    instance_dir.join("config"),
+    instance_dir.join("logs"),
+    instance_dir.join("installers"),
+  ];
+  if loader.server_only() {
+    paths.push(instance_dir.join("plugins"));
+  } else if *loader != Loader::Vanilla {
+    paths.push(instance_dir.join("mods"));
+  }
+
+  for path in paths {
+    fs::create_dir_all(&path).map_err(|err| err.to_string())?;
+  }
+
+  Ok(())
+}
+
+pub(crate) fn install_server(
+  instance: &Instance,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let server_jar = instance_dir.join(SERVER_JAR_FILE);
+  if server_jar.exists() {
+    fs::remove_file(&server_jar).map_err(|err| err.to_string())?;
+  }
+
+  match instance.loader {
+    Loader::Paper | Loader::Folia => install_paper_server(instance, instance_dir, emit),
+    Loader::Purpur => install_purpur_server(instance, instance_dir, emit),
+    Loader::Vanilla => install_vanilla_server(&instance.version, instance_dir, emit),
+    Loader::Fabric => {
+      let loader_version = instance
+        .loader_version
+        .clone()
+        .ok_or_else(|| "fabric loader version is required".to_string())?;
+      install_fabric_server(&instance.version, &loader_version, instance_dir, emit)
+    }
+    Loader::Quilt => Err("Quilt is only supported for client instances".to_string()),
+    Loader::Forge => {
+      let loader_version = instance
+        .loader_version
+        .clone()
+        .ok_or_else(|| "forge version is required".to_string())?;
+      let full_version = forge_full_version(&instance.version, &loader_version);
+      let installer_path = download_forge_installer(&full_version, instance_dir)?;
+      emit(ProgressEvent {
+        stage: "forge".to_string(),
+        message: "Running Forge server installer".to_string(),
+        current: 0,
+        ..Default::default()
+      });
+      run_java_installer(
+        &installer_path,
+        instance_dir,
+        "--installServer",
+        "forge",
+        "Running Forge server installer",
+        emit,
+      )
+    }
+    Loader::NeoForge => {
+      let loader_version = instance
+        .loader_version
+        .clone()
+        .ok_or_else(|| "neoforge version is required".to_string())?;
+      if !neoforge_version_matches_game(&loader_version, &instance.version) {
+        let channel = resolve_neoforge_channel(&instance.version);
+        return Err(format!(
+          "NeoForge version '{}' is incompatible with Minecraft '{}'. Expected NeoForge {}.x.",
+          loader_version, instance.version, channel
+        ));
+      }
+      let installer_path = download_neoforge_installer(&loader_version, instance_dir)?;
+      emit(ProgressEvent {
+        stage: "neoforge".to_string(),
+        message: "Running NeoForge server installer".to_string(),
+        current: 0,
+        ..Default::default()
+      });
+      run_java_installer(
+        &installer_path,
+        instance_dir,
+        "--installServer",
+        "neoforge",
+        "Running NeoForge server installer",
+        emit,
+      )
+    }
+  }
+}
+
+fn install_vanilla_server(
+  game_version: &str,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  emit(ProgressEvent {
+    stage: "version".to_string(),
+    message: format!("Resolving {}", game_version),
+    current: 0,
+    ..Default::default()
+  });
+
+  let manifest: MojangManifest = fetch_json(MOJANG_MANIFEST_URL)?;
+  let entry = manifest
+    .versions
+    .into_iter()
+    .find(|version| version.id == game_version)
+    .ok_or_else(|| format!("vanilla version '{}' not found", game_version))?;
+
+  let version_dir = instance_dir.join("versions").join(&entry.id);
+  fs::create_dir_all(&version_dir).map_err(|err| err.to_string())?;
+  let version_json_path = version_dir.join(format!("{}.json", entry.id));
+  download_to(&entry.url, &version_json_path)?;
+
+  let version_meta: MojangVersionMeta = load_json(&version_json_path)?;
+  let server = version_meta
+    .downloads
+    .server
+    .as_ref()
+    .ok_or_else(|| format!("Minecraft {} has no dedicated server download", game_version))?;
+
+  emit(ProgressEvent {
+    stage: "server".to_string(),
+    message: "Downloading server jar".to_string(),
+    current: 0,
+    ..Default::default()
+  });
+  download_zip_verified(
+    &server.url,
+    &instance_dir.join(SERVER_JAR_FILE),
+    server.size,
+    server.sha1.as_deref(),
+    "server jar",
+  )
+}
+
+fn install_fabric_server(
+  game_version: &str,
+  loader_version: &str,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  emit(ProgressEvent {
+    stage: "fabric".to_string(),
+    message: "Resolving Fabric server launcher".to_string(),
+    current: 0,
+    ..Default::default()
+  });
+
+  let installers: Vec<FabricInstallerVersion> = fetch_json(FABRIC_INSTALLER_URL)?;
+  let installer = installers
+    .iter()
+    .find(|entry| entry.stable)
+    .or_else(|| installers.first())
+    .ok_or_else(|| "no Fabric installer versions available".to_string())?;
+
+  let url = format!(
+    "{}/{}/{}/{}/server/jar",
+    FABRIC_LOADER_URL,
+    urlencoding::encode(game_version),
+    urlencoding::encode(loader_version),
+    urlencoding::encode(&installer.version)
+  );
+
+  emit(ProgressEvent {
+    stage: "server".to_string(),
+    message: "Downloading Fabric server launcher".to_string(),
+    current: 0,
+    ..Default::default()
+  });
+  download_zip_with_retry(&url, &instance_dir.join(SERVER_JAR_FILE), "fabric server launcher")
+}
+
+fn install_paper_server(
+  instance: &Instance,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let project = paper_project_name(&instance.loader)?;
+  emit(ProgressEvent {
+    stage: "server".to_string(),
+    message: format!("Resolving {} build", project),
+    current: 0,
+    ..Default::default()
+  });
+
+  let url = format!(
+    "{}/{}/versions/{}/builds",
+    PAPER_API_BASE,
+    project,
+    urlencoding::encode(&instance.version)
+  );
+  let builds: PaperBuilds = fetch_json(&url)?;
+  let build: PaperBuild = match instance.loader_version.as_deref() {
+    Some(requested) => builds
+      .builds
+      .into_iter()
+      .find(|build| build.build.to_string() == requested)
+      .ok_or_else(|| {
+        format!("{} build '{}' not found for {}", project, requested, instance.version)
+      })?,
+    None => builds
+      .builds
+      .into_iter()
+      .max_by_key(|build| build.build)
+      .ok_or_else(|| format!("no {} builds available for {}", project, instance.version))?,
+  };
+  let download = build
+    .downloads
+    .get("application")
+    .ok_or_else(|| format!("{} build {} has no server download", project, build.build))?;
+
+  let download_url = format!(
+    "{}/{}/versions/{}/builds/{}/downloads/{}",
+    PAPER_API_BASE,
+    project,
+    urlencoding::encode(&instance.version),
+    build.build,
+    urlencoding::encode(&download.name)
+  );
+  emit(ProgressEvent {
+    stage: "server".to_string(),
+    message: format!("Downloading {} build {}", project, build.build),
+    current: 0,
+    detail: Some(download.name.clone()),
+    ..Default::default()
+  });
+  let dest = instance_dir.join(SERVER_JAR_FILE);
+  download_zip_with_retry(&download_url, &dest, "server jar")?;
+
+  let bytes = fs::read(&dest).map_err(|err| err.to_string())?;
+  let actual = format!("{:x}", Sha256::digest(&bytes));
+  if !actual.eq_ignore_ascii_case(&download.sha256) {
+    let _ = fs::remove_file(&dest);
+    return Err(format!("{} server jar checksum mismatch. Please retry.", project));
+  }
+  Ok(())
+}
+
+fn install_purpur_server(
+  instance: &Instance,
+  instance_dir: &Path,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<(), String> {
+  let game_version = urlencoding::encode(&instance.version).to_string();
+  let build = match instance.loader_version.clone() {
+    Some(build) => build,
+    None => {
+      let version: PurpurVersion = fetch_json(&format!("{}/{}", PURPUR_API_BASE, game_version))?;
+      version
+        .builds
+        .all
+        .last()
+        .cloned()
+        .ok_or_else(|| format!("no purpur builds available for {}", instance.version))?
+    }
+  };
+  let build_url = format!(
+    "{}/{}/{}",
+    PURPUR_API_BASE,
+    game_version,
+    urlencoding::encode(&build)
+  );
+  let build_info: PurpurBuild = fetch_json(&build_url)?;
+
+  emit(ProgressEvent {
+    stage: "server".to_string(),
+    message: format!("Downloading purpur build {}", build),
+    current: 0,
+    ..Default::default()
+  });
+  let dest = instance_dir.join(SERVER_JAR_FILE);
+  download_zip_with_retry(&format!("{}/download", build_url), &dest, "server jar")?;
+
+  if let Some(expected) = build_info.md5 {
+    let bytes = fs::read(&dest).map_err(|err| err.to_string())?;
+    let actual = format!("{:x}", md5::compute(&bytes));
+    if !actual.eq_ignore_ascii_case(&expected) {
+      let _ = fs::remove_file(&dest);
+      return Err("purpur server jar checksum mismatch. Please retry.".to_string());
+    }
+  }
+  Ok(())
+}
+
+pub(crate) fn server_eula_accepted(instance_dir: &Path) -> bool {
+  fs::read_to_string(instance_dir.join(SERVER_EULA_FILE))
+    .map(|data| {
+      data.lines().any(|line| {
+        line
+          .split_once('=')
+          .map(|(key, value)| key.trim() == "eula" && value.trim().eq_ignore_ascii_case("true"))
+          .unwrap_or(false)
+      })
+    })
+    .unwrap_or(false)
+}
+
+pub(crate) fn resolve_server_launch_target(
+  instance: &Instance,
+  instance_dir: &Path,
+) -> Option<ServerLaunchTarget> {
+  let args_file = if cfg!(target_os = "windows") {
+    "win_args.txt"
+  } else {
+    "unix_args.txt"
+  };
+
+  match instance.loader {
+    Loader::Vanilla
+    | Loader::Fabric
+    | Loader::Quilt
+    | Loader::Paper
+    | Loader::Folia
+    | Loader::Purpur => {
+      let jar = instance_dir.join(SERVER_JAR_FILE);
+      jar.is_file().then_some(ServerLaunchTarget::Jar(jar))
+    }
+    Loader::Forge => {
+      let loader_version = instance.loader_version.as_deref()?;
+      let full_version = forge_full_version(&instance.version, loader_version);
+      let args_path = instance_dir
+        .join("libraries/net/minecraftforge/forge")
+        .join(&full_version)
+        .join(args_file);
+      if args_path.is_file() {
+        return Some(ServerLaunchTarget::ArgsFile(args_path));
+      }
+      [
+        format!("forge-{}-shim.jar", full_version),
+        format!("forge-{}.jar", full_version),
+        format!("forge-{}-universal.jar", full_version),
+      ]
+      .iter()
+      .map(|name| instance_dir.join(name))
+      .find(|path| path.is_file())
+      .map(ServerLaunchTarget::Jar)
+    }
+    Loader::NeoForge => {
+      let loader_version = instance.loader_version.as_deref()?;
+      let args_path = instance_dir
+        .join("libraries/net/neoforged/neoforge")
+        .join(loader_version)
+        .join(args_file);
+      args_path
+        .is_file()
+        .then_some(ServerLaunchTarget::ArgsFile(args_path))
+    }
+  }
+}
+
+pub(crate) fn launch_server_instance(
+  instance: &Instance,
+  config: &AppConfig,
+  log: Arc<dyn Fn(&str, &str) + Send + Sync>,
+  on_exit: Option<ExitCallback>,
+  on_stdin: Option<Arc<dyn Fn(u32, ChildStdin) + Send + Sync>>,
+) -> Result<u32, String> {
+  let instance_dir = PathBuf::from(&instance.directory);
+  let target = resolve_server_launch_target(instance, &instance_dir)
+    .ok_or_else(|| "server jar missing. Repair the instance to reinstall it.".to_string())?;
+  if !server_eula_accepted(&instance_dir) {
+    return Err(format!(
+      "server EULA not accepted. Review {} and accept it before starting the server.",
+      SERVER_EULA_URL
+    ));
+  }
+
+  let min_ram_mb = instance
+    .java_min_ram_mb
+    .unwrap_or(config.settings.java.min_ram_mb);
+  let max_ram_mb = instance
+    .java_max_ram_mb
+    .unwrap_or(config.settings.java.max_ram_mb);
+
+  let mut args = Vec::new();
+  args.push(format!("-Xms{}M", min_ram_mb));
+  args.push(format!("-Xmx{}M", max_ram_mb));
+  args.extend(split_jvm_args(&config.settings.java.jvm_args));
+  if let Some(extra) = &instance.jvm_args {
+    args.extend(extra.iter().cloned());
+  }
+  match target {
+    ServerLaunchTarget::Jar(path) => {
+      args.push("-jar".to_string());
+      args.push(path.to_string_lossy().to_string());
+    }
+    ServerLaunchTarget::ArgsFile(path) => {
+      args.push(format!("@{}", path.to_string_lossy()));
+    }
+  }
+  args.push("nogui".to_string());
+
+  let java_cmd = resolve_java_command(config, instance)?;
+  for finding in validate_ram_settings(min_ram_mb, max_ram_mb, Some(&java_cmd)) {
+    if finding.severity == "error" {
+      return Err(finding.summary);
+    }
+    log("launcher", &format!("Warning: {}", finding.summary));
+  }
+  log(
+    "launcher",
+    &format!("Starting dedicated server '{}' ({} {})", instance.name, instance.version, java_cmd),
+  );
+  log("launcher", &format!("Args: {}", args.join(" ")));
+
+  let mut child = Command::new(&java_cmd)
+    .args(&args)
+    .current_dir(&instance_dir)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| format!("failed to launch server ({}): {}", java_cmd, err))?;
+
+  if let Some(stdout) = child.stdout.take() {
+    let log = log.clone();
+    thread::spawn(move || {
+      for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        log("stdout", &line);
+      }
+    });
+  }
+
+  if let Some(stderr) = child.stderr.take() {
+    let log = log.clone();
+    thread::spawn(move || {
+      for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+        log("stderr", &line);
+      }
+    });
+  }
+
+  let pid = child.id();
+  if let (Some(stdin), Some(callback)) = (child.stdin.take(), on_stdin) {
+    callback(pid, stdin);
+  }
+
+  thread::spawn(move || {
+    let exit_code = child.wait().ok().and_then(|status| status.code());
+    if let Some(callback) = on_exit {
+      callback(pid, exit_code);
+    }
+  });
+
+  Ok(pid)
+}
@@ -0,0 +1,99 @@
+use crate::config::InstallSummary;
+use std::{
+  cell::RefCell,
+  sync::{Arc, Mutex},
+  time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Default)]
+struct InstallTally {
+  files_downloaded: u64,
+  bytes_downloaded: u64,
+  warnings: Vec<String>,
+}
+
+/// Collects what an install did. Downloads and warnings are attributed to the
+/// recorder active on the current thread, so worker threads must re-enter it.
+#[derive(Clone)]
+pub(crate) struct InstallRecorder {
+  started: Instant,
+  tally: Arc<Mutex<InstallTally>>,
+}
+
+thread_local! {
+  static ACTIVE_RECORDER: RefCell<Option<InstallRecorder>> = const { RefCell::new(None) };
+}
+
+impl InstallRecorder {
+  pub(crate) fn start() -> Self {
+    Self {
+      started: Instant::now(),
+      tally: Arc::new(Mutex::new(InstallTally::default())),
+    }
+  }
+
+  pub(crate) fn scope<T>(&self, run: impl FnOnce() -> T) -> T {
+    let previous = ACTIVE_RECORDER.with(|slot| slot.replace(Some(self.clone())));
+    let result = run();
+    ACTIVE_RECORDER.with(|slot| *slot.borrow_mut() = previous);
+    result
+  }
+
+  pub(crate) fn finish(self) -> InstallSummary {
+    let tally = match self.tally.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    InstallSummary {
+      finished_at_unix: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0),
+      duration_ms: self.started.elapsed().as_millis() as u64,
+      files_downloaded: tally.files_downloaded,
+      bytes_downloaded: tally.bytes_downloaded,
+      warnings: tally.warnings.clone(),
+    }
+  }
+
+  fn update(&self, apply: impl FnOnce(&mut InstallTally)) {
+    let mut tally = match self.tally.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => poisoned.into_inner(),
+    };
+    apply(&mut tally);
+  }
+}
+
+pub(crate) fn active_recorder() -> Option<InstallRecorder> {
+  ACTIVE_RECORDER.with(|slot| slot.borrow().clone())
+}
+
+/// Runs `run` under `recorder` when there is one; used to carry it into worker threads.
+pub(crate) fn with_recorder<T>(recorder: Option<&InstallRecorder>, run: impl FnOnce() -> T) -> T {
+  match recorder {
+    Some(recorder) => recorder.scope(run),
+    None => run(),
+  }
+}
+
+pub(crate) fn record_download(bytes: u64) {
+  if let Some(recorder) = active_recorder() {
+    recorder.update(|tally| {
+      tally.files_downloaded += 1;
+      tally.bytes_downloaded = tally.bytes_downloaded.saturating_add(bytes);
+    });
+  }
+}
+
+pub(crate) fn record_warning(message: impl Into<String>) {
+  let message = message.into();
+  log::warn!("{}", message);
+  if let Some(recorder) = active_recorder() {
+    recorder.update(|tally| {
+      if !tally.warnings.contains(&message) {
+        tally.warnings.push(message);
+      }
+    });
+  }
+}
@@ -1,7 +1,10 @@
+use crate::minecraft::download::load_json;
 use crate::minecraft::models::{
-  FeatureFlags, MavenCoordinate, MojangFeatureRule, MojangLibraryArtifact, MojangRule,
+  FeatureFlags, MavenCoordinate, MojangAssetIndexFile, MojangFeatureRule, MojangLibraryArtifact,
+  MojangRule,
 };
 use crate::minecraft::DEFAULT_LIBRARIES_URL;
+use std::path::{Path, PathBuf};
 
 pub(crate) fn current_os_name() -> &'static str {
   match std::env::consts::OS {
@@ -107,6 +110,10 @@ pub(crate) fn is_excluded(path: &str, excludes: &[String]) -> bool {
 }
 
 pub(crate) fn parse_maven_coordinate(name: &str) -> Result<MavenCoordinate, String> {
+  let (name, extension) = match name.rsplit_once('@') {
+    Some((name, extension)) => (name, extension),
+    None => (name, "jar"),
+  };
   let parts: Vec<&str> = name.split(':').collect();
   if parts.len() < 3 {
     return Err(format!("invalid maven coordinate '{}'", name));
@@ -116,33 +123,71 @@ pub(crate) fn parse_maven_coordinate(name: &str) -> Result<MavenCoordinate, Stri
     artifact: parts[1].to_string(),
     version: parts[2].to_string(),
     classifier: parts.get(3).map(|item| item.to_string()),
+    extension: extension.to_string(),
   })
 }
 
+/// Joins `path` onto `base`, tolerating missing or doubled slashes on either side.
+pub(crate) fn join_url(base: &str, path: &str) -> Result<String, String> {
+  let base = base.trim();
+  let (scheme, rest) = base
+    .split_once("://")
+    .ok_or_else(|| format!("invalid library host '{}'", base))?;
+  if !matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https") {
+    return Err(format!("unsupported library host '{}'", base));
+  }
+  // Query strings and fragments on a repository root would end up in the middle of the URL.
+  let rest = rest.split(['?', '#']).next().unwrap_or_default();
+  let mut segments = rest.split('/').filter(|segment| !segment.is_empty());
+  let host = segments
+    .next()
+    .ok_or_else(|| format!("invalid library host '{}'", base))?;
+  let mut url = format!("{}://{}", scheme, host);
+  for segment in segments.chain(path.split('/').filter(|segment| !segment.is_empty())) {
+    url.push('/');
+    url.push_str(segment);
+  }
+  Ok(url)
+}
+
+fn maven_segment(value: &str, label: &str) -> Result<String, String> {
+  let value = value.trim();
+  if value.is_empty() || value == "." || value == ".." || value.contains(['/', '\\']) {
+    return Err(format!("invalid maven {} '{}'", label, value));
+  }
+  Ok(value.to_string())
+}
+
 pub(crate) fn build_maven_path_url(
   base_url: &str,
   coordinate: &MavenCoordinate,
 ) -> Result<(String, String), String> {
-  let group_path = coordinate.group.replace('.', "/");
-  let classifier = coordinate
-    .classifier
-    .as_ref()
-    .map(|value| format!("-{}", value))
-    .unwrap_or_default();
-  let file_name = format!(
-    "{}-{}{}.jar",
-    coordinate.artifact, coordinate.version, classifier
-  );
-  let path = format!(
-    "{}/{}/{}/{}",
-    group_path, coordinate.artifact, coordinate.version, file_name
-  );
-  let base = if base_url.ends_with('/') {
-    base_url.to_string()
+  let mut segments = coordinate
+    .group
+    .split('.')
+    .map(|part| maven_segment(part, "group"))
+    .collect::<Result<Vec<_>, _>>()?;
+  let artifact = maven_segment(&coordinate.artifact, "artifact")?;
+  let version = maven_segment(&coordinate.version, "version")?;
+  let classifier = match coordinate.classifier.as_deref() {
+    Some(value) => format!("-{}", maven_segment(value, "classifier")?),
+    None => String::new(),
+  };
+  let extension = maven_segment(&coordinate.extension, "extension")?;
+  let file_name = format!("{}-{}{}.{}", artifact, version, classifier, extension);
+  segments.extend([artifact, version, file_name]);
+  let path = segments.join("/");
+  let encoded = segments
+    .iter()
+    .map(|segment| urlencoding::encode(segment).into_owned())
+    .collect::<Vec<_>>()
+    .join("/");
+  let base_url = if base_url.trim().is_empty() {
+    DEFAULT_LIBRARIES_URL
   } else {
-    format!("{}/", base_url)
+    base_url
   };
-  Ok((path.clone(), format!("{}{}", base, path)))
+  Ok((path, join_url(base_url, &encoded)?))
 }
 
 pub(crate) fn resolve_library_artifact(
@@ -154,7 +199,7 @@ pub(crate) fn resolve_library_artifact(
     let url = artifact
       .url
       .clone()
-      .unwrap_or_else(|| format!("{}{}", DEFAULT_LIBRARIES_URL, path));
+      .or_else(|| join_url(DEFAULT_LIBRARIES_URL, path).ok())?;
     return Some((url, path.clone()));
   }
 
@@ -170,6 +215,20 @@ pub(crate) fn resolve_library_artifact(
   None
 }
 
+pub(crate) fn resolve_legacy_assets_dir(instance_dir: &Path, index_id: &str) -> Option<PathBuf> {
+  let index_path = instance_dir
+    .join("assets/indexes")
+    .join(format!("{}.json", index_id));
+  let index: MojangAssetIndexFile = load_json(&index_path).ok()?;
+  if index.map_to_resources {
+    return Some(instance_dir.join("resources"));
+  }
+  if index.virtual_assets {
+    return Some(instance_dir.join("assets/virtual").join(index_id));
+  }
+  None
+}
+
 pub(crate) fn classpath_separator() -> &'static str {
   if cfg!(windows) {
     ";"
@@ -201,3 +260,149 @@ pub(crate) fn slugify(name: &str) -> String {
     trimmed
   }
 }
+
+const WINDOWS_RESERVED_NAMES: [&str; 24] = [
+  "CON", "PRN", "AUX", "NUL", "COM0", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
+  "COM8", "COM9", "LPT0", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+/// Most filesystems cap a single path component at 255 bytes.
+const MAX_PATH_COMPONENT_BYTES: usize = 255;
+
+pub(crate) fn sanitize_path_component(name: &str, fallback: &str) -> String {
+  let replaced: String = name
+    .trim()
+    .chars()
+    .map(|ch| match ch {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+      ch if ch.is_control() => '-',
+      ch => ch,
+    })
+    .collect();
+  let mut end = replaced.len().min(MAX_PATH_COMPONENT_BYTES - 1);
+  while !replaced.is_char_boundary(end) {
+    end -= 1;
+  }
+  let cleaned = replaced[..end].trim_end_matches(['.', ' ']).trim();
+  if cleaned.is_empty() {
+    return fallback.to_string();
+  }
+  let stem = cleaned.split('.').next().unwrap_or(cleaned).trim_end();
+  if WINDOWS_RESERVED_NAMES
+    .iter()
+    .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+  {
+    return format!("_{}", cleaned);
+  }
+  cleaned.to_string()
+}
+
+fn backup_generation_path(path: &Path, generation: usize) -> PathBuf {
+  let mut name = path.file_name().unwrap_or_default().to_os_string();
+  if generation == 1 {
+    name.push(".bak");
+  } else {
+    name.push(format!(".bak{}", generation));
+  }
+  path.with_file_name(name)
+}
+
+/// Copies `path` to `<name>.bak` before it gets overwritten, shifting older copies to
+/// `.bak2`, `.bak3`, ... and dropping anything past `generations`.
+pub(crate) fn rotate_file_backups(path: &Path, generations: usize) -> Result<(), String> {
+  if generations == 0 || !path.is_file() {
+    return Ok(());
+  }
+  let _ = std::fs::remove_file(backup_generation_path(path, generations));
+  for generation in (1..generations).rev() {
+    let source = backup_generation_path(path, generation);
+    if source.is_file() {
+      std::fs::rename(&source, backup_generation_path(path, generation + 1))
+        .map_err(|err| err.to_string())?;
+    }
+  }
+  std::fs::copy(path, backup_generation_path(path, 1)).map_err(|err| err.to_string())?;
+  Ok(())
+}
+
+pub(crate) fn long_path(path: &Path) -> PathBuf {
+  #[cfg(windows)]
+  {
+    let raw = path.to_string_lossy();
+    if path.is_absolute() && !raw.starts_with(r"\\?\") {
+      let normalized = raw.replace('/', "\\");
+      return match normalized.strip_prefix(r"\\") {
+        Some(share) => PathBuf::from(format!(r"\\?\UNC\{}", share)),
+        None => PathBuf::from(format!(r"\\?\{}", normalized)),
+      };
+    }
+  }
+  path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{build_maven_path_url, join_url, parse_maven_coordinate, sanitize_path_component};
+
+  #[test]
+  fn join_url_normalizes_slashes() {
+    let expected = "https://maven.example.com/repo/a/b.jar";
+    assert_eq!(join_url("https://maven.example.com/repo", "a/b.jar").unwrap(), expected);
+    assert_eq!(join_url("https://maven.example.com/repo/", "/a/b.jar").unwrap(), expected);
+    assert_eq!(join_url("https://maven.example.com//repo//", "a//b.jar").unwrap(), expected);
+    assert_eq!(join_url(" https://maven.example.com/repo?x=1", "a/b.jar").unwrap(), expected);
+    assert!(join_url("ftp://maven.example.com", "a").is_err());
+    assert!(join_url("maven.example.com", "a").is_err());
+  }
+
+  #[test]
+  fn maven_urls_cover_classifiers_and_extensions() {
+    let plain = parse_maven_coordinate("org.ow2.asm:asm:9.6").unwrap();
+    let (path, url) = build_maven_path_url("https://maven.example.com/", &plain).unwrap();
+    assert_eq!(path, "org/ow2/asm/asm/9.6/asm-9.6.jar");
+    assert_eq!(url, "https://maven.example.com/org/ow2/asm/asm/9.6/asm-9.6.jar");
+
+    let classified = parse_maven_coordinate("org.lwjgl:lwjgl:3.3.3:natives-linux").unwrap();
+    let (path, _) = build_maven_path_url("", &classified).unwrap();
+    assert_eq!(path, "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar");
+
+    let zipped = parse_maven_coordinate("de.oceanlabs.mcp:mcp_config:1.20.1@zip").unwrap();
+    let (path, _) = build_maven_path_url("", &zipped).unwrap();
+    assert_eq!(path, "de/oceanlabs/mcp/mcp_config/1.20.1/mcp_config-1.20.1.zip");
+
+    let both = parse_maven_coordinate("net.minecraftforge:forge:47.2.0:installer@jar").unwrap();
+    let (path, _) = build_maven_path_url("", &both).unwrap();
+    assert_eq!(path, "net/minecraftforge/forge/47.2.0/forge-47.2.0-installer.jar");
+
+    let escaping = parse_maven_coordinate("com.example:lib:../1").unwrap();
+    assert!(build_maven_path_url("", &escaping).is_err());
+  }
+
+  #[test]
+  fn reserved_device_names_are_prefixed() {
+    assert_eq!(sanitize_path_component("CON", "x"), "_CON");
+    assert_eq!(sanitize_path_component("aux.txt", "x"), "_aux.txt");
+    assert_eq!(sanitize_path_component("com0", "x"), "_com0");
+    assert_eq!(sanitize_path_component("LPT0 ", "x"), "_LPT0");
+    assert_eq!(sanitize_path_component("CONSOLE", "x"), "CONSOLE");
+  }
+
+  #[test]
+  fn trailing_dots_and_spaces_are_trimmed() {
+    assert_eq!(sanitize_path_component("My World. . ", "x"), "My World");
+    assert_eq!(sanitize_path_component("pack...", "x"), "pack");
+    assert_eq!(sanitize_path_component(" . ", "fallback"), "fallback");
+    assert_eq!(sanitize_path_component("a/b:c", "x"), "a-b-c");
+  }
+
+  #[test]
+  fn long_names_fit_in_one_component() {
+    let long = "a".repeat(400);
+    assert_eq!(sanitize_path_component(&long, "x").len(), 254);
+    let multibyte = "é".repeat(200);
+    let cleaned = sanitize_path_component(&multibyte, "x");
+    assert!(cleaned.len() <= 255);
+    assert!(cleaned.chars().all(|ch| ch == 'é'));
+    let dotted = format!("{}.{}", "b".repeat(253), "c".repeat(10));
+    assert_eq!(sanitize_path_component(&dotted, "x"), "b".repeat(253));
+  }
+}
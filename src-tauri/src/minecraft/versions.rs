@@ -1,53 +1,286 @@
-use crate::minecraft::download::{fetch_json, fetch_text};
+use crate::config::{resolve_home_dir, Loader};
+use crate::minecraft::download::{fetch_json, fetch_text, map_json_error};
 use crate::minecraft::models::{
-  FabricGameVersion, FabricLoaderEntry, ForgeVersionSummary, LoaderVersionSummary, MojangManifest,
+  FabricGameVersion, FabricLoaderEntry, ForgeVersionSummary, LoaderAvailability,
+  LoaderVersionSummary,
+  MetadataCacheFile, MojangManifest, PaperBuilds, PaperProject, PatchNotesContent,
+  PatchNotesIndex, PurpurProject, PurpurVersion, VersionPatchNotes, VersionRangeFilter,
   VersionSummary,
 };
 use crate::minecraft::{
-  FABRIC_GAME_VERSIONS_URL, FABRIC_LOADER_URL, FORGE_INDEX_BASE, MOJANG_MANIFEST_URL,
-  NEOFORGE_MAVEN_BASE, NEOFORGE_MAVEN_METADATA_URL,
+  FABRIC_GAME_VERSIONS_URL, FABRIC_LOADER_URL, FORGE_INDEX_BASE, MOJANG_LAUNCHER_CONTENT_BASE,
+  MOJANG_MANIFEST_URL, NEOFORGE_MAVEN_BASE, NEOFORGE_MAVEN_METADATA_URL, PAPER_API_BASE,
+  PURPUR_API_BASE, QUILT_LOADER_URL,
 };
 use regex::Regex;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-pub fn list_vanilla_versions(include_snapshots: bool) -> Result<Vec<VersionSummary>, String> {
-  let manifest: MojangManifest = fetch_json(MOJANG_MANIFEST_URL)?;
-  let mut results = Vec::new();
+const METADATA_CACHE_TTL_SECS: u64 = 300;
+const METADATA_CACHE_MAX_ENTRIES: usize = 64;
 
-  for entry in manifest.versions {
-    let stable = entry.kind == "release";
-    if !include_snapshots && !stable {
-      continue;
+struct MetadataCacheEntry {
+  created_at: Instant,
+  body: String,
+}
+
+static METADATA_CACHE: OnceLock<Mutex<HashMap<String, MetadataCacheEntry>>> = OnceLock::new();
+
+fn metadata_cache() -> &'static Mutex<HashMap<String, MetadataCacheEntry>> {
+  METADATA_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn metadata_cache_path(url: &str) -> Option<PathBuf> {
+  let digest = Sha256::digest(url.as_bytes());
+  let key: String = digest.iter().take(16).map(|byte| format!("{:02x}", byte)).collect();
+  resolve_home_dir().map(|home| {
+    home
+      .join(".monolith")
+      .join("cache")
+      .join("metadata")
+      .join(format!("{}.json", key))
+  })
+}
+
+fn now_epoch_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs())
+    .unwrap_or(0)
+}
+
+fn read_disk_metadata_cache(url: &str) -> Option<MetadataCacheFile> {
+  let data = fs::read_to_string(metadata_cache_path(url)?).ok()?;
+  serde_json::from_str(&data).ok()
+}
+
+fn write_disk_metadata_cache(url: &str, body: &str) {
+  let Some(path) = metadata_cache_path(url) else {
+    return;
+  };
+  if let Some(parent) = path.parent() {
+    if fs::create_dir_all(parent).is_err() {
+      return;
     }
-    results.push(VersionSummary {
-      id: entry.id,
-      kind: entry.kind,
-      stable,
-      released: entry.release_time,
-    });
   }
+  let entry = MetadataCacheFile {
+    fetched_at: now_epoch_secs(),
+    body: body.to_string(),
+  };
+  if let Ok(payload) = serde_json::to_vec(&entry) {
+    let _ = fs::write(path, payload);
+  }
+}
 
-  Ok(results)
+fn write_memory_metadata_cache(url: &str, body: &str) {
+  let mut cache = metadata_cache()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  let ttl = Duration::from_secs(METADATA_CACHE_TTL_SECS);
+  cache.retain(|_, entry| entry.created_at.elapsed() < ttl);
+  if cache.len() >= METADATA_CACHE_MAX_ENTRIES {
+    if let Some(oldest) = cache
+      .iter()
+      .min_by_key(|(_, entry)| entry.created_at)
+      .map(|(key, _)| key.clone())
+    {
+      cache.remove(&oldest);
+    }
+  }
+  cache.insert(
+    url.to_string(),
+    MetadataCacheEntry {
+      created_at: Instant::now(),
+      body: body.to_string(),
+    },
+  );
 }
 
-pub fn list_fabric_game_versions(include_snapshots: bool) -> Result<Vec<VersionSummary>, String> {
-  let versions: Vec<FabricGameVersion> = fetch_json(FABRIC_GAME_VERSIONS_URL)?;
-  let mut results = Vec::new();
+fn fetch_text_cached(url: &str) -> Result<String, String> {
+  {
+    let cache = metadata_cache()
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(entry) = cache.get(url) {
+      if entry.created_at.elapsed() < Duration::from_secs(METADATA_CACHE_TTL_SECS) {
+        return Ok(entry.body.clone());
+      }
+    }
+  }
 
-  for entry in versions {
-    if !include_snapshots && !entry.stable {
-      continue;
+  let disk = read_disk_metadata_cache(url);
+  if let Some(entry) = &disk {
+    if now_epoch_secs().saturating_sub(entry.fetched_at) < METADATA_CACHE_TTL_SECS {
+      write_memory_metadata_cache(url, &entry.body);
+      return Ok(entry.body.clone());
+    }
+  }
+
+  match fetch_text(url) {
+    Ok(body) => {
+      write_memory_metadata_cache(url, &body);
+      write_disk_metadata_cache(url, &body);
+      Ok(body)
     }
-    results.push(VersionSummary {
+    Err(err) => disk.map(|entry| entry.body).ok_or(err),
+  }
+}
+
+fn fetch_json_cached<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+  let text = fetch_text_cached(url)?;
+  serde_json::from_str(&text).map_err(map_json_error)
+}
+
+pub fn list_vanilla_versions(
+  include_snapshots: bool,
+  include_legacy: bool,
+  filter: &VersionRangeFilter,
+) -> Result<Vec<VersionSummary>, String> {
+  let manifest: MojangManifest = fetch_json(MOJANG_MANIFEST_URL)?;
+  let results = manifest
+    .versions
+    .into_iter()
+    .filter(|entry| include_legacy || !is_legacy_version_kind(&entry.kind))
+    .map(|entry| {
+      let stable = entry.kind == "release";
+      VersionSummary {
+        id: entry.id,
+        kind: entry.kind,
+        stable,
+        released: entry.release_time,
+      }
+    })
+    .collect();
+
+  Ok(
+    apply_version_filter(results, true, filter)?
+      .into_iter()
+      .filter(|version| {
+        include_snapshots || version.stable || is_legacy_version_kind(&version.kind)
+      })
+      .collect(),
+  )
+}
+
+fn is_legacy_version_kind(kind: &str) -> bool {
+  kind == "old_beta" || kind == "old_alpha"
+}
+
+pub fn list_fabric_game_versions(
+  include_snapshots: bool,
+  filter: &VersionRangeFilter,
+) -> Result<Vec<VersionSummary>, String> {
+  let versions: Vec<FabricGameVersion> = fetch_json_cached(FABRIC_GAME_VERSIONS_URL)?;
+  let release_times: HashMap<String, Option<String>> =
+    if filter.released_after.is_some() || filter.released_before.is_some() {
+      let manifest: MojangManifest = fetch_json(MOJANG_MANIFEST_URL)?;
+      manifest
+        .versions
+        .into_iter()
+        .map(|entry| (entry.id, entry.release_time))
+        .collect()
+    } else {
+      HashMap::new()
+    };
+
+  let results = versions
+    .into_iter()
+    .map(|entry| VersionSummary {
+      released: release_times.get(&entry.version).cloned().flatten(),
       id: entry.version,
       kind: "game".to_string(),
       stable: entry.stable,
-      released: None,
-    });
+    })
+    .collect();
+
+  apply_version_filter(results, include_snapshots, filter)
+}
+
+fn apply_version_filter(
+  versions: Vec<VersionSummary>,
+  include_snapshots: bool,
+  filter: &VersionRangeFilter,
+) -> Result<Vec<VersionSummary>, String> {
+  let position = |bound: &Option<String>| -> Result<Option<usize>, String> {
+    match bound {
+      Some(id) => versions
+        .iter()
+        .position(|version| &version.id == id)
+        .map(Some)
+        .ok_or_else(|| format!("version '{}' not found", id)),
+      None => Ok(None),
+    }
+  };
+  let newest = position(&filter.max_version)?.unwrap_or(0);
+  let oldest = position(&filter.min_version)?.unwrap_or(usize::MAX);
+  if newest > oldest {
+    return Err("minimum version is newer than maximum version".to_string());
   }
 
-  Ok(results)
+  Ok(
+    versions
+      .into_iter()
+      .enumerate()
+      .filter(|(idx, _)| *idx >= newest && *idx <= oldest)
+      .map(|(_, version)| version)
+      .filter(|version| include_snapshots || version.stable)
+      .filter(|version| release_in_range(version.released.as_deref(), filter))
+      .collect(),
+  )
+}
+
+fn release_in_range(released: Option<&str>, filter: &VersionRangeFilter) -> bool {
+  if filter.released_after.is_none() && filter.released_before.is_none() {
+    return true;
+  }
+  let Some(released) = released else {
+    return false;
+  };
+  let day = released.get(..10).unwrap_or(released);
+  if let Some(after) = filter.released_after.as_deref() {
+    if day < after.get(..10).unwrap_or(after) {
+      return false;
+    }
+  }
+  if let Some(before) = filter.released_before.as_deref() {
+    if day > before.get(..10).unwrap_or(before) {
+      return false;
+    }
+  }
+  true
+}
+
+pub fn fetch_version_patch_notes(game_version: &str) -> Result<VersionPatchNotes, String> {
+  let index: PatchNotesIndex =
+    fetch_json_cached(&format!("{}/javaPatchNotes.json", MOJANG_LAUNCHER_CONTENT_BASE))?;
+  let entry = index
+    .entries
+    .into_iter()
+    .find(|entry| entry.version == game_version)
+    .ok_or_else(|| format!("no patch notes published for {}", game_version))?;
+  let content: PatchNotesContent = fetch_json_cached(&format!(
+    "{}/{}",
+    MOJANG_LAUNCHER_CONTENT_BASE,
+    entry.content_path.trim_start_matches('/')
+  ))?;
+
+  Ok(VersionPatchNotes {
+    version: entry.version,
+    title: entry.title,
+    kind: entry.kind,
+    date: entry.date,
+    short_text: entry.short_text,
+    image_url: entry
+      .image
+      .map(|image| format!("https://launchercontent.mojang.com{}", image.url)),
+    body: content.body,
+  })
 }
 
 pub fn list_fabric_loader_versions(
@@ -55,7 +288,7 @@ pub fn list_fabric_loader_versions(
   include_snapshots: bool,
 ) -> Result<Vec<LoaderVersionSummary>, String> {
   let url = format!("{}/{}", FABRIC_LOADER_URL, urlencoding::encode(game_version));
-  let entries: Vec<FabricLoaderEntry> = fetch_json(&url)?;
+  let entries: Vec<FabricLoaderEntry> = fetch_json_cached(&url)?;
   let mut results = Vec::new();
 
   for entry in entries {
@@ -73,7 +306,7 @@ pub fn list_fabric_loader_versions(
 
 pub fn list_forge_versions(game_version: &str) -> Result<Vec<ForgeVersionSummary>, String> {
   let url = format!("{}/index_{}.html", FORGE_INDEX_BASE, game_version);
-  let html = fetch_text(&url)?;
+  let html = fetch_text_cached(&url)?;
   let re = Regex::new(
     r#"/net/minecraftforge/forge/([^/]+)/forge-[^/]+-installer\.jar"#,
   )
@@ -101,7 +334,7 @@ pub fn list_forge_versions(game_version: &str) -> Result<Vec<ForgeVersionSummary
 
 pub fn list_neoforge_versions(game_version: &str) -> Result<Vec<ForgeVersionSummary>, String> {
   let channel = resolve_neoforge_channel(game_version);
-  let metadata = fetch_text(NEOFORGE_MAVEN_METADATA_URL)?;
+  let metadata = fetch_text_cached(NEOFORGE_MAVEN_METADATA_URL)?;
   let re = Regex::new(r"<version>([^<]+)</version>").map_err(|err| err.to_string())?;
 
   let mut seen = HashSet::new();
@@ -131,6 +364,168 @@ pub fn list_neoforge_versions(game_version: &str) -> Result<Vec<ForgeVersionSumm
   Ok(results)
 }
 
+/// Quilt's meta has no stability flag; pre-releases carry a `-beta.N`/`-rc.N` suffix.
+pub fn list_quilt_loader_versions(
+  game_version: &str,
+  include_snapshots: bool,
+) -> Result<Vec<LoaderVersionSummary>, String> {
+  let url = format!("{}/{}", QUILT_LOADER_URL, urlencoding::encode(game_version));
+  let entries: Vec<serde_json::Value> = fetch_json_cached(&url)?;
+  Ok(
+    entries
+      .iter()
+      .filter_map(|entry| entry.pointer("/loader/version")?.as_str())
+      .map(|version| LoaderVersionSummary {
+        version: version.to_string(),
+        stable: !version.contains('-'),
+      })
+      .filter(|summary| include_snapshots || summary.stable)
+      .collect(),
+  )
+}
+
+fn loader_availability(loader: &str, builds: Result<Vec<String>, String>) -> LoaderAvailability {
+  match builds {
+    Ok(builds) => LoaderAvailability {
+      loader: loader.to_string(),
+      available: !builds.is_empty(),
+      build_count: builds.len(),
+      latest: builds.into_iter().next(),
+      error: None,
+    },
+    // Forge has no index page at all for versions it never supported.
+    Err(err) if err.contains("status code 404") => LoaderAvailability {
+      loader: loader.to_string(),
+      available: false,
+      build_count: 0,
+      latest: None,
+      error: None,
+    },
+    Err(err) => LoaderAvailability {
+      loader: loader.to_string(),
+      available: false,
+      build_count: 0,
+      latest: None,
+      error: Some(err),
+    },
+  }
+}
+
+/// Checks Fabric, Quilt, Forge and NeoForge for builds targeting `game_version`, concurrently.
+pub fn list_supported_loaders(game_version: &str) -> Vec<LoaderAvailability> {
+  std::thread::scope(|scope| {
+    let fabric = scope.spawn(|| {
+      list_fabric_loader_versions(game_version, true)
+        .map(|builds| builds.into_iter().map(|build| build.version).collect())
+    });
+    let quilt = scope.spawn(|| {
+      list_quilt_loader_versions(game_version, true)
+        .map(|builds| builds.into_iter().map(|build| build.version).collect())
+    });
+    let forge = scope.spawn(|| {
+      list_forge_versions(game_version)
+        .map(|builds| builds.into_iter().map(|build| build.version).collect())
+    });
+    let neoforge = scope.spawn(|| {
+      list_neoforge_versions(game_version)
+        .map(|builds| builds.into_iter().map(|build| build.version).collect())
+    });
+    [("fabric", fabric), ("quilt", quilt), ("forge", forge), ("neoforge", neoforge)]
+      .into_iter()
+      .map(|(loader, handle)| {
+        let builds = handle
+          .join()
+          .unwrap_or_else(|_| Err(format!("{} lookup panicked", loader)));
+        loader_availability(loader, builds)
+      })
+      .collect()
+  })
+}
+
+pub fn list_server_game_versions(loader: &Loader) -> Result<Vec<VersionSummary>, String> {
+  let mut versions = match loader {
+    Loader::Paper | Loader::Folia => {
+      let project: PaperProject =
+        fetch_json(&format!("{}/{}", PAPER_API_BASE, paper_project_name(loader)?))?;
+      project.versions
+    }
+    Loader::Purpur => {
+      let project: PurpurProject = fetch_json(PURPUR_API_BASE)?;
+      project.versions
+    }
+    _ => return Err("server software does not provide a build API".to_string()),
+  };
+  versions.sort_by(|a, b| compare_versions_desc(a, b));
+
+  Ok(
+    versions
+      .into_iter()
+      .map(|version| {
+        let stable = !version.contains('-');
+        VersionSummary {
+          id: version,
+          kind: if stable { "release" } else { "snapshot" }.to_string(),
+          stable,
+          released: None,
+        }
+      })
+      .collect(),
+  )
+}
+
+pub fn list_server_builds(
+  loader: &Loader,
+  game_version: &str,
+) -> Result<Vec<LoaderVersionSummary>, String> {
+  match loader {
+    Loader::Paper | Loader::Folia => {
+      let url = format!(
+        "{}/{}/versions/{}/builds",
+        PAPER_API_BASE,
+        paper_project_name(loader)?,
+        urlencoding::encode(game_version)
+      );
+      let builds: PaperBuilds = fetch_json(&url)?;
+      Ok(
+        builds
+          .builds
+          .into_iter()
+          .rev()
+          .map(|build| LoaderVersionSummary {
+            version: build.build.to_string(),
+            stable: build.channel.is_empty() || build.channel == "default",
+          })
+          .collect(),
+      )
+    }
+    Loader::Purpur => {
+      let url = format!("{}/{}", PURPUR_API_BASE, urlencoding::encode(game_version));
+      let version: PurpurVersion = fetch_json(&url)?;
+      Ok(
+        version
+          .builds
+          .all
+          .into_iter()
+          .rev()
+          .map(|build| LoaderVersionSummary {
+            version: build,
+            stable: true,
+          })
+          .collect(),
+      )
+    }
+    _ => Err("server software does not provide a build API".to_string()),
+  }
+}
+
+pub(crate) fn paper_project_name(loader: &Loader) -> Result<&'static str, String> {
+  match loader {
+    Loader::Paper => Ok("paper"),
+    Loader::Folia => Ok("folia"),
+    _ => Err("not a PaperMC project".to_string()),
+  }
+}
+
 pub(crate) fn resolve_neoforge_channel(game_version: &str) -> String {
   let trimmed = game_version.trim();
   let normalized = trimmed.strip_prefix("1.").unwrap_or(trimmed);
@@ -151,7 +546,7 @@ fn neoforge_version_matches_channel(version: &str, channel: &str) -> bool {
   version == channel || version.starts_with(&format!("{}.", channel))
 }
 
-fn compare_versions_desc(a: &str, b: &str) -> Ordering {
+pub(crate) fn compare_versions_desc(a: &str, b: &str) -> Ordering {
   let extract_numbers = |value: &str| {
     value
       .split(|ch: char| !ch.is_ascii_digit())
@@ -5,16 +5,32 @@ use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
-use tauri::State;
-
-use crate::config::ConfigStore;
-use crate::minecraft::download_to;
+use tauri::{AppHandle, State};
+
+use crate::commands::recipes::modrinth_loader;
+use crate::config::{
+  ConfigStore, Instance, InstanceKind, InstanceManifest, Loader, ModrinthSearchDefaults,
+  ReleaseChannel, Settings, INSTANCE_CONFIG_FILE,
+};
+use crate::diagnostics::create_mods_snapshot;
+use crate::events::OperationEmitter;
+use crate::minecraft::{download_cached, sanitize_path_component, ProgressEvent};
 use crate::resolve_instance_dir;
 
 const MODRINTH_BASE_URL: &str = "https://api.modrinth.com/v2";
 const MODRINTH_TIMEOUT_SECS: u64 = 20;
 const MODRINTH_CACHE_TTL_SECS: u64 = 600;
 const MODRINTH_CACHE_MAX_ENTRIES: usize = 64;
+const IRIS_PROJECT_ID: &str = "YL57xq9U";
+const OCULUS_PROJECT_ID: &str = "GchcoXML";
+const SODIUM_PROJECT_ID: &str = "AANobbMI";
+const SHADER_PRESET_PROJECTS: [&str; 2] = [SODIUM_PROJECT_ID, IRIS_PROJECT_ID];
+const FABRIC_API_PROJECT_ID: &str = "P7dR8mSH";
+const OPTIFABRIC_PROJECT_SLUG: &str = "optifabric";
+const SHADER_LOADER_JAR_PREFIXES: [&str; 3] = ["iris", "oculus", "optifine"];
+const ROLLBACK_DIR: &str = ".rollback";
+const ROLLBACK_ENTRY_FILE: &str = "rollback.json";
+const STAGED_DATAPACKS_DIR: &str = "datapacks";
 
 #[derive(Deserialize)]
 struct ModrinthSearchResponse {
@@ -48,16 +64,26 @@ struct ModrinthVersionFile {
   url: String,
   filename: String,
   primary: bool,
+  #[serde(default)]
+  size: u64,
+  #[serde(default)]
+  hashes: HashMap<String, String>,
 }
 
 #[derive(Clone, Deserialize)]
 struct ModrinthVersion {
+  #[serde(default)]
+  id: String,
   version_number: String,
   version_type: String,
   date_published: String,
+  #[serde(default)]
+  game_versions: Vec<String>,
   files: Vec<ModrinthVersionFile>,
   #[serde(default)]
   dependencies: Vec<ModrinthDependency>,
+  #[serde(default)]
+  changelog: Option<String>,
 }
 
 #[derive(Clone, Deserialize)]
@@ -69,6 +95,8 @@ struct ModrinthDependency {
 
 #[derive(Deserialize)]
 struct ModrinthProjectInfo {
+  #[serde(default)]
+  id: String,
   project_type: String,
   #[serde(default)]
   title: Option<String>,
@@ -76,13 +104,36 @@ struct ModrinthProjectInfo {
   slug: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub(crate) struct ModrinthInstallResult {
   filename: String,
   version: String,
   project_id: String,
 }
 
+#[derive(Clone, Serialize)]
+pub(crate) struct ModrinthChangelogEntry {
+  version_number: String,
+  version_type: String,
+  date_published: String,
+  changelog: String,
+}
+
+#[derive(Clone, Serialize)]
+pub(crate) struct ModrinthUpdateChangelog {
+  project_id: String,
+  title: String,
+  installed_version: Option<String>,
+  latest_version: String,
+  entries: Vec<ModrinthChangelogEntry>,
+}
+
+#[derive(Clone, Serialize)]
+struct ModrinthOperationEvent {
+  project_id: String,
+  project_type: String,
+}
+
 #[derive(Clone, Serialize)]
 pub(crate) struct ModrinthDependencyPlanItem {
   project_id: String,
@@ -95,6 +146,38 @@ pub(crate) struct ModrinthDependencyPlan {
   dependencies: Vec<ModrinthDependencyPlanItem>,
 }
 
+#[derive(Serialize)]
+pub(crate) struct ModrinthBulkUpdateResult {
+  snapshot_id: Option<String>,
+  updated: Vec<ModrinthInstallResult>,
+  failures: Vec<String>,
+}
+
+impl ModrinthBulkUpdateResult {
+  pub(crate) fn is_empty(&self) -> bool {
+    self.updated.is_empty() && self.failures.is_empty()
+  }
+
+  pub(crate) fn summary(&self) -> String {
+    format!(
+      "Updated {} mods before launch ({} failed)",
+      self.updated.len(),
+      self.failures.len()
+    )
+  }
+}
+
+#[derive(Serialize)]
+pub(crate) struct ModrinthCompatibilityReport {
+  compatible: bool,
+  version: Option<String>,
+  game_versions: Vec<String>,
+  nearest_version_id: Option<String>,
+  nearest_version: Option<String>,
+  nearest_game_versions: Vec<String>,
+  warning: Option<String>,
+}
+
 #[derive(Clone, Serialize, Deserialize, Default)]
 struct ModrinthInstallIndex {
   #[serde(default)]
@@ -104,7 +187,11 @@ struct ModrinthInstallIndex {
   #[serde(default)]
   shaders: HashMap<String, ModrinthInstallRecord>,
   #[serde(default)]
+  plugins: HashMap<String, ModrinthInstallRecord>,
+  #[serde(default)]
   datapacks: HashMap<String, HashMap<String, ModrinthInstallRecord>>,
+  #[serde(default)]
+  staged_datapacks: HashMap<String, ModrinthInstallRecord>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -112,6 +199,35 @@ struct ModrinthInstallRecord {
   filename: String,
   #[serde(default)]
   version: Option<String>,
+  #[serde(default)]
+  pinned: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModrinthRollbackEntry {
+  project_type: String,
+  #[serde(default)]
+  world_id: Option<String>,
+  record: ModrinthInstallRecord,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct ModrinthRecipeProject {
+  project_type: String,
+  project_id: String,
+  #[serde(default)]
+  version: Option<String>,
+}
+
+pub(crate) struct RecipeDownloadPlan {
+  pub(crate) files: Vec<(PathBuf, u64)>,
+  pub(crate) failures: Vec<String>,
+}
+
+struct ShaderLoaderProject {
+  project_id: &'static str,
+  title: &'static str,
+  loader: &'static str,
 }
 
 #[derive(Clone)]
@@ -120,9 +236,51 @@ struct ModrinthCacheEntry {
   hits: Vec<ModrinthProjectHit>,
 }
 
+static CONTENT_BLACKLIST: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
 static MODRINTH_SEARCH_CACHE: OnceLock<Mutex<HashMap<String, ModrinthCacheEntry>>> =
   OnceLock::new();
 
+fn content_blacklist_slot() -> &'static Mutex<HashSet<String>> {
+  CONTENT_BLACKLIST.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+pub(crate) fn apply_content_blacklist(settings: &Settings) {
+  let mut blacklist = content_blacklist_slot()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  *blacklist = settings
+    .content_blacklist
+    .iter()
+    .map(|entry| entry.to_ascii_lowercase())
+    .collect();
+}
+
+pub(crate) fn is_content_blacklisted(id: &str) -> bool {
+  content_blacklist_slot()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .contains(&id.to_ascii_lowercase())
+}
+
+fn project_blacklisted(project_id: &str, info: &ModrinthProjectInfo) -> bool {
+  is_content_blacklisted(project_id)
+    || info.slug.as_deref().map(is_content_blacklisted).unwrap_or(false)
+}
+
+pub(crate) fn blacklisted_mod_files(instance_dir: &Path) -> HashSet<String> {
+  load_modrinth_index(instance_dir)
+    .map(|installs| {
+      installs
+        .mods
+        .into_iter()
+        .filter(|(project_id, _)| is_content_blacklisted(project_id))
+        .map(|(_, record)| record.filename)
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn encode_json_param<T: Serialize>(value: &T) -> Result<String, String> {
   let json = serde_json::to_string(value).map_err(|err| err.to_string())?;
   Ok(urlencoding::encode(&json).into_owned())
@@ -251,6 +409,20 @@ fn load_modrinth_index(instance_dir: &Path) -> Result<ModrinthInstallIndex, Stri
   serde_json::from_str(&data).map_err(|err| err.to_string())
 }
 
+/// Maps installed mod filenames to their Modrinth project ids.
+pub(crate) fn modrinth_mod_projects(
+  instance_dir: &Path,
+) -> Result<HashMap<String, String>, String> {
+  let installs = load_modrinth_index(instance_dir)?;
+  Ok(
+    installs
+      .mods
+      .into_iter()
+      .map(|(project_id, record)| (record.filename, project_id))
+      .collect(),
+  )
+}
+
 fn save_modrinth_index(
   instance_dir: &Path,
   installs: &ModrinthInstallIndex,
@@ -270,6 +442,74 @@ fn remove_previous_file(target_dir: &Path, record: Option<ModrinthInstallRecord>
   }
 }
 
+fn rollback_dir(instance_dir: &Path, project_id: &str) -> PathBuf {
+  instance_dir
+    .join(ROLLBACK_DIR)
+    .join(sanitize_path_component(project_id, "project"))
+}
+
+fn stash_for_rollback(
+  instance_dir: &Path,
+  target_dir: &Path,
+  project_type: &str,
+  project_id: &str,
+  world_id: Option<&str>,
+  record: &ModrinthInstallRecord,
+) -> Result<(), String> {
+  let source = target_dir.join(&record.filename);
+  if record.filename.is_empty() || !source.is_file() {
+    return Ok(());
+  }
+  let dir = rollback_dir(instance_dir, project_id);
+  if dir.exists() {
+    fs::remove_dir_all(&dir).map_err(|err| err.to_string())?;
+  }
+  fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+  fs::rename(&source, dir.join(&record.filename)).map_err(|err| err.to_string())?;
+  let entry = ModrinthRollbackEntry {
+    project_type: project_type.to_string(),
+    world_id: world_id.map(|value| value.to_string()),
+    record: record.clone(),
+  };
+  let payload = serde_json::to_vec_pretty(&entry).map_err(|err| err.to_string())?;
+  fs::write(dir.join(ROLLBACK_ENTRY_FILE), payload).map_err(|err| err.to_string())
+}
+
+fn restore_from_rollback(
+  instance_dir: &Path,
+  project_id: &str,
+  installs: &mut ModrinthInstallIndex,
+) -> Result<ModrinthInstallResult, String> {
+  let dir = rollback_dir(instance_dir, project_id);
+  let data = fs::read_to_string(dir.join(ROLLBACK_ENTRY_FILE))
+    .map_err(|_| "no previous version is available to roll back to".to_string())?;
+  let entry: ModrinthRollbackEntry = serde_json::from_str(&data).map_err(|err| err.to_string())?;
+  let stashed = dir.join(&entry.record.filename);
+  if !stashed.is_file() {
+    return Err("rollback file is missing".to_string());
+  }
+  let world_id = entry.world_id.as_deref();
+  let target_dir = resolve_target_dir(instance_dir, &entry.project_type, world_id)?;
+  fs::create_dir_all(&target_dir).map_err(|err| err.to_string())?;
+  remove_install_record(installs, &entry.project_type, project_id, world_id, &target_dir);
+  fs::rename(&stashed, target_dir.join(&entry.record.filename)).map_err(|err| err.to_string())?;
+  let result = ModrinthInstallResult {
+    filename: entry.record.filename.clone(),
+    version: entry.record.version.clone().unwrap_or_default(),
+    project_id: project_id.to_string(),
+  };
+  record_install(
+    installs,
+    &entry.project_type,
+    project_id.to_string(),
+    world_id,
+    entry.record,
+    &target_dir,
+  );
+  let _ = fs::remove_dir_all(&dir);
+  Ok(result)
+}
+
 fn build_search_url(
   query: &str,
   project_type: &str,
@@ -283,9 +523,14 @@ fn build_search_url(
   if !game_version.is_empty() {
     facets.push(vec![format!("versions:{}", game_version)]);
   }
-  if project_type == "mod" {
+  if project_type == "mod" || project_type == "plugin" {
     if let Some(loader) = loader {
-      facets.push(vec![format!("categories:{}", loader)]);
+      facets.push(
+        compatible_loaders(loader)
+          .iter()
+          .map(|value| format!("categories:{}", value))
+          .collect(),
+      );
     }
   }
   if let Some(extra) = extra_facets {
@@ -309,14 +554,65 @@ fn build_search_url(
   Ok(url)
 }
 
-fn resolve_loader_filter(project_type: &str, loader: Option<&str>) -> Option<String> {
+fn resolve_loader_filter(project_type: &str, loader: Option<&str>) -> Option<Vec<String>> {
   match project_type {
-    "resourcepack" => Some("minecraft".to_string()),
-    "mod" => loader.map(|value| value.to_string()),
+    "resourcepack" => Some(vec!["minecraft".to_string()]),
+    "mod" | "plugin" => loader.map(compatible_loaders),
     _ => None,
   }
 }
 
+/// Modrinth loader tags whose files run on `loader`; Quilt also loads Fabric mods.
+fn compatible_loaders(loader: &str) -> Vec<String> {
+  match loader {
+    "quilt" => vec!["quilt".to_string(), "fabric".to_string()],
+    other => vec![other.to_string()],
+  }
+}
+
+fn resolve_dependency_project_type(parent_type: &str, dependency_type: String) -> String {
+  if parent_type == "plugin" && dependency_type == "mod" {
+    "plugin".to_string()
+  } else {
+    dependency_type
+  }
+}
+
+/// Whether a game version predates 1.6 (13w24a), when resource packs replaced texture packs.
+fn uses_texture_packs(game_version: &str) -> bool {
+  let version = game_version.trim();
+  if ["a", "b", "c", "rd-", "inf-"].iter().any(|prefix| version.starts_with(prefix)) {
+    return true;
+  }
+  if let Some(rest) = version.strip_prefix("1.") {
+    return rest
+      .split(|ch: char| !ch.is_ascii_digit())
+      .next()
+      .and_then(|minor| minor.parse::<u32>().ok())
+      .map(|minor| minor < 6)
+      .unwrap_or(false);
+  }
+  let mut snapshot = version.splitn(2, 'w');
+  match (
+    snapshot.next().and_then(|year| year.parse::<u32>().ok()),
+    snapshot
+      .next()
+      .and_then(|rest| rest.get(..2))
+      .and_then(|week| week.parse::<u32>().ok()),
+  ) {
+    (Some(year), Some(week)) => year < 13 || (year == 13 && week < 24),
+    _ => false,
+  }
+}
+
+fn instance_uses_texture_packs(instance_dir: &Path) -> bool {
+  fs::read_to_string(instance_dir.join(INSTANCE_CONFIG_FILE))
+    .ok()
+    .and_then(|data| serde_json::from_str::<InstanceManifest>(&data).ok())
+    .map(|manifest| uses_texture_packs(&manifest.version))
+    .unwrap_or(false)
+}
+
 fn resolve_target_dir(
   instance_dir: &Path,
   project_type: &str,
@@ -324,16 +620,57 @@ fn resolve_target_dir(
 ) -> Result<PathBuf, String> {
   match project_type {
     "mod" => Ok(instance_dir.join("mods")),
+    "resourcepack" if instance_uses_texture_packs(instance_dir) => {
+      Ok(instance_dir.join("texturepacks"))
+    }
     "resourcepack" => Ok(instance_dir.join("resourcepacks")),
     "shader" => Ok(instance_dir.join("shaderpacks")),
-    "datapack" => {
-      let world = world_id.ok_or_else(|| "world id is required for datapacks".to_string())?;
-      Ok(instance_dir.join("saves").join(world).join("datapacks"))
-    }
+    "plugin" => Ok(instance_dir.join("plugins")),
+    "datapack" => match world_id {
+      Some(world) => Ok(instance_dir.join("saves").join(world).join("datapacks")),
+      None => Ok(instance_dir.join(STAGED_DATAPACKS_DIR)),
+    },
     _ => Err("unsupported Modrinth project type".to_string()),
   }
 }
 
+/// Datapack records for `world_id`, or the staging folder when no world is given.
+fn datapack_records<'a>(
+  installs: &'a ModrinthInstallIndex,
+  world_id: Option<&str>,
+) -> Option<&'a HashMap<String, ModrinthInstallRecord>> {
+  match world_id {
+    Some(world) => installs.datapacks.get(world),
+    None => Some(&installs.staged_datapacks),
+  }
+}
+
+fn datapack_records_mut<'a>(
+  installs: &'a mut ModrinthInstallIndex,
+  world_id: Option<&str>,
+) -> &'a mut HashMap<String, ModrinthInstallRecord> {
+  match world_id {
+    Some(world) => installs.datapacks.entry(world.to_string()).or_default(),
+    None => &mut installs.staged_datapacks,
+  }
+}
+
+fn take_datapack_record(
+  installs: &mut ModrinthInstallIndex,
+  project_id: &str,
+  world_id: Option<&str>,
+) -> Option<ModrinthInstallRecord> {
+  let Some(world) = world_id else {
+    return installs.staged_datapacks.remove(project_id);
+  };
+  let entry = installs.datapacks.get_mut(world)?;
+  let record = entry.remove(project_id);
+  if entry.is_empty() {
+    installs.datapacks.remove(world);
+  }
+  record
+}
+
 fn install_record_exists(
   instance_dir: &Path,
   project_type: &str,
@@ -355,14 +692,36 @@ fn get_install_record(
     "mod" => installs.mods.get(project_id).cloned(),
     "resourcepack" => installs.resources.get(project_id).cloned(),
     "shader" => installs.shaders.get(project_id).cloned(),
-    "datapack" => world_id
-      .and_then(|world| installs.datapacks.get(world))
+    "plugin" => installs.plugins.get(project_id).cloned(),
+    "datapack" => datapack_records(installs, world_id)
       .and_then(|map| map.get(project_id))
       .cloned(),
     _ => None,
   }
 }
 
+fn get_install_record_mut<'a>(
+  installs: &'a mut ModrinthInstallIndex,
+  project_type: &str,
+  project_id: &str,
+  world_id: Option<&str>,
+) -> Option<&'a mut ModrinthInstallRecord> {
+  match project_type {
+    "mod" => installs.mods.get_mut(project_id),
+    "resourcepack" => installs.resources.get_mut(project_id),
+    "shader" => installs.shaders.get_mut(project_id),
+    "plugin" => installs.plugins.get_mut(project_id),
+    "datapack" => match world_id {
+      Some(world) => installs
+        .datapacks
+        .get_mut(world)
+        .and_then(|map| map.get_mut(project_id)),
+      None => installs.staged_datapacks.get_mut(project_id),
+    },
+    _ => None,
+  }
+}
+
 fn record_install(
   installs: &mut ModrinthInstallIndex,
   project_type: &str,
@@ -384,12 +743,13 @@ fn record_install(
       let prev = installs.shaders.insert(project_id, record);
       remove_previous_file(target_dir, prev);
     }
+    "plugin" => {
+      let prev = installs.plugins.insert(project_id, record);
+      remove_previous_file(target_dir, prev);
+    }
     "datapack" => {
-      if let Some(world) = world_id {
-        let entry = installs.datapacks.entry(world.to_string()).or_default();
-        let prev = entry.insert(project_id, record);
-        remove_previous_file(target_dir, prev);
-      }
+      let prev = datapack_records_mut(installs, world_id).insert(project_id, record);
+      remove_previous_file(target_dir, prev);
     }
     _ => {}
   }
@@ -406,33 +766,85 @@ fn remove_install_record(
     "mod" => installs.mods.remove(project_id),
     "resourcepack" => installs.resources.remove(project_id),
     "shader" => installs.shaders.remove(project_id),
-    "datapack" => {
-      if let Some(world) = world_id {
-        let removed = installs
-          .datapacks
-          .get_mut(world)
-          .and_then(|map| map.remove(project_id));
-        let should_prune = installs
-          .datapacks
-          .get(world)
-          .map(|map| map.is_empty())
-          .unwrap_or(false);
-        if should_prune {
-          installs.datapacks.remove(world);
-        }
-        removed
-      } else {
-        None
-      }
-    }
+    "plugin" => installs.plugins.remove(project_id),
+    "datapack" => take_datapack_record(installs, project_id, world_id),
     _ => None,
   };
   remove_previous_file(target_dir, record);
 }
 
-fn fetch_project_type(project_id: &str) -> Result<String, String> {
-  let info = fetch_project_info(project_id)?;
-  Ok(info.project_type)
+fn resolve_instance_loader(
+  instance_id: &str,
+  state: &State<'_, Mutex<ConfigStore>>,
+) -> Result<Loader, String> {
+  let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+  store
+    .get()
+    .instances
+    .into_iter()
+    .find(|item| item.id == instance_id)
+    .map(|instance| instance.loader)
+    .ok_or_else(|| "instance not found".to_string())
+}
+
+fn resolve_shader_loader_project(loader: &Loader) -> Option<ShaderLoaderProject> {
+  match loader {
+    Loader::Fabric => Some(ShaderLoaderProject {
+      project_id: IRIS_PROJECT_ID,
+      title: "Iris Shaders",
+      loader: "fabric",
+    }),
+    Loader::Quilt => Some(ShaderLoaderProject {
+      project_id: IRIS_PROJECT_ID,
+      title: "Iris Shaders",
+      loader: "quilt",
+    }),
+    Loader::NeoForge => Some(ShaderLoaderProject {
+      project_id: IRIS_PROJECT_ID,
+      title: "Iris Shaders",
+      loader: "neoforge",
+    }),
+    Loader::Forge => Some(ShaderLoaderProject {
+      project_id: OCULUS_PROJECT_ID,
+      title: "Oculus",
+      loader: "forge",
+    }),
+    _ => None,
+  }
+}
+
+fn shader_loader_present(instance_dir: &Path, installs: &ModrinthInstallIndex) -> bool {
+  let recorded = [IRIS_PROJECT_ID, OCULUS_PROJECT_ID].iter().any(|project_id| {
+    get_install_record(installs, "mod", project_id, None)
+      .map(|record| install_record_exists(instance_dir, "mod", None, &record))
+      .unwrap_or(false)
+  });
+  if recorded {
+    return true;
+  }
+  fs::read_dir(instance_dir.join("mods"))
+    .map(|entries| {
+      entries.flatten().any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+        name.ends_with(".jar")
+          && SHADER_LOADER_JAR_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+      })
+    })
+    .unwrap_or(false)
+}
+
+fn missing_shader_loader(
+  instance_dir: &Path,
+  loader: &Loader,
+  installs: &ModrinthInstallIndex,
+) -> Option<ShaderLoaderProject> {
+  let project = resolve_shader_loader_project(loader)?;
+  if is_content_blacklisted(project.project_id) || shader_loader_present(instance_dir, installs) {
+    return None;
+  }
+  Some(project)
 }
 
 fn fetch_project_info(project_id: &str) -> Result<ModrinthProjectInfo, String> {
@@ -445,7 +857,43 @@ fn fetch_version_by_id(version_id: &str) -> Result<ModrinthVersion, String> {
   fetch_modrinth_json(&url)
 }
 
-fn select_version<'a>(versions: &'a [ModrinthVersion]) -> Option<&'a ModrinthVersion> {
+fn fetch_project_version(
+  project_id: &str,
+  version_number: &str,
+) -> Result<ModrinthVersion, String> {
+  let url = format!(
+    "{}/project/{}/version/{}",
+    MODRINTH_BASE_URL,
+    project_id,
+    urlencoding::encode(version_number)
+  );
+  fetch_modrinth_json(&url)
+}
+
+fn instance_release_channel(instance_dir: &Path) -> Option<ReleaseChannel> {
+  let data = fs::read_to_string(instance_dir.join(INSTANCE_CONFIG_FILE)).ok()?;
+  serde_json::from_str::<InstanceManifest>(&data).ok()?.release_channel
+}
+
+fn instance_search_defaults(instance_dir: &Path, project_type: &str) -> ModrinthSearchDefaults {
+  fs::read_to_string(instance_dir.join(INSTANCE_CONFIG_FILE))
+    .ok()
+    .and_then(|data| serde_json::from_str::<InstanceManifest>(&data).ok())
+    .and_then(|mut manifest| manifest.modrinth_search_defaults.remove(project_type))
+    .unwrap_or_default()
+}
+
+/// Newest version the channel allows; without a channel, releases win over other types.
+fn select_version(
+  versions: &[ModrinthVersion],
+  channel: Option<ReleaseChannel>,
+) -> Option<&ModrinthVersion> {
+  if let Some(channel) = channel {
+    return versions
+      .iter()
+      .filter(|version| channel.allows(&version.version_type))
+      .max_by(|a, b| a.date_published.cmp(&b.date_published));
+  }
   if versions.is_empty() {
     return None;
   }
@@ -461,6 +909,41 @@ fn select_version<'a>(versions: &'a [ModrinthVersion]) -> Option<&'a ModrinthVer
     .max_by(|a, b| a.date_published.cmp(&b.date_published))
 }
 
+fn game_version_distance(a: &str, b: &str) -> u64 {
+  let weight = |value: &str| {
+    value
+      .split(|ch: char| !ch.is_ascii_digit())
+      .filter(|chunk| !chunk.is_empty())
+      .filter_map(|chunk| chunk.parse::<u64>().ok())
+      .chain(std::iter::repeat(0))
+      .take(3)
+      .fold(0u64, |acc, part| acc * 1000 + part.min(999))
+  };
+  weight(a).abs_diff(weight(b))
+}
+
+fn select_nearest_version<'a>(
+  versions: &'a [ModrinthVersion],
+  game_version: &str,
+) -> Option<&'a ModrinthVersion> {
+  versions
+    .iter()
+    .filter_map(|version| {
+      version
+        .game_versions
+        .iter()
+        .map(|candidate| game_version_distance(candidate, game_version))
+        .min()
+        .map(|distance| (distance, version))
+    })
+    .min_by(|(a_distance, a), (b_distance, b)| {
+      a_distance
+        .cmp(b_distance)
+        .then_with(|| b.date_published.cmp(&a.date_published))
+    })
+    .map(|(_, version)| version)
+}
+
 fn select_file<'a>(version: &'a ModrinthVersion) -> Option<&'a ModrinthVersionFile> {
   version
     .files
@@ -470,15 +953,50 @@ fn select_file<'a>(version: &'a ModrinthVersion) -> Option<&'a ModrinthVersionFi
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn search_modrinth_projects(
   query: String,
   project_type: String,
-  game_version: String,
+  game_version: Option<String>,
   loader: Option<String>,
   limit: Option<u32>,
   sort: Option<String>,
   extra_facets: Option<Vec<Vec<String>>>,
+  instance_id: Option<String>,
+  state: State<'_, Mutex<ConfigStore>>,
 ) -> Result<Vec<ModrinthProjectHit>, String> {
+  let (game_version, loader, sort, extra_facets) = match instance_id.as_deref() {
+    Some(instance_id) => {
+      let instance = {
+        let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+        store
+          .get()
+          .instances
+          .into_iter()
+          .find(|item| item.id == instance_id)
+          .ok_or_else(|| "instance not found".to_string())?
+      };
+      let instance_dir = resolve_instance_dir(instance_id, &state)?;
+      let defaults = instance_search_defaults(&instance_dir, &project_type);
+      let extra_facets = extra_facets.or_else(|| {
+        if defaults.categories.is_empty() {
+          return None;
+        }
+        Some(vec![defaults
+          .categories
+          .iter()
+          .map(|category| format!("categories:{}", category))
+          .collect()])
+      });
+      (
+        game_version.unwrap_or(instance.version),
+        loader.or_else(|| modrinth_loader(&instance.loader)),
+        sort.or(defaults.sort),
+        extra_facets,
+      )
+    }
+    None => (game_version.unwrap_or_default(), loader, sort, extra_facets),
+  };
   tauri::async_runtime::spawn_blocking(move || {
     let resolved_limit = limit.unwrap_or(8);
     let cache_key = build_modrinth_cache_key(
@@ -532,12 +1050,46 @@ pub(crate) async fn install_modrinth_project(
   loader: Option<String>,
   world_id: Option<String>,
   install_dependencies: Option<bool>,
+  version_id: Option<String>,
+  app: AppHandle,
   state: State<'_, Mutex<ConfigStore>>,
 ) -> Result<ModrinthInstallResult, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
-  tauri::async_runtime::spawn_blocking(move || {
+  let instance_loader = resolve_instance_loader(&instance_id, &state)?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  ops.emit(
+    "modrinth:started",
+    ModrinthOperationEvent {
+      project_id: project_id.clone(),
+      project_type: project_type.clone(),
+    },
+  );
+  let result: Result<ModrinthInstallResult, String> = tauri::async_runtime::spawn_blocking(move || {
     let mut installs = load_modrinth_index(&instance_dir)?;
     let mut visited = HashSet::new();
+    let install_dependencies = install_dependencies.unwrap_or(true);
+    // Dependency resolution can replace jars that are already installed.
+    if project_type == "mod" && install_dependencies && !installs.mods.is_empty() {
+      let reason = format!("Before importing {}", project_id);
+      create_mods_snapshot(&instance_dir, Some(reason), true)?;
+    }
+    if project_type == "shader" && install_dependencies {
+      let shader_loader = missing_shader_loader(&instance_dir, &instance_loader, &installs);
+      if let Some(shader_loader) = shader_loader {
+        install_modrinth_internal(
+          &instance_dir,
+          shader_loader.project_id,
+          "mod",
+          &game_version,
+          Some(shader_loader.loader),
+          None,
+          None,
+          true,
+          &mut installs,
+          &mut visited,
+        )?;
+      }
+    }
     let result = install_modrinth_internal(
       &instance_dir,
       &project_id,
@@ -545,8 +1097,8 @@ pub(crate) async fn install_modrinth_project(
       &game_version,
       loader.as_deref(),
       world_id.as_deref(),
-      None,
-      install_dependencies.unwrap_or(true),
+      version_id.as_deref(),
+      install_dependencies,
       &mut installs,
       &mut visited,
     )?;
@@ -554,7 +1106,88 @@ pub(crate) async fn install_modrinth_project(
     Ok(result)
   })
   .await
-  .map_err(|_| "Modrinth install task failed".to_string())?
+  .map_err(|_| "Modrinth install task failed".to_string())?;
+  match &result {
+    Ok(installed) => ops.emit("modrinth:done", installed.clone()),
+    Err(err) => ops.emit("modrinth:error", err.clone()),
+  }
+  result
+}
+
+/// One-click "shader-ready" preset: Sodium and Iris with their required dependencies,
+/// resolved for the instance's game version, plus an empty `shaderpacks` folder.
+#[tauri::command]
+pub(crate) async fn install_shader_preset(
+  instance_id: String,
+  app: AppHandle,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ModrinthInstallResult>, String> {
+  let instance = {
+    let store = state.lock().map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .find(|item| item.id == instance_id)
+      .ok_or_else(|| "instance not found".to_string())?
+  };
+  if instance.kind == InstanceKind::Server {
+    return Err("the shader preset is only available for client instances".to_string());
+  }
+  if !matches!(instance.loader, Loader::Fabric | Loader::Quilt) {
+    return Err("the shader preset requires a Fabric or Quilt instance".to_string());
+  }
+  let loader = modrinth_loader(&instance.loader);
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  let progress_ops = ops.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let mut installs = load_modrinth_index(&instance_dir)?;
+    let mut visited = HashSet::new();
+    let mut installed = Vec::new();
+    for project_id in SHADER_PRESET_PROJECTS {
+      progress_ops.emit(
+        "modrinth:started",
+        ModrinthOperationEvent {
+          project_id: project_id.to_string(),
+          project_type: "mod".to_string(),
+        },
+      );
+      let result = install_modrinth_internal(
+        &instance_dir,
+        project_id,
+        "mod",
+        &instance.version,
+        loader.as_deref(),
+        None,
+        None,
+        true,
+        &mut installs,
+        &mut visited,
+      );
+      // Keep what already landed on disk in the index even if a later project fails.
+      match result {
+        Ok(record) => {
+          progress_ops.emit("modrinth:done", record.clone());
+          installed.push(record);
+        }
+        Err(err) => {
+          save_modrinth_index(&instance_dir, &installs)?;
+          return Err(format!("{}: {}", project_id, err));
+        }
+      }
+    }
+    save_modrinth_index(&instance_dir, &installs)?;
+    fs::create_dir_all(instance_dir.join("shaderpacks")).map_err(|err| err.to_string())?;
+    Ok::<_, String>(installed)
+  })
+  .await
+  .map_err(|_| "Modrinth install task failed".to_string())?;
+  // Each project already reported its own `modrinth:done` above.
+  if let Err(err) = &result {
+    ops.emit("modrinth:error", err.clone());
+  }
+  result
 }
 
 #[tauri::command]
@@ -565,37 +1198,417 @@ pub(crate) async fn update_modrinth_project(
   game_version: String,
   loader: Option<String>,
   world_id: Option<String>,
+  app: AppHandle,
   state: State<'_, Mutex<ConfigStore>>,
 ) -> Result<ModrinthInstallResult, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
-  tauri::async_runtime::spawn_blocking(move || {
-    let mut installs = load_modrinth_index(&instance_dir)?;
-    let target_dir = resolve_target_dir(&instance_dir, &project_type, world_id.as_deref())?;
-    remove_install_record(
-      &mut installs,
-      &project_type,
-      &project_id,
-      world_id.as_deref(),
-      &target_dir,
-    );
-    let mut visited = HashSet::new();
-    let result = install_modrinth_internal(
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  ops.emit(
+    "modrinth:started",
+    ModrinthOperationEvent {
+      project_id: project_id.clone(),
+      project_type: project_type.clone(),
+    },
+  );
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    update_modrinth_internal(
       &instance_dir,
       &project_id,
       &project_type,
       &game_version,
       loader.as_deref(),
       world_id.as_deref(),
-      None,
-      true,
+    )
+  })
+  .await
+  .map_err(|_| "Modrinth update task failed".to_string())?;
+  match &result {
+    Ok(installed) => ops.emit("modrinth:done", installed.clone()),
+    Err(err) => ops.emit("modrinth:error", err.clone()),
+  }
+  result
+}
+
+#[tauri::command]
+pub(crate) async fn update_modrinth_projects(
+  instance_id: String,
+  project_ids: Vec<String>,
+  project_type: String,
+  game_version: String,
+  loader: Option<String>,
+  world_id: Option<String>,
+  archive_mods: Option<bool>,
+  app: AppHandle,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<ModrinthBulkUpdateResult, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  let progress_ops = ops.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let snapshot = if project_type == "mod" {
+      let reason = format!("Before updating {} mods", project_ids.len());
+      Some(create_mods_snapshot(&instance_dir, Some(reason), archive_mods.unwrap_or(true))?)
+    } else {
+      None
+    };
+    let mut updated = Vec::new();
+    let mut failures = Vec::new();
+    for project_id in &project_ids {
+      progress_ops.emit(
+        "modrinth:started",
+        ModrinthOperationEvent {
+          project_id: project_id.clone(),
+          project_type: project_type.clone(),
+        },
+      );
+      match update_modrinth_internal(
+        &instance_dir,
+        project_id,
+        &project_type,
+        &game_version,
+        loader.as_deref(),
+        world_id.as_deref(),
+      ) {
+        Ok(installed) => updated.push(installed),
+        Err(err) => failures.push(format!("{}: {}", project_id, err)),
+      }
+    }
+    Ok::<_, String>(ModrinthBulkUpdateResult {
+      snapshot_id: snapshot.map(|snapshot| snapshot.id),
+      updated,
+      failures,
+    })
+  })
+  .await
+  .map_err(|_| "Modrinth update task failed".to_string())?;
+  match &result {
+    Ok(summary) => ops.emit("modrinth:done", summary.updated.clone()),
+    Err(err) => ops.emit("modrinth:error", err.clone()),
+  }
+  result
+}
+
+fn update_modrinth_internal(
+  instance_dir: &Path,
+  project_id: &str,
+  project_type: &str,
+  game_version: &str,
+  loader: Option<&str>,
+  world_id: Option<&str>,
+) -> Result<ModrinthInstallResult, String> {
+  let mut installs = load_modrinth_index(instance_dir)?;
+  let previous = get_install_record(&installs, project_type, project_id, world_id);
+  if previous.as_ref().map(|record| record.pinned).unwrap_or(false) {
+    return Err("this project is pinned to its installed version".to_string());
+  }
+  let target_dir = resolve_target_dir(instance_dir, project_type, world_id)?;
+  if let Some(record) = previous.as_ref() {
+    stash_for_rollback(
+      instance_dir,
+      &target_dir,
+      project_type,
+      project_id,
+      world_id,
+      record,
+    )?;
+  }
+  remove_install_record(
+    &mut installs,
+    project_type,
+    project_id,
+    world_id,
+    &target_dir,
+  );
+  let mut visited = HashSet::new();
+  let result = install_modrinth_internal(
+    instance_dir,
+    project_id,
+    project_type,
+    game_version,
+    loader,
+    world_id,
+    None,
+    true,
+    &mut installs,
+    &mut visited,
+  );
+  let installed = match result {
+    Ok(installed) => installed,
+    Err(err) => {
+      if previous.is_some() {
+        let mut restored = load_modrinth_index(instance_dir)?;
+        let _ = restore_from_rollback(instance_dir, project_id, &mut restored);
+        save_modrinth_index(instance_dir, &restored)?;
+      }
+      return Err(err);
+    }
+  };
+  save_modrinth_index(instance_dir, &installs)?;
+  Ok(installed)
+}
+
+pub(crate) fn collect_recipe_projects(
+  instance_dir: &Path,
+) -> Result<Vec<ModrinthRecipeProject>, String> {
+  let installs = load_modrinth_index(instance_dir)?;
+  let groups = [
+    ("mod", &installs.mods),
+    ("resourcepack", &installs.resources),
+    ("shader", &installs.shaders),
+    ("plugin", &installs.plugins),
+  ];
+  let mut projects = Vec::new();
+  for (project_type, records) in groups {
+    for (project_id, record) in records {
+      projects.push(ModrinthRecipeProject {
+        project_type: project_type.to_string(),
+        project_id: project_id.clone(),
+        version: record.version.clone(),
+      });
+    }
+  }
+  projects.sort_by(|a, b| {
+    a.project_type
+      .cmp(&b.project_type)
+      .then_with(|| a.project_id.cmp(&b.project_id))
+  });
+  Ok(projects)
+}
+
+pub(crate) fn install_recipe_projects(
+  instance_dir: &Path,
+  game_version: &str,
+  loader: Option<&str>,
+  projects: &[ModrinthRecipeProject],
+  on_progress: &dyn Fn(usize, &str),
+) -> Result<Vec<String>, String> {
+  let mut installs = load_modrinth_index(instance_dir)?;
+  let mut visited = HashSet::new();
+  let mut failures = Vec::new();
+  for (index, project) in projects.iter().enumerate() {
+    on_progress(index, &project.project_id);
+    let version_id = match project.version.as_deref() {
+      Some(version_number) => {
+        match fetch_project_version(&project.project_id, version_number) {
+          Ok(version) => Some(version.id),
+          Err(err) => {
+            failures.push(format!("{} {}: {}", project.project_id, version_number, err));
+            continue;
+          }
+        }
+      }
+      None => None,
+    };
+    if let Err(err) = install_modrinth_internal(
+      instance_dir,
+      &project.project_id,
+      &project.project_type,
+      game_version,
+      loader,
+      None,
+      version_id.as_deref(),
+      false,
       &mut installs,
       &mut visited,
-    )?;
-    save_modrinth_index(&instance_dir, &installs)?;
-    Ok(result)
+    ) {
+      failures.push(format!("{}: {}", project.project_id, err));
+    }
+  }
+  save_modrinth_index(instance_dir, &installs)?;
+  Ok(failures)
+}
+
+/// Installs the newest Fabric API build for the game version into a fresh instance.
+pub(crate) fn install_fabric_api(instance_dir: &Path, game_version: &str) -> Result<(), String> {
+  install_fabric_mod(instance_dir, FABRIC_API_PROJECT_ID, game_version)
+}
+
+/// OptiFabric lets the OptiFine jar load on Fabric and Quilt.
+pub(crate) fn install_optifabric(instance_dir: &Path, game_version: &str) -> Result<(), String> {
+  // Index records are keyed by project id, so resolve the slug first.
+  let info = fetch_project_info(OPTIFABRIC_PROJECT_SLUG)?;
+  let project_id = if info.id.is_empty() { OPTIFABRIC_PROJECT_SLUG } else { info.id.as_str() };
+  install_fabric_mod(instance_dir, project_id, game_version)
+}
+
+fn install_fabric_mod(
+  instance_dir: &Path,
+  project_id: &str,
+  game_version: &str,
+) -> Result<(), String> {
+  let mut installs = load_modrinth_index(instance_dir)?;
+  let mut visited = HashSet::new();
+  install_modrinth_internal(
+    instance_dir,
+    project_id,
+    "mod",
+    game_version,
+    Some("fabric"),
+    None,
+    None,
+    false,
+    &mut installs,
+    &mut visited,
+  )?;
+  save_modrinth_index(instance_dir, &installs)
+}
+
+pub(crate) fn plan_recipe_downloads(
+  instance_dir: &Path,
+  game_version: &str,
+  loader: Option<&str>,
+  projects: &[ModrinthRecipeProject],
+) -> RecipeDownloadPlan {
+  let mut plan = RecipeDownloadPlan {
+    files: Vec::new(),
+    failures: Vec::new(),
+  };
+  let channel = instance_release_channel(instance_dir);
+  for project in projects {
+    let version = match project.version.as_deref() {
+      Some(version_number) => fetch_project_version(&project.project_id, version_number),
+      None => fetch_latest_version(
+        &project.project_id,
+        &project.project_type,
+        game_version,
+        loader,
+        channel,
+      ),
+    };
+    let resolved = version.and_then(|version| {
+      let target_dir = resolve_target_dir(instance_dir, &project.project_type, None)?;
+      select_file(&version)
+        .map(|file| {
+          let filename = sanitize_path_component(&file.filename, &project.project_id);
+          (target_dir.join(filename), file.size)
+        })
+        .ok_or_else(|| "no downloadable files for Modrinth version".to_string())
+    });
+    match resolved {
+      Ok(file) => plan.files.push(file),
+      Err(err) => plan.failures.push(format!("{}: {}", project.project_id, err)),
+    }
+  }
+  plan
+}
+
+fn fetch_latest_version(
+  project_id: &str,
+  project_type: &str,
+  game_version: &str,
+  loader: Option<&str>,
+  channel: Option<ReleaseChannel>,
+) -> Result<ModrinthVersion, String> {
+  let mut url = format!("{}/project/{}/version", MODRINTH_BASE_URL, project_id);
+  let versions_param = encode_json_param(&vec![game_version])?;
+  url.push_str(&format!("?game_versions={}", versions_param));
+  if let Some(loader_values) = resolve_loader_filter(project_type, loader) {
+    let loaders_param = encode_json_param(&loader_values)?;
+    url.push_str(&format!("&loaders={}", loaders_param));
+  }
+  let versions: Vec<ModrinthVersion> = fetch_modrinth_json(&url)?;
+  select_version(&versions, channel)
+    .cloned()
+    .ok_or_else(|| "no matching Modrinth versions".to_string())
+}
+
+fn build_compatibility_report(
+  project_id: &str,
+  project_type: &str,
+  game_version: &str,
+  loader: Option<&str>,
+  version_id: Option<&str>,
+  channel: Option<ReleaseChannel>,
+) -> Result<ModrinthCompatibilityReport, String> {
+  let mut url = format!("{}/project/{}/version", MODRINTH_BASE_URL, project_id);
+  if let Some(loader_values) = resolve_loader_filter(project_type, loader) {
+    let loaders_param = encode_json_param(&loader_values)?;
+    url.push_str(&format!("?loaders={}", loaders_param));
+  }
+  let versions: Vec<ModrinthVersion> = fetch_modrinth_json(&url)?;
+  let selected = match version_id {
+    Some(version_id) => versions.iter().find(|version| version.id == version_id).cloned(),
+    None => {
+      let matching = versions
+        .iter()
+        .filter(|version| version.game_versions.iter().any(|item| item == game_version))
+        .cloned()
+        .collect::<Vec<_>>();
+      select_version(&matching, channel).cloned()
+    }
+  };
+  let compatible = selected
+    .as_ref()
+    .map(|version| version.game_versions.iter().any(|item| item == game_version))
+    .unwrap_or(false);
+  if compatible {
+    return Ok(ModrinthCompatibilityReport {
+      compatible,
+      version: selected.as_ref().map(|version| version.version_number.clone()),
+      game_versions: selected.map(|version| version.game_versions).unwrap_or_default(),
+      nearest_version_id: None,
+      nearest_version: None,
+      nearest_game_versions: Vec::new(),
+      warning: None,
+    });
+  }
+
+  let nearest = select_nearest_version(&versions, game_version);
+  let warning = match (&selected, nearest) {
+    (Some(version), _) => format!(
+      "Version {} targets {} and may not work on {}.",
+      version.version_number,
+      version.game_versions.join(", "),
+      game_version
+    ),
+    (None, Some(version)) => format!(
+      "No version supports {}. The nearest is {} for {}.",
+      game_version,
+      version.version_number,
+      version.game_versions.join(", ")
+    ),
+    (None, None) => format!("No version supports {}.", game_version),
+  };
+  Ok(ModrinthCompatibilityReport {
+    compatible,
+    version: selected.as_ref().map(|version| version.version_number.clone()),
+    game_versions: selected.map(|version| version.game_versions).unwrap_or_default(),
+    nearest_version_id: nearest.map(|version| version.id.clone()),
+    nearest_version: nearest.map(|version| version.version_number.clone()),
+    nearest_game_versions: nearest
+      .map(|version| version.game_versions.clone())
+      .unwrap_or_default(),
+    warning: Some(warning),
+  })
+}
+
+#[tauri::command]
+pub(crate) async fn check_modrinth_compatibility(
+  project_id: String,
+  project_type: String,
+  game_version: String,
+  loader: Option<String>,
+  version_id: Option<String>,
+  instance_id: Option<String>,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<ModrinthCompatibilityReport, String> {
+  let instance_dir = match instance_id.as_deref() {
+    Some(instance_id) => Some(resolve_instance_dir(instance_id, &state)?),
+    None => None,
+  };
+  tauri::async_runtime::spawn_blocking(move || {
+    let channel = instance_dir.as_deref().and_then(instance_release_channel);
+    build_compatibility_report(
+      &project_id,
+      &project_type,
+      &game_version,
+      loader.as_deref(),
+      version_id.as_deref(),
+      channel,
+    )
   })
   .await
-  .map_err(|_| "Modrinth update task failed".to_string())?
+  .map_err(|_| "Modrinth compatibility task failed".to_string())?
 }
 
 fn install_modrinth_internal(
@@ -646,12 +1659,14 @@ fn install_modrinth_internal(
     let mut url = format!("{}/project/{}/version", MODRINTH_BASE_URL, project_id);
     let versions_param = encode_json_param(&vec![game_version])?;
     url.push_str(&format!("?game_versions={}", versions_param));
-    if let Some(loader_value) = resolve_loader_filter(project_type, loader) {
-      let loaders_param = encode_json_param(&vec![loader_value])?;
+    if let Some(loader_values) = resolve_loader_filter(project_type, loader) {
+      let loaders_param = encode_json_param(&loader_values)?;
       url.push_str(&format!("&loaders={}", loaders_param));
     }
     let versions: Vec<ModrinthVersion> = fetch_modrinth_json(&url)?;
-    select_version(&versions).ok_or_else(|| "no matching Modrinth versions".to_string())?.clone()
+    select_version(&versions, instance_release_channel(instance_dir))
+      .ok_or_else(|| "no matching Modrinth versions".to_string())?
+      .clone()
   };
 
   if install_dependencies {
@@ -663,14 +1678,18 @@ fn install_modrinth_internal(
         Some(project_id) => project_id,
         None => continue,
       };
-      let dep_project_type = fetch_project_type(dep_project_id)?;
+      let info = fetch_project_info(dep_project_id)?;
+      if project_blacklisted(dep_project_id, &info) {
+        continue;
+      }
+      let dep_project_type = resolve_dependency_project_type(project_type, info.project_type);
       if !matches!(
         dep_project_type.as_str(),
-        "mod" | "resourcepack" | "shader" | "datapack"
+        "mod" | "resourcepack" | "shader" | "plugin" | "datapack"
       ) {
         continue;
       }
-      if dep_project_type == "datapack" && world_id.is_none() {
+      if dep_project_type == "datapack" && world_id.is_none() && project_type != "datapack" {
         continue;
       }
       let dep_world_id = if dep_project_type == "datapack" {
@@ -695,12 +1714,16 @@ fn install_modrinth_internal(
 
   let file = select_file(&version)
     .ok_or_else(|| "no downloadable files for Modrinth version".to_string())?;
-  let destination: PathBuf = target_dir.join(&file.filename);
-  download_to(&file.url, &destination)?;
+  let filename = sanitize_path_component(&file.filename, project_id);
+  let destination: PathBuf = target_dir.join(&filename);
+  download_cached(&file.url, &destination, file.hashes.get("sha1").map(String::as_str))?;
 
   let record = ModrinthInstallRecord {
-    filename: file.filename.clone(),
+    filename: filename.clone(),
     version: Some(version.version_number.clone()),
+    pinned: get_install_record(installs, project_type, project_id, world_id)
+      .map(|record| record.pinned)
+      .unwrap_or(false),
   };
   record_install(
     installs,
@@ -712,7 +1735,7 @@ fn install_modrinth_internal(
   );
 
   Ok(ModrinthInstallResult {
-    filename: file.filename.clone(),
+    filename,
     version: version.version_number,
     project_id: project_id.to_string(),
   })
@@ -741,12 +1764,14 @@ fn collect_required_dependencies(
     let mut url = format!("{}/project/{}/version", MODRINTH_BASE_URL, project_id);
     let versions_param = encode_json_param(&vec![game_version])?;
     url.push_str(&format!("?game_versions={}", versions_param));
-    if let Some(loader_value) = resolve_loader_filter(project_type, loader) {
-      let loaders_param = encode_json_param(&vec![loader_value])?;
+    if let Some(loader_values) = resolve_loader_filter(project_type, loader) {
+      let loaders_param = encode_json_param(&loader_values)?;
       url.push_str(&format!("&loaders={}", loaders_param));
     }
     let versions: Vec<ModrinthVersion> = fetch_modrinth_json(&url)?;
-    select_version(&versions).ok_or_else(|| "no matching Modrinth versions".to_string())?.clone()
+    select_version(&versions, instance_release_channel(instance_dir))
+      .ok_or_else(|| "no matching Modrinth versions".to_string())?
+      .clone()
   };
 
   for dependency in &version.dependencies {
@@ -757,14 +1782,18 @@ fn collect_required_dependencies(
       Some(project_id) => project_id,
       None => continue,
     };
-    let info = fetch_project_info(dep_project_id)?;
+    let mut info = fetch_project_info(dep_project_id)?;
+    if project_blacklisted(dep_project_id, &info) {
+      continue;
+    }
+    info.project_type = resolve_dependency_project_type(project_type, info.project_type);
     if !matches!(
       info.project_type.as_str(),
-      "mod" | "resourcepack" | "shader" | "datapack"
+      "mod" | "resourcepack" | "shader" | "plugin" | "datapack"
     ) {
       continue;
     }
-    if info.project_type == "datapack" && world_id.is_none() {
+    if info.project_type == "datapack" && world_id.is_none() && project_type != "datapack" {
       continue;
     }
     let dep_world_id = if info.project_type == "datapack" {
@@ -815,10 +1844,21 @@ pub(crate) async fn get_modrinth_install_plan(
   state: State<'_, Mutex<ConfigStore>>,
 ) -> Result<ModrinthDependencyPlan, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let instance_loader = resolve_instance_loader(&instance_id, &state)?;
   tauri::async_runtime::spawn_blocking(move || {
     let installs = load_modrinth_index(&instance_dir)?;
     let mut visited = HashSet::new();
     let mut dependencies = Vec::new();
+    if project_type == "shader" {
+      let shader_loader = missing_shader_loader(&instance_dir, &instance_loader, &installs);
+      if let Some(shader_loader) = shader_loader {
+        dependencies.push(ModrinthDependencyPlanItem {
+          project_id: shader_loader.project_id.to_string(),
+          title: shader_loader.title.to_string(),
+          project_type: "mod".to_string(),
+        });
+      }
+    }
     collect_required_dependencies(
       &instance_dir,
       &project_id,
@@ -849,30 +1889,43 @@ fn collect_modrinth_records(
     "mod" => installs
       .mods
       .iter()
-      .filter(|(_, record)| install_record_exists(instance_dir, project_type, None, record))
+      .filter(|(_, record)| {
+        !record.pinned && install_record_exists(instance_dir, project_type, None, record)
+      })
       .map(|(id, record)| (id.clone(), record.version.clone()))
       .collect::<Vec<_>>(),
     "resourcepack" => installs
       .resources
       .iter()
-      .filter(|(_, record)| install_record_exists(instance_dir, project_type, None, record))
+      .filter(|(_, record)| {
+        !record.pinned && install_record_exists(instance_dir, project_type, None, record)
+      })
       .map(|(id, record)| (id.clone(), record.version.clone()))
       .collect::<Vec<_>>(),
     "shader" => installs
       .shaders
       .iter()
-      .filter(|(_, record)| install_record_exists(instance_dir, project_type, None, record))
+      .filter(|(_, record)| {
+        !record.pinned && install_record_exists(instance_dir, project_type, None, record)
+      })
+      .map(|(id, record)| (id.clone(), record.version.clone()))
+      .collect::<Vec<_>>(),
+    "plugin" => installs
+      .plugins
+      .iter()
+      .filter(|(_, record)| {
+        !record.pinned && install_record_exists(instance_dir, project_type, None, record)
+      })
       .map(|(id, record)| (id.clone(), record.version.clone()))
       .collect::<Vec<_>>(),
     "datapack" => {
-      let world = world_id.ok_or_else(|| "world id is required for datapacks".to_string())?;
-      installs
-        .datapacks
-        .get(world)
+      datapack_records(installs, world_id)
         .map(|entry| {
           entry
             .iter()
-            .filter(|(_, record)| install_record_exists(instance_dir, project_type, Some(world), record))
+            .filter(|(_, record)| {
+              !record.pinned && install_record_exists(instance_dir, project_type, world_id, record)
+            })
             .map(|(id, record)| (id.clone(), record.version.clone()))
             .collect::<Vec<_>>()
         })
@@ -888,7 +1941,18 @@ fn fetch_latest_project_version(
   project_type: &str,
   game_version: &str,
   loader: Option<&str>,
+  channel: Option<ReleaseChannel>,
 ) -> Result<Option<String>, String> {
+  let versions = fetch_compatible_versions(project_id, project_type, game_version, loader)?;
+  Ok(select_version(&versions, channel).map(|entry| entry.version_number.clone()))
+}
+
+fn fetch_compatible_versions(
+  project_id: &str,
+  project_type: &str,
+  game_version: &str,
+  loader: Option<&str>,
+) -> Result<Vec<ModrinthVersion>, String> {
   let mut url = format!("{}/project/{}/version", MODRINTH_BASE_URL, project_id);
   let mut has_query = false;
   if !game_version.is_empty() {
@@ -896,13 +1960,13 @@ fn fetch_latest_project_version(
     url.push_str(&format!("?game_versions={}", versions_param));
     has_query = true;
   }
-  if let Some(loader_value) = resolve_loader_filter(project_type, loader) {
-    let loaders_param = encode_json_param(&vec![loader_value])?;
+  if let Some(loader_values) = resolve_loader_filter(project_type, loader) {
+    let loaders_param = encode_json_param(&loader_values)?;
     url.push_str(if has_query { "&" } else { "?" });
     url.push_str(&format!("loaders={}", loaders_param));
   }
   let versions: Vec<ModrinthVersion> = fetch_modrinth_json(&url)?;
-  Ok(select_version(&versions).map(|entry| entry.version_number.clone()))
+  Ok(versions)
 }
 
 #[tauri::command]
@@ -933,15 +1997,19 @@ pub(crate) fn list_modrinth_installs(
       .filter(|(_, record)| install_record_exists(&instance_dir, &project_type, None, record))
       .map(|(id, _)| id.clone())
       .collect::<Vec<_>>(),
+    "plugin" => installs
+      .plugins
+      .iter()
+      .filter(|(_, record)| install_record_exists(&instance_dir, &project_type, None, record))
+      .map(|(id, _)| id.clone())
+      .collect::<Vec<_>>(),
     "datapack" => {
-      let world = world_id
-        .ok_or_else(|| "world id is required for datapacks".to_string())?;
-      installs
-        .datapacks
-        .get(&world)
+      datapack_records(&installs, world_id.as_deref())
         .map(|map| {
           map.iter()
-            .filter(|(_, record)| install_record_exists(&instance_dir, &project_type, Some(&world), record))
+            .filter(|(_, record)| {
+              install_record_exists(&instance_dir, &project_type, world_id.as_deref(), record)
+            })
             .map(|(id, _)| id.clone())
             .collect::<Vec<_>>()
         })
@@ -964,31 +2032,307 @@ pub(crate) async fn list_modrinth_updates(
 ) -> Result<Vec<String>, String> {
   let instance_dir = resolve_instance_dir(&instance_id, &state)?;
   tauri::async_runtime::spawn_blocking(move || {
-    let installs = load_modrinth_index(&instance_dir)?;
-    let records = collect_modrinth_records(&instance_dir, &installs, &project_type, world_id.as_deref())?;
-    let mut updates = Vec::new();
+    pending_modrinth_updates(
+      &instance_dir,
+      &project_type,
+      &game_version,
+      loader.as_deref(),
+      world_id.as_deref(),
+    )
+  })
+  .await
+  .map_err(|_| "Modrinth update task failed".to_string())?
+}
+
+/// Applies pending mod updates right before a launch. Per-project failures are
+/// collected instead of aborting so a broken update never blocks the game.
+pub(crate) fn auto_update_instance_mods(
+  instance: &Instance,
+  emit: &dyn Fn(ProgressEvent),
+) -> Result<ModrinthBulkUpdateResult, String> {
+  let empty = ModrinthBulkUpdateResult {
+    snapshot_id: None,
+    updated: Vec::new(),
+    failures: Vec::new(),
+  };
+  if instance.kind != InstanceKind::Client {
+    return Ok(empty);
+  }
+  let Some(loader) = modrinth_loader(&instance.loader) else {
+    return Ok(empty);
+  };
+  let instance_dir = PathBuf::from(&instance.directory);
+  emit(ProgressEvent {
+    stage: "mod-updates".to_string(),
+    message: "Checking for mod updates".to_string(),
+    current: 0,
+    ..Default::default()
+  });
+  let project_ids =
+    pending_modrinth_updates(&instance_dir, "mod", &instance.version, Some(&loader), None)?;
+  if project_ids.is_empty() {
+    return Ok(empty);
+  }
+  let reason = format!("Before auto-updating {} mods on launch", project_ids.len());
+  let snapshot = create_mods_snapshot(&instance_dir, Some(reason), true)?;
+  let total = project_ids.len() as u64;
+  let mut updated = Vec::new();
+  let mut failures = Vec::new();
+  for (index, project_id) in project_ids.iter().enumerate() {
+    emit(ProgressEvent {
+      stage: "mod-updates".to_string(),
+      message: "Updating mods".to_string(),
+      current: index as u64,
+      total: Some(total),
+      detail: Some(project_id.clone()),
+      ..Default::default()
+    });
+    match update_modrinth_internal(
+      &instance_dir,
+      project_id,
+      "mod",
+      &instance.version,
+      Some(&loader),
+      None,
+    ) {
+      Ok(installed) => updated.push(installed),
+      Err(err) => failures.push(format!("{}: {}", project_id, err)),
+    }
+  }
+  emit(ProgressEvent {
+    stage: "mod-updates".to_string(),
+    message: "Mod updates applied".to_string(),
+    current: total,
+    total: Some(total),
+    ..Default::default()
+  });
+  Ok(ModrinthBulkUpdateResult {
+    snapshot_id: Some(snapshot.id),
+    updated,
+    failures,
+  })
+}
+
+/// Project ids with a newer compatible version; pinned and blacklisted entries are skipped.
+pub(crate) fn pending_modrinth_updates(
+  instance_dir: &Path,
+  project_type: &str,
+  game_version: &str,
+  loader: Option<&str>,
+  world_id: Option<&str>,
+) -> Result<Vec<String>, String> {
+  let installs = load_modrinth_index(instance_dir)?;
+  let records = collect_modrinth_records(instance_dir, &installs, project_type, world_id)?;
+  let channel = instance_release_channel(instance_dir);
+  let mut updates = Vec::new();
+
+  for (project_id, installed_version) in records {
+    if is_content_blacklisted(&project_id) {
+      continue;
+    }
+    let latest = match fetch_latest_project_version(
+      &project_id,
+      project_type,
+      game_version,
+      loader,
+      channel,
+    ) {
+      Ok(version) => version,
+      Err(_) => continue,
+    };
+    let Some(latest_version) = latest else { continue };
+    if installed_version.as_deref() != Some(latest_version.as_str()) {
+      updates.push(project_id);
+    }
+  }
+
+  updates.sort();
+  Ok(updates)
+}
+
+/// Changelogs for every compatible version between the installed and latest one, newest first.
+fn collect_update_changelog(
+  project_id: &str,
+  installed_version: Option<&str>,
+  project_type: &str,
+  game_version: &str,
+  loader: Option<&str>,
+  channel: Option<ReleaseChannel>,
+) -> Result<Option<ModrinthUpdateChangelog>, String> {
+  let versions = fetch_compatible_versions(project_id, project_type, game_version, loader)?;
+  let Some(latest) = select_version(&versions, channel) else {
+    return Ok(None);
+  };
+  if installed_version == Some(latest.version_number.as_str()) {
+    return Ok(None);
+  }
+  let installed_date = installed_version.and_then(|installed| {
+    versions
+      .iter()
+      .find(|version| version.version_number == installed)
+      .map(|version| version.date_published.clone())
+  });
+  let mut pending = versions
+    .iter()
+    .filter(|version| {
+      version.date_published <= latest.date_published
+        && installed_date
+          .as_ref()
+          .map(|date| version.date_published > *date)
+          .unwrap_or(version.id == latest.id)
+    })
+    .collect::<Vec<_>>();
+  pending.sort_by(|a, b| b.date_published.cmp(&a.date_published));
+  let title = fetch_project_info(project_id)
+    .ok()
+    .and_then(|info| info.title.or(info.slug))
+    .unwrap_or_else(|| project_id.to_string());
+  Ok(Some(ModrinthUpdateChangelog {
+    project_id: project_id.to_string(),
+    title,
+    installed_version: installed_version.map(|value| value.to_string()),
+    latest_version: latest.version_number.clone(),
+    entries: pending
+      .into_iter()
+      .map(|version| ModrinthChangelogEntry {
+        version_number: version.version_number.clone(),
+        version_type: version.version_type.clone(),
+        date_published: version.date_published.clone(),
+        changelog: version.changelog.clone().unwrap_or_default(),
+      })
+      .collect(),
+  }))
+}
 
+#[tauri::command]
+pub(crate) async fn list_modrinth_update_changelogs(
+  instance_id: String,
+  project_type: String,
+  game_version: String,
+  loader: Option<String>,
+  world_id: Option<String>,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<ModrinthUpdateChangelog>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  tauri::async_runtime::spawn_blocking(move || {
+    let installs = load_modrinth_index(&instance_dir)?;
+    let records =
+      collect_modrinth_records(&instance_dir, &installs, &project_type, world_id.as_deref())?;
+    let channel = instance_release_channel(&instance_dir);
+    let mut changelogs = Vec::new();
     for (project_id, installed_version) in records {
-      let latest = match fetch_latest_project_version(
+      if is_content_blacklisted(&project_id) {
+        continue;
+      }
+      match collect_update_changelog(
         &project_id,
+        installed_version.as_deref(),
         &project_type,
         &game_version,
         loader.as_deref(),
+        channel,
       ) {
-        Ok(version) => version,
-        Err(_) => continue,
-      };
-      let Some(latest_version) = latest else { continue };
-      if installed_version.as_deref() != Some(latest_version.as_str()) {
-        updates.push(project_id);
+        Ok(Some(changelog)) => changelogs.push(changelog),
+        Ok(None) | Err(_) => continue,
       }
     }
-
-    updates.sort();
-    Ok(updates)
+    changelogs.sort_by_key(|changelog| changelog.title.to_lowercase());
+    Ok(changelogs)
   })
   .await
-  .map_err(|_| "Modrinth update task failed".to_string())?
+  .map_err(|_| "Modrinth changelog task failed".to_string())?
+}
+
+#[tauri::command]
+pub(crate) fn rollback_modrinth_update(
+  instance_id: String,
+  project_id: String,
+  app: AppHandle,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<ModrinthInstallResult, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let ops = OperationEmitter::new(&app, Some(&instance_id));
+  let mut installs = load_modrinth_index(&instance_dir)?;
+  let result = restore_from_rollback(&instance_dir, &project_id, &mut installs)
+    .and_then(|restored| save_modrinth_index(&instance_dir, &installs).map(|_| restored));
+  match &result {
+    Ok(restored) => ops.emit("modrinth:done", restored.clone()),
+    Err(err) => ops.emit("modrinth:error", err.clone()),
+  }
+  result
+}
+
+#[tauri::command]
+pub(crate) fn list_modrinth_rollbacks(
+  instance_id: String,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<String>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let installs = load_modrinth_index(&instance_dir)?;
+  let project_ids = installs
+    .mods
+    .keys()
+    .chain(installs.resources.keys())
+    .chain(installs.shaders.keys())
+    .chain(installs.plugins.keys())
+    .chain(installs.datapacks.values().flat_map(|map| map.keys()))
+    .chain(installs.staged_datapacks.keys())
+    .filter(|project_id| {
+      rollback_dir(&instance_dir, project_id)
+        .join(ROLLBACK_ENTRY_FILE)
+        .is_file()
+    })
+    .cloned()
+    .collect::<HashSet<_>>();
+  let mut entries = project_ids.into_iter().collect::<Vec<_>>();
+  entries.sort();
+  Ok(entries)
+}
+
+#[tauri::command]
+pub(crate) fn set_modrinth_project_pinned(
+  instance_id: String,
+  project_id: String,
+  project_type: String,
+  world_id: Option<String>,
+  pinned: bool,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<(), String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let mut installs = load_modrinth_index(&instance_dir)?;
+  let record =
+    get_install_record_mut(&mut installs, &project_type, &project_id, world_id.as_deref())
+    .ok_or_else(|| "Modrinth project is not installed".to_string())?;
+  record.pinned = pinned;
+  save_modrinth_index(&instance_dir, &installs)
+}
+
+#[tauri::command]
+pub(crate) fn list_modrinth_pinned(
+  instance_id: String,
+  project_type: String,
+  world_id: Option<String>,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<Vec<String>, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let installs = load_modrinth_index(&instance_dir)?;
+  let records = match project_type.as_str() {
+    "mod" => installs.mods,
+    "resourcepack" => installs.resources,
+    "shader" => installs.shaders,
+    "plugin" => installs.plugins,
+    "datapack" => datapack_records(&installs, world_id.as_deref())
+      .cloned()
+      .unwrap_or_default(),
+    _ => return Err("unsupported Modrinth project type".to_string()),
+  };
+  let mut entries = records
+    .into_iter()
+    .filter(|(_, record)| record.pinned)
+    .map(|(id, _)| id)
+    .collect::<Vec<_>>();
+  entries.sort();
+  Ok(entries)
 }
 
 #[tauri::command]
@@ -1011,23 +2355,14 @@ pub(crate) fn uninstall_modrinth_project(
       instance_dir.join("shaderpacks"),
       installs.shaders.remove(&project_id),
     ),
-    "datapack" => {
-      let world = world_id
-        .ok_or_else(|| "world id is required for datapacks".to_string())?;
-      let record = if let Some(entry) = installs.datapacks.get_mut(&world) {
-        let record = entry.remove(&project_id);
-        if entry.is_empty() {
-          installs.datapacks.remove(&world);
-        }
-        record
-      } else {
-        None
-      };
-      (
-        instance_dir.join("saves").join(&world).join("datapacks"),
-        record,
-      )
-    }
+    "plugin" => (
+      instance_dir.join("plugins"),
+      installs.plugins.remove(&project_id),
+    ),
+    "datapack" => (
+      resolve_target_dir(&instance_dir, "datapack", world_id.as_deref())?,
+      take_datapack_record(&mut installs, &project_id, world_id.as_deref()),
+    ),
     _ => return Err("unsupported Modrinth project type".to_string()),
   };
 
@@ -1040,3 +2375,69 @@ pub(crate) fn uninstall_modrinth_project(
   }
   Ok(())
 }
+
+/// Copies staged datapacks into each of `world_ids`; staged copies stay for later worlds.
+#[tauri::command]
+pub(crate) fn deploy_staged_datapacks(
+  instance_id: String,
+  world_ids: Vec<String>,
+  project_ids: Option<Vec<String>>,
+  state: State<'_, Mutex<ConfigStore>>,
+) -> Result<usize, String> {
+  let instance_dir = resolve_instance_dir(&instance_id, &state)?;
+  let mut installs = load_modrinth_index(&instance_dir)?;
+  let staging_dir = instance_dir.join(STAGED_DATAPACKS_DIR);
+  let staged = installs
+    .staged_datapacks
+    .iter()
+    .filter(|(project_id, _)| {
+      project_ids
+        .as_ref()
+        .map_or(true, |ids| ids.iter().any(|id| id == *project_id))
+    })
+    .filter(|(_, record)| staging_dir.join(&record.filename).is_file())
+    .map(|(project_id, record)| (project_id.clone(), record.clone()))
+    .collect::<Vec<_>>();
+  if staged.is_empty() {
+    return Err("no staged datapacks to deploy".to_string());
+  }
+
+  for world_id in &world_ids {
+    if sanitize_path_component(world_id, "world") != *world_id {
+      return Err(format!("invalid world id '{}'", world_id));
+    }
+    if !instance_dir.join("saves").join(world_id).is_dir() {
+      return Err(format!("world '{}' not found", world_id));
+    }
+  }
+
+  let mut deployed = 0;
+  for world_id in &world_ids {
+    let target_dir = resolve_target_dir(&instance_dir, "datapack", Some(world_id))?;
+    fs::create_dir_all(&target_dir).map_err(|err| err.to_string())?;
+    for (project_id, record) in &staged {
+      fs::copy(
+        staging_dir.join(&record.filename),
+        target_dir.join(&record.filename),
+      )
+      .map_err(|err| err.to_string())?;
+      let previous = datapack_records_mut(&mut installs, Some(world_id)).insert(
+        project_id.clone(),
+        record.clone(),
+      );
+      if let Some(previous) = previous {
+        let entry = get_install_record_mut(&mut installs, "datapack", project_id, Some(world_id));
+        if let Some(entry) = entry {
+          entry.pinned = previous.pinned;
+        }
+        if previous.filename != record.filename {
+          remove_previous_file(&target_dir, Some(previous));
+        }
+      }
+      // Record each copy right away so a later failure leaves no untracked datapacks behind.
+      save_modrinth_index(&instance_dir, &installs)?;
+      deployed += 1;
+    }
+  }
+  Ok(deployed)
+}
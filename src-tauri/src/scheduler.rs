@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tauri::{AppHandle, Manager};
+
+use crate::commands::recipes::modrinth_loader;
+use crate::config::{AppConfig, ConfigStore, InstanceKind, ScheduledTaskSettings};
+use crate::diagnostics::{backup_changed_worlds, prune_instance_logs};
+use crate::events::{InstanceOperationGuard, OperationEmitter};
+use crate::modrinth::pending_modrinth_updates;
+
+const SCHEDULER_STATE_FILE: &str = "scheduler.json";
+const SCHEDULER_STARTUP_DELAY_SECS: u64 = 60;
+const SCHEDULER_TICK_SECS: u64 = 300;
+const MOD_UPDATE_INTERVAL_SECS: u64 = 7 * 86_400;
+const LOG_PRUNE_INTERVAL_SECS: u64 = 86_400;
+const TOKEN_REFRESH_INTERVAL_SECS: u64 = 6 * 3_600;
+
+static SCHEDULER_STATE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ScheduledTask {
+  CheckModUpdates,
+  PruneLogs,
+  BackupWorlds,
+  RefreshTokens,
+}
+
+impl ScheduledTask {
+  const ALL: [ScheduledTask; 4] = [
+    ScheduledTask::CheckModUpdates,
+    ScheduledTask::PruneLogs,
+    ScheduledTask::BackupWorlds,
+    ScheduledTask::RefreshTokens,
+  ];
+
+  fn parse(value: &str) -> Result<Self, String> {
+    serde_json::from_value(serde_json::Value::String(value.trim().to_string()))
+      .map_err(|_| format!("unknown scheduled task '{}'", value))
+  }
+
+  fn key(self) -> &'static str {
+    match self {
+      ScheduledTask::CheckModUpdates => "check_mod_updates",
+      ScheduledTask::PruneLogs => "prune_logs",
+      ScheduledTask::BackupWorlds => "backup_worlds",
+      ScheduledTask::RefreshTokens => "refresh_tokens",
+    }
+  }
+
+  /// World backups have no interval; they run when a game session ends.
+  fn interval_secs(self) -> Option<u64> {
+    match self {
+      ScheduledTask::CheckModUpdates => Some(MOD_UPDATE_INTERVAL_SECS),
+      ScheduledTask::PruneLogs => Some(LOG_PRUNE_INTERVAL_SECS),
+      ScheduledTask::BackupWorlds => None,
+      ScheduledTask::RefreshTokens => Some(TOKEN_REFRESH_INTERVAL_SECS),
+    }
+  }
+
+  fn enabled(self, settings: &ScheduledTaskSettings) -> bool {
+    match self {
+      ScheduledTask::CheckModUpdates => settings.check_mod_updates,
+      ScheduledTask::PruneLogs => settings.prune_logs,
+      ScheduledTask::BackupWorlds => settings.backup_worlds,
+      ScheduledTask::RefreshTokens => settings.refresh_tokens,
+    }
+  }
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct SchedulerState {
+  #[serde(default)]
+  last_run_unix: HashMap<String, u64>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct ScheduledTaskResult {
+  task: ScheduledTask,
+  instance_id: Option<String>,
+  success: bool,
+  summary: String,
+  finished_at_unix: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ModUpdatesAvailable {
+  instance_id: String,
+  project_ids: Vec<String>,
+}
+
+fn now_unix() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|value| value.as_secs())
+    .unwrap_or(0)
+}
+
+fn load_config(app: &AppHandle) -> Result<(AppConfig, PathBuf), String> {
+  let store_state = app.state::<Mutex<ConfigStore>>();
+  let store = store_state
+    .lock()
+    .map_err(|_| "config store lock poisoned".to_string())?;
+  let state_path = store
+    .path()
+    .parent()
+    .map(|parent| parent.join(SCHEDULER_STATE_FILE))
+    .ok_or_else(|| "config directory not found".to_string())?;
+  Ok((store.get(), state_path))
+}
+
+fn load_scheduler_state(path: &Path) -> SchedulerState {
+  fs::read_to_string(path)
+    .ok()
+    .and_then(|data| serde_json::from_str(&data).ok())
+    .unwrap_or_default()
+}
+
+fn record_last_run(path: &Path, task: ScheduledTask, finished_at: u64) {
+  let _guard = SCHEDULER_STATE_LOCK
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  let mut state = load_scheduler_state(path);
+  state.last_run_unix.insert(task.key().to_string(), finished_at);
+  if let Ok(payload) = serde_json::to_vec_pretty(&state) {
+    let _ = fs::write(path, payload);
+  }
+}
+
+fn running_instances(app: &AppHandle) -> Vec<String> {
+  let running = app.state::<Mutex<HashMap<String, u32>>>();
+  let map = match running.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  };
+  map.keys().cloned().collect()
+}
+
+fn check_mod_updates(
+  app: &AppHandle,
+  config: &AppConfig,
+  instance_id: Option<&str>,
+) -> Result<String, String> {
+  let mut instances_with_updates = 0;
+  let mut total = 0;
+  let mut checked = 0;
+  let mut failed = Vec::new();
+  for instance in &config.instances {
+    if instance.kind != InstanceKind::Client || instance_id.is_some_and(|id| id != instance.id) {
+      continue;
+    }
+    let Some(loader) = modrinth_loader(&instance.loader) else {
+      continue;
+    };
+    checked += 1;
+    let instance_dir = PathBuf::from(&instance.directory);
+    let project_ids =
+      match pending_modrinth_updates(&instance_dir, "mod", &instance.version, Some(&loader), None) {
+        Ok(project_ids) => project_ids,
+        Err(err) => {
+          log::warn!("mod update check failed for {}: {}", instance.id, err);
+          failed.push(instance.name.clone());
+          continue;
+        }
+      };
+    if project_ids.is_empty() {
+      continue;
+    }
+    instances_with_updates += 1;
+    total += project_ids.len();
+    OperationEmitter::new(app, Some(&instance.id)).emit(
+      "modrinth:updates-available",
+      ModUpdatesAvailable {
+        instance_id: instance.id.clone(),
+        project_ids,
+      },
+    );
+  }
+  if checked > 0 && failed.len() == checked {
+    return Err(format!("Mod update check failed for {}", failed.join(", ")));
+  }
+  let mut summary = format!(
+    "{} mod updates available across {} instances",
+    total, instances_with_updates
+  );
+  if !failed.is_empty() {
+    summary.push_str(&format!("; could not check {}", failed.join(", ")));
+  }
+  Ok(summary)
+}
+
+fn prune_logs(config: &AppConfig, instance_id: Option<&str>) -> Result<String, String> {
+  let days = config.settings.scheduled_tasks.log_retention_days.max(1);
+  let mut removed = 0;
+  for instance in &config.instances {
+    if instance_id.is_some_and(|id| id != instance.id) {
+      continue;
+    }
+    removed += prune_instance_logs(&PathBuf::from(&instance.directory), days)?;
+  }
+  Ok(format!("Removed {} log files older than {} days", removed, days))
+}
+
+fn backup_worlds(
+  app: &AppHandle,
+  config: &AppConfig,
+  instance_id: Option<&str>,
+) -> Result<String, String> {
+  let running = running_instances(app);
+  let mut worlds = 0;
+  for instance in &config.instances {
+    if instance.kind != InstanceKind::Client || instance_id.is_some_and(|id| id != instance.id) {
+      continue;
+    }
+    // A loaded world is still being written to; `handle_session_ended` backs it up on exit.
+    if running.contains(&instance.id) {
+      continue;
+    }
+    let instance_dir = PathBuf::from(&instance.directory);
+    let guard = InstanceOperationGuard::acquire(&instance.id, &instance_dir, "world backup");
+    let _guard = match guard {
+      Ok(guard) => guard,
+      Err(err) => {
+        log::info!("skipping world backup for {}: {}", instance.id, err);
+        continue;
+      }
+    };
+    worlds += backup_changed_worlds(&instance_dir)?.len();
+  }
+  Ok(format!("Backed up {} worlds", worlds))
+}
+
+fn refresh_tokens(app: &AppHandle) -> Result<String, String> {
+  let store_state = app.state::<Mutex<ConfigStore>>();
+  let mut refreshed = store_state
+    .lock()
+    .map_err(|_| "config store lock poisoned".to_string())?
+    .get();
+  // Talks to Microsoft for every account, so the store stays unlocked meanwhile.
+  let statuses = crate::refresh_microsoft_accounts_inner(&mut refreshed);
+  {
+    let mut store = store_state
+      .lock()
+      .map_err(|_| "config store lock poisoned".to_string())?;
+    let mut latest = store.get();
+    for account in &mut latest.accounts {
+      let Some(updated) = refreshed.accounts.iter().find(|item| item.id == account.id) else {
+        continue;
+      };
+      let was_refreshed = statuses
+        .iter()
+        .any(|status| status.account_id == account.id && status.status == "refreshed");
+      if was_refreshed {
+        *account = updated.clone();
+      } else if account.owns_minecraft.is_none() {
+        account.owns_minecraft = updated.owns_minecraft;
+      }
+    }
+    store.set(latest).map_err(|err| err.to_string())?;
+  }
+  // Accounts that need a fresh login are reported, not failed, so the task does not
+  // stay due and retry them on every tick.
+  let failed = statuses.iter().filter(|status| status.status == "error").count();
+  let mut summary = format!("{} accounts checked", statuses.len());
+  if failed > 0 {
+    summary.push_str(&format!("; {} need to sign in again", failed));
+  }
+  Ok(summary)
+}
+
+fn execute_task(
+  app: &AppHandle,
+  task: ScheduledTask,
+  instance_id: Option<&str>,
+) -> ScheduledTaskResult {
+  let outcome = load_config(app).and_then(|(config, state_path)| {
+    let summary = match task {
+      ScheduledTask::CheckModUpdates => check_mod_updates(app, &config, instance_id),
+      ScheduledTask::PruneLogs => prune_logs(&config, instance_id),
+      ScheduledTask::BackupWorlds => backup_worlds(app, &config, instance_id),
+      ScheduledTask::RefreshTokens => refresh_tokens(app),
+    };
+    // A failed run stays due, so the next tick retries it.
+    if summary.is_ok() {
+      record_last_run(&state_path, task, now_unix());
+    }
+    summary
+  });
+  let result = ScheduledTaskResult {
+    task,
+    instance_id: instance_id.map(|id| id.to_string()),
+    success: outcome.is_ok(),
+    summary: outcome.unwrap_or_else(|err| err),
+    finished_at_unix: now_unix(),
+  };
+  OperationEmitter::new(app, instance_id).emit("scheduler:task", result.clone());
+  result
+}
+
+fn run_due_tasks(app: &AppHandle) {
+  let Ok((config, state_path)) = load_config(app) else {
+    return;
+  };
+  let state = load_scheduler_state(&state_path);
+  let now = now_unix();
+  for task in ScheduledTask::ALL {
+    let Some(interval) = task.interval_secs() else {
+      continue;
+    };
+    if !task.enabled(&config.settings.scheduled_tasks) {
+      continue;
+    }
+    let last_run = state.last_run_unix.get(task.key()).copied().unwrap_or(0);
+    if now.saturating_sub(last_run) >= interval {
+      execute_task(app, task, None);
+    }
+  }
+}
+
+pub(crate) fn spawn_scheduler(app: AppHandle) {
+  thread::spawn(move || {
+    thread::sleep(Duration::from_secs(SCHEDULER_STARTUP_DELAY_SECS));
+    loop {
+      run_due_tasks(&app);
+      thread::sleep(Duration::from_secs(SCHEDULER_TICK_SECS));
+    }
+  });
+}
+
+/// Hook for the end of a game session; backs up the instance's worlds when enabled.
+pub(crate) fn handle_session_ended(app: &AppHandle, instance_id: &str) {
+  let enabled = load_config(app)
+    .map(|(config, _)| ScheduledTask::BackupWorlds.enabled(&config.settings.scheduled_tasks))
+    .unwrap_or(false);
+  if !enabled {
+    return;
+  }
+  let app = app.clone();
+  let instance_id = instance_id.to_string();
+  thread::spawn(move || {
+    execute_task(&app, ScheduledTask::BackupWorlds, Some(&instance_id));
+  });
+}
+
+#[tauri::command]
+pub(crate) async fn run_task_now(
+  task: String,
+  instance_id: Option<String>,
+  app: AppHandle,
+) -> Result<ScheduledTaskResult, String> {
+  let task = ScheduledTask::parse(&task)?;
+  tauri::async_runtime::spawn_blocking(move || execute_task(&app, task, instance_id.as_deref()))
+    .await
+    .map_err(|_| "scheduled task cancelled".to_string())
+}
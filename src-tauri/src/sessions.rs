@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::{AppHandle, Manager};
+
+use crate::config::ConfigStore;
+
+const SESSION_LEDGER_FILE: &str = "play-sessions.json";
+const SESSION_LEDGER_LIMIT: usize = 10_000;
+const SECS_PER_DAY: i64 = 86_400;
+
+static SESSION_LEDGER_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct PlaySession {
+  pub(crate) instance_id: String,
+  pub(crate) started_at_unix: u64,
+  pub(crate) ended_at_unix: u64,
+  pub(crate) duration_secs: u64,
+  pub(crate) exit_code: Option<i32>,
+  pub(crate) exit_status: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct PlaytimeBucket {
+  period_start_unix: i64,
+  total_secs: u64,
+  session_count: u32,
+}
+
+#[derive(Clone, Copy)]
+enum PlaytimePeriod {
+  Day,
+  Week,
+}
+
+impl PlaytimePeriod {
+  fn parse(value: &str) -> Result<Self, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+      "day" | "daily" => Ok(PlaytimePeriod::Day),
+      "week" | "weekly" => Ok(PlaytimePeriod::Week),
+      other => Err(format!("unknown playtime period '{}'", other)),
+    }
+  }
+
+  fn length_secs(self) -> i64 {
+    match self {
+      PlaytimePeriod::Day => SECS_PER_DAY,
+      PlaytimePeriod::Week => 7 * SECS_PER_DAY,
+    }
+  }
+}
+
+fn now_unix() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|value| value.as_secs())
+    .unwrap_or(0)
+}
+
+fn ledger_path(app: &AppHandle) -> Result<PathBuf, String> {
+  let store_state = app.state::<Mutex<ConfigStore>>();
+  let store = store_state
+    .lock()
+    .map_err(|_| "config store lock poisoned".to_string())?;
+  store
+    .path()
+    .parent()
+    .map(|parent| parent.join(SESSION_LEDGER_FILE))
+    .ok_or_else(|| "config directory not found".to_string())
+}
+
+fn load_ledger(path: &Path) -> Vec<PlaySession> {
+  fs::read_to_string(path)
+    .ok()
+    .and_then(|data| serde_json::from_str(&data).ok())
+    .unwrap_or_default()
+}
+
+pub(crate) fn load_sessions(app: &AppHandle) -> Result<Vec<PlaySession>, String> {
+  let path = ledger_path(app)?;
+  let _guard = SESSION_LEDGER_LOCK
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  Ok(load_ledger(&path))
+}
+
+fn exit_status_label(exit_code: Option<i32>) -> &'static str {
+  match exit_code {
+    Some(0) => "clean",
+    Some(_) => "error",
+    None => "terminated",
+  }
+}
+
+/// Appends a finished session to the local ledger. Failures are logged, never surfaced.
+pub(crate) fn record_session(
+  app: &AppHandle,
+  instance_id: &str,
+  started_at_unix: u64,
+  exit_code: Option<i32>,
+) {
+  let path = match ledger_path(app) {
+    Ok(path) => path,
+    Err(err) => {
+      log::warn!("session ledger unavailable: {}", err);
+      return;
+    }
+  };
+  let ended_at_unix = now_unix().max(started_at_unix);
+  let session = PlaySession {
+    instance_id: instance_id.to_string(),
+    started_at_unix,
+    ended_at_unix,
+    duration_secs: ended_at_unix - started_at_unix,
+    exit_code,
+    exit_status: exit_status_label(exit_code).to_string(),
+  };
+  let _guard = SESSION_LEDGER_LOCK
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  let mut sessions = load_ledger(&path);
+  sessions.push(session);
+  if sessions.len() > SESSION_LEDGER_LIMIT {
+    let overflow = sessions.len() - SESSION_LEDGER_LIMIT;
+    sessions.drain(..overflow);
+  }
+  match serde_json::to_vec_pretty(&sessions) {
+    Ok(payload) => {
+      if let Err(err) = fs::write(&path, payload) {
+        log::warn!("failed to write session ledger: {}", err);
+      }
+    }
+    Err(err) => log::warn!("failed to encode session ledger: {}", err),
+  }
+}
+
+/// Start of the local day/week (weeks begin on Monday) containing `unix`.
+fn period_start(unix: i64, offset_secs: i64, period: PlaytimePeriod) -> i64 {
+  let day = (unix + offset_secs).div_euclid(SECS_PER_DAY);
+  let day = match period {
+    PlaytimePeriod::Day => day,
+    // 1970-01-01 was a Thursday.
+    PlaytimePeriod::Week => day - (day + 3).rem_euclid(7),
+  };
+  day * SECS_PER_DAY - offset_secs
+}
+
+fn aggregate_sessions(
+  sessions: &[PlaySession],
+  period: PlaytimePeriod,
+  offset_secs: i64,
+) -> Vec<PlaytimeBucket> {
+  let mut buckets: BTreeMap<i64, PlaytimeBucket> = BTreeMap::new();
+  for session in sessions {
+    let mut cursor = session.started_at_unix as i64;
+    let end = session.ended_at_unix as i64;
+    let start_bucket = period_start(cursor, offset_secs, period);
+    buckets
+      .entry(start_bucket)
+      .or_insert(PlaytimeBucket {
+        period_start_unix: start_bucket,
+        total_secs: 0,
+        session_count: 0,
+      })
+      .session_count += 1;
+    // Sessions that run past midnight count towards each period they overlap.
+    while cursor < end {
+      let bucket_start = period_start(cursor, offset_secs, period);
+      let slice_end = end.min(bucket_start + period.length_secs());
+      buckets
+        .entry(bucket_start)
+        .or_insert(PlaytimeBucket {
+          period_start_unix: bucket_start,
+          total_secs: 0,
+          session_count: 0,
+        })
+        .total_secs += (slice_end - cursor) as u64;
+      cursor = slice_end;
+    }
+  }
+  buckets.into_values().collect()
+}
+
+fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
+
+fn render_sessions_csv(sessions: &[PlaySession], names: &HashMap<String, String>) -> String {
+  let mut output = String::from(
+    "instance_id,instance_name,started_at_unix,ended_at_unix,duration_secs,exit_code,exit_status\n",
+  );
+  for session in sessions {
+    let name = names.get(&session.instance_id).map(String::as_str).unwrap_or("");
+    output.push_str(&format!(
+      "{},{},{},{},{},{},{}\n",
+      csv_field(&session.instance_id),
+      csv_field(name),
+      session.started_at_unix,
+      session.ended_at_unix,
+      session.duration_secs,
+      session.exit_code.map(|code| code.to_string()).unwrap_or_default(),
+      session.exit_status
+    ));
+  }
+  output
+}
+
+#[tauri::command]
+pub(crate) fn list_play_sessions(
+  instance_id: Option<String>,
+  limit: Option<usize>,
+  app: AppHandle,
+) -> Result<Vec<PlaySession>, String> {
+  let mut sessions = load_sessions(&app)?;
+  if let Some(id) = instance_id.as_deref() {
+    sessions.retain(|session| session.instance_id == id);
+  }
+  sessions.reverse();
+  if let Some(limit) = limit {
+    sessions.truncate(limit);
+  }
+  Ok(sessions)
+}
+
+#[tauri::command]
+pub(crate) fn get_playtime_stats(
+  period: String,
+  instance_id: Option<String>,
+  since_unix: Option<u64>,
+  utc_offset_minutes: Option<i32>,
+  app: AppHandle,
+) -> Result<Vec<PlaytimeBucket>, String> {
+  let period = PlaytimePeriod::parse(&period)?;
+  let offset_secs = i64::from(utc_offset_minutes.unwrap_or(0)) * 60;
+  let mut sessions = load_sessions(&app)?;
+  sessions.retain(|session| {
+    instance_id.as_deref().map_or(true, |id| session.instance_id == id)
+      && since_unix.map_or(true, |since| session.ended_at_unix >= since)
+  });
+  Ok(aggregate_sessions(&sessions, period, offset_secs))
+}
+
+/// Writes the whole ledger to `path`; a `.json` extension selects JSON, anything else CSV.
+#[tauri::command]
+pub(crate) fn export_playtime_csv(path: String, app: AppHandle) -> Result<usize, String> {
+  let target = PathBuf::from(path.trim());
+  if target.as_os_str().is_empty() {
+    return Err("export path is required".to_string());
+  }
+  let sessions = load_sessions(&app)?;
+  let names = {
+    let store_state = app.state::<Mutex<ConfigStore>>();
+    let store = store_state
+      .lock()
+      .map_err(|_| "config store lock poisoned".to_string())?;
+    store
+      .get()
+      .instances
+      .into_iter()
+      .map(|instance| (instance.id, instance.name))
+      .collect::<HashMap<_, _>>()
+  };
+  let as_json = target
+    .extension()
+    .map(|ext| ext.eq_ignore_ascii_case("json"))
+    .unwrap_or(false);
+  let payload = if as_json {
+    let rows = sessions
+      .iter()
+      .map(|session| {
+        let mut row = serde_json::to_value(session).map_err(|err| err.to_string())?;
+        if let Some(map) = row.as_object_mut() {
+          map.insert(
+            "instance_name".to_string(),
+            serde_json::Value::from(names.get(&session.instance_id).cloned()),
+          );
+        }
+        Ok(row)
+      })
+      .collect::<Result<Vec<_>, String>>()?;
+    serde_json::to_vec_pretty(&rows).map_err(|err| err.to_string())?
+  } else {
+    render_sessions_csv(&sessions, &names).into_bytes()
+  };
+  if let Some(parent) = target.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+    fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+  }
+  fs::write(&target, payload).map_err(|err| err.to_string())?;
+  Ok(sessions.len())
+}
@@ -0,0 +1,429 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::recipes::build_instance_recipe;
+use crate::commands::servers::{
+  load_server_list, merge_server_lists, server_entry_fingerprint, store_server_list, ServerEntry,
+};
+use crate::config::{AppConfig, ConfigStore, InstanceKind, Settings};
+use crate::events::OperationEmitter;
+use crate::DiscordRpcState;
+
+const SYNC_FOLDER: &str = "monolith-sync";
+const SYNC_SETTINGS_FILE: &str = "settings.json";
+const SYNC_RECIPES_DIR: &str = "recipes";
+const SYNC_SERVERS_DIR: &str = "servers";
+const SYNC_STATE_FILE: &str = "sync-state.json";
+const RECIPE_SUFFIX: &str = ".monolith-recipe.json";
+// Paths and per-machine choices that would be wrong on another computer.
+const LOCAL_ONLY_SETTINGS: &[&str] = &[
+  "sync_directory",
+  "reference_instance_id",
+  "performance_gamemode",
+  "performance_mangohud",
+  "performance_zink",
+  "performance_discrete_gpu",
+];
+// Fields inside nested settings objects; those objects are merged field by field.
+const LOCAL_ONLY_NESTED_SETTINGS: &[(&str, &[&str])] = &[
+  (
+    "java",
+    &[
+      "runtime",
+      "runtimes",
+      "overrides",
+      "min_ram_mb",
+      "max_ram_mb",
+      "min_ram_gb",
+      "max_ram_gb",
+    ],
+  ),
+  // Instance roots are configured per machine.
+  ("new_instance_defaults", &["root_id"]),
+];
+
+/// What both machines agreed on after the previous sync; used as the merge base.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct SyncState {
+  #[serde(default)]
+  settings: Map<String, Value>,
+  #[serde(default)]
+  recipes: HashMap<String, String>,
+  #[serde(default)]
+  servers: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct SyncConflict {
+  item: String,
+  detail: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct SyncReport {
+  directory: String,
+  pushed: Vec<String>,
+  pulled: Vec<String>,
+  merged: Vec<String>,
+  conflicts: Vec<SyncConflict>,
+  available_recipes: Vec<String>,
+  synced_at_unix: u64,
+}
+
+impl SyncReport {
+  fn new(directory: &Path) -> Self {
+    Self {
+      directory: directory.to_string_lossy().to_string(),
+      pushed: Vec::new(),
+      pulled: Vec::new(),
+      merged: Vec::new(),
+      conflicts: Vec::new(),
+      available_recipes: Vec::new(),
+      synced_at_unix: now_unix(),
+    }
+  }
+
+  fn conflict(&mut self, item: String, detail: &str) {
+    self.conflicts.push(SyncConflict {
+      item,
+      detail: detail.to_string(),
+    });
+  }
+}
+
+fn now_unix() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|value| value.as_secs())
+    .unwrap_or(0)
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+  let mut hasher = Sha256::new();
+  hasher.update(bytes);
+  format!("{:x}", hasher.finalize())
+}
+
+fn write_synced_file(path: &Path, payload: &[u8]) -> Result<(), String> {
+  // Sync clients pick up half-written files, so write beside the target and rename.
+  let temp = path.with_extension("tmp");
+  fs::write(&temp, payload).map_err(|err| err.to_string())?;
+  fs::rename(&temp, path).map_err(|err| err.to_string())
+}
+
+fn load_sync_state(path: &Path) -> SyncState {
+  fs::read_to_string(path)
+    .ok()
+    .and_then(|data| serde_json::from_str(&data).ok())
+    .unwrap_or_default()
+}
+
+fn save_sync_state(path: &Path, state: &SyncState) -> Result<(), String> {
+  let payload = serde_json::to_vec_pretty(state).map_err(|err| err.to_string())?;
+  fs::write(path, payload).map_err(|err| err.to_string())
+}
+
+fn shareable_settings(settings: &Settings) -> Result<Map<String, Value>, String> {
+  let value = serde_json::to_value(settings).map_err(|err| err.to_string())?;
+  let Value::Object(mut map) = value else {
+    return Err("settings did not serialize to an object".to_string());
+  };
+  for key in LOCAL_ONLY_SETTINGS {
+    map.remove(*key);
+  }
+  for (section, keys) in LOCAL_ONLY_NESTED_SETTINGS {
+    if let Some(Value::Object(fields)) = map.get_mut(*section) {
+      for key in *keys {
+        fields.remove(*key);
+      }
+    }
+  }
+  Ok(map)
+}
+
+fn apply_shared_settings(
+  settings: &Settings,
+  shared: &Map<String, Value>,
+) -> Result<Settings, String> {
+  let mut value = serde_json::to_value(settings).map_err(|err| err.to_string())?;
+  let Some(target) = value.as_object_mut() else {
+    return Err("settings did not serialize to an object".to_string());
+  };
+  for (key, incoming) in shared {
+    match (target.get_mut(key), incoming) {
+      (Some(Value::Object(existing)), Value::Object(fields))
+        if LOCAL_ONLY_NESTED_SETTINGS.iter().any(|(section, _)| section == key) =>
+      {
+        for (field, field_value) in fields {
+          existing.insert(field.clone(), field_value.clone());
+        }
+      }
+      _ => {
+        target.insert(key.clone(), incoming.clone());
+      }
+    }
+  }
+  serde_json::from_value(value).map_err(|err| err.to_string())
+}
+
+/// Per-key three-way merge; on a first sync the existing folder wins.
+fn merge_settings(
+  base: &Map<String, Value>,
+  local: &Map<String, Value>,
+  remote: &Map<String, Value>,
+  report: &mut SyncReport,
+) -> Map<String, Value> {
+  let keys = local.keys().chain(remote.keys()).cloned().collect::<BTreeSet<_>>();
+  let mut merged = Map::new();
+  for key in keys {
+    let local_value = local.get(&key);
+    let remote_value = remote.get(&key);
+    let chosen = if local_value == remote_value {
+      local_value
+    } else if base.is_empty() || local_value == base.get(&key) {
+      report.pulled.push(format!("settings.{}", key));
+      remote_value.or(local_value)
+    } else if remote_value == base.get(&key) {
+      report.pushed.push(format!("settings.{}", key));
+      local_value.or(remote_value)
+    } else {
+      report.conflict(
+        format!("settings.{}", key),
+        "changed on both machines; kept the local value",
+      );
+      local_value.or(remote_value)
+    };
+    if let Some(value) = chosen {
+      merged.insert(key, value.clone());
+    }
+  }
+  merged
+}
+
+fn sync_settings(
+  app: &AppHandle,
+  config: &AppConfig,
+  root: &Path,
+  state: &mut SyncState,
+  report: &mut SyncReport,
+) -> Result<(), String> {
+  let remote_path = root.join(SYNC_SETTINGS_FILE);
+  let local = shareable_settings(&config.settings)?;
+  let remote = fs::read_to_string(&remote_path)
+    .ok()
+    .and_then(|data| serde_json::from_str::<Map<String, Value>>(&data).ok());
+  let merged = match remote {
+    Some(remote) => {
+      let merged = merge_settings(&state.settings, &local, &remote, report);
+      if merged != remote {
+        let payload = serde_json::to_vec_pretty(&merged).map_err(|err| err.to_string())?;
+        write_synced_file(&remote_path, &payload)?;
+      }
+      merged
+    }
+    None => {
+      let payload = serde_json::to_vec_pretty(&local).map_err(|err| err.to_string())?;
+      write_synced_file(&remote_path, &payload)?;
+      report.pushed.push("settings".to_string());
+      local.clone()
+    }
+  };
+
+  if merged != local {
+    let store_state = app.state::<Mutex<ConfigStore>>();
+    let mut store = store_state
+      .lock()
+      .map_err(|_| "config store lock poisoned".to_string())?;
+    let mut latest = store.get();
+    latest.settings = apply_shared_settings(&latest.settings, &merged)?;
+    store.set(latest).map_err(|err| err.to_string())?;
+    let settings = store.get().settings;
+    drop(store);
+    crate::minecraft::apply_network_settings(&settings);
+    crate::events::apply_progress_settings(&settings);
+    crate::modrinth::apply_content_blacklist(&settings);
+    let discord = app.state::<Mutex<DiscordRpcState>>();
+    if let Ok(mut rpc) = discord.lock() {
+      rpc.set_config(settings.discord_presence, settings.discord_presence_mode.clone());
+    };
+  }
+  state.settings = merged;
+  Ok(())
+}
+
+fn sync_recipes(
+  config: &AppConfig,
+  root: &Path,
+  state: &mut SyncState,
+  report: &mut SyncReport,
+) -> Result<(), String> {
+  let recipes_dir = root.join(SYNC_RECIPES_DIR);
+  fs::create_dir_all(&recipes_dir).map_err(|err| err.to_string())?;
+  for instance in &config.instances {
+    let recipe = match build_instance_recipe(instance) {
+      Ok(recipe) => recipe,
+      Err(err) => {
+        log::warn!("skipping recipe sync for '{}': {}", instance.id, err);
+        continue;
+      }
+    };
+    let payload = serde_json::to_vec_pretty(&recipe).map_err(|err| err.to_string())?;
+    let local_hash = hash_bytes(&payload);
+    let remote_path = recipes_dir.join(format!("{}{}", instance.id, RECIPE_SUFFIX));
+    let item = format!("recipe:{}", instance.id);
+    let Ok(remote) = fs::read(&remote_path) else {
+      write_synced_file(&remote_path, &payload)?;
+      state.recipes.insert(instance.id.clone(), local_hash);
+      report.pushed.push(item);
+      continue;
+    };
+    let remote_hash = hash_bytes(&remote);
+    let base = state.recipes.get(&instance.id);
+    if remote_hash == local_hash {
+      state.recipes.insert(instance.id.clone(), local_hash);
+    } else if base == Some(&remote_hash) {
+      write_synced_file(&remote_path, &payload)?;
+      state.recipes.insert(instance.id.clone(), local_hash);
+      report.pushed.push(item);
+    } else if base == Some(&local_hash) {
+      // Recipes describe whole instances; applying one is an explicit import.
+      report
+        .available_recipes
+        .push(remote_path.to_string_lossy().to_string());
+    } else {
+      report.conflict(item, "instance changed on both machines; the synced recipe was left as is");
+    }
+  }
+
+  let entries = fs::read_dir(&recipes_dir).map_err(|err| err.to_string())?;
+  for entry in entries.flatten() {
+    let name = entry.file_name().to_string_lossy().to_string();
+    let Some(id) = name.strip_suffix(RECIPE_SUFFIX) else {
+      continue;
+    };
+    if !config.instances.iter().any(|instance| instance.id == id) {
+      report
+        .available_recipes
+        .push(entry.path().to_string_lossy().to_string());
+    }
+  }
+  Ok(())
+}
+
+fn fingerprints(servers: &[ServerEntry]) -> Vec<String> {
+  servers.iter().map(server_entry_fingerprint).collect()
+}
+
+fn sync_server_lists(
+  config: &AppConfig,
+  root: &Path,
+  state: &mut SyncState,
+  report: &mut SyncReport,
+) -> Result<(), String> {
+  let servers_dir = root.join(SYNC_SERVERS_DIR);
+  fs::create_dir_all(&servers_dir).map_err(|err| err.to_string())?;
+  for instance in &config.instances {
+    if instance.kind == InstanceKind::Server {
+      continue;
+    }
+    let instance_dir = PathBuf::from(&instance.directory);
+    let local = load_server_list(&instance_dir)?;
+    let remote_path = servers_dir.join(format!("{}.json", instance.id));
+    let item = format!("servers:{}", instance.id);
+    let remote = fs::read_to_string(&remote_path)
+      .ok()
+      .and_then(|data| serde_json::from_str::<Vec<ServerEntry>>(&data).ok());
+    let Some(remote) = remote else {
+      if !local.is_empty() {
+        let payload = serde_json::to_vec_pretty(&local).map_err(|err| err.to_string())?;
+        write_synced_file(&remote_path, &payload)?;
+        report.pushed.push(item);
+      }
+      state.servers.insert(instance.id.clone(), fingerprints(&local));
+      continue;
+    };
+
+    let base = state.servers.get(&instance.id).cloned().unwrap_or_default();
+    let local_fingerprints = fingerprints(&local);
+    let remote_fingerprints = fingerprints(&remote);
+    let merged = merge_server_lists(&base, local, remote);
+    let merged_fingerprints = fingerprints(&merged);
+    let local_changed = merged_fingerprints != local_fingerprints;
+    let remote_changed = merged_fingerprints != remote_fingerprints;
+    if remote_changed {
+      let payload = serde_json::to_vec_pretty(&merged).map_err(|err| err.to_string())?;
+      write_synced_file(&remote_path, &payload)?;
+    }
+    if local_changed {
+      store_server_list(&instance_dir, merged)?;
+    }
+    match (local_changed, remote_changed) {
+      (true, true) => report.merged.push(item),
+      (true, false) => report.pulled.push(item),
+      (false, true) => report.pushed.push(item),
+      (false, false) => {}
+    }
+    state.servers.insert(instance.id.clone(), merged_fingerprints);
+  }
+  Ok(())
+}
+
+/// Syncs settings, recipes and server lists; `None` when no sync directory is set.
+pub(crate) fn sync_launcher_data(app: &AppHandle) -> Result<Option<SyncReport>, String> {
+  let (config, state_path) = {
+    let store_state = app.state::<Mutex<ConfigStore>>();
+    let store = store_state
+      .lock()
+      .map_err(|_| "config store lock poisoned".to_string())?;
+    let state_path = store
+      .path()
+      .parent()
+      .map(|parent| parent.join(SYNC_STATE_FILE))
+      .ok_or_else(|| "config directory not found".to_string())?;
+    (store.get(), state_path)
+  };
+  let Some(directory) = config.settings.sync_directory.clone() else {
+    return Ok(None);
+  };
+  let directory = PathBuf::from(directory);
+  if !directory.is_dir() {
+    return Err(format!("sync directory '{}' not found", directory.display()));
+  }
+  let root = directory.join(SYNC_FOLDER);
+  fs::create_dir_all(&root).map_err(|err| err.to_string())?;
+
+  let mut state = load_sync_state(&state_path);
+  let mut report = SyncReport::new(&root);
+  sync_settings(app, &config, &root, &mut state, &mut report)?;
+  sync_recipes(&config, &root, &mut state, &mut report)?;
+  sync_server_lists(&config, &root, &mut state, &mut report)?;
+  save_sync_state(&state_path, &state)?;
+  Ok(Some(report))
+}
+
+pub(crate) fn spawn_startup_sync(app: AppHandle) {
+  thread::spawn(move || {
+    let ops = OperationEmitter::new(&app, None);
+    match sync_launcher_data(&app) {
+      Ok(Some(report)) => ops.emit("sync:done", report),
+      Ok(None) => {}
+      Err(err) => ops.emit("sync:error", err),
+    }
+  });
+}
+
+#[tauri::command]
+pub(crate) async fn run_sync_now(app: AppHandle) -> Result<SyncReport, String> {
+  tauri::async_runtime::spawn_blocking(move || sync_launcher_data(&app))
+    .await
+    .map_err(|_| "sync task cancelled".to_string())?
+    .and_then(|report| report.ok_or_else(|| "sync directory is not configured".to_string()))
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::{AppConfig, ConfigStore, INSTANCE_CONFIG_FILE};
+use crate::events::{self, OperationEmitter};
+use crate::minecraft;
+use crate::modrinth;
+
+const CONFIG_WATCH_DEBOUNCE_MS: u64 = 400;
+const SCREENSHOT_POLL_MS: u64 = 250;
+pub(crate) const SCREENSHOTS_DIR: &str = "screenshots";
+
+fn watched_dirs(config_path: &Path, config: &AppConfig) -> HashSet<PathBuf> {
+  let mut dirs = HashSet::new();
+  if let Some(parent) = config_path.parent() {
+    dirs.insert(parent.to_path_buf());
+  }
+  dirs.extend(instance_roots(config));
+  for instance in &config.instances {
+    dirs.insert(PathBuf::from(&instance.directory));
+  }
+  dirs
+}
+
+fn instance_roots(config: &AppConfig) -> HashSet<PathBuf> {
+  config
+    .instance_roots
+    .iter()
+    .map(|root| PathBuf::from(&root.path))
+    .collect()
+}
+
+fn touches_instances(event: &Event, roots: &HashSet<PathBuf>) -> bool {
+  event.paths.iter().any(|path| {
+    path
+      .file_name()
+      .map(|name| name == INSTANCE_CONFIG_FILE)
+      .unwrap_or(false)
+      || path.parent().map(|parent| roots.contains(parent)).unwrap_or(false)
+  })
+}
+
+pub(crate) fn spawn_config_watcher(app: AppHandle) {
+  thread::spawn(move || {
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = match notify::recommended_watcher(tx) {
+      Ok(watcher) => watcher,
+      Err(_) => return,
+    };
+
+    let (config_path, config) = {
+      let state = app.state::<Mutex<ConfigStore>>();
+      let Ok(store) = state.lock() else {
+        return;
+      };
+      (store.path().to_path_buf(), store.get())
+    };
+    let mut watched = HashSet::new();
+    for dir in watched_dirs(&config_path, &config) {
+      if watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok() {
+        watched.insert(dir);
+      }
+    }
+
+    let mut roots = instance_roots(&config);
+
+    while let Ok(first) = rx.recv() {
+      let mut config_touched = false;
+      let mut instances_touched = false;
+      let mut pending = Some(first);
+      while let Some(event) = pending.take() {
+        if let Ok(event) = event {
+          config_touched |= event.paths.iter().any(|path| path == &config_path);
+          instances_touched |= touches_instances(&event, &roots);
+        }
+        pending = rx.recv_timeout(Duration::from_millis(CONFIG_WATCH_DEBOUNCE_MS)).ok();
+      }
+      if !config_touched && !instances_touched {
+        continue;
+      }
+
+      let (reloaded, config) = {
+        let state = app.state::<Mutex<ConfigStore>>();
+        let Ok(mut store) = state.lock() else {
+          continue;
+        };
+        let reloaded = config_touched && store.reload_from_disk().unwrap_or(false);
+        (reloaded, store.get())
+      };
+      if !reloaded && !instances_touched {
+        continue;
+      }
+      minecraft::apply_network_settings(&config.settings);
+      events::apply_progress_settings(&config.settings);
+      modrinth::apply_content_blacklist(&config.settings);
+      roots = instance_roots(&config);
+
+      let next = watched_dirs(&config_path, &config);
+      for dir in watched.difference(&next) {
+        let _ = watcher.unwatch(dir);
+      }
+      for dir in next.difference(&watched) {
+        let _ = watcher.watch(dir, RecursiveMode::NonRecursive);
+      }
+      watched = next;
+
+      let _ = app.emit("config:changed", &config);
+    }
+  });
+}
+
+#[derive(Clone, Serialize)]
+struct ScreenshotEvent {
+  instance_id: String,
+  path: String,
+  file_name: String,
+}
+
+/// Keeps a screenshot watcher alive for one game session; stopping or dropping it ends the thread.
+pub(crate) struct ScreenshotWatch {
+  stop: Arc<AtomicBool>,
+}
+
+impl ScreenshotWatch {
+  pub(crate) fn stop(&self) {
+    self.stop.store(true, Ordering::Relaxed);
+  }
+}
+
+impl Drop for ScreenshotWatch {
+  fn drop(&mut self) {
+    self.stop();
+  }
+}
+
+/// Emits `screenshot:taken` for each PNG the game writes into the instance's screenshots folder.
+pub(crate) fn spawn_screenshot_watcher(
+  ops: OperationEmitter,
+  instance_id: String,
+  instance_dir: &Path,
+) -> ScreenshotWatch {
+  let stop = Arc::new(AtomicBool::new(false));
+  let thread_stop = stop.clone();
+  let dir = instance_dir.join(SCREENSHOTS_DIR);
+  thread::spawn(move || {
+    // The game only creates the folder on the first screenshot, so make it up front to watch it.
+    if fs::create_dir_all(&dir).is_err() {
+      return;
+    }
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let Ok(mut watcher) = notify::recommended_watcher(tx) else {
+      return;
+    };
+    if watcher.watch(&dir, RecursiveMode::NonRecursive).is_err() {
+      return;
+    }
+    // Files are reported once their size stops changing, so listeners never see a half-written PNG.
+    let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+    let mut reported = HashSet::new();
+    while !thread_stop.load(Ordering::Relaxed) {
+      match rx.recv_timeout(Duration::from_millis(SCREENSHOT_POLL_MS)) {
+        Ok(Ok(event)) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+          for path in event.paths {
+            if is_screenshot_file(&path) && !reported.contains(&path) {
+              pending.entry(path).or_insert(0);
+            }
+          }
+          continue;
+        }
+        Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+      }
+      let mut settled = Vec::new();
+      for (path, last_size) in pending.iter_mut() {
+        let size = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+        if size > 0 && size == *last_size {
+          settled.push(path.clone());
+        } else {
+          *last_size = size;
+        }
+      }
+      for path in settled {
+        pending.remove(&path);
+        let file_name = path
+          .file_name()
+          .map(|name| name.to_string_lossy().to_string())
+          .unwrap_or_default();
+        ops.emit(
+          "screenshot:taken",
+          ScreenshotEvent {
+            instance_id: instance_id.clone(),
+            path: path.to_string_lossy().to_string(),
+            file_name,
+          },
+        );
+        reported.insert(path);
+      }
+    }
+  });
+  ScreenshotWatch { stop }
+}
+
+fn is_screenshot_file(path: &Path) -> bool {
+  path
+    .extension()
+    .map(|ext| ext.eq_ignore_ascii_case("png"))
+    .unwrap_or(false)
+}